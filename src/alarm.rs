@@ -1,3 +1,2169 @@
 //! Alarm functionality for RTC devices.
+//!
+//! An alarm fires when the current time matches an [`AlarmConfig`]. Each field
+//! of the configuration is either a value the time must have or `None` for
+//! "any", which is how hardware alarms are programmed: "every day at 07:00"
+//! leaves the day unset, "every hour at minute 15" also leaves the hour unset.
+//! The day is given either as a day of month or as a set of weekdays.
+//!
+//! Chips can have several alarms, addressed by [`AlarmId`]. When an alarm
+//! fires, the chip sets a flag that stays set until cleared. If the alarm's
+//! interrupt is enabled, the chip also asserts its interrupt pin, which is
+//! used to wake the MCU:
+//!
+//! ```
+//! use rtc_hal::alarm::{Alarm, AlarmConfig, AlarmId};
+//!
+//! fn arm_wake_up<A: Alarm>(rtc: &mut A) -> Result<(), A::Error> {
+//!     rtc.set_alarm(AlarmId::ALARM1, &AlarmConfig::daily_at(7, 0, 0))?;
+//!     rtc.enable_alarm_interrupt(AlarmId::ALARM1)
+//! }
+//!
+//! // Called after the interrupt pin woke the MCU
+//! fn on_interrupt<A: Alarm>(rtc: &mut A) -> Result<bool, A::Error> {
+//!     if !rtc.alarm_triggered(AlarmId::ALARM1)? {
+//!         return Ok(false);
+//!     }
+//!     rtc.clear_alarm_flag(AlarmId::ALARM1)?;
+//!     Ok(true)
+//! }
+//! ```
+use crate::datetime::{DateTime, DateTimeError, Weekday, WeekdaySet};
+use crate::rtc::Rtc;
 
-// TODO
+mod builder;
+mod ds3231;
+mod recurring;
+mod schedule;
+mod snooze;
+
+pub use builder::AlarmConfigBuilder;
+pub use ds3231::{
+    decode_ds3231_alarm1, decode_ds3231_alarm2, encode_ds3231_alarm1, encode_ds3231_alarm2,
+};
+pub use recurring::{Recurrence, RecurringAlarm};
+pub use schedule::Schedule;
+pub use snooze::SnoozableAlarm;
+
+/// Error returned when validating an [`AlarmConfig`]
+///
+/// Maps to [`ErrorKind::InvalidAlarmConfig`](crate::error::ErrorKind::InvalidAlarmConfig).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum AlarmConfigError {
+    /// Second is not in 0-59
+    InvalidSecond,
+    /// Minute is not in 0-59
+    InvalidMinute,
+    /// Hour is not in 0-23
+    InvalidHour,
+    /// Day of month is not in 1-31
+    InvalidDay,
+    /// Both day of month and weekday are set
+    DayAndWeekday,
+    /// The set fields do not form an [`AlarmMatch`] mode, e.g. minute without second
+    UnsupportedMatch,
+    /// The chip has no alarm with this [`AlarmId`]
+    InvalidAlarmId,
+    /// The weekday set is empty, so the alarm would never fire
+    EmptyWeekdaySet,
+    /// The weekday set has several days, but the hardware matches a single weekday
+    MultipleWeekdays,
+    /// The match mode compares the time, but no time is given
+    MissingTime,
+    /// The match mode compares the day of month or weekday, but none is given
+    MissingDay,
+    /// A day is given that the match mode does not compare
+    MatchModeConflict,
+    /// Month is not in 1-12
+    InvalidMonth,
+    /// A recurring alarm interval is 0 or longer than [`Recurrence::MAX_INTERVAL`],
+    /// or a snooze is 0 minutes
+    InvalidInterval,
+}
+
+impl core::fmt::Display for AlarmConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AlarmConfigError::InvalidSecond => write!(f, "invalid alarm second"),
+            AlarmConfigError::InvalidMinute => write!(f, "invalid alarm minute"),
+            AlarmConfigError::InvalidHour => write!(f, "invalid alarm hour"),
+            AlarmConfigError::InvalidDay => write!(f, "invalid alarm day of month"),
+            AlarmConfigError::DayAndWeekday => {
+                write!(f, "alarm cannot match both day of month and weekday")
+            }
+            AlarmConfigError::UnsupportedMatch => write!(f, "unsupported alarm match mode"),
+            AlarmConfigError::InvalidAlarmId => write!(f, "no such alarm"),
+            AlarmConfigError::EmptyWeekdaySet => write!(f, "alarm weekday set is empty"),
+            AlarmConfigError::MultipleWeekdays => {
+                write!(f, "alarm can only match a single weekday")
+            }
+            AlarmConfigError::MissingTime => write!(f, "alarm time not set"),
+            AlarmConfigError::MissingDay => write!(f, "alarm day not set"),
+            AlarmConfigError::MatchModeConflict => {
+                write!(f, "alarm day not compared by the match mode")
+            }
+            AlarmConfigError::InvalidMonth => write!(f, "invalid schedule month"),
+            AlarmConfigError::InvalidInterval => write!(f, "invalid recurring alarm interval"),
+        }
+    }
+}
+
+impl core::error::Error for AlarmConfigError {}
+
+impl AlarmConfigError {
+    /// Every error, in declaration order
+    #[cfg(feature = "serde")]
+    const ALL: [Self; 14] = [
+        Self::InvalidSecond,
+        Self::InvalidMinute,
+        Self::InvalidHour,
+        Self::InvalidDay,
+        Self::DayAndWeekday,
+        Self::UnsupportedMatch,
+        Self::InvalidAlarmId,
+        Self::EmptyWeekdaySet,
+        Self::MultipleWeekdays,
+        Self::MissingTime,
+        Self::MissingDay,
+        Self::MatchModeConflict,
+        Self::InvalidMonth,
+        Self::InvalidInterval,
+    ];
+
+    /// Stable names used by the `serde` impls, the variant names in declaration order
+    #[cfg(feature = "serde")]
+    const NAMES: [&'static str; 14] = [
+        "InvalidSecond",
+        "InvalidMinute",
+        "InvalidHour",
+        "InvalidDay",
+        "DayAndWeekday",
+        "UnsupportedMatch",
+        "InvalidAlarmId",
+        "EmptyWeekdaySet",
+        "MultipleWeekdays",
+        "MissingTime",
+        "MissingDay",
+        "MatchModeConflict",
+        "InvalidMonth",
+        "InvalidInterval",
+    ];
+}
+
+/// Serialized as the variant name, e.g. `"InvalidHour"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlarmConfigError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Self::NAMES[*self as usize])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlarmConfigError {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index =
+            deserializer.deserialize_str(crate::serde_names::Name::Variant(&Self::NAMES))?;
+        Ok(Self::ALL[index])
+    }
+}
+
+/// Which fields of the time an alarm compares
+///
+/// Each mode matches the fields of the one before plus one more, which is
+/// the set of modes hardware alarms offer. The DS3231 selects the mode with
+/// the mask bit in bit 7 of each alarm register (set = ignore the field) and
+/// the DY/DT bit of the day register:
+///
+/// | Mode                         | A1M4 | A1M3 | A1M2 | A1M1 | DY/DT |
+/// |------------------------------|------|------|------|------|-------|
+/// | `EverySecond`                | 1    | 1    | 1    | 1    | X     |
+/// | `Seconds`                    | 1    | 1    | 1    | 0    | X     |
+/// | `MinutesSeconds`             | 1    | 1    | 0    | 0    | X     |
+/// | `HoursMinutesSeconds`        | 1    | 0    | 0    | 0    | X     |
+/// | `DateHoursMinutesSeconds`    | 0    | 0    | 0    | 0    | 0     |
+/// | `WeekdayHoursMinutesSeconds` | 0    | 0    | 0    | 0    | 1     |
+///
+/// The DS3231 alarm 2, the DS1337 and the PCF8563/PCF85063 family have no
+/// seconds register: they fire at second 0 and use the same table without the
+/// seconds bit (the PCF chips call the bits AE, with the same polarity).
+/// Alarm 2 "once per minute" is `Seconds` with the second set to 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AlarmMatch {
+    /// Fire every second
+    EverySecond,
+    /// Fire when the second matches, once a minute
+    Seconds,
+    /// Fire when minute and second match, once an hour
+    MinutesSeconds,
+    /// Fire when hour, minute and second match, once a day
+    HoursMinutesSeconds,
+    /// Fire when day of month, hour, minute and second match, once a month
+    DateHoursMinutesSeconds,
+    /// Fire when the weekday is in the set and hour, minute and second match
+    WeekdayHoursMinutesSeconds,
+}
+
+impl AlarmMatch {
+    /// DS3231 alarm 1 mask bits `[A1M1, A1M2, A1M3, A1M4]` for this mode
+    ///
+    /// `true` means the bit is set and the field is ignored. The DY/DT bit is
+    /// set for [`WeekdayHoursMinutesSeconds`](Self::WeekdayHoursMinutesSeconds)
+    /// only, see [`uses_weekday`](Self::uses_weekday).
+    pub const fn mask_bits_ds3231(&self) -> [bool; 4] {
+        match self {
+            Self::EverySecond => [true, true, true, true],
+            Self::Seconds => [false, true, true, true],
+            Self::MinutesSeconds => [false, false, true, true],
+            Self::HoursMinutesSeconds => [false, false, false, true],
+            Self::DateHoursMinutesSeconds | Self::WeekdayHoursMinutesSeconds => {
+                [false, false, false, false]
+            }
+        }
+    }
+
+    /// Whether the day is matched by weekday rather than day of month
+    pub const fn uses_weekday(&self) -> bool {
+        matches!(self, Self::WeekdayHoursMinutesSeconds)
+    }
+
+    /// Every mode, in declaration order
+    #[cfg(feature = "serde")]
+    const ALL: [Self; 6] = [
+        Self::EverySecond,
+        Self::Seconds,
+        Self::MinutesSeconds,
+        Self::HoursMinutesSeconds,
+        Self::DateHoursMinutesSeconds,
+        Self::WeekdayHoursMinutesSeconds,
+    ];
+
+    /// Stable names used by the `serde` impls, the variant names in declaration order
+    #[cfg(feature = "serde")]
+    const NAMES: [&'static str; 6] = [
+        "EverySecond",
+        "Seconds",
+        "MinutesSeconds",
+        "HoursMinutesSeconds",
+        "DateHoursMinutesSeconds",
+        "WeekdayHoursMinutesSeconds",
+    ];
+}
+
+/// Serialized as the variant name, e.g. `"HoursMinutesSeconds"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlarmMatch {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Self::NAMES[*self as usize])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlarmMatch {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index =
+            deserializer.deserialize_str(crate::serde_names::Name::Variant(&Self::NAMES))?;
+        Ok(Self::ALL[index])
+    }
+}
+
+/// When an alarm fires
+///
+/// Each field is the value the current time must have, or `None` to match any
+/// value. The default configuration matches every second.
+///
+/// Chips match the day either by day of month or by weekday, selected with a
+/// DY/DT bit, so [`validate`](Self::validate) rejects configurations with
+/// both. Drivers for hardware that can match both at once (the alarm then
+/// fires only when both agree, e.g. Friday the 13th) use
+/// [`validate_ranges`](Self::validate_ranges) instead.
+///
+/// Weekdays are a [`WeekdaySet`], as chips like the RV-8803 match a bitmask
+/// of days ("Mon, Wed, Fri at 06:00"). Drivers for hardware with a single
+/// weekday register get it from [`single_weekday`](Self::single_weekday),
+/// which rejects sets of several days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmConfig {
+    /// Second (0-59)
+    pub second: Option<u8>,
+    /// Minute (0-59)
+    pub minute: Option<u8>,
+    /// Hour (0-23)
+    pub hour: Option<u8>,
+    /// Day of month (1-31)
+    pub day_of_month: Option<u8>,
+    /// Days of the week; must not be empty
+    pub weekdays: Option<WeekdaySet>,
+}
+
+impl AlarmConfig {
+    /// Fire once a minute, at `second`.
+    pub const fn every_minute_at(second: u8) -> Self {
+        AlarmConfig {
+            second: Some(second),
+            minute: None,
+            hour: None,
+            day_of_month: None,
+            weekdays: None,
+        }
+    }
+
+    /// Fire once an hour, at `minute:00`.
+    pub const fn hourly_at_minute(minute: u8) -> Self {
+        AlarmConfig {
+            minute: Some(minute),
+            ..Self::every_minute_at(0)
+        }
+    }
+
+    /// Fire once a day at `hour:minute:second`.
+    pub const fn daily_at(hour: u8, minute: u8, second: u8) -> Self {
+        AlarmConfig {
+            hour: Some(hour),
+            minute: Some(minute),
+            ..Self::every_minute_at(second)
+        }
+    }
+
+    /// Fire once a week on `weekday` at `hour:minute:second`.
+    pub const fn weekly_at(weekday: Weekday, hour: u8, minute: u8, second: u8) -> Self {
+        Self::on_weekdays_at(WeekdaySet::single(weekday), hour, minute, second)
+    }
+
+    /// Fire on each day in `weekdays` at `hour:minute:second`.
+    pub const fn on_weekdays_at(weekdays: WeekdaySet, hour: u8, minute: u8, second: u8) -> Self {
+        AlarmConfig {
+            weekdays: Some(weekdays),
+            ..Self::daily_at(hour, minute, second)
+        }
+    }
+
+    /// Fire once a month on `day_of_month` at `hour:minute:second`.
+    ///
+    /// Months shorter than `day_of_month` are skipped.
+    pub const fn monthly_at(day_of_month: u8, hour: u8, minute: u8, second: u8) -> Self {
+        AlarmConfig {
+            day_of_month: Some(day_of_month),
+            ..Self::daily_at(hour, minute, second)
+        }
+    }
+
+    /// Fire at `datetime`, matching date and time exactly.
+    ///
+    /// Alarms have no month or year, so this fires again a month later on the
+    /// same day unless re-armed or disabled first.
+    pub fn once_at(datetime: &DateTime) -> Self {
+        Self::monthly_at(
+            datetime.day_of_month(),
+            datetime.hour(),
+            datetime.minute(),
+            datetime.second(),
+        )
+    }
+
+    /// Check that every set field is in range and that the day is matched
+    /// either by day of month or by weekday, not both.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `AlarmConfigError` for the first field out of range, or
+    /// `AlarmConfigError::DayAndWeekday`.
+    pub fn validate(&self) -> Result<(), AlarmConfigError> {
+        self.validate_ranges()?;
+        if self.day_of_month.is_some() && self.weekdays.is_some() {
+            return Err(AlarmConfigError::DayAndWeekday);
+        }
+        Ok(())
+    }
+
+    /// Check that every set field is in range, allowing day of month and
+    /// weekday together.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `AlarmConfigError` for the first field out of range.
+    pub fn validate_ranges(&self) -> Result<(), AlarmConfigError> {
+        if self.second.is_some_and(|s| s > 59) {
+            return Err(AlarmConfigError::InvalidSecond);
+        }
+        if self.minute.is_some_and(|m| m > 59) {
+            return Err(AlarmConfigError::InvalidMinute);
+        }
+        if self.hour.is_some_and(|h| h > 23) {
+            return Err(AlarmConfigError::InvalidHour);
+        }
+        if self.day_of_month.is_some_and(|d| !(1..=31).contains(&d)) {
+            return Err(AlarmConfigError::InvalidDay);
+        }
+        if self.weekdays.is_some_and(WeekdaySet::is_empty) {
+            return Err(AlarmConfigError::EmptyWeekdaySet);
+        }
+        Ok(())
+    }
+
+    /// The weekday to match, for hardware with a single weekday register.
+    ///
+    /// Returns `None` if the weekday is not matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::MultipleWeekdays` if the set has more than
+    /// one day, or `AlarmConfigError::EmptyWeekdaySet` if it has none.
+    pub fn single_weekday(&self) -> Result<Option<Weekday>, AlarmConfigError> {
+        let Some(weekdays) = self.weekdays else {
+            return Ok(None);
+        };
+        let mut days = weekdays.iter();
+        match (days.next(), days.next()) {
+            (Some(day), None) => Ok(Some(day)),
+            (Some(_), Some(_)) => Err(AlarmConfigError::MultipleWeekdays),
+            (None, _) => Err(AlarmConfigError::EmptyWeekdaySet),
+        }
+    }
+
+    /// The hardware match mode formed by the set fields.
+    ///
+    /// Drivers translate the mode into mask bits, e.g. with
+    /// [`AlarmMatch::mask_bits_ds3231`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::UnsupportedMatch` if the set fields are not
+    /// one of the [`AlarmMatch`] modes, e.g. an hour without minute and second.
+    pub fn match_mode(&self) -> Result<AlarmMatch, AlarmConfigError> {
+        let time = (
+            self.hour.is_some(),
+            self.minute.is_some(),
+            self.second.is_some(),
+        );
+        let day = (self.day_of_month.is_some(), self.weekdays.is_some());
+        match (day, time) {
+            ((false, false), (false, false, false)) => Ok(AlarmMatch::EverySecond),
+            ((false, false), (false, false, true)) => Ok(AlarmMatch::Seconds),
+            ((false, false), (false, true, true)) => Ok(AlarmMatch::MinutesSeconds),
+            ((false, false), (true, true, true)) => Ok(AlarmMatch::HoursMinutesSeconds),
+            ((true, false), (true, true, true)) => Ok(AlarmMatch::DateHoursMinutesSeconds),
+            ((false, true), (true, true, true)) => Ok(AlarmMatch::WeekdayHoursMinutesSeconds),
+            _ => Err(AlarmConfigError::UnsupportedMatch),
+        }
+    }
+
+    /// Check whether the alarm fires at `datetime`.
+    pub fn matches(&self, datetime: &DateTime) -> bool {
+        let field = |expected: Option<u8>, actual: u8| expected.is_none_or(|e| e == actual);
+        field(self.second, datetime.second())
+            && field(self.minute, datetime.minute())
+            && field(self.hour, datetime.hour())
+            && self.matches_date(datetime)
+    }
+
+    /// Check whether the alarm fires on the date of `datetime`, at some time of day.
+    fn matches_date(&self, datetime: &DateTime) -> bool {
+        self.day_of_month
+            .is_none_or(|d| d == datetime.day_of_month())
+            && self
+                .weekdays
+                .is_none_or(|days| days.contains(datetime.weekday()))
+    }
+
+    /// Earliest time of day matching the time fields, strictly after `after` if given.
+    fn first_time(&self, after: Option<crate::datetime::Time>) -> Option<crate::datetime::Time> {
+        let values = |field: Option<u8>, max: u8| match field {
+            Some(value) => value..=value,
+            None => 0..=max,
+        };
+        let after = after.map(|t| (t.hour(), t.minute(), t.second()));
+        let (min_hour, min_minute) = after.map_or((0, 0), |(h, m, _)| (h, m));
+
+        for hour in values(self.hour, 23).filter(|&h| h >= min_hour) {
+            for minute in values(self.minute, 59).filter(|&m| hour > min_hour || m >= min_minute) {
+                for second in values(self.second, 59) {
+                    if after.is_none_or(|after| (hour, minute, second) > after) {
+                        return crate::datetime::Time::new(hour, minute, second).ok();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// The next time, strictly after `now`, at which the alarm fires.
+    ///
+    /// Computed in software from the [`match_mode`](Self::match_mode), so it
+    /// can be used to schedule work without touching the hardware:
+    ///
+    /// - every second: `now` plus one second
+    /// - seconds, minutes or time of day: the next such second, minute or
+    ///   time, rolling over to the next minute, hour or day
+    /// - day of month: this month if the day and time are still ahead,
+    ///   otherwise the next month that has the day (day 31 skips 30-day
+    ///   months, day 29 skips February outside leap years)
+    /// - weekdays: the nearest day in the set, today included if the time is
+    ///   still ahead
+    ///
+    /// Field combinations that are not a match mode, e.g. day of month and
+    /// weekday together, are resolved by searching day by day.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `DateTimeError` for a field out of range
+    /// (`DateTimeError::InvalidWeekday` for an empty weekday set), or
+    /// `DateTimeError::InvalidYear` if the next match is after
+    /// [`DateTime::MAX`].
+    pub fn next_trigger(&self, now: &DateTime) -> Result<DateTime, DateTimeError> {
+        self.validate_ranges().map_err(|error| match error {
+            AlarmConfigError::InvalidSecond => DateTimeError::InvalidSecond,
+            AlarmConfigError::InvalidMinute => DateTimeError::InvalidMinute,
+            AlarmConfigError::InvalidHour => DateTimeError::InvalidHour,
+            AlarmConfigError::InvalidDay => DateTimeError::InvalidDay,
+            // Only an empty weekday set is left
+            _ => DateTimeError::InvalidWeekday,
+        })?;
+        let second = u32::from(self.second.unwrap_or(0));
+        let minute = u32::from(self.minute.unwrap_or(0));
+        let hour = u32::from(self.hour.unwrap_or(0));
+        // Seconds from `now` to the next `target` in a cycle of `period` seconds, in 1..=period
+        let until = |target: u32, current: u32, period: u32| {
+            i64::from((target + period - current - 1) % period + 1)
+        };
+
+        match self.match_mode() {
+            Ok(AlarmMatch::EverySecond) => now.checked_add_seconds(1),
+            Ok(AlarmMatch::Seconds) => {
+                now.checked_add_seconds(until(second, now.second().into(), 60))
+            }
+            Ok(AlarmMatch::MinutesSeconds) => {
+                let current = u32::from(now.minute()) * 60 + u32::from(now.second());
+                now.checked_add_seconds(until(minute * 60 + second, current, 3_600))
+            }
+            Ok(AlarmMatch::HoursMinutesSeconds) => {
+                let target = hour * 3_600 + minute * 60 + second;
+                let current = now.time().seconds_since_midnight();
+                now.checked_add_seconds(until(target, current, 86_400))
+            }
+            Ok(AlarmMatch::DateHoursMinutesSeconds) => {
+                let day = self.day_of_month.unwrap_or(1);
+                let (mut year, mut month) = (now.year(), now.month());
+                // Every day 1-31 occurs within any 12 consecutive months
+                for _ in 0..12 {
+                    if let Ok(candidate) =
+                        DateTime::new(year, month, day, hour as u8, minute as u8, second as u8)
+                        && candidate > *now
+                    {
+                        return Ok(candidate);
+                    }
+                    if month == 12 {
+                        year = year.checked_add(1).ok_or(DateTimeError::InvalidYear)?;
+                        month = 1;
+                    } else {
+                        month += 1;
+                    }
+                }
+                Err(DateTimeError::InvalidYear)
+            }
+            Ok(AlarmMatch::WeekdayHoursMinutesSeconds) => {
+                let time = crate::datetime::Time::new(hour as u8, minute as u8, second as u8)?;
+                let today = now.weekday();
+                let days = self
+                    .weekdays
+                    .unwrap_or(WeekdaySet::ALL)
+                    .iter()
+                    .map(|day| match today.days_until(day) {
+                        0 if time <= now.time() => 7,
+                        days => days,
+                    })
+                    .min()
+                    .ok_or(DateTimeError::InvalidWeekday)?;
+                let date = now.start_of_day().checked_add_days(days.into())?;
+                Ok(DateTime::from_date_time(date.date(), time))
+            }
+            Err(_) => self.search_after(now).ok_or(DateTimeError::InvalidYear),
+        }
+    }
+
+    /// Find the next match by trying each day in turn, for any field combination.
+    fn search_after(&self, now: &DateTime) -> Option<DateTime> {
+        // Long enough for any day of month to fall on any weekday (at most 609 days)
+        const SEARCH_DAYS: i32 = 2 * 366;
+
+        let midnight = now.start_of_day();
+        for days in 0..=SEARCH_DAYS {
+            let day = midnight.checked_add_days(days).ok()?;
+            if !self.matches_date(&day) {
+                continue;
+            }
+            let threshold = (days == 0).then(|| now.time());
+            if let Some(time) = self.first_time(threshold) {
+                return Some(DateTime::from_date_time(day.date(), time));
+            }
+        }
+        None
+    }
+}
+
+/// Serialized as a struct of optional fields, with the weekdays as an array of
+/// names, e.g. `{"second": 0, "minute": 30, "hour": 6, "day_of_month": null,
+/// "weekdays": ["Monday", "Friday"]}` in JSON
+///
+/// Missing fields deserialize as `None`. The configuration is not validated.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlarmConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut config = serializer.serialize_struct("AlarmConfig", 5)?;
+        config.serialize_field("second", &self.second)?;
+        config.serialize_field("minute", &self.minute)?;
+        config.serialize_field("hour", &self.hour)?;
+        config.serialize_field("day_of_month", &self.day_of_month)?;
+        config.serialize_field("weekdays", &self.weekdays)?;
+        config.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlarmConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const FIELDS: &[&str] = &["second", "minute", "hour", "day_of_month", "weekdays"];
+
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = AlarmConfig;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "an alarm configuration")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<AlarmConfig, A::Error> {
+                let mut config = AlarmConfig::default();
+                while let Some(field) =
+                    map.next_key_seed(crate::serde_names::Name::Field(FIELDS))?
+                {
+                    match field {
+                        0 => config.second = map.next_value()?,
+                        1 => config.minute = map.next_value()?,
+                        2 => config.hour = map.next_value()?,
+                        3 => config.day_of_month = map.next_value()?,
+                        _ => config.weekdays = map.next_value()?,
+                    }
+                }
+                Ok(config)
+            }
+        }
+
+        deserializer.deserialize_struct("AlarmConfig", FIELDS, Visitor)
+    }
+}
+
+/// Identifies one of the alarms of a chip
+///
+/// Alarms are numbered from 0, so `AlarmId(0)` is the one datasheets call
+/// "alarm 1". Chips with a single alarm only accept [`AlarmId::ALARM1`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmId(pub u8);
+
+impl AlarmId {
+    /// The first alarm, and the only one on single-alarm chips
+    pub const ALARM1: AlarmId = AlarmId(0);
+    /// The second alarm, e.g. DS3231 alarm 2
+    pub const ALARM2: AlarmId = AlarmId(1);
+
+    /// Check that this alarm exists on a chip with `alarm_count` alarms.
+    ///
+    /// Lets drivers reject unknown ids with `?`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::InvalidAlarmId` if the id is not below `alarm_count`.
+    pub const fn check(self, alarm_count: u8) -> Result<Self, AlarmConfigError> {
+        if self.0 < alarm_count {
+            Ok(self)
+        } else {
+            Err(AlarmConfigError::InvalidAlarmId)
+        }
+    }
+}
+
+impl core::fmt::Display for AlarmId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "alarm {}", self.0 + 1)
+    }
+}
+
+/// Serialized as the alarm number from 0, e.g. `1` for [`AlarmId::ALARM2`]
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlarmId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlarmId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u8 as serde::Deserialize>::deserialize(deserializer).map(AlarmId)
+    }
+}
+
+/// What an alarm can do, as reported by [`Alarm::alarm_capabilities`]
+///
+/// The default has every capability off, which is also what an alarm the
+/// chip does not have reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmCapabilities {
+    /// The alarm can match the second; without it, it fires at second 0
+    pub seconds_match: bool,
+    /// The alarm can match a weekday
+    pub weekday_match: bool,
+    /// The alarm can match a day of month
+    pub date_match: bool,
+    /// The alarm can assert an interrupt pin, rather than only set a flag to poll
+    pub interrupt_output: bool,
+}
+
+/// Serialized as a struct of booleans; missing fields deserialize as `false`
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlarmCapabilities {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut capabilities = serializer.serialize_struct("AlarmCapabilities", 4)?;
+        capabilities.serialize_field("seconds_match", &self.seconds_match)?;
+        capabilities.serialize_field("weekday_match", &self.weekday_match)?;
+        capabilities.serialize_field("date_match", &self.date_match)?;
+        capabilities.serialize_field("interrupt_output", &self.interrupt_output)?;
+        capabilities.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlarmCapabilities {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const FIELDS: &[&str] = &[
+            "seconds_match",
+            "weekday_match",
+            "date_match",
+            "interrupt_output",
+        ];
+
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = AlarmCapabilities;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "alarm capabilities")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<AlarmCapabilities, A::Error> {
+                let mut capabilities = AlarmCapabilities::default();
+                while let Some(field) =
+                    map.next_key_seed(crate::serde_names::Name::Field(FIELDS))?
+                {
+                    match field {
+                        0 => capabilities.seconds_match = map.next_value()?,
+                        1 => capabilities.weekday_match = map.next_value()?,
+                        2 => capabilities.date_match = map.next_value()?,
+                        _ => capabilities.interrupt_output = map.next_value()?,
+                    }
+                }
+                Ok(capabilities)
+            }
+        }
+
+        deserializer.deserialize_struct("AlarmCapabilities", FIELDS, Visitor)
+    }
+}
+
+/// This trait extends [`Rtc`] with one or more alarms.
+///
+/// Each alarm is addressed by an [`AlarmId`]. Alarms are independent: each
+/// has its own configuration and triggered flag. Drivers should reject ids
+/// the chip does not have, and configurations the chosen alarm cannot express
+/// (e.g. seconds matching on DS3231 alarm 2), with an error of kind
+/// [`ErrorKind::InvalidAlarmConfig`](crate::error::ErrorKind::InvalidAlarmConfig),
+/// e.g. by converting the `AlarmConfigError` from [`AlarmId::check`] or
+/// [`AlarmConfig::validate`].
+///
+/// Matching and interrupts are controlled separately. An armed alarm always
+/// sets its flag when it matches, which can be polled; whether the match also
+/// asserts the interrupt pin is gated by
+/// [`enable_alarm_interrupt`](Alarm::enable_alarm_interrupt). This mirrors
+/// chips like the DS3231, where the comparison always runs and the A1IE/A2IE
+/// bits only route the flag to the pin.
+///
+/// On many chips the interrupt pin is shared with the square-wave output
+/// (DS3231 INT/SQW, selected by INTCN). Drivers for such chips switch the pin
+/// to interrupt mode when an alarm interrupt is enabled, which stops the
+/// square wave, and starting the square wave leaves alarm interrupts
+/// configured but unable to reach the pin, see
+/// [`SquareWave::configure_output_pin`](crate::square_wave::SquareWave::configure_output_pin).
+/// Drivers should document this.
+///
+/// Applications that only use one alarm can wrap the RTC in [`SingleAlarm`].
+pub trait Alarm: Rtc {
+    /// Number of alarms, addressed as `AlarmId(0)` to `AlarmId(alarm_count() - 1)`.
+    ///
+    /// Defaults to 1; drivers for chips with more alarms must override it.
+    fn alarm_count(&self) -> u8 {
+        1
+    }
+
+    /// What alarm `id` can match and whether it can raise an interrupt.
+    ///
+    /// Drivers should fill this in from the datasheet rather than probe the
+    /// hardware, so it needs no bus access. Ids the chip does not have report
+    /// [`AlarmCapabilities::default`], which is also the default
+    /// implementation: drivers must override it to advertise anything.
+    ///
+    /// ```
+    /// use rtc_hal::alarm::{Alarm, AlarmConfig, AlarmId};
+    ///
+    /// // Wake-up time, rounded down to the minute if no alarm matches seconds
+    /// fn arm_wake_up<A: Alarm>(rtc: &mut A, hour: u8, minute: u8, second: u8) -> Result<bool, A::Error> {
+    ///     let Some(id) = (0..rtc.alarm_count())
+    ///         .map(AlarmId)
+    ///         .filter(|&id| rtc.alarm_capabilities(id).interrupt_output)
+    ///         .min_by_key(|&id| !rtc.alarm_capabilities(id).seconds_match)
+    ///     else {
+    ///         return Ok(false);
+    ///     };
+    ///     let second = if rtc.alarm_capabilities(id).seconds_match { second } else { 0 };
+    ///     rtc.set_alarm(id, &AlarmConfig::daily_at(hour, minute, second))?;
+    ///     rtc.enable_alarm_interrupt(id)?;
+    ///     Ok(true)
+    /// }
+    /// ```
+    fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+        let _ = id;
+        AlarmCapabilities::default()
+    }
+
+    /// Configure and arm alarm `id`.
+    ///
+    /// Neither the triggered flag nor the interrupt enable is changed; clear
+    /// the flag with [`clear_alarm_flag`](Alarm::clear_alarm_flag) if a stale
+    /// flag must be discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind
+    /// [`ErrorKind::InvalidAlarmConfig`](crate::error::ErrorKind::InvalidAlarmConfig)
+    /// if the chip has no alarm `id` or it cannot express `config`, or
+    /// `Self::Error` if communication fails.
+    fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error>;
+
+    /// Disarm alarm `id` so that it no longer fires.
+    fn disable_alarm(&mut self, id: AlarmId) -> Result<(), Self::Error>;
+
+    /// Check whether alarm `id` has fired since its flag was last cleared.
+    ///
+    /// Reading does not clear the flag: it keeps reading `true`, and the
+    /// interrupt pin stays asserted, until
+    /// [`clear_alarm_flag`](Alarm::clear_alarm_flag). After waking up,
+    /// firmware can check every alarm to find out why, then clear the ones it
+    /// handled.
+    ///
+    /// On some chips reading the status register clears the flags, e.g. the
+    /// AM18x5 with ARST set or the M41T8x flags register. Drivers for those
+    /// chips must remember flags they have read until they are cleared
+    /// through this trait, so that the contract above holds.
+    fn alarm_triggered(&mut self, id: AlarmId) -> Result<bool, Self::Error>;
+
+    /// Clear the triggered flag of alarm `id`, releasing the interrupt pin if
+    /// no other enabled alarm holds it.
+    ///
+    /// The flags of other alarms are not changed.
+    fn clear_alarm_flag(&mut self, id: AlarmId) -> Result<(), Self::Error>;
+
+    /// Let alarm `id` assert the interrupt pin when it fires.
+    ///
+    /// If the flag is already set, the pin is asserted immediately.
+    fn enable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error>;
+
+    /// Keep the interrupt pin quiet when alarm `id` fires.
+    ///
+    /// The alarm still sets its flag, so it can be polled with
+    /// [`alarm_triggered`](Alarm::alarm_triggered).
+    fn disable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error>;
+
+    /// Disarm every alarm, keep it off the interrupt pin and clear its flag.
+    ///
+    /// For each alarm from `AlarmId(0)` to `alarm_count() - 1`, the default
+    /// implementation calls [`disable_alarm_interrupt`](Alarm::disable_alarm_interrupt),
+    /// [`disable_alarm`](Alarm::disable_alarm) and then
+    /// [`clear_alarm_flag`](Alarm::clear_alarm_flag), so that the pin is
+    /// released first and a match in between leaves no flag behind. This is
+    /// meant for teardown, e.g. before a firmware update, where no stale alarm
+    /// may assert the interrupt pin.
+    ///
+    /// The alarms are disarmed, not reconfigured: whether the match registers
+    /// keep their values is up to the chip, so re-arm them with
+    /// [`set_alarm`](Alarm::set_alarm).
+    ///
+    /// # Errors
+    ///
+    /// Every step is attempted for every alarm even if an earlier one fails,
+    /// and the first error is returned.
+    fn disable_all_alarms(&mut self) -> Result<(), Self::Error> {
+        let mut result = Ok(());
+        for id in (0..self.alarm_count()).map(AlarmId) {
+            for step in [
+                Self::disable_alarm_interrupt,
+                Self::disable_alarm,
+                Self::clear_alarm_flag,
+            ] {
+                if let Err(e) = step(self, id)
+                    && result.is_ok()
+                {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Arm alarm `id` to fire `seconds` from now and return the time it fires at.
+    ///
+    /// The default implementation reads the current time, adds `seconds`
+    /// across day, month and year boundaries, and arms the result with
+    /// [`AlarmConfig::once_at`]. Like any date match, the alarm fires again a
+    /// month later unless disabled or re-armed.
+    ///
+    /// The clock keeps running between the read and the write, so if it
+    /// ticks in between the alarm fires up to a second early relative to the
+    /// moment the alarm was written; with `seconds` = 0 the target may
+    /// already have passed and the alarm not fire until next month. Alarms
+    /// without a seconds register, such as DS3231 alarm 2, reject targets that
+    /// do not fall on second 0.
+    ///
+    /// Only available when the driver's error type can be built from a
+    /// `DateTimeError`, as for [`Rtc::set_unix_time`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication fails or the alarm cannot be
+    /// configured, or a `DateTimeError` converted into it if the target is
+    /// after [`DateTime::MAX`].
+    fn set_alarm_in_seconds(&mut self, id: AlarmId, seconds: u32) -> Result<DateTime, Self::Error>
+    where
+        Self::Error: From<DateTimeError>,
+    {
+        let target = self.get_datetime()?.checked_add_seconds(seconds.into())?;
+        self.set_alarm(id, &AlarmConfig::once_at(&target))?;
+        Ok(target)
+    }
+}
+
+/// blanket impl for all `&mut T`
+impl<T: Alarm + ?Sized> Alarm for &mut T {
+    #[inline]
+    fn alarm_count(&self) -> u8 {
+        T::alarm_count(self)
+    }
+
+    #[inline]
+    fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+        T::alarm_capabilities(self, id)
+    }
+
+    #[inline]
+    fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+        T::set_alarm(self, id, config)
+    }
+
+    #[inline]
+    fn disable_alarm(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::disable_alarm(self, id)
+    }
+
+    #[inline]
+    fn alarm_triggered(&mut self, id: AlarmId) -> Result<bool, Self::Error> {
+        T::alarm_triggered(self, id)
+    }
+
+    #[inline]
+    fn clear_alarm_flag(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::clear_alarm_flag(self, id)
+    }
+
+    #[inline]
+    fn enable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::enable_alarm_interrupt(self, id)
+    }
+
+    #[inline]
+    fn disable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::disable_alarm_interrupt(self, id)
+    }
+
+    #[inline]
+    fn disable_all_alarms(&mut self) -> Result<(), Self::Error> {
+        T::disable_all_alarms(self)
+    }
+
+    #[inline]
+    fn set_alarm_in_seconds(&mut self, id: AlarmId, seconds: u32) -> Result<DateTime, Self::Error>
+    where
+        Self::Error: From<DateTimeError>,
+    {
+        T::set_alarm_in_seconds(self, id, seconds)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Alarm + ?Sized> Alarm for alloc::boxed::Box<T> {
+    #[inline]
+    fn alarm_count(&self) -> u8 {
+        T::alarm_count(self)
+    }
+
+    #[inline]
+    fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+        T::alarm_capabilities(self, id)
+    }
+
+    #[inline]
+    fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+        T::set_alarm(self, id, config)
+    }
+
+    #[inline]
+    fn disable_alarm(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::disable_alarm(self, id)
+    }
+
+    #[inline]
+    fn alarm_triggered(&mut self, id: AlarmId) -> Result<bool, Self::Error> {
+        T::alarm_triggered(self, id)
+    }
+
+    #[inline]
+    fn clear_alarm_flag(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::clear_alarm_flag(self, id)
+    }
+
+    #[inline]
+    fn enable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::enable_alarm_interrupt(self, id)
+    }
+
+    #[inline]
+    fn disable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        T::disable_alarm_interrupt(self, id)
+    }
+
+    #[inline]
+    fn disable_all_alarms(&mut self) -> Result<(), Self::Error> {
+        T::disable_all_alarms(self)
+    }
+
+    #[inline]
+    fn set_alarm_in_seconds(&mut self, id: AlarmId, seconds: u32) -> Result<DateTime, Self::Error>
+    where
+        Self::Error: From<DateTimeError>,
+    {
+        T::set_alarm_in_seconds(self, id, seconds)
+    }
+}
+
+/// One alarm of an RTC, used without passing its [`AlarmId`] around
+///
+/// For chips with a single alarm, or applications that only use one:
+///
+/// ```
+/// use rtc_hal::alarm::{Alarm, AlarmConfig, SingleAlarm};
+///
+/// fn wake_daily<A: Alarm>(rtc: A) -> Result<SingleAlarm<A>, A::Error> {
+///     let mut alarm = SingleAlarm::new(rtc);
+///     alarm.set(&AlarmConfig::daily_at(7, 0, 0))?;
+///     Ok(alarm)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SingleAlarm<R> {
+    rtc: R,
+    id: AlarmId,
+}
+
+impl<R: Alarm> SingleAlarm<R> {
+    /// Use the first alarm of `rtc`
+    pub fn new(rtc: R) -> Self {
+        Self::with_id(rtc, AlarmId::ALARM1)
+    }
+
+    /// Use alarm `id` of `rtc`
+    pub fn with_id(rtc: R, id: AlarmId) -> Self {
+        Self { rtc, id }
+    }
+
+    /// The alarm this wrapper controls
+    pub fn id(&self) -> AlarmId {
+        self.id
+    }
+
+    /// Configure and arm the alarm, see [`Alarm::set_alarm`]
+    pub fn set(&mut self, config: &AlarmConfig) -> Result<(), R::Error> {
+        self.rtc.set_alarm(self.id, config)
+    }
+
+    /// Disarm the alarm, see [`Alarm::disable_alarm`]
+    pub fn disable(&mut self) -> Result<(), R::Error> {
+        self.rtc.disable_alarm(self.id)
+    }
+
+    /// Check whether the alarm fired, see [`Alarm::alarm_triggered`]
+    pub fn is_triggered(&mut self) -> Result<bool, R::Error> {
+        self.rtc.alarm_triggered(self.id)
+    }
+
+    /// Clear the triggered flag, see [`Alarm::clear_alarm_flag`]
+    pub fn clear(&mut self) -> Result<(), R::Error> {
+        self.rtc.clear_alarm_flag(self.id)
+    }
+
+    /// Route the alarm to the interrupt pin, see [`Alarm::enable_alarm_interrupt`]
+    pub fn enable_interrupt(&mut self) -> Result<(), R::Error> {
+        self.rtc.enable_alarm_interrupt(self.id)
+    }
+
+    /// Keep the interrupt pin quiet, see [`Alarm::disable_alarm_interrupt`]
+    pub fn disable_interrupt(&mut self) -> Result<(), R::Error> {
+        self.rtc.disable_alarm_interrupt(self.id)
+    }
+
+    /// Mutable access to the RTC, e.g. to read the time
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.rtc
+    }
+
+    /// Release the RTC
+    pub fn into_inner(self) -> R {
+        self.rtc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, ErrorKind, ErrorType};
+
+    /// Chip with two alarms like the DS3231, checked whenever the time is stepped
+    ///
+    /// Alarm 2 has no seconds register and always fires at second 0.
+    struct MockRtc {
+        now: DateTime,
+        alarms: [Option<AlarmConfig>; 2],
+        triggered: [bool; 2],
+        interrupts: [bool; 2],
+        /// Alarm whose flag cannot be cleared, to test error handling
+        stuck_flag: Option<AlarmId>,
+    }
+
+    impl MockRtc {
+        /// Level of the active-low INT pin
+        fn int_pin_low(&self) -> bool {
+            (0..2).any(|i| self.triggered[i] && self.interrupts[i])
+        }
+
+        fn tick(&mut self) {
+            self.now = self.now.checked_add_seconds(1).unwrap();
+            for (alarm, triggered) in self.alarms.iter().zip(&mut self.triggered) {
+                if alarm.is_some_and(|alarm| alarm.matches(&self.now)) {
+                    *triggered = true;
+                }
+            }
+        }
+    }
+
+    impl ErrorType for MockRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MockRtc {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(self.now)
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.now = *datetime;
+            Ok(())
+        }
+    }
+
+    impl Alarm for MockRtc {
+        fn alarm_count(&self) -> u8 {
+            2
+        }
+
+        fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+            match id {
+                AlarmId::ALARM1 | AlarmId::ALARM2 => AlarmCapabilities {
+                    seconds_match: id == AlarmId::ALARM1,
+                    weekday_match: true,
+                    date_match: true,
+                    interrupt_output: true,
+                },
+                _ => AlarmCapabilities::default(),
+            }
+        }
+
+        fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+            id.check(self.alarm_count())?;
+            config.validate()?;
+            if id == AlarmId::ALARM2 && config.second != Some(0) {
+                return Err(ErrorKind::InvalidAlarmConfig);
+            }
+            self.alarms[usize::from(id.0)] = Some(*config);
+            Ok(())
+        }
+
+        fn disable_alarm(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+            id.check(self.alarm_count())?;
+            self.alarms[usize::from(id.0)] = None;
+            Ok(())
+        }
+
+        fn alarm_triggered(&mut self, id: AlarmId) -> Result<bool, Self::Error> {
+            id.check(self.alarm_count())?;
+            Ok(self.triggered[usize::from(id.0)])
+        }
+
+        fn clear_alarm_flag(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+            id.check(self.alarm_count())?;
+            if self.stuck_flag == Some(id) {
+                return Err(ErrorKind::Bus);
+            }
+            self.triggered[usize::from(id.0)] = false;
+            Ok(())
+        }
+
+        fn enable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+            id.check(self.alarm_count())?;
+            self.interrupts[usize::from(id.0)] = true;
+            Ok(())
+        }
+
+        fn disable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+            id.check(self.alarm_count())?;
+            self.interrupts[usize::from(id.0)] = false;
+            Ok(())
+        }
+    }
+
+    fn at(day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(2024, 8, day, hour, minute, second).unwrap()
+    }
+
+    fn mock(now: DateTime) -> MockRtc {
+        MockRtc {
+            now,
+            alarms: [None; 2],
+            triggered: [false; 2],
+            interrupts: [false; 2],
+            stuck_flag: None,
+        }
+    }
+
+    /// Generic application code, as it would be written against any driver
+    fn wait_for_alarm<A: Alarm>(
+        alarm: &mut SingleAlarm<A>,
+        tick: impl Fn(&mut A),
+    ) -> Result<u32, A::Error> {
+        let mut seconds = 0;
+        while !alarm.is_triggered()? {
+            tick(alarm.inner_mut());
+            seconds += 1;
+        }
+        alarm.clear()?;
+        Ok(seconds)
+    }
+
+    #[test]
+    fn test_arm_trigger_clear() {
+        let mut alarm = SingleAlarm::new(mock(at(16, 6, 29, 0)));
+        alarm.set(&AlarmConfig::daily_at(6, 30, 0)).unwrap();
+        assert_eq!(alarm.is_triggered(), Ok(false));
+
+        assert_eq!(wait_for_alarm(&mut alarm, MockRtc::tick), Ok(60));
+        let rtc = alarm.inner_mut();
+        assert_eq!(rtc.get_datetime(), Ok(at(16, 6, 30, 0)));
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(false));
+
+        // Fires again the next day, but not once disabled
+        rtc.set_datetime(&at(17, 6, 29, 59)).unwrap();
+        rtc.tick();
+        assert_eq!(alarm.is_triggered(), Ok(true));
+        alarm.clear().unwrap();
+        alarm.disable().unwrap();
+        let rtc = alarm.inner_mut();
+        rtc.set_datetime(&at(18, 6, 29, 59)).unwrap();
+        rtc.tick();
+        assert_eq!(alarm.is_triggered(), Ok(false));
+    }
+
+    #[test]
+    fn test_two_alarms_are_independent() {
+        let mut rtc = mock(at(16, 6, 59, 0));
+        rtc.set_alarm(AlarmId::ALARM1, &AlarmConfig::every_minute_at(30))
+            .unwrap();
+        rtc.set_alarm(AlarmId::ALARM2, &AlarmConfig::daily_at(7, 0, 0))
+            .unwrap();
+        for _ in 0..30 {
+            rtc.tick();
+        }
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM2), Ok(false));
+        rtc.clear_alarm_flag(AlarmId::ALARM1).unwrap();
+
+        for _ in 0..30 {
+            rtc.tick();
+        }
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(false));
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM2), Ok(true));
+
+        // Clearing or disabling one leaves the other alone
+        rtc.disable_alarm(AlarmId::ALARM1).unwrap();
+        rtc.clear_alarm_flag(AlarmId::ALARM1).unwrap();
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM2), Ok(true));
+        assert!(rtc.alarms[1].is_some());
+    }
+
+    #[test]
+    fn test_flag_persists_until_cleared() {
+        let mut rtc = mock(at(16, 6, 59, 59));
+        rtc.set_alarm(AlarmId::ALARM1, &AlarmConfig::daily_at(7, 0, 0))
+            .unwrap();
+        rtc.set_alarm(AlarmId::ALARM2, &AlarmConfig::daily_at(7, 0, 0))
+            .unwrap();
+        rtc.tick();
+        for _ in 0..3 {
+            assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+        }
+        rtc.tick();
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+
+        rtc.clear_alarm_flag(AlarmId::ALARM1).unwrap();
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(false));
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM2), Ok(true));
+    }
+
+    #[test]
+    fn test_flag_set_with_interrupt_disabled() {
+        let mut alarm = SingleAlarm::with_id(mock(at(16, 6, 59, 59)), AlarmId::ALARM2);
+        alarm.set(&AlarmConfig::daily_at(7, 0, 0)).unwrap();
+        alarm.inner_mut().tick();
+        assert_eq!(alarm.is_triggered(), Ok(true));
+        assert!(!alarm.inner_mut().int_pin_low());
+
+        // Enabling with the flag set asserts the pin straight away
+        alarm.enable_interrupt().unwrap();
+        assert!(alarm.inner_mut().int_pin_low());
+        alarm.clear().unwrap();
+        assert!(!alarm.inner_mut().int_pin_low());
+
+        alarm.disable_interrupt().unwrap();
+        alarm.inner_mut().set_datetime(&at(17, 6, 59, 59)).unwrap();
+        alarm.inner_mut().tick();
+        assert_eq!(alarm.is_triggered(), Ok(true));
+        assert!(!alarm.inner_mut().int_pin_low());
+    }
+
+    #[test]
+    fn test_set_alarm_in_seconds() {
+        let mut rtc = mock(at(16, 12, 0, 0));
+        assert_eq!(
+            rtc.set_alarm_in_seconds(AlarmId::ALARM1, 600),
+            Ok(at(16, 12, 10, 0))
+        );
+        assert_eq!(rtc.alarms[0], Some(AlarmConfig::monthly_at(16, 12, 10, 0)));
+        for _ in 0..599 {
+            rtc.tick();
+        }
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(false));
+        rtc.tick();
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+    }
+
+    #[test]
+    fn test_set_alarm_in_seconds_rolls_over() {
+        // Past midnight at the end of the month
+        let mut rtc = mock(at(31, 23, 59, 30));
+        let target = DateTime::new(2024, 9, 1, 0, 1, 0).unwrap();
+        assert_eq!(arm_in(&mut rtc, AlarmId::ALARM2, 90), Ok(target));
+        assert_eq!(rtc.alarms[1], Some(AlarmConfig::once_at(&target)));
+        for _ in 0..90 {
+            rtc.tick();
+        }
+        assert_eq!(rtc.get_datetime(), Ok(target));
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM2), Ok(true));
+
+        // Alarm 2 cannot fire on second 30, and nothing is armed past MAX
+        assert_eq!(
+            rtc.set_alarm_in_seconds(AlarmId::ALARM2, 30),
+            Err(ErrorKind::InvalidAlarmConfig)
+        );
+        rtc.set_datetime(&DateTime::MAX).unwrap();
+        assert_eq!(
+            rtc.set_alarm_in_seconds(AlarmId::ALARM1, 1),
+            Err(ErrorKind::InvalidDateTime)
+        );
+        assert_eq!(rtc.alarms[0], None);
+    }
+
+    /// Arms through the `&mut T` blanket impl
+    fn arm_in<A>(mut rtc: A, id: AlarmId, seconds: u32) -> Result<DateTime, A::Error>
+    where
+        A: Alarm,
+        A::Error: From<DateTimeError>,
+    {
+        rtc.set_alarm_in_seconds(id, seconds)
+    }
+
+    #[test]
+    fn test_disable_all_alarms() {
+        let mut rtc = mock(at(16, 6, 59, 59));
+        for id in [AlarmId::ALARM1, AlarmId::ALARM2] {
+            rtc.set_alarm(id, &AlarmConfig::daily_at(7, 0, 0)).unwrap();
+            rtc.enable_alarm_interrupt(id).unwrap();
+        }
+        rtc.tick();
+        assert!(rtc.int_pin_low());
+
+        assert_eq!(rtc.disable_all_alarms(), Ok(()));
+        assert_eq!(rtc.alarms, [None; 2]);
+        assert_eq!(rtc.triggered, [false; 2]);
+        assert_eq!(rtc.interrupts, [false; 2]);
+        assert!(!rtc.int_pin_low());
+    }
+
+    #[test]
+    fn test_disable_all_alarms_continues_after_error() {
+        let mut rtc = mock(at(16, 6, 59, 59));
+        for id in [AlarmId::ALARM1, AlarmId::ALARM2] {
+            rtc.set_alarm(id, &AlarmConfig::daily_at(7, 0, 0)).unwrap();
+            rtc.enable_alarm_interrupt(id).unwrap();
+        }
+        rtc.tick();
+        rtc.stuck_flag = Some(AlarmId::ALARM1);
+
+        // Alarm 1's flag stays set, but it is off the pin and alarm 2 is torn down
+        assert_eq!(disable_all(&mut rtc), Err(ErrorKind::Bus));
+        assert_eq!(rtc.alarms, [None; 2]);
+        assert_eq!(rtc.triggered, [true, false]);
+        assert_eq!(rtc.interrupts, [false; 2]);
+        assert!(!rtc.int_pin_low());
+    }
+
+    /// Tears down through the `&mut T` blanket impl
+    fn disable_all<A: Alarm>(mut rtc: A) -> Result<(), A::Error> {
+        rtc.disable_all_alarms()
+    }
+
+    /// Arms through the `&mut T` blanket impl
+    fn arm<A: Alarm>(mut rtc: A, id: AlarmId, config: AlarmConfig) -> Result<(), A::Error> {
+        rtc.set_alarm(id, &config)
+    }
+
+    #[test]
+    fn test_driver_rejects_id_or_config() {
+        let mut rtc = mock(at(16, 0, 0, 0));
+        let rejected = [
+            (AlarmId::ALARM2, AlarmConfig::daily_at(6, 30, 15)),
+            (AlarmId::ALARM2, AlarmConfig::default()),
+            (AlarmId::ALARM1, AlarmConfig::daily_at(24, 0, 0)),
+            (AlarmId(2), AlarmConfig::daily_at(6, 30, 0)),
+        ];
+        for (id, config) in rejected {
+            assert_eq!(
+                arm(&mut rtc, id, config),
+                Err(ErrorKind::InvalidAlarmConfig)
+            );
+        }
+        assert_eq!(rtc.alarms, [None; 2]);
+        assert_eq!(
+            rtc.alarm_triggered(AlarmId(2)),
+            Err(ErrorKind::InvalidAlarmConfig)
+        );
+        assert_eq!(<&mut MockRtc as Alarm>::alarm_count(&&mut rtc), 2);
+    }
+
+    /// PCF8563-like chip: one alarm without seconds
+    struct MinuteAlarmRtc {
+        alarm: Option<AlarmConfig>,
+        interrupt: bool,
+    }
+
+    impl ErrorType for MinuteAlarmRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MinuteAlarmRtc {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(DateTime::MIN)
+        }
+
+        fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl Alarm for MinuteAlarmRtc {
+        fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+            if id != AlarmId::ALARM1 {
+                return AlarmCapabilities::default();
+            }
+            AlarmCapabilities {
+                weekday_match: true,
+                date_match: true,
+                interrupt_output: true,
+                ..AlarmCapabilities::default()
+            }
+        }
+
+        fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+            id.check(1)?;
+            if config.second != Some(0) {
+                return Err(ErrorKind::InvalidAlarmConfig);
+            }
+            self.alarm = Some(*config);
+            Ok(())
+        }
+
+        fn disable_alarm(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            self.alarm = None;
+            Ok(())
+        }
+
+        fn alarm_triggered(&mut self, _: AlarmId) -> Result<bool, Self::Error> {
+            Ok(false)
+        }
+
+        fn clear_alarm_flag(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn enable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            self.interrupt = true;
+            Ok(())
+        }
+
+        fn disable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            self.interrupt = false;
+            Ok(())
+        }
+    }
+
+    /// Generic code picking an interrupt-capable alarm, preferably one that
+    /// matches seconds, and rounding down to the minute otherwise
+    fn arm_wake_up<A: Alarm>(
+        rtc: &mut A,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Option<AlarmId>, A::Error> {
+        let Some(id) = (0..rtc.alarm_count())
+            .map(AlarmId)
+            .filter(|&id| rtc.alarm_capabilities(id).interrupt_output)
+            .min_by_key(|&id| !rtc.alarm_capabilities(id).seconds_match)
+        else {
+            return Ok(None);
+        };
+        let second = if rtc.alarm_capabilities(id).seconds_match {
+            second
+        } else {
+            0
+        };
+        rtc.set_alarm(id, &AlarmConfig::daily_at(hour, minute, second))?;
+        rtc.enable_alarm_interrupt(id)?;
+        Ok(Some(id))
+    }
+
+    #[test]
+    fn test_capabilities_drive_generic_code() {
+        let mut ds3231 = mock(at(16, 0, 0, 0));
+        assert_eq!(
+            arm_wake_up(&mut ds3231, 6, 30, 15),
+            Ok(Some(AlarmId::ALARM1))
+        );
+        assert_eq!(ds3231.alarms[0], Some(AlarmConfig::daily_at(6, 30, 15)));
+        assert!(ds3231.interrupts[0]);
+        assert_eq!(
+            ds3231.alarm_capabilities(AlarmId(2)),
+            AlarmCapabilities::default()
+        );
+
+        let mut pcf = MinuteAlarmRtc {
+            alarm: None,
+            interrupt: false,
+        };
+        assert_eq!(pcf.alarm_count(), 1);
+        assert_eq!(arm_wake_up(&mut pcf, 6, 30, 15), Ok(Some(AlarmId::ALARM1)));
+        assert_eq!(pcf.alarm, Some(AlarmConfig::daily_at(6, 30, 0)));
+        assert!(pcf.interrupt);
+
+        // Forwarded through the `&mut T` blanket impl
+        assert_eq!(
+            <&mut MinuteAlarmRtc as Alarm>::alarm_capabilities(&&mut pcf, AlarmId::ALARM1),
+            pcf.alarm_capabilities(AlarmId::ALARM1)
+        );
+        assert_eq!(
+            pcf.alarm_capabilities(AlarmId::ALARM2),
+            AlarmCapabilities::default()
+        );
+    }
+
+    #[test]
+    fn test_alarm_id() {
+        assert_eq!(AlarmId::ALARM2.check(2), Ok(AlarmId::ALARM2));
+        assert_eq!(
+            AlarmId::ALARM2.check(1),
+            Err(AlarmConfigError::InvalidAlarmId)
+        );
+        assert_eq!(AlarmId(0).check(0), Err(AlarmConfigError::InvalidAlarmId));
+        assert_eq!(AlarmId::ALARM1.to_string(), "alarm 1");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use serde_test::{Token, assert_de_tokens, assert_de_tokens_error, assert_tokens};
+
+        for (index, mode) in AlarmMatch::ALL.into_iter().enumerate() {
+            assert_eq!(mode as usize, index);
+            assert_tokens(&mode, &[Token::Str(AlarmMatch::NAMES[index])]);
+        }
+        for (index, error) in AlarmConfigError::ALL.into_iter().enumerate() {
+            assert_eq!(error as usize, index);
+            assert_tokens(&error, &[Token::Str(AlarmConfigError::NAMES[index])]);
+        }
+        assert_tokens(&AlarmId::ALARM2, &[Token::U8(1)]);
+
+        let struct_tokens = |weekdays: &[Token]| {
+            let mut tokens = vec![
+                Token::Struct {
+                    name: "AlarmConfig",
+                    len: 5,
+                },
+                Token::Str("second"),
+                Token::Some,
+                Token::U8(0),
+                Token::Str("minute"),
+                Token::Some,
+                Token::U8(30),
+                Token::Str("hour"),
+                Token::Some,
+                Token::U8(6),
+                Token::Str("day_of_month"),
+                Token::None,
+                Token::Str("weekdays"),
+            ];
+            tokens.extend_from_slice(weekdays);
+            tokens.push(Token::StructEnd);
+            tokens
+        };
+        assert_tokens(
+            &AlarmConfig::daily_at(6, 30, 0),
+            &struct_tokens(&[Token::None]),
+        );
+        assert_tokens(
+            &AlarmConfig::on_weekdays_at(
+                WeekdaySet::from_iter([Weekday::Monday, Weekday::Friday]),
+                6,
+                30,
+                0,
+            ),
+            &struct_tokens(&[
+                Token::Some,
+                Token::Seq { len: Some(2) },
+                Token::Str("Monday"),
+                Token::Str("Friday"),
+                Token::SeqEnd,
+            ]),
+        );
+        assert_de_tokens(
+            &AlarmConfig::hourly_at_minute(15),
+            &[
+                Token::Map { len: None },
+                Token::Str("minute"),
+                Token::Some,
+                Token::U8(15),
+                Token::Str("second"),
+                Token::Some,
+                Token::U8(0),
+                Token::MapEnd,
+            ],
+        );
+        assert_de_tokens_error::<AlarmConfig>(
+            &[Token::Map { len: None }, Token::Str("weekday")],
+            "unknown field `weekday`, expected one of `second`, `minute`, `hour`, \
+             `day_of_month`, `weekdays`",
+        );
+
+        assert_tokens(
+            &AlarmCapabilities {
+                seconds_match: true,
+                weekday_match: false,
+                date_match: true,
+                interrupt_output: true,
+            },
+            &[
+                Token::Struct {
+                    name: "AlarmCapabilities",
+                    len: 4,
+                },
+                Token::Str("seconds_match"),
+                Token::Bool(true),
+                Token::Str("weekday_match"),
+                Token::Bool(false),
+                Token::Str("date_match"),
+                Token::Bool(true),
+                Token::Str("interrupt_output"),
+                Token::Bool(true),
+                Token::StructEnd,
+            ],
+        );
+        assert_de_tokens(
+            &AlarmCapabilities::default(),
+            &[Token::Map { len: Some(0) }, Token::MapEnd],
+        );
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<AlarmConfig>();
+        assert_format::<AlarmConfigBuilder>();
+        assert_format::<AlarmConfigError>();
+        assert_format::<AlarmMatch>();
+        assert_format::<AlarmId>();
+        assert_format::<AlarmCapabilities>();
+        assert_format::<Recurrence>();
+        assert_format::<Schedule>();
+        assert_format::<WeekdaySet>();
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(AlarmConfig::default().validate(), Ok(()));
+        assert_eq!(AlarmConfig::monthly_at(31, 23, 59, 59).validate(), Ok(()));
+        assert_eq!(
+            AlarmConfig::weekly_at(Weekday::Sunday, 23, 59, 59).validate(),
+            Ok(())
+        );
+        let cases = [
+            (
+                AlarmConfig::daily_at(0, 0, 60),
+                AlarmConfigError::InvalidSecond,
+            ),
+            (
+                AlarmConfig::daily_at(0, 60, 0),
+                AlarmConfigError::InvalidMinute,
+            ),
+            (
+                AlarmConfig::daily_at(24, 0, 0),
+                AlarmConfigError::InvalidHour,
+            ),
+            (
+                AlarmConfig::monthly_at(0, 0, 0, 0),
+                AlarmConfigError::InvalidDay,
+            ),
+            (
+                AlarmConfig::monthly_at(32, 0, 0, 0),
+                AlarmConfigError::InvalidDay,
+            ),
+        ];
+        for (config, error) in cases {
+            assert_eq!(config.validate(), Err(error));
+            assert_eq!(error.kind(), ErrorKind::InvalidAlarmConfig);
+        }
+    }
+
+    #[test]
+    fn test_weekday_sets() {
+        let mwf = WeekdaySet::single(Weekday::Monday)
+            .union(WeekdaySet::single(Weekday::Wednesday))
+            .union(WeekdaySet::single(Weekday::Friday));
+        let config = AlarmConfig::on_weekdays_at(mwf, 6, 0, 0);
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(
+            config.match_mode(),
+            Ok(AlarmMatch::WeekdayHoursMinutesSeconds)
+        );
+
+        // Single-weekday hardware
+        assert_eq!(
+            config.single_weekday(),
+            Err(AlarmConfigError::MultipleWeekdays)
+        );
+        assert_eq!(
+            AlarmConfig::weekly_at(Weekday::Friday, 6, 0, 0).single_weekday(),
+            Ok(Some(Weekday::Friday))
+        );
+        assert_eq!(AlarmConfig::daily_at(6, 0, 0).single_weekday(), Ok(None));
+
+        // An empty set never fires
+        let never = AlarmConfig::on_weekdays_at(WeekdaySet::EMPTY, 6, 0, 0);
+        assert_eq!(never.validate(), Err(AlarmConfigError::EmptyWeekdaySet));
+        assert_eq!(
+            never.single_weekday(),
+            Err(AlarmConfigError::EmptyWeekdaySet)
+        );
+        assert_eq!(never.next_trigger(&at(16, 0, 0, 0)).ok(), None);
+    }
+
+    #[test]
+    fn test_next_occurrence_on_weekdays() {
+        // 2024-08-16 is a Friday
+        let next = |days, now| {
+            AlarmConfig::on_weekdays_at(days, 6, 0, 0)
+                .next_trigger(&now)
+                .ok()
+        };
+        let mwf = WeekdaySet::single(Weekday::Monday)
+            .union(WeekdaySet::single(Weekday::Wednesday))
+            .union(WeekdaySet::single(Weekday::Friday));
+        assert_eq!(next(mwf, at(16, 5, 0, 0)), Some(at(16, 6, 0, 0)));
+        // Today's time has passed, so the next day in the set
+        assert_eq!(next(mwf, at(16, 6, 0, 0)), Some(at(19, 6, 0, 0)));
+        assert_eq!(next(mwf, at(19, 7, 0, 0)), Some(at(21, 6, 0, 0)));
+        // Today is not in the set
+        assert_eq!(
+            next(WeekdaySet::WEEKEND, at(16, 5, 0, 0)),
+            Some(at(17, 6, 0, 0))
+        );
+        assert_eq!(
+            next(WeekdaySet::WEEKDAYS, at(17, 5, 0, 0)),
+            Some(at(19, 6, 0, 0))
+        );
+        assert_eq!(
+            next(WeekdaySet::single(Weekday::Thursday), at(16, 5, 0, 0)),
+            Some(at(22, 6, 0, 0))
+        );
+        assert_eq!(
+            next(WeekdaySet::ALL, at(16, 6, 0, 0)),
+            Some(at(17, 6, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_validate_ranges_allows_day_and_weekday() {
+        let friday_13th = AlarmConfig {
+            weekdays: Some(WeekdaySet::single(Weekday::Friday)),
+            ..AlarmConfig::monthly_at(13, 0, 0, 0)
+        };
+        assert_eq!(friday_13th.validate_ranges(), Ok(()));
+        assert_eq!(
+            AlarmConfig {
+                weekdays: Some(WeekdaySet::single(Weekday::Friday)),
+                ..AlarmConfig::monthly_at(32, 0, 0, 0)
+            }
+            .validate_ranges(),
+            Err(AlarmConfigError::InvalidDay)
+        );
+        // 2024-09-13 and 2024-12-13 are Fridays
+        assert_eq!(
+            friday_13th.next_trigger(&at(16, 0, 0, 0)).ok(),
+            DateTime::new(2024, 9, 13, 0, 0, 0).ok()
+        );
+        assert_eq!(
+            friday_13th
+                .next_trigger(&DateTime::new(2024, 9, 13, 0, 0, 0).unwrap())
+                .ok(),
+            DateTime::new(2024, 12, 13, 0, 0, 0).ok()
+        );
+    }
+
+    #[test]
+    fn test_constructors() {
+        let any = AlarmConfig::default();
+        assert_eq!(
+            AlarmConfig::every_minute_at(30),
+            AlarmConfig {
+                second: Some(30),
+                ..any
+            }
+        );
+        assert_eq!(
+            AlarmConfig::hourly_at_minute(15),
+            AlarmConfig {
+                second: Some(0),
+                minute: Some(15),
+                ..any
+            }
+        );
+        assert_eq!(
+            AlarmConfig::daily_at(7, 0, 0),
+            AlarmConfig {
+                second: Some(0),
+                minute: Some(0),
+                hour: Some(7),
+                ..any
+            }
+        );
+        assert_eq!(
+            AlarmConfig::weekly_at(Weekday::Monday, 7, 0, 0),
+            AlarmConfig {
+                weekdays: Some(WeekdaySet::single(Weekday::Monday)),
+                ..AlarmConfig::daily_at(7, 0, 0)
+            }
+        );
+        assert_eq!(
+            AlarmConfig::monthly_at(1, 7, 0, 0),
+            AlarmConfig {
+                day_of_month: Some(1),
+                ..AlarmConfig::daily_at(7, 0, 0)
+            }
+        );
+    }
+
+    #[test]
+    fn test_mask_bits_ds3231() {
+        // DS3231 datasheet, table 2 "Alarm Mask Bits"
+        let table = [
+            (AlarmMatch::EverySecond, [1, 1, 1, 1], false),
+            (AlarmMatch::Seconds, [0, 1, 1, 1], false),
+            (AlarmMatch::MinutesSeconds, [0, 0, 1, 1], false),
+            (AlarmMatch::HoursMinutesSeconds, [0, 0, 0, 1], false),
+            (AlarmMatch::DateHoursMinutesSeconds, [0, 0, 0, 0], false),
+            (AlarmMatch::WeekdayHoursMinutesSeconds, [0, 0, 0, 0], true),
+        ];
+        for (mode, bits, dy) in table {
+            assert_eq!(mode.mask_bits_ds3231(), bits.map(|b| b == 1), "{mode:?}");
+            assert_eq!(mode.uses_weekday(), dy, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn test_match_mode() {
+        let cases = [
+            (AlarmConfig::default(), AlarmMatch::EverySecond),
+            (AlarmConfig::every_minute_at(30), AlarmMatch::Seconds),
+            (
+                AlarmConfig::hourly_at_minute(15),
+                AlarmMatch::MinutesSeconds,
+            ),
+            (
+                AlarmConfig::daily_at(7, 0, 0),
+                AlarmMatch::HoursMinutesSeconds,
+            ),
+            (
+                AlarmConfig::monthly_at(1, 7, 0, 0),
+                AlarmMatch::DateHoursMinutesSeconds,
+            ),
+            (
+                AlarmConfig::weekly_at(Weekday::Monday, 7, 0, 0),
+                AlarmMatch::WeekdayHoursMinutesSeconds,
+            ),
+        ];
+        for (config, mode) in cases {
+            assert_eq!(config.match_mode(), Ok(mode));
+        }
+
+        let unsupported = [
+            AlarmConfig {
+                second: None,
+                ..AlarmConfig::hourly_at_minute(15)
+            },
+            AlarmConfig {
+                minute: None,
+                ..AlarmConfig::daily_at(7, 0, 0)
+            },
+            AlarmConfig {
+                hour: None,
+                ..AlarmConfig::monthly_at(1, 7, 0, 0)
+            },
+            AlarmConfig {
+                day_of_month: Some(13),
+                ..AlarmConfig::weekly_at(Weekday::Friday, 0, 0, 0)
+            },
+        ];
+        for config in unsupported {
+            assert_eq!(config.match_mode(), Err(AlarmConfigError::UnsupportedMatch));
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let config = AlarmConfig {
+            minute: Some(15),
+            ..AlarmConfig::default()
+        };
+        assert!(config.matches(&at(16, 3, 15, 0)));
+        assert!(config.matches(&at(16, 3, 15, 59)));
+        assert!(!config.matches(&at(16, 3, 16, 0)));
+        assert!(AlarmConfig::default().matches(&at(1, 0, 0, 0)));
+        assert!(AlarmConfig::monthly_at(16, 12, 0, 0).matches(&at(16, 12, 0, 0)));
+        assert!(!AlarmConfig::monthly_at(16, 12, 0, 0).matches(&at(17, 12, 0, 0)));
+        // 2024-08-16 is a Friday
+        let friday = AlarmConfig::weekly_at(Weekday::Friday, 12, 0, 0);
+        assert!(friday.matches(&at(16, 12, 0, 0)));
+        assert!(!friday.matches(&at(17, 12, 0, 0)));
+        assert!(friday.matches(&at(23, 12, 0, 0)));
+        assert!(AlarmConfig::hourly_at_minute(15).matches(&at(16, 3, 15, 0)));
+        assert!(!AlarmConfig::hourly_at_minute(15).matches(&at(16, 3, 15, 1)));
+    }
+
+    fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn test_next_trigger() {
+        let friday = WeekdaySet::single(Weekday::Friday);
+        #[rustfmt::skip]
+        let cases = [
+            // Every second
+            (AlarmConfig::default(), dt(2024, 8, 16, 12, 0, 0), dt(2024, 8, 16, 12, 0, 1)),
+            (AlarmConfig::default(), dt(2024, 12, 31, 23, 59, 59), dt(2025, 1, 1, 0, 0, 0)),
+            // Seconds
+            (AlarmConfig::every_minute_at(30), dt(2024, 8, 16, 12, 0, 29), dt(2024, 8, 16, 12, 0, 30)),
+            (AlarmConfig::every_minute_at(30), dt(2024, 8, 16, 12, 0, 30), dt(2024, 8, 16, 12, 1, 30)),
+            (AlarmConfig::every_minute_at(0), dt(2024, 8, 16, 23, 59, 59), dt(2024, 8, 17, 0, 0, 0)),
+            // Minutes and seconds
+            (AlarmConfig::hourly_at_minute(15), dt(2024, 8, 16, 12, 14, 0), dt(2024, 8, 16, 12, 15, 0)),
+            (AlarmConfig::hourly_at_minute(15), dt(2024, 8, 16, 12, 15, 0), dt(2024, 8, 16, 13, 15, 0)),
+            (AlarmConfig::hourly_at_minute(15), dt(2024, 8, 16, 23, 30, 0), dt(2024, 8, 17, 0, 15, 0)),
+            // Daily: today or tomorrow
+            (AlarmConfig::daily_at(7, 0, 0), dt(2024, 8, 16, 6, 59, 59), dt(2024, 8, 16, 7, 0, 0)),
+            (AlarmConfig::daily_at(7, 0, 0), dt(2024, 8, 16, 7, 0, 0), dt(2024, 8, 17, 7, 0, 0)),
+            (AlarmConfig::daily_at(0, 0, 0), dt(2024, 2, 28, 12, 0, 0), dt(2024, 2, 29, 0, 0, 0)),
+            (AlarmConfig::daily_at(0, 0, 0), dt(2023, 12, 31, 0, 0, 0), dt(2024, 1, 1, 0, 0, 0)),
+            // Day of month: this month, or the next month that has the day
+            (AlarmConfig::monthly_at(20, 8, 0, 0), dt(2024, 8, 16, 12, 0, 0), dt(2024, 8, 20, 8, 0, 0)),
+            (AlarmConfig::monthly_at(16, 8, 0, 0), dt(2024, 8, 16, 12, 0, 0), dt(2024, 9, 16, 8, 0, 0)),
+            (AlarmConfig::monthly_at(16, 13, 0, 0), dt(2024, 8, 16, 12, 0, 0), dt(2024, 8, 16, 13, 0, 0)),
+            (AlarmConfig::monthly_at(1, 0, 0, 0), dt(2024, 12, 1, 0, 0, 0), dt(2025, 1, 1, 0, 0, 0)),
+            (AlarmConfig::monthly_at(31, 8, 0, 0), dt(2024, 8, 31, 9, 0, 0), dt(2024, 10, 31, 8, 0, 0)),
+            (AlarmConfig::monthly_at(31, 8, 0, 0), dt(2024, 1, 31, 9, 0, 0), dt(2024, 3, 31, 8, 0, 0)),
+            (AlarmConfig::monthly_at(30, 8, 0, 0), dt(2024, 1, 30, 9, 0, 0), dt(2024, 3, 30, 8, 0, 0)),
+            // Day 29: February only in leap years
+            (AlarmConfig::monthly_at(29, 8, 0, 0), dt(2024, 1, 29, 9, 0, 0), dt(2024, 2, 29, 8, 0, 0)),
+            (AlarmConfig::monthly_at(29, 8, 0, 0), dt(2023, 1, 29, 9, 0, 0), dt(2023, 3, 29, 8, 0, 0)),
+            (AlarmConfig::monthly_at(29, 8, 0, 0), dt(2100, 1, 29, 9, 0, 0), dt(2100, 3, 29, 8, 0, 0)),
+            (AlarmConfig::monthly_at(29, 8, 0, 0), dt(2000, 1, 29, 9, 0, 0), dt(2000, 2, 29, 8, 0, 0)),
+            // Weekdays (2024-08-16 is a Friday)
+            (AlarmConfig::on_weekdays_at(friday, 7, 0, 0), dt(2024, 8, 16, 6, 0, 0), dt(2024, 8, 16, 7, 0, 0)),
+            (AlarmConfig::on_weekdays_at(friday, 7, 0, 0), dt(2024, 8, 16, 7, 0, 0), dt(2024, 8, 23, 7, 0, 0)),
+            (AlarmConfig::weekly_at(Weekday::Monday, 7, 0, 0), dt(2024, 8, 16, 6, 0, 0), dt(2024, 8, 19, 7, 0, 0)),
+            (AlarmConfig::weekly_at(Weekday::Thursday, 7, 0, 0), dt(2024, 8, 16, 6, 0, 0), dt(2024, 8, 22, 7, 0, 0)),
+            (AlarmConfig::on_weekdays_at(WeekdaySet::WEEKDAYS, 7, 0, 0), dt(2024, 8, 16, 8, 0, 0), dt(2024, 8, 19, 7, 0, 0)),
+            (AlarmConfig::weekly_at(Weekday::Wednesday, 0, 0, 0), dt(2024, 12, 31, 12, 0, 0), dt(2025, 1, 1, 0, 0, 0)),
+        ];
+        for (config, now, expected) in cases {
+            assert_eq!(
+                config.next_trigger(&now),
+                Ok(expected),
+                "{config:?} after {now:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_trigger_errors() {
+        let now = dt(2024, 8, 16, 12, 0, 0);
+        let cases = [
+            (
+                AlarmConfig::every_minute_at(60),
+                DateTimeError::InvalidSecond,
+            ),
+            (
+                AlarmConfig::hourly_at_minute(60),
+                DateTimeError::InvalidMinute,
+            ),
+            (AlarmConfig::daily_at(24, 0, 0), DateTimeError::InvalidHour),
+            (
+                AlarmConfig::monthly_at(32, 0, 0, 0),
+                DateTimeError::InvalidDay,
+            ),
+            (
+                AlarmConfig::on_weekdays_at(WeekdaySet::EMPTY, 0, 0, 0),
+                DateTimeError::InvalidWeekday,
+            ),
+        ];
+        for (config, error) in cases {
+            assert_eq!(config.next_trigger(&now), Err(error), "{config:?}");
+        }
+        assert_eq!(
+            AlarmConfig::default().next_trigger(&DateTime::MAX),
+            Err(DateTimeError::InvalidYear)
+        );
+        assert_eq!(
+            AlarmConfig::monthly_at(31, 0, 0, 0).next_trigger(&DateTime::MAX),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn test_next_trigger_agrees_with_search() {
+        let configs = [
+            AlarmConfig::default(),
+            AlarmConfig::every_minute_at(0),
+            AlarmConfig::hourly_at_minute(59),
+            AlarmConfig::daily_at(23, 59, 59),
+            AlarmConfig::monthly_at(31, 0, 0, 0),
+            AlarmConfig::monthly_at(29, 12, 30, 0),
+            AlarmConfig::on_weekdays_at(WeekdaySet::WEEKEND, 6, 0, 0),
+        ];
+        let times = crate::testing::RandomDateTimes::new(368).with_years(1970, 2200);
+        for now in times.take(200) {
+            for config in &configs {
+                assert_eq!(
+                    config.next_trigger(&now).ok(),
+                    config.search_after(&now),
+                    "{config:?} after {now:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let next = |config: AlarmConfig, now| config.next_trigger(&now).ok();
+        assert_eq!(
+            next(AlarmConfig::default(), at(16, 23, 59, 59)),
+            Some(at(17, 0, 0, 0))
+        );
+        assert_eq!(
+            next(AlarmConfig::daily_at(6, 30, 0), at(16, 6, 30, 0)),
+            Some(at(17, 6, 30, 0))
+        );
+        assert_eq!(
+            next(AlarmConfig::daily_at(6, 30, 0), at(16, 6, 29, 59)),
+            Some(at(16, 6, 30, 0))
+        );
+        let quarter_past = AlarmConfig::hourly_at_minute(15);
+        assert_eq!(next(quarter_past, at(16, 3, 15, 0)), Some(at(16, 4, 15, 0)));
+        assert_eq!(
+            next(quarter_past, at(16, 3, 14, 59)),
+            Some(at(16, 3, 15, 0))
+        );
+        // Day 31 skips September
+        assert_eq!(
+            next(AlarmConfig::monthly_at(31, 8, 0, 0), at(31, 9, 0, 0)),
+            DateTime::new(2024, 10, 31, 8, 0, 0).ok()
+        );
+        assert_eq!(
+            next(
+                AlarmConfig::weekly_at(Weekday::Friday, 12, 0, 0),
+                at(16, 12, 0, 0)
+            ),
+            Some(at(23, 12, 0, 0))
+        );
+        assert_eq!(next(AlarmConfig::daily_at(24, 0, 0), at(16, 0, 0, 0)), None);
+    }
+}
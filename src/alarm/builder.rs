@@ -0,0 +1,293 @@
+//! Checked construction of [`AlarmConfig`]
+use crate::datetime::WeekdaySet;
+
+use super::{AlarmConfig, AlarmConfigError, AlarmMatch};
+
+/// Builder for an [`AlarmConfig`] that is checked as a whole by
+/// [`build`](Self::build)
+///
+/// The time is given with [`at_time`](Self::at_time) and the day, if any,
+/// with [`on_day`](Self::on_day) or [`on_weekdays`](Self::on_weekdays). The
+/// match mode follows from what is set, or can be chosen with
+/// [`match_mode`](Self::match_mode), which compares only the time fields the
+/// mode uses. Every day:
+///
+/// ```
+/// use rtc_hal::alarm::AlarmConfig;
+///
+/// let config = AlarmConfig::builder().at_time(7, 30, 0).build()?;
+/// assert_eq!(config, AlarmConfig::daily_at(7, 30, 0));
+/// # Ok::<(), rtc_hal::alarm::AlarmConfigError>(())
+/// ```
+///
+/// Every hour, at minute 15:
+///
+/// ```
+/// use rtc_hal::alarm::{AlarmConfig, AlarmMatch};
+///
+/// let config = AlarmConfig::builder()
+///     .at_time(0, 15, 0)
+///     .match_mode(AlarmMatch::MinutesSeconds)
+///     .build()?;
+/// assert_eq!(config, AlarmConfig::hourly_at_minute(15));
+/// # Ok::<(), rtc_hal::alarm::AlarmConfigError>(())
+/// ```
+///
+/// On the 1st of every month at midnight:
+///
+/// ```
+/// use rtc_hal::alarm::AlarmConfig;
+///
+/// let config = AlarmConfig::builder().on_day(1).at_time(0, 0, 0).build()?;
+/// assert_eq!(config, AlarmConfig::monthly_at(1, 0, 0, 0));
+/// # Ok::<(), rtc_hal::alarm::AlarmConfigError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmConfigBuilder {
+    time: Option<(u8, u8, u8)>,
+    day_of_month: Option<u8>,
+    weekdays: Option<WeekdaySet>,
+    mode: Option<AlarmMatch>,
+}
+
+impl AlarmConfig {
+    /// Start building an alarm configuration, see [`AlarmConfigBuilder`]
+    pub fn builder() -> AlarmConfigBuilder {
+        AlarmConfigBuilder::default()
+    }
+}
+
+impl AlarmConfigBuilder {
+    /// Fire at `hour:minute:second`
+    pub fn at_time(mut self, hour: u8, minute: u8, second: u8) -> Self {
+        self.time = Some((hour, minute, second));
+        self
+    }
+
+    /// Fire on `day_of_month` (1-31)
+    pub fn on_day(mut self, day_of_month: u8) -> Self {
+        self.day_of_month = Some(day_of_month);
+        self
+    }
+
+    /// Fire on the days in `weekdays`
+    pub fn on_weekdays(mut self, weekdays: WeekdaySet) -> Self {
+        self.weekdays = Some(weekdays);
+        self
+    }
+
+    /// Compare only the fields of `mode`, instead of every field that is set
+    pub fn match_mode(mut self, mode: AlarmMatch) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Check the combination and build the configuration.
+    ///
+    /// # Errors
+    ///
+    /// - `AlarmConfigError::DayAndWeekday` if both a day of month and weekdays are set
+    /// - `AlarmConfigError::MatchModeConflict` if a day is set that the match
+    ///   mode does not compare
+    /// - `AlarmConfigError::MissingTime` if a day or a match mode that compares
+    ///   the time is set without a time
+    /// - `AlarmConfigError::MissingDay` if the match mode compares a day that is not set
+    /// - the error of [`AlarmConfig::validate`] for a value out of range or an
+    ///   empty weekday set
+    pub fn build(self) -> Result<AlarmConfig, AlarmConfigError> {
+        if self.day_of_month.is_some() && self.weekdays.is_some() {
+            return Err(AlarmConfigError::DayAndWeekday);
+        }
+        let mode = match self.mode {
+            Some(mode) => mode,
+            None if self.day_of_month.is_some() => AlarmMatch::DateHoursMinutesSeconds,
+            None if self.weekdays.is_some() => AlarmMatch::WeekdayHoursMinutesSeconds,
+            None if self.time.is_some() => AlarmMatch::HoursMinutesSeconds,
+            None => AlarmMatch::EverySecond,
+        };
+
+        let (day_of_month, weekdays) = match mode {
+            AlarmMatch::DateHoursMinutesSeconds if self.weekdays.is_some() => {
+                return Err(AlarmConfigError::MatchModeConflict);
+            }
+            AlarmMatch::DateHoursMinutesSeconds => (
+                Some(self.day_of_month.ok_or(AlarmConfigError::MissingDay)?),
+                None,
+            ),
+            AlarmMatch::WeekdayHoursMinutesSeconds if self.day_of_month.is_some() => {
+                return Err(AlarmConfigError::MatchModeConflict);
+            }
+            AlarmMatch::WeekdayHoursMinutesSeconds => (
+                None,
+                Some(self.weekdays.ok_or(AlarmConfigError::MissingDay)?),
+            ),
+            _ if self.day_of_month.is_some() || self.weekdays.is_some() => {
+                return Err(AlarmConfigError::MatchModeConflict);
+            }
+            _ => (None, None),
+        };
+
+        let (hour, minute, second) = match (mode, self.time) {
+            (AlarmMatch::EverySecond, _) => (None, None, None),
+            (_, None) => return Err(AlarmConfigError::MissingTime),
+            (AlarmMatch::Seconds, Some((_, _, s))) => (None, None, Some(s)),
+            (AlarmMatch::MinutesSeconds, Some((_, m, s))) => (None, Some(m), Some(s)),
+            (_, Some((h, m, s))) => (Some(h), Some(m), Some(s)),
+        };
+
+        let config = AlarmConfig {
+            second,
+            minute,
+            hour,
+            day_of_month,
+            weekdays,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::Weekday;
+
+    #[test]
+    fn test_inferred_modes() {
+        let builder = AlarmConfig::builder();
+        assert_eq!(builder.build(), Ok(AlarmConfig::default()));
+        assert_eq!(
+            builder.at_time(7, 0, 0).build(),
+            Ok(AlarmConfig::daily_at(7, 0, 0))
+        );
+        assert_eq!(
+            builder.on_day(31).at_time(7, 0, 0).build(),
+            Ok(AlarmConfig::monthly_at(31, 7, 0, 0))
+        );
+        assert_eq!(
+            builder
+                .on_weekdays(WeekdaySet::WEEKDAYS)
+                .at_time(7, 0, 0)
+                .build(),
+            Ok(AlarmConfig::on_weekdays_at(WeekdaySet::WEEKDAYS, 7, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_explicit_modes() {
+        let at = AlarmConfig::builder().at_time(7, 15, 30);
+        let cases = [
+            (AlarmMatch::EverySecond, AlarmConfig::default()),
+            (AlarmMatch::Seconds, AlarmConfig::every_minute_at(30)),
+            (
+                AlarmMatch::MinutesSeconds,
+                AlarmConfig {
+                    second: Some(30),
+                    ..AlarmConfig::hourly_at_minute(15)
+                },
+            ),
+            (
+                AlarmMatch::HoursMinutesSeconds,
+                AlarmConfig::daily_at(7, 15, 30),
+            ),
+        ];
+        for (mode, expected) in cases {
+            let config = at.match_mode(mode).build();
+            assert_eq!(config, Ok(expected));
+            assert_eq!(config.unwrap().match_mode(), Ok(mode));
+        }
+        assert_eq!(
+            at.on_weekdays(WeekdaySet::single(Weekday::Sunday))
+                .match_mode(AlarmMatch::WeekdayHoursMinutesSeconds)
+                .build(),
+            Ok(AlarmConfig::weekly_at(Weekday::Sunday, 7, 15, 30))
+        );
+    }
+
+    #[test]
+    fn test_rejected_combinations() {
+        let builder = AlarmConfig::builder();
+        let cases = [
+            (
+                builder
+                    .on_day(1)
+                    .on_weekdays(WeekdaySet::ALL)
+                    .at_time(0, 0, 0),
+                AlarmConfigError::DayAndWeekday,
+            ),
+            (builder.on_day(1), AlarmConfigError::MissingTime),
+            (
+                builder.on_weekdays(WeekdaySet::WEEKEND),
+                AlarmConfigError::MissingTime,
+            ),
+            (
+                builder.match_mode(AlarmMatch::Seconds),
+                AlarmConfigError::MissingTime,
+            ),
+            (
+                builder
+                    .at_time(0, 0, 0)
+                    .match_mode(AlarmMatch::DateHoursMinutesSeconds),
+                AlarmConfigError::MissingDay,
+            ),
+            (
+                builder
+                    .at_time(0, 0, 0)
+                    .match_mode(AlarmMatch::WeekdayHoursMinutesSeconds),
+                AlarmConfigError::MissingDay,
+            ),
+            (
+                builder
+                    .on_day(1)
+                    .at_time(0, 0, 0)
+                    .match_mode(AlarmMatch::HoursMinutesSeconds),
+                AlarmConfigError::MatchModeConflict,
+            ),
+            (
+                builder
+                    .on_weekdays(WeekdaySet::ALL)
+                    .match_mode(AlarmMatch::EverySecond),
+                AlarmConfigError::MatchModeConflict,
+            ),
+            (
+                builder
+                    .on_day(1)
+                    .at_time(0, 0, 0)
+                    .match_mode(AlarmMatch::WeekdayHoursMinutesSeconds),
+                AlarmConfigError::MatchModeConflict,
+            ),
+            (
+                builder
+                    .on_weekdays(WeekdaySet::ALL)
+                    .at_time(0, 0, 0)
+                    .match_mode(AlarmMatch::DateHoursMinutesSeconds),
+                AlarmConfigError::MatchModeConflict,
+            ),
+            (builder.at_time(24, 0, 0), AlarmConfigError::InvalidHour),
+            (
+                builder.on_day(0).at_time(0, 0, 0),
+                AlarmConfigError::InvalidDay,
+            ),
+            (
+                builder.on_weekdays(WeekdaySet::EMPTY).at_time(0, 0, 0),
+                AlarmConfigError::EmptyWeekdaySet,
+            ),
+        ];
+        for (builder, error) in cases {
+            assert_eq!(builder.build(), Err(error), "{builder:?}");
+        }
+    }
+
+    #[test]
+    fn test_unused_time_fields_not_checked() {
+        // Only the compared fields are kept, so they are the only ones validated
+        assert_eq!(
+            AlarmConfig::builder()
+                .at_time(99, 15, 0)
+                .match_mode(AlarmMatch::MinutesSeconds)
+                .build(),
+            Ok(AlarmConfig::hourly_at_minute(15))
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! DS3231 alarm register codec
+use crate::bcd;
+use crate::datetime::{Weekday, WeekdaySet};
+
+use super::{AlarmConfig, AlarmConfigError, AlarmMatch};
+
+/// Mask bit (AxMy) in bit 7 of every alarm register: set = ignore the field
+const MASK: u8 = 0x80;
+/// DY/DT bit of the day register: set = match the weekday
+const DY: u8 = 0x40;
+
+/// Encode `config` into the DS3231 alarm 1 registers 07h-0Ah
+///
+/// The registers are `[seconds, minutes, hours, day/date]` in BCD, with the
+/// mask bit in bit 7 of each as given by [`AlarmMatch::mask_bits_ds3231`].
+/// Ignored fields are written as just the mask bit. The hour is encoded in
+/// 24-hour mode. A weekday is written as DY/DT plus [`Weekday::to_number`]
+/// (1 = Sunday), the numbering [`bcd::encode_datetime`] uses for the day
+/// register, which the alarm is compared with.
+///
+/// ```
+/// use rtc_hal::alarm::{encode_ds3231_alarm1, AlarmConfig};
+///
+/// let registers = encode_ds3231_alarm1(&AlarmConfig::daily_at(7, 30, 0))?;
+/// assert_eq!(registers, [0x00, 0x30, 0x07, 0x80]);
+/// # Ok::<(), rtc_hal::alarm::AlarmConfigError>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of [`AlarmConfig::match_mode`], [`AlarmConfig::validate`]
+/// or [`AlarmConfig::single_weekday`].
+pub fn encode_ds3231_alarm1(config: &AlarmConfig) -> Result<[u8; 4], AlarmConfigError> {
+    let mode = config.match_mode()?;
+    config.validate()?;
+    let [m1, m2, m3, m4] = mode.mask_bits_ds3231();
+    Ok([
+        encode_field(config.second, m1),
+        encode_field(config.minute, m2),
+        encode_field(config.hour, m3),
+        encode_day(config, m4)?,
+    ])
+}
+
+/// Encode `config` into the DS3231 alarm 2 registers 0Bh-0Dh
+///
+/// The registers are `[minutes, hours, day/date]`, encoded as for
+/// [`encode_ds3231_alarm1`] with mask bits A2M2-A2M4. Alarm 2 has no seconds
+/// register and fires at second 0, so the second must be `Some(0)`; "once per
+/// minute" is [`AlarmConfig::every_minute_at(0)`](AlarmConfig::every_minute_at).
+///
+/// # Errors
+///
+/// Returns `AlarmConfigError::UnsupportedMatch` if the second is not 0,
+/// otherwise as for [`encode_ds3231_alarm1`].
+pub fn encode_ds3231_alarm2(config: &AlarmConfig) -> Result<[u8; 3], AlarmConfigError> {
+    let mode = config.match_mode()?;
+    config.validate()?;
+    if mode == AlarmMatch::EverySecond || config.second != Some(0) {
+        return Err(AlarmConfigError::UnsupportedMatch);
+    }
+    let [_, m2, m3, m4] = mode.mask_bits_ds3231();
+    Ok([
+        encode_field(config.minute, m2),
+        encode_field(config.hour, m3),
+        encode_day(config, m4)?,
+    ])
+}
+
+/// Decode the DS3231 alarm 1 registers 07h-0Ah, see [`encode_ds3231_alarm1`]
+///
+/// Hours in 12-hour mode are converted to 24-hour time. The value bits of
+/// ignored fields are not checked.
+///
+/// # Errors
+///
+/// Returns `AlarmConfigError::UnsupportedMatch` for a combination of mask
+/// bits missing from the datasheet table, e.g. the minutes ignored but the
+/// hours compared, or the error for the first field holding invalid BCD or
+/// an out-of-range value. An invalid weekday is reported as `InvalidDay`.
+pub fn decode_ds3231_alarm1(registers: &[u8; 4]) -> Result<AlarmConfig, AlarmConfigError> {
+    check_masks(registers)?;
+    let [second, minute, hour, day] = *registers;
+    let (day_of_month, weekdays) = decode_day(day)?;
+    let config = AlarmConfig {
+        second: decode_field(second, AlarmConfigError::InvalidSecond)?,
+        minute: decode_field(minute, AlarmConfigError::InvalidMinute)?,
+        hour: decode_hour(hour)?,
+        day_of_month,
+        weekdays,
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+/// Decode the DS3231 alarm 2 registers 0Bh-0Dh, see [`encode_ds3231_alarm2`]
+///
+/// The second of the result is always `Some(0)`.
+///
+/// # Errors
+///
+/// As for [`decode_ds3231_alarm1`].
+pub fn decode_ds3231_alarm2(registers: &[u8; 3]) -> Result<AlarmConfig, AlarmConfigError> {
+    check_masks(registers)?;
+    let [minute, hour, day] = *registers;
+    let (day_of_month, weekdays) = decode_day(day)?;
+    let config = AlarmConfig {
+        second: Some(0),
+        minute: decode_field(minute, AlarmConfigError::InvalidMinute)?,
+        hour: decode_hour(hour)?,
+        day_of_month,
+        weekdays,
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+fn encode_field(value: Option<u8>, masked: bool) -> u8 {
+    match value {
+        Some(value) if !masked => bcd::from_decimal(value),
+        _ => MASK,
+    }
+}
+
+fn encode_day(config: &AlarmConfig, masked: bool) -> Result<u8, AlarmConfigError> {
+    if masked {
+        return Ok(MASK);
+    }
+    Ok(match config.single_weekday()? {
+        Some(weekday) => DY | weekday.to_number(),
+        None => encode_field(config.day_of_month, false),
+    })
+}
+
+/// Once a field is ignored, every following field must be ignored too
+fn check_masks(registers: &[u8]) -> Result<(), AlarmConfigError> {
+    let masked = |i: usize| registers[i] & MASK != 0;
+    if (1..registers.len()).all(|i| !masked(i - 1) || masked(i)) {
+        Ok(())
+    } else {
+        Err(AlarmConfigError::UnsupportedMatch)
+    }
+}
+
+fn decode_field(byte: u8, error: AlarmConfigError) -> Result<Option<u8>, AlarmConfigError> {
+    if byte & MASK != 0 {
+        return Ok(None);
+    }
+    bcd::try_to_decimal(byte).map(Some).map_err(|_| error)
+}
+
+fn decode_hour(byte: u8) -> Result<Option<u8>, AlarmConfigError> {
+    if byte & MASK != 0 {
+        return Ok(None);
+    }
+    match bcd::decode_hours(byte) {
+        Ok((hour, _)) => Ok(Some(hour)),
+        Err(_) => Err(AlarmConfigError::InvalidHour),
+    }
+}
+
+fn decode_day(byte: u8) -> Result<(Option<u8>, Option<WeekdaySet>), AlarmConfigError> {
+    if byte & MASK != 0 {
+        return Ok((None, None));
+    }
+    if byte & DY == 0 {
+        return Ok((decode_field(byte, AlarmConfigError::InvalidDay)?, None));
+    }
+    let weekday = bcd::try_to_decimal(byte & !DY)
+        .ok()
+        .and_then(|n| Weekday::from_number(n).ok())
+        .ok_or(AlarmConfigError::InvalidDay)?;
+    Ok((None, Some(WeekdaySet::single(weekday))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const ALARM1: [(AlarmConfig, [u8; 4]); 6] = [
+        (AlarmConfig { second: None, minute: None, hour: None, day_of_month: None, weekdays: None },
+            [0x80, 0x80, 0x80, 0x80]),
+        (AlarmConfig::every_minute_at(45), [0x45, 0x80, 0x80, 0x80]),
+        (AlarmConfig { second: Some(30), ..AlarmConfig::hourly_at_minute(59) },
+            [0x30, 0x59, 0x80, 0x80]),
+        (AlarmConfig::daily_at(23, 5, 9), [0x09, 0x05, 0x23, 0x80]),
+        (AlarmConfig::monthly_at(31, 12, 0, 0), [0x00, 0x00, 0x12, 0x31]),
+        (AlarmConfig::weekly_at(Weekday::Saturday, 6, 30, 15), [0x15, 0x30, 0x06, 0x47]),
+    ];
+
+    #[rustfmt::skip]
+    const ALARM2: [(AlarmConfig, [u8; 3]); 5] = [
+        (AlarmConfig::every_minute_at(0), [0x80, 0x80, 0x80]),
+        (AlarmConfig::hourly_at_minute(15), [0x15, 0x80, 0x80]),
+        (AlarmConfig::daily_at(7, 0, 0), [0x00, 0x07, 0x80]),
+        (AlarmConfig::monthly_at(1, 0, 0, 0), [0x00, 0x00, 0x01]),
+        (AlarmConfig::weekly_at(Weekday::Sunday, 19, 45, 0), [0x45, 0x19, 0x41]),
+    ];
+
+    #[test]
+    fn test_alarm1_every_mode() {
+        for (config, registers) in ALARM1 {
+            assert_eq!(encode_ds3231_alarm1(&config), Ok(registers), "{config:?}");
+            assert_eq!(
+                decode_ds3231_alarm1(&registers),
+                Ok(config),
+                "{registers:02x?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_alarm2_every_mode() {
+        for (config, registers) in ALARM2 {
+            assert_eq!(encode_ds3231_alarm2(&config), Ok(registers), "{config:?}");
+            assert_eq!(
+                decode_ds3231_alarm2(&registers),
+                Ok(config),
+                "{registers:02x?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_mask_bits_follow_datasheet_table() {
+        for (config, registers) in ALARM1 {
+            let mode = config.match_mode().unwrap();
+            assert_eq!(registers.map(|r| r & MASK != 0), mode.mask_bits_ds3231());
+            if !mode.mask_bits_ds3231()[3] {
+                assert_eq!(registers[3] & DY != 0, mode.uses_weekday());
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_rejects() {
+        let cases = [
+            (
+                AlarmConfig::daily_at(24, 0, 0),
+                AlarmConfigError::InvalidHour,
+            ),
+            (
+                AlarmConfig {
+                    second: None,
+                    ..AlarmConfig::daily_at(7, 0, 0)
+                },
+                AlarmConfigError::UnsupportedMatch,
+            ),
+            (
+                AlarmConfig::on_weekdays_at(WeekdaySet::WEEKDAYS, 7, 0, 0),
+                AlarmConfigError::MultipleWeekdays,
+            ),
+        ];
+        for (config, error) in cases {
+            assert_eq!(encode_ds3231_alarm1(&config), Err(error));
+            assert_eq!(encode_ds3231_alarm2(&config), Err(error));
+        }
+
+        // Alarm 2 only fires at second 0
+        assert_eq!(
+            encode_ds3231_alarm2(&AlarmConfig::default()),
+            Err(AlarmConfigError::UnsupportedMatch)
+        );
+        assert_eq!(
+            encode_ds3231_alarm2(&AlarmConfig::daily_at(7, 0, 30)),
+            Err(AlarmConfigError::UnsupportedMatch)
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects() {
+        let cases = [
+            // Minutes ignored, hours compared
+            ([0x00, 0x80, 0x07, 0x80], AlarmConfigError::UnsupportedMatch),
+            ([0x80, 0x00, 0x80, 0x80], AlarmConfigError::UnsupportedMatch),
+            ([0x5A, 0x80, 0x80, 0x80], AlarmConfigError::InvalidSecond),
+            ([0x00, 0x60, 0x80, 0x80], AlarmConfigError::InvalidMinute),
+            ([0x00, 0x00, 0x24, 0x80], AlarmConfigError::InvalidHour),
+            ([0x00, 0x00, 0x00, 0x00], AlarmConfigError::InvalidDay),
+            ([0x00, 0x00, 0x00, 0x32], AlarmConfigError::InvalidDay),
+            ([0x00, 0x00, 0x00, 0x40], AlarmConfigError::InvalidDay),
+            ([0x00, 0x00, 0x00, 0x48], AlarmConfigError::InvalidDay),
+        ];
+        for (registers, error) in cases {
+            assert_eq!(
+                decode_ds3231_alarm1(&registers),
+                Err(error),
+                "{registers:02x?}"
+            );
+        }
+        assert_eq!(
+            decode_ds3231_alarm2(&[0x80, 0x07, 0x80]),
+            Err(AlarmConfigError::UnsupportedMatch)
+        );
+    }
+
+    #[test]
+    fn test_decode_lenient_fields() {
+        // 12-hour mode, 7 PM
+        assert_eq!(
+            decode_ds3231_alarm1(&[0x00, 0x30, 0x67, 0x80]),
+            Ok(AlarmConfig::daily_at(19, 30, 0))
+        );
+        // Value bits of ignored fields, and DY/DT while the day is ignored
+        assert_eq!(
+            decode_ds3231_alarm2(&[0x15, 0xFF, 0xC7]),
+            Ok(AlarmConfig::hourly_at_minute(15))
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Recurring alarms emulated by re-arming a match alarm
+use crate::datetime::{DateTime, DateTimeError};
+
+use super::{Alarm, AlarmConfig, AlarmConfigError, AlarmId};
+
+/// How often a [`RecurringAlarm`] fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Recurrence {
+    /// A fixed number of seconds, at most [`Recurrence::MAX_INTERVAL`]
+    Interval(u32),
+}
+
+impl Recurrence {
+    /// Longest interval, 28 days
+    ///
+    /// Each occurrence is armed as an exact date-and-time match, which fires
+    /// again a month later, so the next occurrence must be less than the
+    /// shortest month away.
+    pub const MAX_INTERVAL: u32 = 28 * 86_400;
+}
+
+/// Serialized as an externally tagged enum, e.g. `{"Interval": 2700}` in JSON
+#[cfg(feature = "serde")]
+impl serde::Serialize for Recurrence {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Interval(seconds) => {
+                serializer.serialize_newtype_variant("Recurrence", 0, "Interval", seconds)
+            }
+        }
+    }
+}
+
+/// Intervals that [`RecurringAlarm::new`] would reject are rejected here too
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Recurrence {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{EnumAccess, Error, Unexpected, VariantAccess};
+
+        const VARIANTS: &[&str] = &["Interval"];
+
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Recurrence;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a recurrence")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Recurrence, A::Error> {
+                let (_, variant) =
+                    data.variant_seed(crate::serde_names::Name::Variant(VARIANTS))?;
+                let seconds: u32 = variant.newtype_variant()?;
+                if !(1..=Recurrence::MAX_INTERVAL).contains(&seconds) {
+                    return Err(A::Error::invalid_value(
+                        Unexpected::Unsigned(seconds.into()),
+                        &"an interval of 1 second to 28 days",
+                    ));
+                }
+                Ok(Recurrence::Interval(seconds))
+            }
+        }
+
+        deserializer.deserialize_enum("Recurrence", VARIANTS, Visitor)
+    }
+}
+
+/// Alarm that fires repeatedly, e.g. every 45 minutes
+///
+/// Hardware alarms only match a fixed pattern, so the wrapper arms the next
+/// occurrence with [`AlarmConfig::once_at`] each time the alarm has fired.
+/// Occurrences stay on the original grid: with a 45 minute interval started at
+/// 12:00, the alarm fires at 12:45, 13:30, and so on, however late the
+/// interrupt is serviced. Occurrences that passed while the interrupt was not
+/// serviced are skipped rather than fired in a burst, and counted in
+/// [`missed`](Self::missed).
+///
+/// The interrupt is not enabled by the wrapper; use
+/// [`Alarm::enable_alarm_interrupt`] on the RTC if the alarm should wake the MCU.
+///
+/// ```
+/// use rtc_hal::alarm::{Alarm, AlarmConfigError, AlarmId, Recurrence, RecurringAlarm};
+/// use rtc_hal::datetime::DateTimeError;
+///
+/// fn every_45_minutes<A>(rtc: A) -> Result<RecurringAlarm<A>, A::Error>
+/// where
+///     A: Alarm,
+///     A::Error: From<DateTimeError> + From<AlarmConfigError>,
+/// {
+///     let mut alarm = RecurringAlarm::new(rtc, AlarmId::ALARM1, Recurrence::Interval(45 * 60))?;
+///     alarm.arm_next()?;
+///     Ok(alarm)
+/// }
+///
+/// // In the interrupt handler or main loop, once the alarm fired:
+/// fn on_alarm<A>(alarm: &mut RecurringAlarm<A>) -> Result<(), A::Error>
+/// where
+///     A: Alarm,
+///     A::Error: From<DateTimeError>,
+/// {
+///     alarm.on_triggered()?;
+///     // ... periodic work ...
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RecurringAlarm<A> {
+    rtc: A,
+    id: AlarmId,
+    recurrence: Recurrence,
+    next: Option<DateTime>,
+    missed: u32,
+}
+
+impl<A: Alarm> RecurringAlarm<A> {
+    /// Create a recurring alarm on alarm `id` of `rtc`; nothing is armed until
+    /// [`arm_next`](Self::arm_next)
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::InvalidInterval` if an interval is 0 or
+    /// longer than [`Recurrence::MAX_INTERVAL`].
+    pub fn new(rtc: A, id: AlarmId, recurrence: Recurrence) -> Result<Self, AlarmConfigError> {
+        match recurrence {
+            Recurrence::Interval(seconds) => {
+                if !(1..=Recurrence::MAX_INTERVAL).contains(&seconds) {
+                    return Err(AlarmConfigError::InvalidInterval);
+                }
+            }
+        }
+        Ok(Self {
+            rtc,
+            id,
+            recurrence,
+            next: None,
+            missed: 0,
+        })
+    }
+
+    /// The occurrence currently armed, if any
+    pub fn next(&self) -> Option<DateTime> {
+        self.next
+    }
+
+    /// Occurrences skipped by the last [`arm_next`](Self::arm_next) because
+    /// they were already in the past
+    pub fn missed(&self) -> u32 {
+        self.missed
+    }
+
+    /// Arm the next occurrence after the RTC's current time and return it.
+    ///
+    /// The first call arms one interval from now. Later calls continue from
+    /// the previous occurrence, skipping any that have already passed. An
+    /// occurrence that is still ahead is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `A::Error` if the RTC fails, or a `DateTimeError` converted into
+    /// it if the next occurrence is after [`DateTime::MAX`].
+    pub fn arm_next(&mut self) -> Result<DateTime, A::Error>
+    where
+        A::Error: From<DateTimeError>,
+    {
+        let now = self.rtc.get_datetime()?;
+        let (next, missed) = self.following(&now)?;
+        self.rtc.set_alarm(self.id, &AlarmConfig::once_at(&next))?;
+        self.next = Some(next);
+        self.missed = missed;
+        Ok(next)
+    }
+
+    /// Clear the alarm flag and arm the next occurrence, see
+    /// [`arm_next`](Self::arm_next).
+    ///
+    /// Call this once the alarm has fired.
+    ///
+    /// # Errors
+    ///
+    /// As for [`arm_next`](Self::arm_next).
+    pub fn on_triggered(&mut self) -> Result<DateTime, A::Error>
+    where
+        A::Error: From<DateTimeError>,
+    {
+        self.rtc.clear_alarm_flag(self.id)?;
+        self.arm_next()
+    }
+
+    /// Disarm the alarm; a later [`arm_next`](Self::arm_next) starts a new
+    /// grid one interval from then.
+    pub fn stop(&mut self) -> Result<(), A::Error> {
+        self.rtc.disable_alarm(self.id)?;
+        self.next = None;
+        Ok(())
+    }
+
+    /// Mutable access to the RTC
+    pub fn inner_mut(&mut self) -> &mut A {
+        &mut self.rtc
+    }
+
+    /// Release the RTC, leaving the alarm as it is
+    pub fn into_inner(self) -> A {
+        self.rtc
+    }
+
+    /// The first occurrence after `now` and how many were skipped to get there
+    fn following(&self, now: &DateTime) -> Result<(DateTime, u32), DateTimeError> {
+        let Recurrence::Interval(interval) = self.recurrence;
+        let Some(previous) = self.next else {
+            return Ok((now.checked_add_seconds(interval.into())?, 0));
+        };
+        if previous > *now {
+            return Ok((previous, 0));
+        }
+        let behind = now.to_unix_timestamp() - previous.to_unix_timestamp();
+        let steps = behind / u64::from(interval) + 1;
+        let next = previous.checked_add_seconds((steps * u64::from(interval)) as i64)?;
+        Ok((next, (steps - 1) as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::mock::FakeRtc;
+
+    fn at(hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(2024, 8, 16, hour, minute, second).unwrap()
+    }
+
+    fn every_45_minutes(start: DateTime) -> RecurringAlarm<FakeRtc> {
+        RecurringAlarm::new(
+            FakeRtc::new(start),
+            AlarmId::ALARM1,
+            Recurrence::Interval(45 * 60),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fires_every_interval() {
+        let mut alarm = every_45_minutes(at(12, 0, 0));
+        assert_eq!(alarm.arm_next(), Ok(at(12, 45, 0)));
+
+        for expected in [at(13, 30, 0), at(14, 15, 0), at(15, 0, 0)] {
+            alarm.inner_mut().advance_seconds(45 * 60);
+            assert_eq!(alarm.inner_mut().alarm_triggered(AlarmId::ALARM1), Ok(true));
+            assert_eq!(alarm.on_triggered(), Ok(expected));
+            assert_eq!(alarm.missed(), 0);
+            assert_eq!(
+                alarm.inner_mut().alarm_triggered(AlarmId::ALARM1),
+                Ok(false)
+            );
+        }
+        assert_eq!(alarm.inner_mut().take_fired_alarms().len(), 3);
+    }
+
+    #[test]
+    fn test_late_service_skips_missed_occurrences() {
+        let mut alarm = every_45_minutes(at(12, 0, 0));
+        alarm.arm_next().unwrap();
+
+        // Serviced 2 h 20 min late: 12:45 fired, 13:30, 14:15 and 15:00 were missed
+        alarm.inner_mut().advance_seconds(45 * 60 + 140 * 60);
+        assert_eq!(alarm.inner_mut().take_fired_alarms(), [(0, at(12, 45, 0))]);
+        assert_eq!(alarm.on_triggered(), Ok(at(15, 45, 0)));
+        assert_eq!(alarm.missed(), 3);
+
+        // Back on schedule, with a single firing
+        alarm.inner_mut().advance_seconds(40 * 60);
+        assert_eq!(alarm.inner_mut().take_fired_alarms(), [(0, at(15, 45, 0))]);
+        assert_eq!(alarm.on_triggered(), Ok(at(16, 30, 0)));
+        assert_eq!(alarm.missed(), 0);
+    }
+
+    #[test]
+    fn test_serviced_exactly_on_next_occurrence() {
+        let mut alarm = every_45_minutes(at(12, 0, 0));
+        alarm.arm_next().unwrap();
+        alarm.inner_mut().advance_seconds(90 * 60);
+        assert_eq!(alarm.on_triggered(), Ok(at(14, 15, 0)));
+        assert_eq!(alarm.missed(), 1);
+    }
+
+    #[test]
+    fn test_rearming_early_keeps_occurrence() {
+        let mut alarm = every_45_minutes(at(23, 30, 0));
+        assert_eq!(
+            alarm.arm_next(),
+            DateTime::new(2024, 8, 17, 0, 15, 0).map_err(ErrorKind::from)
+        );
+        alarm.inner_mut().advance_seconds(60);
+        assert_eq!(alarm.arm_next(), Ok(alarm.next().unwrap()));
+
+        alarm.stop().unwrap();
+        assert_eq!(alarm.next(), None);
+        alarm.inner_mut().advance_seconds(3_600);
+        assert!(alarm.inner_mut().take_fired_alarms().is_empty());
+        assert_eq!(
+            alarm.arm_next(),
+            DateTime::new(2024, 8, 17, 1, 16, 0).map_err(ErrorKind::from)
+        );
+    }
+
+    #[test]
+    fn test_rtc_errors_propagate() {
+        let mut alarm = every_45_minutes(at(12, 0, 0));
+        alarm.inner_mut().set_error(ErrorKind::Bus);
+        assert_eq!(alarm.arm_next(), Err(ErrorKind::Bus));
+        assert_eq!(alarm.next(), None);
+
+        let mut alarm = RecurringAlarm::new(
+            FakeRtc::new(DateTime::MAX),
+            AlarmId::ALARM1,
+            Recurrence::Interval(1),
+        )
+        .unwrap();
+        assert_eq!(alarm.arm_next(), Err(ErrorKind::InvalidDateTime));
+    }
+
+    #[test]
+    fn test_invalid_interval_rejected() {
+        for seconds in [0, Recurrence::MAX_INTERVAL + 1] {
+            let alarm = RecurringAlarm::new(
+                FakeRtc::new(DateTime::MIN),
+                AlarmId::ALARM1,
+                Recurrence::Interval(seconds),
+            );
+            assert_eq!(alarm.err(), Some(AlarmConfigError::InvalidInterval));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_recurrence_serde() {
+        use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+        let variant = Token::NewtypeVariant {
+            name: "Recurrence",
+            variant: "Interval",
+        };
+        assert_tokens(&Recurrence::Interval(45 * 60), &[variant, Token::U32(2700)]);
+        assert_tokens(
+            &Recurrence::Interval(Recurrence::MAX_INTERVAL),
+            &[variant, Token::U32(Recurrence::MAX_INTERVAL)],
+        );
+        for seconds in [0, Recurrence::MAX_INTERVAL + 1] {
+            assert_de_tokens_error::<Recurrence>(
+                &[variant, Token::U32(seconds)],
+                &format!(
+                    "invalid value: integer `{seconds}`, expected an interval of 1 second to 28 days"
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,434 @@
+//! Cron-like schedules armed one occurrence at a time
+use crate::datetime::{DateTime, WeekdaySet, calculate_weekday, days_in_month};
+
+use super::{AlarmConfig, AlarmConfigError};
+
+/// Cron-like schedule, e.g. "02:30 on the 1st and 15th"
+///
+/// A schedule is a set of minutes, hours, days of month, months and weekdays,
+/// each stored as a bitmask. A minute is due when it is in every set, and
+/// occurrences are at second 0. Unlike cron, where a restricted day of month
+/// and a restricted weekday match either, both must match here, so "Friday
+/// the 13th" can be expressed.
+///
+/// Start from [`Schedule::EVERY_MINUTE`] and restrict the fields that matter:
+///
+/// ```
+/// use rtc_hal::alarm::Schedule;
+/// use rtc_hal::datetime::DateTime;
+///
+/// let maintenance = Schedule::EVERY_MINUTE
+///     .minutes(&[30])?
+///     .hours(&[2])?
+///     .days(&[1, 15])?;
+///
+/// let now = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+/// assert_eq!(
+///     maintenance.next_after(&now),
+///     Some(DateTime::new(2024, 9, 1, 2, 30, 0).unwrap())
+/// );
+/// # Ok::<(), rtc_hal::alarm::AlarmConfigError>(())
+/// ```
+///
+/// A hardware alarm matches a single pattern, so
+/// [`to_alarm_config`](Self::to_alarm_config) arms only the next occurrence,
+/// and the alarm has to be armed again each time it fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Schedule {
+    /// Bit n for minute n (0-59)
+    minutes: u64,
+    /// Bit n for hour n (0-23)
+    hours: u32,
+    /// Bit n for day of month n (1-31)
+    days: u32,
+    /// Bit n for month n (1-12)
+    months: u16,
+    weekdays: WeekdaySet,
+}
+
+impl Schedule {
+    /// Due every minute, the starting point for restricting fields
+    pub const EVERY_MINUTE: Schedule = Schedule {
+        minutes: (1 << 60) - 1,
+        hours: (1 << 24) - 1,
+        days: !1,
+        months: 0b1_1111_1111_1110,
+        weekdays: WeekdaySet::ALL,
+    };
+
+    /// Due only at `minutes` (0-59); an empty list is never due.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::InvalidMinute` for a minute over 59.
+    pub fn minutes(self, minutes: &[u8]) -> Result<Self, AlarmConfigError> {
+        let minutes = mask(minutes, 59, AlarmConfigError::InvalidMinute)?;
+        Ok(Self { minutes, ..self })
+    }
+
+    /// Due only in `hours` (0-23); an empty list is never due.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::InvalidHour` for an hour over 23.
+    pub fn hours(self, hours: &[u8]) -> Result<Self, AlarmConfigError> {
+        let hours = mask(hours, 23, AlarmConfigError::InvalidHour)? as u32;
+        Ok(Self { hours, ..self })
+    }
+
+    /// Due only on `days` of the month (1-31); an empty list is never due.
+    ///
+    /// Months without the day are skipped, e.g. day 31 is never due in April.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::InvalidDay` for a day of 0 or over 31.
+    pub fn days(self, days: &[u8]) -> Result<Self, AlarmConfigError> {
+        if days.contains(&0) {
+            return Err(AlarmConfigError::InvalidDay);
+        }
+        let days = mask(days, 31, AlarmConfigError::InvalidDay)? as u32;
+        Ok(Self { days, ..self })
+    }
+
+    /// Due only in `months` (1-12); an empty list is never due.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::InvalidMonth` for a month of 0 or over 12.
+    pub fn months(self, months: &[u8]) -> Result<Self, AlarmConfigError> {
+        if months.contains(&0) {
+            return Err(AlarmConfigError::InvalidMonth);
+        }
+        let months = mask(months, 12, AlarmConfigError::InvalidMonth)? as u16;
+        Ok(Self { months, ..self })
+    }
+
+    /// Due only on `weekdays`; an empty set is never due.
+    pub fn weekdays(self, weekdays: WeekdaySet) -> Self {
+        Self { weekdays, ..self }
+    }
+
+    /// Check whether the minute of `datetime` is due; the second is ignored.
+    pub fn matches(&self, datetime: &DateTime) -> bool {
+        self.minutes & 1 << datetime.minute() != 0
+            && self.hours & 1 << datetime.hour() != 0
+            && self.days & 1 << datetime.day_of_month() != 0
+            && self.months & 1 << datetime.month() != 0
+            && self.weekdays.contains(datetime.weekday())
+    }
+
+    /// The first occurrence strictly after `now`.
+    ///
+    /// Returns `None` if the schedule is never due, e.g. on February 30 or
+    /// with an empty field, or if the next occurrence is after
+    /// [`DateTime::MAX`].
+    pub fn next_after(&self, now: &DateTime) -> Option<DateTime> {
+        if !self.can_fire() {
+            return None;
+        }
+        // Occurrences are whole minutes, so the earliest is the next one
+        let start = now.checked_add_seconds(60 - i64::from(now.second())).ok()?;
+        let start_date = (start.year(), start.month(), start.day_of_month());
+
+        let (mut year, mut month, mut day) = start_date;
+        // Terminates, as any day of any month falls on every weekday within 400 years
+        loop {
+            if self.months & 1 << month != 0 {
+                let last = days_in_month(year, month);
+                while let Some(candidate) = first_from(self.days.into(), day).filter(|&d| d <= last)
+                {
+                    let weekday = calculate_weekday(year, month, candidate).ok()?;
+                    let from = if (year, month, candidate) == start_date {
+                        (start.hour(), start.minute())
+                    } else {
+                        (0, 0)
+                    };
+                    if self.weekdays.contains(weekday)
+                        && let Some((hour, minute)) = self.first_time(from)
+                    {
+                        return DateTime::new(year, month, candidate, hour, minute, 0).ok();
+                    }
+                    day = candidate + 1;
+                }
+            }
+            day = 1;
+            if month == 12 {
+                year = year.checked_add(1)?;
+                month = 1;
+            } else {
+                month += 1;
+            }
+        }
+    }
+
+    /// The alarm configuration for the first occurrence strictly after `now`,
+    /// and that occurrence.
+    ///
+    /// The configuration is [`AlarmConfig::once_at`] the occurrence, which
+    /// matches its day of month and time, so it also matches in every month
+    /// before the occurrence that has the day. When the schedule skips months,
+    /// check on firing whether the returned occurrence has been reached, and
+    /// arm again otherwise.
+    ///
+    /// Returns `None` when [`next_after`](Self::next_after) does.
+    pub fn to_alarm_config(&self, now: &DateTime) -> Option<(AlarmConfig, DateTime)> {
+        let next = self.next_after(now)?;
+        Some((AlarmConfig::once_at(&next), next))
+    }
+
+    /// Whether some month in the set has some day in the set, and no field is empty
+    fn can_fire(&self) -> bool {
+        // 2000 is a leap year, so February has its 29th
+        let some_day = (1..=12).any(|month| {
+            let last = days_in_month(2000, month);
+            self.months & 1 << month != 0 && u64::from(self.days) & ((2 << last) - 1) != 0
+        });
+        some_day && self.minutes != 0 && self.hours != 0 && !self.weekdays.is_empty()
+    }
+
+    /// Earliest due hour and minute at or after `from` on a due day
+    fn first_time(&self, (from_hour, from_minute): (u8, u8)) -> Option<(u8, u8)> {
+        let mut hour = first_from(self.hours.into(), from_hour)?;
+        let mut minute_from = if hour == from_hour { from_minute } else { 0 };
+        loop {
+            if let Some(minute) = first_from(self.minutes, minute_from) {
+                return Some((hour, minute));
+            }
+            hour = first_from(self.hours.into(), hour + 1)?;
+            minute_from = 0;
+        }
+    }
+}
+
+/// Bitmask of `values`, each at most `max`
+fn mask(values: &[u8], max: u8, error: AlarmConfigError) -> Result<u64, AlarmConfigError> {
+    values.iter().try_fold(0, |mask, &value| {
+        if value > max {
+            return Err(error);
+        }
+        Ok(mask | 1 << value)
+    })
+}
+
+/// Lowest set bit of `mask` at or above `from`
+fn first_from(mask: u64, from: u8) -> Option<u8> {
+    let rest = mask.checked_shr(from.into())?;
+    (rest != 0).then(|| from + rest.trailing_zeros() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::Weekday;
+
+    fn at(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute, second).unwrap()
+    }
+
+    fn maintenance() -> Schedule {
+        Schedule::EVERY_MINUTE
+            .minutes(&[30])
+            .unwrap()
+            .hours(&[2])
+            .unwrap()
+            .days(&[1, 15])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_next_after_is_strict() {
+        let schedule = maintenance();
+        let cases = [
+            (at(2024, 8, 16, 12, 0, 0), at(2024, 9, 1, 2, 30, 0)),
+            (at(2024, 9, 1, 2, 29, 59), at(2024, 9, 1, 2, 30, 0)),
+            (at(2024, 9, 1, 2, 30, 0), at(2024, 9, 15, 2, 30, 0)),
+            (at(2024, 9, 1, 2, 30, 1), at(2024, 9, 15, 2, 30, 0)),
+            (at(2024, 12, 15, 2, 30, 0), at(2025, 1, 1, 2, 30, 0)),
+        ];
+        for (now, expected) in cases {
+            assert_eq!(schedule.next_after(&now), Some(expected), "{now:?}");
+        }
+    }
+
+    #[test]
+    fn test_every_minute() {
+        let every = Schedule::EVERY_MINUTE;
+        assert_eq!(
+            every.next_after(&at(2024, 8, 16, 12, 0, 0)),
+            Some(at(2024, 8, 16, 12, 1, 0))
+        );
+        assert_eq!(
+            every.next_after(&at(2024, 12, 31, 23, 59, 59)),
+            Some(at(2025, 1, 1, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_rollover_within_the_day() {
+        let schedule = Schedule::EVERY_MINUTE
+            .minutes(&[0, 45])
+            .unwrap()
+            .hours(&[9, 17])
+            .unwrap();
+        let cases = [
+            (at(2024, 8, 16, 9, 45, 0), at(2024, 8, 16, 17, 0, 0)),
+            (at(2024, 8, 16, 17, 44, 0), at(2024, 8, 16, 17, 45, 0)),
+            (at(2024, 8, 16, 17, 45, 0), at(2024, 8, 17, 9, 0, 0)),
+            (at(2024, 8, 16, 23, 59, 0), at(2024, 8, 17, 9, 0, 0)),
+        ];
+        for (now, expected) in cases {
+            assert_eq!(schedule.next_after(&now), Some(expected), "{now:?}");
+        }
+    }
+
+    #[test]
+    fn test_short_months_and_leap_days() {
+        let on = |days: &[u8], months: &[u8]| {
+            Schedule::EVERY_MINUTE
+                .minutes(&[0])
+                .unwrap()
+                .hours(&[0])
+                .unwrap()
+                .days(days)
+                .unwrap()
+                .months(months)
+                .unwrap()
+        };
+        assert_eq!(
+            on(&[31], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12])
+                .next_after(&at(2024, 4, 1, 0, 0, 0)),
+            Some(at(2024, 5, 31, 0, 0, 0))
+        );
+        assert_eq!(
+            on(&[29], &[2]).next_after(&at(2024, 3, 1, 0, 0, 0)),
+            Some(at(2028, 2, 29, 0, 0, 0))
+        );
+        assert_eq!(
+            on(&[29, 30], &[2, 4]).next_after(&at(2025, 2, 1, 0, 0, 0)),
+            Some(at(2025, 4, 29, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_never_due() {
+        let now = at(2024, 1, 1, 0, 0, 0);
+        let every = Schedule::EVERY_MINUTE;
+        let never = [
+            every.days(&[30, 31]).unwrap().months(&[2]).unwrap(),
+            every.days(&[31]).unwrap().months(&[4, 6, 9, 11]).unwrap(),
+            every.minutes(&[]).unwrap(),
+            every.hours(&[]).unwrap(),
+            every.days(&[]).unwrap(),
+            every.months(&[]).unwrap(),
+            every.weekdays(WeekdaySet::EMPTY),
+        ];
+        for schedule in never {
+            assert_eq!(schedule.next_after(&now), None, "{schedule:?}");
+            assert_eq!(schedule.to_alarm_config(&now), None);
+        }
+    }
+
+    #[test]
+    fn test_day_and_weekday_both_match() {
+        let friday_13th = Schedule::EVERY_MINUTE
+            .minutes(&[0])
+            .unwrap()
+            .hours(&[12])
+            .unwrap()
+            .days(&[13])
+            .unwrap()
+            .weekdays(Weekday::Friday.into());
+        assert_eq!(
+            friday_13th.next_after(&at(2024, 8, 16, 0, 0, 0)),
+            Some(at(2024, 9, 13, 12, 0, 0))
+        );
+        assert_eq!(
+            friday_13th.next_after(&at(2024, 9, 13, 12, 0, 0)),
+            Some(at(2024, 12, 13, 12, 0, 0))
+        );
+
+        // 20 years to the next leap day on a Monday
+        let leap_monday = Schedule::EVERY_MINUTE
+            .days(&[29])
+            .unwrap()
+            .months(&[2])
+            .unwrap()
+            .weekdays(Weekday::Monday.into());
+        assert_eq!(
+            leap_monday.next_after(&at(2024, 3, 1, 0, 0, 0)),
+            Some(at(2044, 2, 29, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_matches_agree_with_next_after() {
+        let office_hours = Schedule::EVERY_MINUTE
+            .minutes(&[0, 30])
+            .unwrap()
+            .hours(&[9, 10, 11, 12, 13, 14, 15, 16, 17])
+            .unwrap()
+            .weekdays(WeekdaySet::WEEKDAYS);
+        let schedules = [office_hours, maintenance(), Schedule::EVERY_MINUTE];
+
+        for schedule in schedules {
+            // Minute by minute over 20 days, from a non-zero second
+            let mut now = at(2024, 8, 28, 16, 59, 30);
+            let mut next = schedule.next_after(&now).unwrap();
+            for _ in 0..20 * 1_440 {
+                now = now.checked_add_seconds(60).unwrap();
+                if now < next {
+                    assert!(!schedule.matches(&now), "{now:?}");
+                    continue;
+                }
+                assert_eq!(now, next.checked_add_seconds(30).unwrap());
+                assert!(schedule.matches(&now));
+                next = schedule.next_after(&now).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_past_max() {
+        let new_year = Schedule::EVERY_MINUTE
+            .minutes(&[0])
+            .unwrap()
+            .hours(&[0])
+            .unwrap()
+            .days(&[1])
+            .unwrap()
+            .months(&[1])
+            .unwrap();
+        assert_eq!(new_year.next_after(&DateTime::MAX), None);
+        assert_eq!(new_year.next_after(&at(u16::MAX, 1, 1, 0, 0, 0)), None);
+        assert_eq!(
+            new_year.next_after(&at(u16::MAX - 1, 6, 1, 0, 0, 0)),
+            Some(at(u16::MAX, 1, 1, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_to_alarm_config() {
+        let now = at(2024, 8, 16, 12, 0, 0);
+        let (config, next) = maintenance().to_alarm_config(&now).unwrap();
+        assert_eq!(next, at(2024, 9, 1, 2, 30, 0));
+        assert_eq!(config, AlarmConfig::monthly_at(1, 2, 30, 0));
+        assert_eq!(config.next_trigger(&now).ok(), Some(next));
+    }
+
+    #[test]
+    fn test_out_of_range_fields() {
+        let every = Schedule::EVERY_MINUTE;
+        assert_eq!(
+            every.minutes(&[0, 60]),
+            Err(AlarmConfigError::InvalidMinute)
+        );
+        assert_eq!(every.hours(&[24]), Err(AlarmConfigError::InvalidHour));
+        assert_eq!(every.days(&[0]), Err(AlarmConfigError::InvalidDay));
+        assert_eq!(every.days(&[32]), Err(AlarmConfigError::InvalidDay));
+        assert_eq!(every.months(&[0]), Err(AlarmConfigError::InvalidMonth));
+        assert_eq!(every.months(&[13]), Err(AlarmConfigError::InvalidMonth));
+        assert_eq!(every.minutes(&[59]).map(|s| s.minutes), Ok(1 << 59));
+    }
+}
@@ -0,0 +1,214 @@
+//! Snoozing an alarm without losing its schedule
+use crate::datetime::{DateTime, DateTimeError};
+
+use super::{Alarm, AlarmConfig, AlarmConfigError, AlarmId};
+
+/// Alarm that can be snoozed and then put back on its schedule
+///
+/// The wrapper remembers the configuration set with [`set`](Self::set).
+/// [`snooze`](Self::snooze) re-arms the same hardware alarm a number of
+/// minutes from now, and [`resume_schedule`](Self::resume_schedule) restores
+/// the remembered configuration once the snoozed alarm has been dismissed.
+///
+/// A hardware alarm holds one configuration, so while snoozed the schedule
+/// is not armed: an occurrence that falls within the snooze does not fire.
+///
+/// ```
+/// use rtc_hal::alarm::{Alarm, AlarmConfigError, SnoozableAlarm};
+/// use rtc_hal::datetime::DateTimeError;
+///
+/// fn on_snooze_button<A>(alarm: &mut SnoozableAlarm<A>) -> Result<(), A::Error>
+/// where
+///     A: Alarm,
+///     A::Error: From<DateTimeError> + From<AlarmConfigError>,
+/// {
+///     alarm.snooze(9)?;
+///     Ok(())
+/// }
+///
+/// fn on_stop_button<A: Alarm>(alarm: &mut SnoozableAlarm<A>) -> Result<(), A::Error> {
+///     // Back to the daily alarm, e.g. 06:30 tomorrow
+///     alarm.resume_schedule()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SnoozableAlarm<A> {
+    rtc: A,
+    id: AlarmId,
+    schedule: Option<AlarmConfig>,
+    snoozed_until: Option<DateTime>,
+}
+
+impl<A: Alarm> SnoozableAlarm<A> {
+    /// Wrap alarm `id` of `rtc`; nothing is armed until [`set`](Self::set)
+    pub fn new(rtc: A, id: AlarmId) -> Self {
+        Self {
+            rtc,
+            id,
+            schedule: None,
+            snoozed_until: None,
+        }
+    }
+
+    /// Arm the alarm with `config` and remember it as the schedule, ending
+    /// any snooze.
+    pub fn set(&mut self, config: &AlarmConfig) -> Result<(), A::Error> {
+        self.rtc.set_alarm(self.id, config)?;
+        self.schedule = Some(*config);
+        self.snoozed_until = None;
+        Ok(())
+    }
+
+    /// The configuration restored by [`resume_schedule`](Self::resume_schedule)
+    pub fn schedule(&self) -> Option<AlarmConfig> {
+        self.schedule
+    }
+
+    /// When the snoozed alarm fires, while snoozed
+    pub fn snoozed_until(&self) -> Option<DateTime> {
+        self.snoozed_until
+    }
+
+    /// Clear the alarm flag and fire again `minutes` from now, returning when.
+    ///
+    /// Snoozing again while snoozed starts over from the current time. The
+    /// schedule is kept for [`resume_schedule`](Self::resume_schedule).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlarmConfigError::InvalidInterval` converted into `A::Error`
+    /// if `minutes` is 0, leaving the alarm untouched; otherwise as for
+    /// [`Alarm::set_alarm_in_seconds`].
+    pub fn snooze(&mut self, minutes: u8) -> Result<DateTime, A::Error>
+    where
+        A::Error: From<DateTimeError> + From<AlarmConfigError>,
+    {
+        if minutes == 0 {
+            return Err(AlarmConfigError::InvalidInterval.into());
+        }
+        self.rtc.clear_alarm_flag(self.id)?;
+        let until = self
+            .rtc
+            .set_alarm_in_seconds(self.id, u32::from(minutes) * 60)?;
+        self.snoozed_until = Some(until);
+        Ok(until)
+    }
+
+    /// Clear the alarm flag and re-arm the schedule, ending any snooze.
+    ///
+    /// Without a schedule the alarm is disabled.
+    pub fn resume_schedule(&mut self) -> Result<(), A::Error> {
+        self.rtc.clear_alarm_flag(self.id)?;
+        match self.schedule {
+            Some(config) => self.rtc.set_alarm(self.id, &config)?,
+            None => self.rtc.disable_alarm(self.id)?,
+        }
+        self.snoozed_until = None;
+        Ok(())
+    }
+
+    /// Check whether the alarm has fired, see [`Alarm::alarm_triggered`]
+    pub fn is_triggered(&mut self) -> Result<bool, A::Error> {
+        self.rtc.alarm_triggered(self.id)
+    }
+
+    /// Mutable access to the RTC
+    pub fn inner_mut(&mut self) -> &mut A {
+        &mut self.rtc
+    }
+
+    /// Release the RTC, leaving the alarm as it is
+    pub fn into_inner(self) -> A {
+        self.rtc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::mock::FakeRtc;
+
+    fn at(day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(2024, 8, day, hour, minute, second).unwrap()
+    }
+
+    fn late_alarm() -> SnoozableAlarm<FakeRtc> {
+        let mut alarm = SnoozableAlarm::new(FakeRtc::new(at(16, 23, 50, 0)), AlarmId::ALARM1);
+        alarm.set(&AlarmConfig::daily_at(23, 55, 0)).unwrap();
+        alarm
+    }
+
+    #[test]
+    fn test_snooze_across_midnight_then_resume() {
+        let mut alarm = late_alarm();
+        alarm.inner_mut().advance_seconds(5 * 60);
+        assert_eq!(alarm.is_triggered(), Ok(true));
+
+        assert_eq!(alarm.snooze(9), Ok(at(17, 0, 4, 0)));
+        assert_eq!(alarm.snoozed_until(), Some(at(17, 0, 4, 0)));
+        assert_eq!(alarm.is_triggered(), Ok(false));
+        alarm.inner_mut().advance_seconds(9 * 60);
+        assert_eq!(alarm.is_triggered(), Ok(true));
+
+        alarm.resume_schedule().unwrap();
+        assert_eq!(alarm.snoozed_until(), None);
+        assert_eq!(alarm.is_triggered(), Ok(false));
+
+        // Fires the next evening at the scheduled time, and only then
+        alarm.inner_mut().advance_seconds(86_400);
+        assert_eq!(
+            alarm.inner_mut().take_fired_alarms(),
+            [
+                (0, at(16, 23, 55, 0)),
+                (0, at(17, 0, 4, 0)),
+                (0, at(17, 23, 55, 0))
+            ]
+        );
+        assert_eq!(alarm.schedule(), Some(AlarmConfig::daily_at(23, 55, 0)));
+    }
+
+    #[test]
+    fn test_snooze_again_restarts_from_now() {
+        let mut alarm = late_alarm();
+        alarm.inner_mut().advance_seconds(5 * 60);
+        alarm.snooze(9).unwrap();
+        alarm.inner_mut().advance_seconds(9 * 60);
+        assert_eq!(alarm.snooze(5), Ok(at(17, 0, 9, 0)));
+        alarm.inner_mut().advance_seconds(4 * 60);
+        assert_eq!(alarm.is_triggered(), Ok(false));
+        alarm.inner_mut().advance_seconds(60);
+        assert_eq!(alarm.is_triggered(), Ok(true));
+    }
+
+    #[test]
+    fn test_resume_without_schedule_disables() {
+        let mut alarm = SnoozableAlarm::new(FakeRtc::new(at(16, 12, 0, 0)), AlarmId::ALARM1);
+        alarm.snooze(1).unwrap();
+        alarm.resume_schedule().unwrap();
+        alarm.inner_mut().advance_seconds(3_600);
+        assert!(alarm.inner_mut().take_fired_alarms().is_empty());
+    }
+
+    #[test]
+    fn test_errors_keep_state() {
+        let mut alarm = late_alarm();
+        alarm.inner_mut().set_error(ErrorKind::Bus);
+        assert_eq!(alarm.snooze(9), Err(ErrorKind::Bus));
+        assert_eq!(alarm.snoozed_until(), None);
+
+        alarm.snooze(9).unwrap();
+        alarm.inner_mut().set_error(ErrorKind::Bus);
+        assert_eq!(alarm.resume_schedule(), Err(ErrorKind::Bus));
+        assert_eq!(alarm.snoozed_until(), Some(at(16, 23, 59, 0)));
+    }
+
+    #[test]
+    fn test_zero_minutes_rejected() {
+        let mut alarm = late_alarm();
+        alarm.inner_mut().advance_seconds(5 * 60);
+        assert_eq!(alarm.snooze(0), Err(ErrorKind::InvalidAlarmConfig));
+        assert_eq!(alarm.snoozed_until(), None);
+        assert_eq!(alarm.is_triggered(), Ok(true));
+    }
+}
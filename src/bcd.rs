@@ -22,20 +22,612 @@
 //! | 59      | 0101 1001    | 0x59    |
 //! | 99      | 1001 1001    | 0x99    |
 //!
+//! ## Const Evaluation
+//!
+//! The single-byte conversions are `const fn`, so register tables can be built
+//! at compile time:
+//!
+//! ```
+//! use rtc_hal::bcd;
+//!
+//! const ALARM_MINUTES: [u8; 4] = [
+//!     bcd::from_decimal(0),
+//!     bcd::from_decimal(15),
+//!     bcd::from_decimal(30),
+//!     bcd::from_decimal(45),
+//! ];
+//! assert_eq!(ALARM_MINUTES, [0x00, 0x15, 0x30, 0x45]);
+//! ```
+//!
+
+use crate::datetime::{DateTime, DateTimeError};
+
+/// Errors that can occur when converting to or from BCD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BcdError {
+    /// Decimal value too large to encode
+    OutOfRange,
+    /// BCD value contains a nibble greater than 9
+    InvalidDigit,
+    /// Source and destination slices have different lengths
+    LengthMismatch,
+}
+
+impl core::fmt::Display for BcdError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BcdError::OutOfRange => write!(f, "value out of range for BCD"),
+            BcdError::InvalidDigit => write!(f, "invalid BCD digit"),
+            BcdError::LengthMismatch => write!(f, "slice length mismatch"),
+        }
+    }
+}
+
+impl core::error::Error for BcdError {}
+
+/// A byte that is known to hold valid BCD (both nibbles 0-9)
+///
+/// Codecs that receive a `Bcd` can skip revalidation. The free functions in this
+/// module remain available for plain `u8` values.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Bcd(u8);
+
+impl Bcd {
+    /// Wrap a raw BCD byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BcdError::InvalidDigit` if either nibble is greater than 9.
+    pub const fn new(bcd: u8) -> Result<Self, BcdError> {
+        if !is_valid_bcd(bcd) {
+            return Err(BcdError::InvalidDigit);
+        }
+        Ok(Bcd(bcd))
+    }
+
+    /// Encode a decimal value (0-99).
+    ///
+    /// # Errors
+    ///
+    /// Returns `BcdError::OutOfRange` if `decimal` is greater than 99.
+    pub const fn from_decimal(decimal: u8) -> Result<Self, BcdError> {
+        match try_from_decimal(decimal) {
+            Ok(bcd) => Ok(Bcd(bcd)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the decimal value (0-99).
+    pub const fn to_decimal(self) -> u8 {
+        to_decimal(self.0)
+    }
+
+    /// Get the raw BCD byte.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl core::fmt::Debug for Bcd {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Bcd({:#04x} = {})", self.0, self.to_decimal())
+    }
+}
+
+impl core::fmt::Display for Bcd {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:#04x} ({})", self.0, self.to_decimal())
+    }
+}
+
+impl From<Bcd> for u8 {
+    fn from(bcd: Bcd) -> Self {
+        bcd.0
+    }
+}
+
+impl TryFrom<u8> for Bcd {
+    type Error = BcdError;
+
+    /// Wrap a raw BCD byte, see [`Bcd::new`].
+    fn try_from(bcd: u8) -> Result<Self, Self::Error> {
+        Self::new(bcd)
+    }
+}
 
 /// Convert a BCD encoded byte to decimal
 /// First 4 bits are tens of the number
 /// Last 4 bits are ones of the number
-pub fn to_decimal(bcd: u8) -> u8 {
+///
+/// This does not check the nibbles, so it is suitable for register values
+/// that have already been masked. Use [`try_to_decimal`] to reject
+/// corrupted values such as `0x7A`.
+pub const fn to_decimal(bcd: u8) -> u8 {
     ((bcd >> 4) * 10) + (bcd & 0x0F)
 }
 
+/// Convert a BCD encoded byte to decimal, rejecting invalid nibbles
+///
+/// # Errors
+///
+/// Returns `BcdError::InvalidDigit` if either nibble is greater than 9.
+pub const fn try_to_decimal(bcd: u8) -> Result<u8, BcdError> {
+    if !is_valid_bcd(bcd) {
+        return Err(BcdError::InvalidDigit);
+    }
+    Ok(to_decimal(bcd))
+}
+
+/// Check that both nibbles of a byte are valid BCD digits (0-9)
+pub const fn is_valid_bcd(bcd: u8) -> bool {
+    (bcd >> 4) <= 9 && (bcd & 0x0F) <= 9
+}
+
 /// Convert decimal byte to BCD encoding
-pub fn from_decimal(decimal: u8) -> u8 {
-    debug_assert!(decimal <= 99, "Decimal value must be <= 99 for BCD");
+///
+/// # Panics
+///
+/// Panics if `decimal` is greater than 99, in every build profile.
+/// Use [`try_from_decimal`] for untrusted input.
+pub const fn from_decimal(decimal: u8) -> u8 {
+    assert!(decimal <= 99, "Decimal value must be <= 99 for BCD");
     ((decimal / 10) << 4) | (decimal % 10)
 }
 
+/// Convert decimal byte to BCD encoding, rejecting values above 99
+///
+/// # Errors
+///
+/// Returns `BcdError::OutOfRange` if `decimal` is greater than 99.
+pub const fn try_from_decimal(decimal: u8) -> Result<u8, BcdError> {
+    if decimal > 99 {
+        return Err(BcdError::OutOfRange);
+    }
+    Ok(((decimal / 10) << 4) | (decimal % 10))
+}
+
+/// Convert a slice of BCD bytes to decimal
+///
+/// Every byte is checked before anything is written, so `dst` is left
+/// untouched on error.
+///
+/// # Errors
+///
+/// Returns `BcdError::LengthMismatch` if the slices differ in length, or
+/// `BcdError::InvalidDigit` if any byte has a nibble greater than 9.
+pub fn decode_slice(src: &[u8], dst: &mut [u8]) -> Result<(), BcdError> {
+    if src.len() != dst.len() {
+        return Err(BcdError::LengthMismatch);
+    }
+    if !src.iter().all(|&b| is_valid_bcd(b)) {
+        return Err(BcdError::InvalidDigit);
+    }
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = to_decimal(s);
+    }
+    Ok(())
+}
+
+/// Convert a slice of decimal values (0-99) to BCD
+///
+/// Every value is checked before anything is written, so `dst` is left
+/// untouched on error.
+///
+/// # Errors
+///
+/// Returns `BcdError::LengthMismatch` if the slices differ in length, or
+/// `BcdError::OutOfRange` if any value is greater than 99.
+pub fn encode_slice(src: &[u8], dst: &mut [u8]) -> Result<(), BcdError> {
+    if src.len() != dst.len() {
+        return Err(BcdError::LengthMismatch);
+    }
+    if src.iter().any(|&b| b > 99) {
+        return Err(BcdError::OutOfRange);
+    }
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = from_decimal(s);
+    }
+    Ok(())
+}
+
+/// Convert a buffer of BCD bytes to decimal in place
+///
+/// The buffer is left untouched on error.
+///
+/// # Errors
+///
+/// Returns `BcdError::InvalidDigit` if any byte has a nibble greater than 9.
+pub fn decode_in_place(buf: &mut [u8]) -> Result<(), BcdError> {
+    if !buf.iter().all(|&b| is_valid_bcd(b)) {
+        return Err(BcdError::InvalidDigit);
+    }
+    for b in buf.iter_mut() {
+        *b = to_decimal(*b);
+    }
+    Ok(())
+}
+
+/// Convert a buffer of decimal values (0-99) to BCD in place
+///
+/// The buffer is left untouched on error.
+///
+/// # Errors
+///
+/// Returns `BcdError::OutOfRange` if any value is greater than 99.
+pub fn encode_in_place(buf: &mut [u8]) -> Result<(), BcdError> {
+    if buf.iter().any(|&b| b > 99) {
+        return Err(BcdError::OutOfRange);
+    }
+    for b in buf.iter_mut() {
+        *b = from_decimal(*b);
+    }
+    Ok(())
+}
+
+/// Convert a 4-digit decimal value (0-9999) to two BCD bytes
+///
+/// The bytes are in big-endian order: `[0]` holds the thousands and hundreds
+/// digits, `[1]` holds the tens and ones digits. For example, 2024 becomes
+/// `[0x20, 0x24]`.
+///
+/// # Errors
+///
+/// Returns `BcdError::OutOfRange` if `value` is greater than 9999.
+pub fn u16_to_bcd(value: u16) -> Result<[u8; 2], BcdError> {
+    if value > 9999 {
+        return Err(BcdError::OutOfRange);
+    }
+    Ok([
+        from_decimal((value / 100) as u8),
+        from_decimal((value % 100) as u8),
+    ])
+}
+
+/// Convert two BCD bytes to a 4-digit decimal value (0-9999)
+///
+/// The bytes are in big-endian order, as produced by [`u16_to_bcd`].
+///
+/// # Errors
+///
+/// Returns `BcdError::InvalidDigit` if any nibble is greater than 9.
+pub fn bcd_to_u16(bcd: [u8; 2]) -> Result<u16, BcdError> {
+    let high = try_to_decimal(bcd[0])? as u16;
+    let low = try_to_decimal(bcd[1])? as u16;
+    Ok(high * 100 + low)
+}
+
+/// Convert a decimal value (0-9999) to BCD packed into a `u16`
+///
+/// Digits are stored most significant first: bits 15-12 hold the thousands
+/// digit and bits 3-0 hold the ones digit, so 365 becomes `0x0365`. Values
+/// below 1000 fit in the low 12 bits, which suits three-digit counters such
+/// as day-of-year registers.
+///
+/// # Errors
+///
+/// Returns `BcdError::OutOfRange` if `value` is greater than 9999.
+pub const fn from_decimal_u16(value: u16) -> Result<u16, BcdError> {
+    if value > 9999 {
+        return Err(BcdError::OutOfRange);
+    }
+    Ok(
+        ((value / 1000) << 12)
+            | ((value / 100 % 10) << 8)
+            | ((value / 10 % 10) << 4)
+            | (value % 10),
+    )
+}
+
+/// Convert BCD packed into a `u16` to a decimal value (0-9999)
+///
+/// Uses the same nibble ordering as [`from_decimal_u16`].
+///
+/// # Errors
+///
+/// Returns `BcdError::InvalidDigit` if any nibble is greater than 9.
+pub const fn to_decimal_u16(bcd: u16) -> Result<u16, BcdError> {
+    let mut value = 0;
+    let mut shift = 16;
+    while shift > 0 {
+        shift -= 4;
+        let digit = (bcd >> shift) & 0x0F;
+        if digit > 9 {
+            return Err(BcdError::InvalidDigit);
+        }
+        value = value * 10 + digit;
+    }
+    Ok(value)
+}
+
+/// Split a full year into BCD century and year-of-century bytes
+///
+/// Returns `(century, year)`, e.g. 2024 becomes `(0x20, 0x24)`. This matches
+/// chips that keep the century in its own register next to a two-digit year.
+///
+/// # Errors
+///
+/// Returns `BcdError::OutOfRange` if `year` is greater than 9999.
+pub fn year_to_bcd(year: u16) -> Result<(u8, u8), BcdError> {
+    let [century, year] = u16_to_bcd(year)?;
+    Ok((century, year))
+}
+
+/// Join BCD century and year-of-century bytes into a full year
+///
+/// # Errors
+///
+/// Returns `BcdError::InvalidDigit` if any nibble is greater than 9.
+pub fn year_from_bcd(century: u8, year: u8) -> Result<u16, BcdError> {
+    bcd_to_u16([century, year])
+}
+
+/// Order of the time and date registers in a 7-byte burst read or write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RegisterLayout {
+    /// DS1307/DS3231 style: seconds, minutes, hours, weekday (1-7, 1 = Sunday),
+    /// day of month, month, year
+    Ds1307,
+    /// PCF8563 style: seconds, minutes, hours, day of month,
+    /// weekday (0-6, 0 = Sunday), month, year
+    Pcf8563,
+}
+
+/// Encode a `DateTime` into a 7-byte BCD register block
+///
+/// Only the two-digit year (`year % 100`) is stored, and no control bits
+/// (clock halt, 12-hour mode, century) are set. Drivers must check that the
+/// year is within the chip's range and OR in any control bits they need.
+pub fn encode_datetime(datetime: &DateTime, registers: &mut [u8; 7], layout: RegisterLayout) {
+    let weekday = datetime.weekday();
+
+    registers[0] = from_decimal(datetime.second());
+    registers[1] = from_decimal(datetime.minute());
+    registers[2] = from_decimal(datetime.hour());
+    match layout {
+        RegisterLayout::Ds1307 => {
+            registers[3] = from_decimal(weekday.to_number());
+            registers[4] = from_decimal(datetime.day_of_month());
+        }
+        RegisterLayout::Pcf8563 => {
+            registers[3] = from_decimal(datetime.day_of_month());
+            registers[4] = from_decimal(weekday.to_zero_based_sunday());
+        }
+    }
+    registers[5] = from_decimal(datetime.month());
+    registers[6] = from_decimal((datetime.year() % 100) as u8);
+}
+
+/// Decode a 7-byte BCD register block into a `DateTime`
+///
+/// Control bits are masked off before decoding: the clock-halt/voltage-low bit
+/// in the seconds register, and the century bit (bit 7) in the month register.
+/// For [`RegisterLayout::Ds1307`], an hours register in 12-hour mode is converted
+/// to 24-hour time. The weekday register is ignored.
+///
+/// The year is `century_base + two-digit year`, plus 100 if the century bit is set.
+///
+/// # Errors
+///
+/// Returns the `DateTimeError` for the first register that holds an invalid BCD
+/// digit (e.g. `InvalidSecond` for a corrupted seconds register), or the error
+/// from validating the resulting `DateTime`.
+pub fn decode_datetime(
+    registers: &[u8; 7],
+    layout: RegisterLayout,
+    century_base: u16,
+) -> Result<DateTime, DateTimeError> {
+    let (day_register, hours_mask) = match layout {
+        RegisterLayout::Ds1307 => (registers[4], 0x7F),
+        RegisterLayout::Pcf8563 => (registers[3], 0x3F),
+    };
+
+    let second = try_to_decimal(registers[0] & 0x7F).map_err(|_| DateTimeError::InvalidSecond)?;
+    let minute = try_to_decimal(registers[1] & 0x7F).map_err(|_| DateTimeError::InvalidMinute)?;
+    let (hour, _) =
+        decode_hours(registers[2] & hours_mask).map_err(|_| DateTimeError::InvalidHour)?;
+    let day_of_month =
+        try_to_decimal(day_register & 0x3F).map_err(|_| DateTimeError::InvalidDay)?;
+    let (month, century_flag) =
+        decode_month_with_century(registers[5]).map_err(|_| DateTimeError::InvalidMonth)?;
+    let year = try_to_decimal(registers[6]).map_err(|_| DateTimeError::InvalidYear)?;
+    let year = year_from_century_flag(century_flag, year, century_base)
+        .map_err(|_| DateTimeError::InvalidYear)?;
+
+    DateTime::new(year, month, day_of_month, hour, minute, second)
+}
+
+/// Encode a month (1-12) into a month register with a century flag in bit 7
+///
+/// This is the layout used by PCF8563/PCF2127-class chips and the DS3231.
+///
+/// # Panics
+///
+/// Panics if `month` is not in 1-12.
+pub const fn encode_month_with_century(month: u8, century_flag: bool) -> u8 {
+    assert!(month >= 1 && month <= 12, "Month must be 1-12");
+    let century = if century_flag { 0x80 } else { 0 };
+    century | from_decimal(month)
+}
+
+/// Decode a month register into the month (1-12) and the century flag (bit 7)
+///
+/// Bits 6 and 5 are unused on supported chips and are ignored.
+///
+/// # Errors
+///
+/// Returns `BcdError::InvalidDigit` if a nibble is greater than 9, or
+/// `BcdError::OutOfRange` if the month is not in 1-12.
+pub const fn decode_month_with_century(byte: u8) -> Result<(u8, bool), BcdError> {
+    let month = match try_to_decimal(byte & 0x1F) {
+        Ok(month) => month,
+        Err(e) => return Err(e),
+    };
+    if month < 1 || month > 12 {
+        return Err(BcdError::OutOfRange);
+    }
+    Ok((month, byte & 0x80 != 0))
+}
+
+/// Map a century flag and two-digit year (0-99) to a full year
+///
+/// The chip toggles the century flag when the year register rolls over from
+/// 99 to 00, but it does not define which century either state means. That is
+/// the driver's convention, expressed here as `century_base`: the year when
+/// the flag is clear and the year register is 00. The flag adds 100 years.
+///
+/// ```
+/// use rtc_hal::bcd::year_from_century_flag;
+///
+/// // Flag clear means 20xx, flag set means 21xx
+/// assert_eq!(year_from_century_flag(false, 99, 2000), Ok(2099));
+/// assert_eq!(year_from_century_flag(true, 0, 2000), Ok(2100));
+/// ```
+///
+/// Drivers that store the flag this way must write it back when setting the
+/// time, otherwise the year 2100 decodes as 2000 again.
+///
+/// # Errors
+///
+/// Returns `BcdError::OutOfRange` if `yy` is greater than 99 or the year does
+/// not fit in a `u16`.
+pub const fn year_from_century_flag(
+    century_flag: bool,
+    yy: u8,
+    century_base: u16,
+) -> Result<u16, BcdError> {
+    if yy > 99 {
+        return Err(BcdError::OutOfRange);
+    }
+    let offset = yy as u16 + if century_flag { 100 } else { 0 };
+    match century_base.checked_add(offset) {
+        Some(year) => Ok(year),
+        None => Err(BcdError::OutOfRange),
+    }
+}
+
+/// Convert a BCD encoded byte to decimal after clearing control bits
+///
+/// Only the bits set in `mask` are decoded, e.g. `to_decimal_masked(seconds, 0x7F)`
+/// ignores the DS1307 clock-halt bit. Like [`to_decimal`], the nibbles are not checked.
+pub const fn to_decimal_masked(bcd: u8, mask: u8) -> u8 {
+    to_decimal(bcd & mask)
+}
+
+/// Hour format of a DS1307-style hours register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HourMode {
+    /// 24-hour mode (bit 6 clear, hours 0-23 in bits 5..0)
+    TwentyFourHour,
+    /// 12-hour mode (bit 6 set, bit 5 = PM, hours 1-12 in bits 4..0)
+    TwelveHour,
+}
+
+/// Decode a DS1307-style hours register to a 0-23 hour and its mode
+///
+/// In 12-hour mode, 12 AM decodes to 0 and 12 PM decodes to 12.
+/// Bit 7 must already be masked off if the chip uses it for something else.
+///
+/// # Errors
+///
+/// Returns `BcdError::InvalidDigit` for an invalid nibble, or
+/// `BcdError::OutOfRange` for an hour outside 0-23 (24-hour mode)
+/// or 1-12 (12-hour mode).
+pub fn decode_hours(byte: u8) -> Result<(u8, HourMode), BcdError> {
+    if byte & 0x40 == 0 {
+        let hour = try_to_decimal(byte & 0x3F)?;
+        if hour > 23 {
+            return Err(BcdError::OutOfRange);
+        }
+        return Ok((hour, HourMode::TwentyFourHour));
+    }
+
+    let hour = try_to_decimal(byte & 0x1F)?;
+    let pm = byte & 0x20 != 0;
+    let hour = match (hour, pm) {
+        (12, false) => 0,
+        (12, true) => 12,
+        (1..=11, false) => hour,
+        (1..=11, true) => hour + 12,
+        _ => return Err(BcdError::OutOfRange),
+    };
+    Ok((hour, HourMode::TwelveHour))
+}
+
+/// Encode a 0-23 hour into a DS1307-style hours register
+///
+/// # Panics
+///
+/// Panics if `hour` is greater than 23.
+pub const fn encode_hours(hour: u8, mode: HourMode) -> u8 {
+    assert!(hour <= 23, "Hour must be <= 23");
+    match mode {
+        HourMode::TwentyFourHour => from_decimal(hour),
+        HourMode::TwelveHour => {
+            let pm = if hour >= 12 { 0x20 } else { 0 };
+            let hour_12 = match hour % 12 {
+                0 => 12,
+                h => h,
+            };
+            0x40 | pm | from_decimal(hour_12)
+        }
+    }
+}
+
+/// Increment a BCD byte, wrapping to zero after `max_decimal`
+///
+/// Returns the new byte and a carry flag that is `true` when the value
+/// wrapped. Chaining the carry lets seconds roll into minutes and minutes into
+/// hours without decoding the registers:
+///
+/// ```
+/// use rtc_hal::bcd::bcd_increment;
+///
+/// let (seconds, carry) = bcd_increment(0x59, 59);
+/// let (minutes, _) = if carry { bcd_increment(0x14, 59) } else { (0x14, false) };
+/// assert_eq!((seconds, minutes), (0x00, 0x15));
+/// ```
+///
+/// A value already above `max_decimal` wraps to zero with a carry. The byte
+/// is decoded leniently, see [`to_decimal`].
+///
+/// # Panics
+///
+/// Panics if `max_decimal` is greater than 99.
+pub const fn bcd_increment(byte: u8, max_decimal: u8) -> (u8, bool) {
+    assert!(max_decimal <= 99, "Decimal value must be <= 99 for BCD");
+    let value = to_decimal(byte);
+    if value >= max_decimal {
+        (0x00, true)
+    } else {
+        (from_decimal(value + 1), false)
+    }
+}
+
+/// Decrement a BCD byte, wrapping to `max_decimal` below zero
+///
+/// Returns the new byte and a borrow flag that is `true` when the value
+/// wrapped. A value above `max_decimal` is brought back to `max_decimal`
+/// without a borrow. The byte is decoded leniently, see [`to_decimal`].
+///
+/// # Panics
+///
+/// Panics if `max_decimal` is greater than 99.
+pub const fn bcd_decrement(byte: u8, max_decimal: u8) -> (u8, bool) {
+    assert!(max_decimal <= 99, "Decimal value must be <= 99 for BCD");
+    let value = to_decimal(byte);
+    if value == 0 {
+        (from_decimal(max_decimal), true)
+    } else if value > max_decimal {
+        (from_decimal(max_decimal), false)
+    } else {
+        (from_decimal(value - 1), false)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +656,563 @@ mod tests {
         assert_eq!(from_decimal(99), 0x99);
     }
 
+    #[test]
+    fn test_try_from_decimal() {
+        for i in 0..=99 {
+            assert_eq!(try_from_decimal(i), Ok(from_decimal(i)));
+        }
+        assert_eq!(try_from_decimal(100), Err(BcdError::OutOfRange));
+        assert_eq!(try_from_decimal(255), Err(BcdError::OutOfRange));
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal value must be <= 99 for BCD")]
+    fn test_from_decimal_panics_above_99() {
+        from_decimal(100);
+    }
+
+    #[test]
+    fn test_try_to_decimal_valid() {
+        for i in 0..=99 {
+            assert_eq!(try_to_decimal(from_decimal(i)), Ok(i));
+        }
+    }
+
+    #[test]
+    fn test_try_to_decimal_invalid_nibbles() {
+        for high in 0..=0xF_u8 {
+            for low in 0..=0xF_u8 {
+                let bcd = (high << 4) | low;
+                let valid = high <= 9 && low <= 9;
+                assert_eq!(is_valid_bcd(bcd), valid, "{bcd:#04x}");
+                if !valid {
+                    assert_eq!(try_to_decimal(bcd), Err(BcdError::InvalidDigit));
+                }
+            }
+        }
+
+        // High nibble bad
+        assert_eq!(try_to_decimal(0xA0), Err(BcdError::InvalidDigit));
+        // Low nibble bad, e.g. a glitched DS1307 seconds register
+        assert_eq!(try_to_decimal(0x7A), Err(BcdError::InvalidDigit));
+        // Both bad
+        assert_eq!(try_to_decimal(0xFF), Err(BcdError::InvalidDigit));
+
+        // The lenient conversion still accepts them
+        assert_eq!(to_decimal(0x7A), 80);
+    }
+
+    #[test]
+    fn test_u16_bcd_round_trip() {
+        for value in 0..=9999 {
+            let bcd = u16_to_bcd(value).unwrap();
+            assert_eq!(bcd_to_u16(bcd), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_packed_u16_round_trip() {
+        for value in 0..=9999 {
+            let bcd = from_decimal_u16(value).unwrap();
+            assert_eq!(to_decimal_u16(bcd), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_packed_u16_nibble_order() {
+        assert_eq!(from_decimal_u16(365), Ok(0x0365));
+        assert_eq!(from_decimal_u16(1234), Ok(0x1234));
+        assert_eq!(from_decimal_u16(9999), Ok(0x9999));
+        assert_eq!(to_decimal_u16(0x0366), Ok(366));
+    }
+
+    #[test]
+    fn test_packed_u16_rejects_invalid() {
+        assert_eq!(from_decimal_u16(10000), Err(BcdError::OutOfRange));
+        assert_eq!(from_decimal_u16(u16::MAX), Err(BcdError::OutOfRange));
+        for bad in [0xFFFF, 0xA000, 0x0B00, 0x00C0, 0x000D, 0x12F4] {
+            assert_eq!(to_decimal_u16(bad), Err(BcdError::InvalidDigit));
+        }
+    }
+
+    #[test]
+    fn test_u16_bcd_byte_order() {
+        assert_eq!(u16_to_bcd(2024), Ok([0x20, 0x24]));
+        assert_eq!(u16_to_bcd(7), Ok([0x00, 0x07]));
+        assert_eq!(u16_to_bcd(9999), Ok([0x99, 0x99]));
+        assert_eq!(bcd_to_u16([0x19, 0x70]), Ok(1970));
+    }
+
+    #[test]
+    fn test_u16_bcd_errors() {
+        assert_eq!(u16_to_bcd(10000), Err(BcdError::OutOfRange));
+        assert_eq!(u16_to_bcd(u16::MAX), Err(BcdError::OutOfRange));
+        assert_eq!(bcd_to_u16([0x2A, 0x24]), Err(BcdError::InvalidDigit));
+        assert_eq!(bcd_to_u16([0x20, 0xF4]), Err(BcdError::InvalidDigit));
+    }
+
+    #[test]
+    fn test_year_century_split() {
+        assert_eq!(year_to_bcd(2024), Ok((0x20, 0x24)));
+        assert_eq!(year_to_bcd(1999), Ok((0x19, 0x99)));
+        assert_eq!(year_from_bcd(0x21, 0x00), Ok(2100));
+        assert_eq!(year_from_bcd(0x20, 0xAA), Err(BcdError::InvalidDigit));
+        assert_eq!(year_to_bcd(10000), Err(BcdError::OutOfRange));
+    }
+
+    #[test]
+    fn test_encode_datetime_ds1307() {
+        // Friday 2024-08-16 12:34:56
+        let dt = DateTime::new(2024, 8, 16, 12, 34, 56).unwrap();
+        let mut registers = [0; 7];
+        encode_datetime(&dt, &mut registers, RegisterLayout::Ds1307);
+        assert_eq!(registers, [0x56, 0x34, 0x12, 0x06, 0x16, 0x08, 0x24]);
+
+        // Sunday 2000-01-02 00:00:00
+        let dt = DateTime::new(2000, 1, 2, 0, 0, 0).unwrap();
+        encode_datetime(&dt, &mut registers, RegisterLayout::Ds1307);
+        assert_eq!(registers, [0x00, 0x00, 0x00, 0x01, 0x02, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_encode_datetime_pcf8563() {
+        // Friday 2024-08-16 12:34:56
+        let dt = DateTime::new(2024, 8, 16, 12, 34, 56).unwrap();
+        let mut registers = [0; 7];
+        encode_datetime(&dt, &mut registers, RegisterLayout::Pcf8563);
+        assert_eq!(registers, [0x56, 0x34, 0x12, 0x16, 0x05, 0x08, 0x24]);
+
+        // Saturday 2099-12-31 23:59:59
+        let dt = DateTime::new(2099, 12, 31, 23, 59, 59).unwrap();
+        encode_datetime(&dt, &mut registers, RegisterLayout::Pcf8563);
+        assert_eq!(registers, [0x59, 0x59, 0x23, 0x31, 0x04, 0x12, 0x99]);
+    }
+
+    #[test]
+    fn test_decode_datetime_ds1307() {
+        let registers = [0x56, 0x34, 0x12, 0x06, 0x16, 0x08, 0x24];
+        assert_eq!(
+            decode_datetime(&registers, RegisterLayout::Ds1307, 2000),
+            DateTime::new(2024, 8, 16, 12, 34, 56)
+        );
+
+        // DS1307 power-on state: CH bit set, 2000-01-01 00:00:00
+        let registers = [0x80, 0x00, 0x00, 0x01, 0x01, 0x01, 0x00];
+        assert_eq!(
+            decode_datetime(&registers, RegisterLayout::Ds1307, 2000),
+            DateTime::new(2000, 1, 1, 0, 0, 0)
+        );
+
+        // CH bit set on top of a running time
+        let registers = [0x80 | 0x56, 0x34, 0x12, 0x06, 0x16, 0x08, 0x24];
+        assert_eq!(
+            decode_datetime(&registers, RegisterLayout::Ds1307, 2000),
+            DateTime::new(2024, 8, 16, 12, 34, 56)
+        );
+    }
+
+    #[test]
+    fn test_decode_datetime_ds1307_12_hour_mode() {
+        // 12-hour mode, PM, 11 o'clock -> 23
+        let registers = [0x00, 0x00, 0x40 | 0x20 | 0x11, 0x06, 0x16, 0x08, 0x24];
+        let dt = decode_datetime(&registers, RegisterLayout::Ds1307, 2000).unwrap();
+        assert_eq!(dt.hour(), 23);
+
+        // 12-hour mode, AM, 12 o'clock -> midnight
+        let registers = [0x00, 0x00, 0x40 | 0x12, 0x06, 0x16, 0x08, 0x24];
+        let dt = decode_datetime(&registers, RegisterLayout::Ds1307, 2000).unwrap();
+        assert_eq!(dt.hour(), 0);
+
+        // 12-hour mode, hour 0 is invalid
+        let registers = [0x00, 0x00, 0x40, 0x06, 0x16, 0x08, 0x24];
+        assert_eq!(
+            decode_datetime(&registers, RegisterLayout::Ds1307, 2000),
+            Err(DateTimeError::InvalidHour)
+        );
+    }
+
+    #[test]
+    fn test_decode_datetime_pcf8563() {
+        // VL bit set, century bit set
+        let registers = [0x80 | 0x56, 0x34, 0x12, 0x16, 0x05, 0x80 | 0x08, 0x24];
+        assert_eq!(
+            decode_datetime(&registers, RegisterLayout::Pcf8563, 1900),
+            DateTime::new(2024, 8, 16, 12, 34, 56)
+        );
+
+        let registers = [0x56, 0x34, 0x12, 0x16, 0x05, 0x08, 0x99];
+        assert_eq!(
+            decode_datetime(&registers, RegisterLayout::Pcf8563, 1900),
+            DateTime::new(1999, 8, 16, 12, 34, 56)
+        );
+    }
+
+    #[test]
+    fn test_decode_datetime_round_trip() {
+        let dt = DateTime::new(2031, 2, 28, 7, 8, 9).unwrap();
+        for layout in [RegisterLayout::Ds1307, RegisterLayout::Pcf8563] {
+            let mut registers = [0; 7];
+            encode_datetime(&dt, &mut registers, layout);
+            assert_eq!(decode_datetime(&registers, layout, 2000), Ok(dt));
+        }
+    }
+
+    #[test]
+    fn test_known_answer_vectors() {
+        for (registers, datetime) in crate::testing::DS_STYLE_REGISTER_VECTORS {
+            let mut encoded = [0; 7];
+            encode_datetime(datetime, &mut encoded, RegisterLayout::Ds1307);
+            assert_eq!(&encoded, registers, "encoding {datetime:?}");
+            assert_eq!(
+                decode_datetime(registers, RegisterLayout::Ds1307, 2000),
+                Ok(*datetime)
+            );
+        }
+    }
+
+    #[test]
+    fn test_random_round_trip() {
+        for datetime in crate::testing::RandomDateTimes::new(0x5EED)
+            .with_years(2000, 2199)
+            .take(2_000)
+        {
+            for layout in [RegisterLayout::Ds1307, RegisterLayout::Pcf8563] {
+                let mut registers = [0; 7];
+                encode_datetime(&datetime, &mut registers, layout);
+                registers[5] = encode_month_with_century(datetime.month(), datetime.year() >= 2100);
+                assert_eq!(decode_datetime(&registers, layout, 2000), Ok(datetime));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_datetime_corrupted() {
+        let valid = [0x56, 0x34, 0x12, 0x06, 0x16, 0x08, 0x24];
+        let cases = [
+            (0, 0x7A, DateTimeError::InvalidSecond),
+            (1, 0x3F, DateTimeError::InvalidMinute),
+            (2, 0x1C, DateTimeError::InvalidHour),
+            (4, 0x1A, DateTimeError::InvalidDay),
+            (5, 0x0F, DateTimeError::InvalidMonth),
+            (6, 0xA0, DateTimeError::InvalidYear),
+            // Valid BCD, but not a valid date
+            (0, 0x60, DateTimeError::InvalidSecond),
+            (4, 0x32, DateTimeError::InvalidDay),
+            (5, 0x13, DateTimeError::InvalidMonth),
+        ];
+
+        for (index, value, error) in cases {
+            let mut registers = valid;
+            registers[index] = value;
+            assert_eq!(
+                decode_datetime(&registers, RegisterLayout::Ds1307, 2000),
+                Err(error),
+                "register {index} = {value:#04x}"
+            );
+        }
+
+        assert_eq!(
+            decode_datetime(&valid, RegisterLayout::Ds1307, u16::MAX),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn test_to_decimal_masked() {
+        assert_eq!(to_decimal_masked(0x80 | 0x45, 0x7F), 45);
+        assert_eq!(to_decimal_masked(0x80 | 0x12, 0x1F), 12);
+        assert_eq!(to_decimal_masked(0x59, 0xFF), 59);
+    }
+
+    #[test]
+    fn test_hours_24_hour_mode() {
+        for hour in 0..=23 {
+            let byte = encode_hours(hour, HourMode::TwentyFourHour);
+            assert_eq!(byte, from_decimal(hour));
+            assert_eq!(decode_hours(byte), Ok((hour, HourMode::TwentyFourHour)));
+        }
+        assert_eq!(decode_hours(0x24), Err(BcdError::OutOfRange));
+        assert_eq!(decode_hours(0x1A), Err(BcdError::InvalidDigit));
+    }
+
+    #[test]
+    fn test_hours_12_hour_mode() {
+        let expected = [
+            0x52, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x50, 0x51, // AM
+            0x72, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x70, 0x71, // PM
+        ];
+        for hour in 0..=23 {
+            let byte = encode_hours(hour, HourMode::TwelveHour);
+            assert_eq!(byte, expected[hour as usize], "hour {hour}");
+            assert_eq!(decode_hours(byte), Ok((hour, HourMode::TwelveHour)));
+        }
+
+        // 12 AM is midnight and 12 PM is noon
+        assert_eq!(decode_hours(0x52), Ok((0, HourMode::TwelveHour)));
+        assert_eq!(decode_hours(0x72), Ok((12, HourMode::TwelveHour)));
+
+        // Hours 0 and 13+ do not exist in 12-hour mode
+        assert_eq!(decode_hours(0x40), Err(BcdError::OutOfRange));
+        assert_eq!(decode_hours(0x60), Err(BcdError::OutOfRange));
+        assert_eq!(decode_hours(0x53), Err(BcdError::OutOfRange));
+        assert_eq!(decode_hours(0x4A), Err(BcdError::InvalidDigit));
+    }
+
+    #[test]
+    #[should_panic(expected = "Hour must be <= 23")]
+    fn test_encode_hours_panics_above_23() {
+        encode_hours(24, HourMode::TwentyFourHour);
+    }
+
+    #[test]
+    fn test_month_with_century_round_trip() {
+        for month in 1..=12 {
+            for flag in [false, true] {
+                let byte = encode_month_with_century(month, flag);
+                assert_eq!(byte & 0x80 != 0, flag);
+                assert_eq!(decode_month_with_century(byte), Ok((month, flag)));
+            }
+        }
+        assert_eq!(encode_month_with_century(12, true), 0x92);
+        assert_eq!(encode_month_with_century(1, false), 0x01);
+    }
+
+    #[test]
+    fn test_decode_month_with_century_rejects_invalid() {
+        for flag in [0x00, 0x80] {
+            assert_eq!(decode_month_with_century(flag), Err(BcdError::OutOfRange));
+            assert_eq!(
+                decode_month_with_century(flag | 0x13),
+                Err(BcdError::OutOfRange)
+            );
+            assert_eq!(
+                decode_month_with_century(flag | 0x19),
+                Err(BcdError::OutOfRange)
+            );
+            assert_eq!(
+                decode_month_with_century(flag | 0x0A),
+                Err(BcdError::InvalidDigit)
+            );
+        }
+        // Unused bits 6 and 5 are ignored
+        assert_eq!(decode_month_with_century(0x61), Ok((1, false)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Month must be 1-12")]
+    fn test_encode_month_with_century_panics_on_invalid_month() {
+        encode_month_with_century(13, false);
+    }
+
+    #[test]
+    fn test_year_from_century_flag() {
+        assert_eq!(year_from_century_flag(false, 0, 2000), Ok(2000));
+        assert_eq!(year_from_century_flag(false, 99, 2000), Ok(2099));
+        assert_eq!(year_from_century_flag(true, 0, 2000), Ok(2100));
+        assert_eq!(year_from_century_flag(true, 99, 2000), Ok(2199));
+        assert_eq!(year_from_century_flag(true, 24, 1900), Ok(2024));
+        assert_eq!(
+            year_from_century_flag(false, 100, 2000),
+            Err(BcdError::OutOfRange)
+        );
+        assert_eq!(
+            year_from_century_flag(true, 0, u16::MAX - 50),
+            Err(BcdError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_bcd_increment_wraps() {
+        assert_eq!(bcd_increment(0x58, 59), (0x59, false));
+        assert_eq!(bcd_increment(0x59, 59), (0x00, true));
+        assert_eq!(bcd_increment(0x09, 23), (0x10, false));
+        assert_eq!(bcd_increment(0x23, 23), (0x00, true));
+        assert_eq!(bcd_increment(0x11, 12), (0x12, false));
+        assert_eq!(bcd_increment(0x12, 12), (0x00, true));
+        assert_eq!(bcd_increment(0x98, 99), (0x99, false));
+        assert_eq!(bcd_increment(0x99, 99), (0x00, true));
+        assert_eq!(bcd_increment(0x75, 59), (0x00, true));
+    }
+
+    #[test]
+    fn test_bcd_decrement_wraps() {
+        assert_eq!(bcd_decrement(0x00, 59), (0x59, true));
+        assert_eq!(bcd_decrement(0x10, 59), (0x09, false));
+        assert_eq!(bcd_decrement(0x00, 23), (0x23, true));
+        assert_eq!(bcd_decrement(0x00, 12), (0x12, true));
+        assert_eq!(bcd_decrement(0x12, 12), (0x11, false));
+        assert_eq!(bcd_decrement(0x00, 99), (0x99, true));
+        assert_eq!(bcd_decrement(0x99, 99), (0x98, false));
+        assert_eq!(bcd_decrement(0x75, 59), (0x59, false));
+    }
+
+    #[test]
+    fn test_bcd_increment_decrement_inverse() {
+        for max in [12, 23, 59, 99] {
+            for value in 0..=max {
+                let byte = from_decimal(value);
+                let (up, carry) = bcd_increment(byte, max);
+                let (down, borrow) = bcd_decrement(up, max);
+                assert_eq!(down, byte);
+                assert_eq!(carry, borrow);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bcd_increment_chained() {
+        // 23:59:59 rolls over to 00:00:00
+        let (mut hours, mut minutes, mut seconds) = (0x23, 0x59, 0x59);
+        let (s, carry) = bcd_increment(seconds, 59);
+        seconds = s;
+        if carry {
+            let (m, carry) = bcd_increment(minutes, 59);
+            minutes = m;
+            if carry {
+                hours = bcd_increment(hours, 23).0;
+            }
+        }
+        assert_eq!((hours, minutes, seconds), (0x00, 0x00, 0x00));
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal value must be <= 99 for BCD")]
+    fn test_bcd_increment_panics_above_99() {
+        bcd_increment(0x00, 100);
+    }
+
+    #[test]
+    fn test_bcd_newtype_round_trip() {
+        for i in 0..=99 {
+            let bcd = Bcd::from_decimal(i).unwrap();
+            assert_eq!(bcd.to_decimal(), i);
+            assert_eq!(bcd.get(), from_decimal(i));
+            assert_eq!(Bcd::new(bcd.get()), Ok(bcd));
+            assert_eq!(u8::from(bcd), bcd.get());
+        }
+    }
+
+    #[test]
+    fn test_bcd_newtype_rejects_invalid() {
+        assert_eq!(Bcd::new(0x7A), Err(BcdError::InvalidDigit));
+        assert_eq!(Bcd::new(0xA0), Err(BcdError::InvalidDigit));
+        assert_eq!(Bcd::try_from(0xFF), Err(BcdError::InvalidDigit));
+        assert_eq!(Bcd::from_decimal(100), Err(BcdError::OutOfRange));
+    }
+
+    #[test]
+    fn test_bcd_newtype_formatting() {
+        let bcd = Bcd::new(0x59).unwrap();
+        assert_eq!(format!("{bcd}"), "0x59 (59)");
+        assert_eq!(format!("{bcd:?}"), "Bcd(0x59 = 59)");
+        assert_eq!(format!("{}", Bcd::new(0x07).unwrap()), "0x07 (7)");
+    }
+
+    #[test]
+    fn test_const_seconds_table() {
+        const SECONDS_TABLE: [u8; 60] = {
+            let mut table = [0; 60];
+            let mut i = 0;
+            while i < 60 {
+                table[i] = from_decimal(i as u8);
+                i += 1;
+            }
+            table
+        };
+        const CHECKED: Result<u8, BcdError> = try_from_decimal(100);
+        const DECODED: u8 = to_decimal(0x59);
+        const VALID: bool = is_valid_bcd(0x7A);
+        const WRAPPED: Result<Bcd, BcdError> = Bcd::new(0x42);
+
+        for (i, &bcd) in SECONDS_TABLE.iter().enumerate() {
+            assert_eq!(bcd, from_decimal(i as u8));
+        }
+        assert_eq!(SECONDS_TABLE[59], 0x59);
+        assert_eq!(CHECKED, Err(BcdError::OutOfRange));
+        assert_eq!(DECODED, 59);
+        const { assert!(!VALID) };
+        assert_eq!(WRAPPED.unwrap().to_decimal(), 42);
+    }
+
+    #[test]
+    fn test_slice_round_trip() {
+        let mut state = 0x1234_5678_u32;
+        for len in 0..=20 {
+            let mut decimal = [0u8; 20];
+            for d in decimal.iter_mut().take(len) {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                *d = ((state >> 16) % 100) as u8;
+            }
+            let decimal = &decimal[..len];
+
+            let mut encoded = [0u8; 20];
+            encode_slice(decimal, &mut encoded[..len]).unwrap();
+            for (e, d) in encoded.iter().zip(decimal) {
+                assert_eq!(*e, from_decimal(*d));
+            }
+
+            let mut decoded = [0u8; 20];
+            decode_slice(&encoded[..len], &mut decoded[..len]).unwrap();
+            assert_eq!(&decoded[..len], decimal);
+
+            let mut buf = [0u8; 20];
+            buf[..len].copy_from_slice(decimal);
+            encode_in_place(&mut buf[..len]).unwrap();
+            assert_eq!(buf[..len], encoded[..len]);
+            decode_in_place(&mut buf[..len]).unwrap();
+            assert_eq!(&buf[..len], decimal);
+        }
+    }
+
+    #[test]
+    fn test_slice_length_mismatch() {
+        let mut dst = [0u8; 6];
+        assert_eq!(
+            decode_slice(&[0x12; 7], &mut dst),
+            Err(BcdError::LengthMismatch)
+        );
+        assert_eq!(
+            encode_slice(&[12; 5], &mut dst),
+            Err(BcdError::LengthMismatch)
+        );
+        assert_eq!(dst, [0; 6]);
+    }
+
+    #[test]
+    fn test_slice_invalid_values_leave_output_untouched() {
+        let mut dst = [0xEE; 3];
+        assert_eq!(
+            decode_slice(&[0x12, 0x34, 0x5A], &mut dst),
+            Err(BcdError::InvalidDigit)
+        );
+        assert_eq!(dst, [0xEE; 3]);
+        assert_eq!(
+            encode_slice(&[12, 100, 5], &mut dst),
+            Err(BcdError::OutOfRange)
+        );
+        assert_eq!(dst, [0xEE; 3]);
+
+        let mut buf = [0x12, 0xF0];
+        assert_eq!(decode_in_place(&mut buf), Err(BcdError::InvalidDigit));
+        assert_eq!(buf, [0x12, 0xF0]);
+        let mut buf = [12, 200];
+        assert_eq!(encode_in_place(&mut buf), Err(BcdError::OutOfRange));
+        assert_eq!(buf, [12, 200]);
+    }
+
+    #[test]
+    fn test_display_bcd_error() {
+        assert_eq!(
+            format!("{}", BcdError::OutOfRange),
+            "value out of range for BCD"
+        );
+        assert_eq!(format!("{}", BcdError::InvalidDigit), "invalid BCD digit");
+        assert_eq!(
+            format!("{}", BcdError::LengthMismatch),
+            "slice length mismatch"
+        );
+    }
+
     #[test]
     fn test_bcd_round_trip() {
         for i in 0..=99 {
@@ -0,0 +1,278 @@
+//! # Drift measurement
+//!
+//! [`DriftMeter`] compares a clock under test against a reference clock (for
+//! example an MCU-internal RTC against a TCXO-based DS3231) and reports how far
+//! it has drifted since a baseline.
+//!
+//! ```
+//! use rtc_hal::calibration::DriftMeter;
+//! # use rtc_hal::{datetime::DateTime, error::{ErrorKind, ErrorType}, rtc::Rtc};
+//! # struct Ds3231;
+//! # impl ErrorType for Ds3231 { type Error = ErrorKind; }
+//! # impl Rtc for Ds3231 {
+//! #     fn get_datetime(&mut self) -> Result<DateTime, ErrorKind> { Ok(DateTime::MIN) }
+//! #     fn set_datetime(&mut self, _: &DateTime) -> Result<(), ErrorKind> { Ok(()) }
+//! # }
+//! # struct InternalRtc;
+//! # impl ErrorType for InternalRtc { type Error = ErrorKind; }
+//! # impl Rtc for InternalRtc {
+//! #     fn get_datetime(&mut self) -> Result<DateTime, ErrorKind> { Ok(DateTime::MIN) }
+//! #     fn set_datetime(&mut self, _: &DateTime) -> Result<(), ErrorKind> { Ok(()) }
+//! # }
+//!
+//! let mut meter = DriftMeter::new(Ds3231, InternalRtc);
+//! meter.start()?;
+//! // ... let a few hours pass ...
+//! let drift = meter.measure()?;
+//! if drift.ppm.abs() > 20 {
+//!     // trim the internal oscillator
+//! }
+//! # Ok::<(), rtc_hal::calibration::DriftError<ErrorKind, ErrorKind>>(())
+//! ```
+//!
+//! ## Accuracy
+//!
+//! The clocks are read one after the other, reference first, with 1 second
+//! resolution. Keeping the same order for the baseline and every measurement
+//! makes a constant delay between the two reads cancel out. What remains is:
+//!
+//! - up to ±1 s from truncating each clock to whole seconds at both ends of
+//!   the interval, so `offset_seconds` can be off by one in either direction;
+//! - any variation in the delay between the two reads (bus contention,
+//!   interrupts), added on top.
+//!
+//! The resulting error in `ppm` shrinks with the interval: about ±1 ppm after
+//! roughly 12 days, ±12 ppm after a day. Measure over long intervals.
+use crate::error::{Error, ErrorKind};
+use crate::rtc::Rtc;
+
+/// Drift of the clock under test relative to the reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Drift {
+    /// Seconds elapsed on the reference clock since the baseline
+    pub elapsed_ref: u64,
+    /// Seconds the clock under test has gained (positive) or lost (negative)
+    pub offset_seconds: i64,
+    /// Rate error in parts per million, positive if the clock under test runs fast
+    ///
+    /// Zero while no time has elapsed on the reference.
+    pub ppm: i32,
+}
+
+impl Drift {
+    fn compute(elapsed_ref: u64, elapsed_target: u64) -> Self {
+        let offset = elapsed_target as i128 - elapsed_ref as i128;
+        let ppm = if elapsed_ref == 0 {
+            0
+        } else {
+            offset * 1_000_000 / elapsed_ref as i128
+        };
+        Self {
+            elapsed_ref,
+            offset_seconds: offset.clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+            ppm: ppm.clamp(i32::MIN as i128, i32::MAX as i128) as i32,
+        }
+    }
+}
+
+/// Error returned by [`DriftMeter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DriftError<A, B> {
+    /// Reading the reference clock failed
+    Reference(A),
+    /// Reading the clock under test failed
+    Target(B),
+    /// [`DriftMeter::measure`] was called before [`DriftMeter::start`]
+    NotStarted,
+}
+
+impl<A: Error, B: Error> Error for DriftError<A, B> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            DriftError::Reference(e) => e.kind(),
+            DriftError::Target(e) => e.kind(),
+            DriftError::NotStarted => ErrorKind::Other,
+        }
+    }
+}
+
+impl<A: core::fmt::Display, B: core::fmt::Display> core::fmt::Display for DriftError<A, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DriftError::Reference(e) => write!(f, "reference clock: {e}"),
+            DriftError::Target(e) => write!(f, "clock under test: {e}"),
+            DriftError::NotStarted => write!(f, "no baseline recorded"),
+        }
+    }
+}
+
+/// Measures drift of one [`Rtc`] against another
+///
+/// See the [module documentation](self) for the accuracy of the result.
+pub struct DriftMeter<A, B> {
+    reference: A,
+    target: B,
+    baseline: Option<(u64, u64)>,
+}
+
+impl<A: Rtc, B: Rtc> DriftMeter<A, B> {
+    /// Create a meter comparing `target` against `reference`
+    pub fn new(reference: A, target: B) -> Self {
+        Self {
+            reference,
+            target,
+            baseline: None,
+        }
+    }
+
+    /// Record the baseline readings, replacing any previous baseline
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either clock cannot be read. The previous
+    /// baseline is kept in that case.
+    pub fn start(&mut self) -> Result<(), DriftError<A::Error, B::Error>> {
+        self.baseline = Some(self.read()?);
+        Ok(())
+    }
+
+    /// Compute the drift accumulated since [`start`](Self::start)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DriftError::NotStarted`] if no baseline was recorded, or an
+    /// error if either clock cannot be read.
+    pub fn measure(&mut self) -> Result<Drift, DriftError<A::Error, B::Error>> {
+        let (ref_start, target_start) = self.baseline.ok_or(DriftError::NotStarted)?;
+        let (ref_now, target_now) = self.read()?;
+        // A clock that went backwards (e.g. was set meanwhile) counts as no time elapsed
+        Ok(Drift::compute(
+            ref_now.saturating_sub(ref_start),
+            target_now.saturating_sub(target_start),
+        ))
+    }
+
+    /// Release both clocks, reference first
+    pub fn into_inner(self) -> (A, B) {
+        (self.reference, self.target)
+    }
+
+    /// Read both clocks, always in the same order
+    fn read(&mut self) -> Result<(u64, u64), DriftError<A::Error, B::Error>> {
+        let reference = self
+            .reference
+            .get_unix_time()
+            .map_err(DriftError::Reference)?;
+        let target = self.target.get_unix_time().map_err(DriftError::Target)?;
+        Ok((reference, target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::DateTime;
+    use crate::error::ErrorType;
+
+    /// Advances by `rate_num / rate_den` seconds per read, starting at `start`
+    struct MockRtc {
+        ticks: u64,
+        start: u64,
+        rate_num: u64,
+        rate_den: u64,
+        fail: bool,
+    }
+
+    impl MockRtc {
+        fn new(start: u64, rate_num: u64, rate_den: u64) -> Self {
+            Self {
+                ticks: 0,
+                start,
+                rate_num,
+                rate_den,
+                fail: false,
+            }
+        }
+    }
+
+    impl ErrorType for MockRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MockRtc {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            if self.fail {
+                return Err(ErrorKind::Bus);
+            }
+            let secs = self.start + self.ticks * self.rate_num / self.rate_den;
+            self.ticks += 1_000;
+            Ok(DateTime::from_unix_timestamp(secs)?)
+        }
+
+        fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_fast_clock() {
+        // 1000 s per read on the reference, 1000.05 s on the target: +50 ppm
+        let reference = MockRtc::new(1_700_000_000, 1, 1);
+        let target = MockRtc::new(1_600_000_000, 100_005, 100_000);
+        let mut meter = DriftMeter::new(reference, target);
+        meter.start().unwrap();
+        for _ in 0..98 {
+            meter.reference.get_unix_time().unwrap();
+            meter.target.get_unix_time().unwrap();
+        }
+        assert_eq!(
+            meter.measure(),
+            Ok(Drift {
+                elapsed_ref: 99_000,
+                offset_seconds: 4,
+                ppm: 40,
+            })
+        );
+    }
+
+    #[test]
+    fn test_slow_clock() {
+        let reference = MockRtc::new(0, 1, 1);
+        let target = MockRtc::new(0, 999, 1_000);
+        let mut meter = DriftMeter::new(reference, target);
+        meter.start().unwrap();
+        let drift = meter.measure().unwrap();
+        assert_eq!(drift.elapsed_ref, 1_000);
+        assert_eq!(drift.offset_seconds, -1);
+        assert_eq!(drift.ppm, -1_000);
+    }
+
+    #[test]
+    fn test_no_elapsed_time() {
+        assert_eq!(
+            Drift::compute(0, 5),
+            Drift {
+                elapsed_ref: 0,
+                offset_seconds: 5,
+                ppm: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut meter = DriftMeter::new(MockRtc::new(0, 1, 1), MockRtc::new(0, 1, 1));
+        assert_eq!(meter.measure(), Err(DriftError::NotStarted));
+        meter.target.fail = true;
+        assert_eq!(meter.start(), Err(DriftError::Target(ErrorKind::Bus)));
+        assert_eq!(meter.measure(), Err(DriftError::NotStarted));
+        meter.target.fail = false;
+        meter.start().unwrap();
+        meter.reference.fail = true;
+        let err = meter.measure().unwrap_err();
+        assert_eq!(err, DriftError::Reference(ErrorKind::Bus));
+        assert_eq!(err.kind(), ErrorKind::Bus);
+    }
+}
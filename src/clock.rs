@@ -0,0 +1,386 @@
+//! # Monotonic clock adapter
+//!
+//! [`RtcClock`] turns any [`Rtc`] into a clock with 1 Hz resolution, counted
+//! in seconds since the Unix epoch. This lets RTC time feed libraries that
+//! work with `fugit` instants or take an `embedded_time::Clock`.
+//!
+//! Enabled with the `fugit` feature, which adds `try_now` returning a `fugit`
+//! [`Instant`], and the `embedded-time` feature, which implements
+//! `embedded_time::Clock`. With both enabled, the inherent `try_now` shadows
+//! the trait method, so call the latter as `Clock::try_now(&clock)`.
+//!
+//! ## Interior mutability
+//!
+//! Reading the time needs `&mut R`, but clocks are read through a shared
+//! reference. `RtcClock` keeps the RTC in a [`RefCell`] so that its methods
+//! take `&self`. A reentrant read (e.g. from a callback running while the RTC
+//! is borrowed) fails with [`ClockError::Busy`] instead of panicking.
+//!
+//! `RefCell` is not `Sync`, so an `RtcClock` cannot be shared with interrupt
+//! handlers. [`CriticalSectionClock`] keeps the RTC in a
+//! `critical_section::Mutex` instead and reads it inside a critical section,
+//! so it can be placed in a `static`. It needs the `critical-section` feature.
+//!
+//! ```
+//! # #[cfg(feature = "fugit")] {
+//! use rtc_hal::clock::RtcClock;
+//! # use rtc_hal::{datetime::DateTime, error::{ErrorKind, ErrorType}, rtc::Rtc};
+//! # struct Ds1307;
+//! # impl ErrorType for Ds1307 { type Error = ErrorKind; }
+//! # impl Rtc for Ds1307 {
+//! #     fn get_datetime(&mut self) -> Result<DateTime, ErrorKind> { DateTime::new(2024, 8, 16, 12, 0, 0).map_err(Into::into) }
+//! #     fn set_datetime(&mut self, _: &DateTime) -> Result<(), ErrorKind> { Ok(()) }
+//! # }
+//!
+//! let clock = RtcClock::new(Ds1307);
+//! let start = clock.try_now()?;
+//! // ...
+//! let elapsed = clock.try_now()? - start;
+//! assert!(elapsed.to_secs() < 60);
+//! # }
+//! # Ok::<(), rtc_hal::clock::ClockError<rtc_hal::error::ErrorKind>>(())
+//! ```
+use core::cell::RefCell;
+
+use crate::error::{Error, ErrorKind};
+use crate::rtc::Rtc;
+
+/// Instant in seconds since the Unix epoch (1970-01-01 00:00:00)
+#[cfg(feature = "fugit")]
+pub type Instant = fugit::Instant<u64, 1, 1>;
+
+/// Duration in seconds
+#[cfg(feature = "fugit")]
+pub type Duration = fugit::Duration<u64, 1, 1>;
+
+/// Error returned when reading a clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClockError<E> {
+    /// Reading the RTC failed
+    Rtc(E),
+    /// The RTC is already borrowed (reentrant read)
+    Busy,
+}
+
+impl<E: Error> Error for ClockError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ClockError::Rtc(e) => e.kind(),
+            ClockError::Busy => ErrorKind::Busy,
+        }
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for ClockError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ClockError::Rtc(e) => write!(f, "{e}"),
+            ClockError::Busy => write!(f, "RTC is already borrowed"),
+        }
+    }
+}
+
+/// `embedded_time` has no room for the cause, so an oscillator stop
+/// ([`ErrorKind::ClockIntegrity`]) becomes `NotRunning` and everything else
+/// `Unspecified`.
+#[cfg(feature = "embedded-time")]
+impl<E: Error> From<ClockError<E>> for embedded_time::clock::Error {
+    fn from(error: ClockError<E>) -> Self {
+        match error.kind() {
+            ErrorKind::ClockIntegrity => embedded_time::clock::Error::NotRunning,
+            _ => embedded_time::clock::Error::Unspecified,
+        }
+    }
+}
+
+fn read<R: Rtc>(rtc: &RefCell<R>) -> Result<u64, ClockError<R::Error>> {
+    let mut rtc = rtc.try_borrow_mut().map_err(|_| ClockError::Busy)?;
+    rtc.get_unix_time().map_err(ClockError::Rtc)
+}
+
+/// Clock with 1 Hz resolution backed by an [`Rtc`]
+pub struct RtcClock<R> {
+    rtc: RefCell<R>,
+}
+
+impl<R: Rtc> RtcClock<R> {
+    /// Create a clock that reads the given RTC
+    pub const fn new(rtc: R) -> Self {
+        Self {
+            rtc: RefCell::new(rtc),
+        }
+    }
+
+    /// Release the RTC
+    pub fn into_inner(self) -> R {
+        self.rtc.into_inner()
+    }
+
+    /// Read the current time from the RTC, in seconds since the Unix epoch
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClockError::Rtc` if reading the RTC fails, or
+    /// `ClockError::Busy` if the RTC is already borrowed.
+    pub fn try_unix_time(&self) -> Result<u64, ClockError<R::Error>> {
+        read(&self.rtc)
+    }
+
+    /// Read the current time from the RTC
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClockError::Rtc` if reading the RTC fails, or
+    /// `ClockError::Busy` if the RTC is already borrowed.
+    #[cfg(feature = "fugit")]
+    pub fn try_now(&self) -> Result<Instant, ClockError<R::Error>> {
+        self.try_unix_time().map(Instant::from_ticks)
+    }
+}
+
+/// Reads the RTC; errors are translated as described on
+/// [`ClockError`]'s `From` impl.
+#[cfg(feature = "embedded-time")]
+impl<R: Rtc> embedded_time::Clock for RtcClock<R> {
+    type T = u64;
+    const SCALING_FACTOR: embedded_time::fraction::Fraction =
+        embedded_time::fraction::Fraction::new(1, 1);
+
+    fn try_now(&self) -> Result<embedded_time::Instant<Self>, embedded_time::clock::Error> {
+        Ok(embedded_time::Instant::new(self.try_unix_time()?))
+    }
+}
+
+/// Clock with 1 Hz resolution backed by an [`Rtc`] in a
+/// `critical_section::Mutex`
+///
+/// Unlike [`RtcClock`] it is `Sync` when `R` is `Send`, so it can live in a
+/// `static` shared with interrupt handlers. Every read runs inside a critical
+/// section, so interrupts are masked for the duration of the bus transfer.
+///
+/// ```
+/// use rtc_hal::clock::CriticalSectionClock;
+/// # use rtc_hal::{datetime::DateTime, error::{ErrorKind, ErrorType}, rtc::Rtc};
+/// # struct Ds1307;
+/// # impl ErrorType for Ds1307 { type Error = ErrorKind; }
+/// # impl Rtc for Ds1307 {
+/// #     fn get_datetime(&mut self) -> Result<DateTime, ErrorKind> { DateTime::new(2024, 8, 16, 12, 0, 0).map_err(Into::into) }
+/// #     fn set_datetime(&mut self, _: &DateTime) -> Result<(), ErrorKind> { Ok(()) }
+/// # }
+///
+/// static CLOCK: CriticalSectionClock<Ds1307> = CriticalSectionClock::new(Ds1307);
+///
+/// assert_eq!(CLOCK.try_unix_time()?, 1_723_809_600);
+/// # Ok::<(), rtc_hal::clock::ClockError<ErrorKind>>(())
+/// ```
+#[cfg(feature = "critical-section")]
+pub struct CriticalSectionClock<R> {
+    rtc: critical_section::Mutex<RefCell<R>>,
+}
+
+#[cfg(feature = "critical-section")]
+impl<R: Rtc> CriticalSectionClock<R> {
+    /// Create a clock that reads the given RTC
+    pub const fn new(rtc: R) -> Self {
+        Self {
+            rtc: critical_section::Mutex::new(RefCell::new(rtc)),
+        }
+    }
+
+    /// Release the RTC
+    pub fn into_inner(self) -> R {
+        self.rtc.into_inner().into_inner()
+    }
+
+    /// Read the current time from the RTC, in seconds since the Unix epoch
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClockError::Rtc` if reading the RTC fails, or
+    /// `ClockError::Busy` if the RTC is already borrowed by an enclosing
+    /// read.
+    pub fn try_unix_time(&self) -> Result<u64, ClockError<R::Error>> {
+        critical_section::with(|cs| read(self.rtc.borrow(cs)))
+    }
+
+    /// Read the current time from the RTC
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_unix_time`](Self::try_unix_time).
+    #[cfg(feature = "fugit")]
+    pub fn try_now(&self) -> Result<Instant, ClockError<R::Error>> {
+        self.try_unix_time().map(Instant::from_ticks)
+    }
+}
+
+/// Reads the RTC inside a critical section; errors are translated as for
+/// [`RtcClock`].
+#[cfg(all(feature = "critical-section", feature = "embedded-time"))]
+impl<R: Rtc> embedded_time::Clock for CriticalSectionClock<R> {
+    type T = u64;
+    const SCALING_FACTOR: embedded_time::fraction::Fraction =
+        embedded_time::fraction::Fraction::new(1, 1);
+
+    fn try_now(&self) -> Result<embedded_time::Instant<Self>, embedded_time::clock::Error> {
+        Ok(embedded_time::Instant::new(self.try_unix_time()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::DateTime;
+    use crate::error::ErrorType;
+
+    /// Advances by `step` seconds on every read
+    struct Ticking {
+        now: u64,
+        step: u64,
+        fail: bool,
+    }
+
+    impl ErrorType for Ticking {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for Ticking {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            if self.fail {
+                return Err(ErrorKind::Bus);
+            }
+            let now = DateTime::from_unix_timestamp(self.now)?;
+            self.now += self.step;
+            Ok(now)
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.now = datetime.to_unix_timestamp();
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "fugit")]
+    #[test]
+    fn test_try_now_advances() {
+        let clock = RtcClock::new(Ticking {
+            now: 1_723_809_600,
+            step: 5,
+            fail: false,
+        });
+        let first = clock.try_now().unwrap();
+        assert_eq!(first.ticks(), 1_723_809_600);
+        let second = clock.try_now().unwrap();
+        assert_eq!(second - first, Duration::secs(5));
+        assert!(second > first);
+        assert_eq!(clock.into_inner().now, 1_723_809_610);
+    }
+
+    #[cfg(feature = "fugit")]
+    #[test]
+    fn test_try_now_errors() {
+        let clock = RtcClock::new(Ticking {
+            now: 0,
+            step: 1,
+            fail: true,
+        });
+        let err = clock.try_now().unwrap_err();
+        assert_eq!(err, ClockError::Rtc(ErrorKind::Bus));
+        assert_eq!(err.kind(), ErrorKind::Bus);
+
+        let clock = RtcClock::new(Ticking {
+            now: 0,
+            step: 1,
+            fail: false,
+        });
+        let _guard = clock.rtc.borrow_mut();
+        let err = clock.try_now().unwrap_err();
+        assert_eq!(err, ClockError::Busy);
+        assert_eq!(err.kind(), ErrorKind::Busy);
+    }
+
+    #[cfg(feature = "embedded-time")]
+    #[test]
+    fn test_embedded_time_clock() {
+        use embedded_time::Clock;
+        use embedded_time::duration::Seconds;
+
+        let clock = RtcClock::new(Ticking {
+            now: 1_723_809_600,
+            step: 5,
+            fail: false,
+        });
+        let first = Clock::try_now(&clock).unwrap();
+        assert_eq!(
+            Seconds::<u64>::try_from(first.duration_since_epoch()),
+            Ok(Seconds(1_723_809_600))
+        );
+        let second = Clock::try_now(&clock).unwrap();
+        assert_eq!(
+            Seconds::<u64>::try_from(second.checked_duration_since(&first).unwrap()),
+            Ok(Seconds(5))
+        );
+        assert!(second > first);
+    }
+
+    #[cfg(feature = "embedded-time")]
+    #[test]
+    fn test_embedded_time_errors() {
+        use embedded_time::Clock;
+        use embedded_time::clock::Error;
+
+        let clock = RtcClock::new(Ticking {
+            now: 0,
+            step: 1,
+            fail: true,
+        });
+        assert_eq!(Clock::try_now(&clock).err(), Some(Error::Unspecified));
+
+        let clock = RtcClock::new(Ticking {
+            now: 0,
+            step: 1,
+            fail: false,
+        });
+        let guard = clock.rtc.borrow_mut();
+        assert_eq!(Clock::try_now(&clock).err(), Some(Error::Unspecified));
+        drop(guard);
+
+        assert_eq!(
+            Error::from(ClockError::Rtc(ErrorKind::ClockIntegrity)),
+            Error::NotRunning
+        );
+        assert_eq!(
+            Error::from(ClockError::<ErrorKind>::Busy),
+            Error::Unspecified
+        );
+    }
+
+    #[cfg(feature = "critical-section")]
+    #[test]
+    fn test_critical_section_clock() {
+        static CLOCK: CriticalSectionClock<Ticking> = CriticalSectionClock::new(Ticking {
+            now: 1_723_809_600,
+            step: 5,
+            fail: false,
+        });
+
+        assert_eq!(CLOCK.try_unix_time(), Ok(1_723_809_600));
+        assert_eq!(CLOCK.try_unix_time(), Ok(1_723_809_605));
+
+        // A read from inside another read's critical section finds the RTC
+        // borrowed
+        critical_section::with(|cs| {
+            let _guard = CLOCK.rtc.borrow(cs).borrow_mut();
+            assert_eq!(CLOCK.try_unix_time(), Err(ClockError::Busy));
+        });
+
+        let clock = CriticalSectionClock::new(Ticking {
+            now: 0,
+            step: 1,
+            fail: true,
+        });
+        assert_eq!(clock.try_unix_time(), Err(ClockError::Rtc(ErrorKind::Bus)));
+        assert!(clock.into_inner().fail);
+    }
+}
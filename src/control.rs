@@ -10,3 +10,163 @@ pub trait RtcPowerControl: Rtc {
     /// Halt the RTC oscillator, pausing timekeeping until restarted.
     fn halt_clock(&mut self) -> Result<(), Self::Error>;
 }
+
+#[cfg(feature = "alloc")]
+impl<T: RtcPowerControl + ?Sized> RtcPowerControl for alloc::boxed::Box<T> {
+    #[inline]
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        T::start_clock(self)
+    }
+
+    #[inline]
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        T::halt_clock(self)
+    }
+}
+
+/// This trait extends [`Rtc`] with detection of interrupted timekeeping.
+///
+/// Chips flag when the oscillator stopped, e.g. after the backup battery ran
+/// out: the DS1307 clock-halt (CH) bit, the DS3231 oscillator-stop flag (OSF)
+/// and the PCF8563 voltage-low (VL) bit. Once set, the stored time cannot be
+/// trusted until it is written again.
+///
+/// ## Relationship with [`RtcPowerControl`]
+///
+/// On some chips the flag and the power control share a bit: on the DS1307,
+/// [`RtcPowerControl::halt_clock`] sets CH, so the time reads as invalid until
+/// [`RtcPowerControl::start_clock`] clears it. On chips with a separate flag,
+/// such as the DS3231, starting the clock does not clear the flag, and
+/// [`clear_integrity_flag`](ClockIntegrity::clear_integrity_flag) does not
+/// start the oscillator.
+///
+/// ## Example
+///
+/// ```ignore
+/// if !rtc.datetime_valid()? {
+///     let now = network_time()?;
+///     rtc.set_datetime(&now)?;
+///     rtc.clear_integrity_flag()?;
+/// }
+/// let now = rtc.get_datetime()?;
+/// ```
+pub trait ClockIntegrity: Rtc {
+    /// Check whether timekeeping has been uninterrupted since the flag was last cleared.
+    ///
+    /// Returns `Ok(false)` if the oscillator stopped and the time must be set again.
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error>;
+
+    /// Clear the integrity flag, typically right after setting the time.
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ClockIntegrity + ?Sized> ClockIntegrity for alloc::boxed::Box<T> {
+    #[inline]
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+        T::datetime_valid(self)
+    }
+
+    #[inline]
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+        T::clear_integrity_flag(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::DateTime;
+    use crate::error::{ErrorKind, ErrorType};
+
+    /// DS3231-like mock: the oscillator-stop flag is set at power-up and
+    /// survives until cleared, independently of the running state.
+    struct MockRtc {
+        now: DateTime,
+        running: bool,
+        oscillator_stopped: bool,
+    }
+
+    impl ErrorType for MockRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MockRtc {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(self.now)
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.now = *datetime;
+            Ok(())
+        }
+    }
+
+    impl RtcPowerControl for MockRtc {
+        fn start_clock(&mut self) -> Result<(), Self::Error> {
+            self.running = true;
+            Ok(())
+        }
+
+        fn halt_clock(&mut self) -> Result<(), Self::Error> {
+            self.running = false;
+            self.oscillator_stopped = true;
+            Ok(())
+        }
+    }
+
+    impl ClockIntegrity for MockRtc {
+        fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.oscillator_stopped)
+        }
+
+        fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+            self.oscillator_stopped = false;
+            Ok(())
+        }
+    }
+
+    /// Resync from a trusted source if the stored time is stale
+    fn checked_now<R: ClockIntegrity>(
+        rtc: &mut R,
+        network_time: DateTime,
+    ) -> Result<DateTime, R::Error> {
+        if !rtc.datetime_valid()? {
+            rtc.set_datetime(&network_time)?;
+            rtc.clear_integrity_flag()?;
+        }
+        rtc.get_datetime()
+    }
+
+    #[test]
+    fn test_resync_when_stale() {
+        let network = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        let mut rtc = MockRtc {
+            now: DateTime::new(2000, 1, 1, 0, 0, 0).unwrap(),
+            running: true,
+            oscillator_stopped: true,
+        };
+        assert_eq!(checked_now(&mut rtc, network), Ok(network));
+        assert_eq!(rtc.datetime_valid(), Ok(true));
+
+        // Valid time is kept even if the network says otherwise
+        let later = DateTime::new(2024, 8, 16, 13, 0, 0).unwrap();
+        assert_eq!(checked_now(&mut rtc, later), Ok(network));
+    }
+
+    #[test]
+    fn test_halt_invalidates_until_cleared() {
+        let mut rtc = MockRtc {
+            now: DateTime::MIN,
+            running: true,
+            oscillator_stopped: false,
+        };
+        rtc.halt_clock().unwrap();
+        assert_eq!(rtc.datetime_valid(), Ok(false));
+        rtc.start_clock().unwrap();
+        assert!(rtc.running);
+        assert_eq!(rtc.datetime_valid(), Ok(false));
+        rtc.clear_integrity_flag().unwrap();
+        assert_eq!(rtc.datetime_valid(), Ok(true));
+    }
+}
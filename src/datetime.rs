@@ -22,6 +22,15 @@
 //! ## Weekday Format
 //! - This module uses **1=Sunday to 7=Saturday**
 //! - Drivers must handle conversion if required
+//!
+//! ## Time Zones
+//! `DateTime` carries no time zone. [`UtcOffset`] converts between UTC and a fixed
+//! local offset, and the `dst` submodule implements the EU and US daylight
+//! saving time rules on top of it. The DST rules are opt-in through the `dst`
+//! feature.
+
+#[cfg(feature = "dst")]
+pub mod dst;
 
 /// Errors that can occur when working with DateTime
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,7 +75,16 @@ impl core::error::Error for DateTimeError {}
 ///
 /// - Validates that `year >= 1970`
 /// - Other limits (e.g., 2000-2099) must be enforced by individual drivers
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Values are ordered chronologically, since the fields are compared from the
+/// most significant (year) to the least significant (second).
+///
+/// ## Weekday
+/// A `DateTime` can optionally carry the weekday read from an RTC register
+/// (see [`DateTime::new_with_weekday`]). The carried weekday is always checked
+/// against the date and the setters keep it consistent, so it never disagrees
+/// with [`DateTime::calculate_weekday`]. It is not part of equality or ordering.
+#[derive(Debug, Clone, Copy)]
 pub struct DateTime {
     /// Year (full year, e.g., 2024)
     year: u16,
@@ -80,9 +98,77 @@ pub struct DateTime {
     minute: u8,
     /// Second (0-59)
     second: u8,
+    /// Weekday matching the date, if one was supplied
+    weekday: Option<Weekday>,
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields() == other.fields()
+    }
+}
+
+impl Eq for DateTime {}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DateTime {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.fields().cmp(&other.fields())
+    }
 }
 
 impl DateTime {
+    /// The earliest representable `DateTime`: 1970-01-01 00:00:00.
+    pub const MIN: DateTime = DateTime {
+        year: 1970,
+        month: 1,
+        day_of_month: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        weekday: None,
+    };
+
+    /// The latest representable `DateTime`: 65535-12-31 23:59:59.
+    pub const MAX: DateTime = DateTime {
+        year: u16::MAX,
+        month: 12,
+        day_of_month: 31,
+        hour: 23,
+        minute: 59,
+        second: 59,
+        weekday: None,
+    };
+
+    /// Create a `DateTime` in a const context without validation.
+    ///
+    /// Callers must pass a valid date and time; tables built with this are
+    /// checked by their tests.
+    #[cfg(any(test, feature = "mock"))]
+    pub(crate) const fn new_unchecked(
+        year: u16,
+        month: u8,
+        day_of_month: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Self {
+        DateTime {
+            year,
+            month,
+            day_of_month,
+            hour,
+            minute,
+            second,
+            weekday: None,
+        }
+    }
+
     /// Create a new `DateTime` instance with validation.
     ///
     /// # Errors
@@ -103,11 +189,38 @@ impl DateTime {
             hour,
             minute,
             second,
+            weekday: None,
         };
         dt.validate()?;
         Ok(dt)
     }
 
+    /// Create a new `DateTime` instance that carries a weekday.
+    ///
+    /// Intended for drivers that read a weekday register alongside the date,
+    /// so a chip whose weekday disagrees with its date is caught as corrupted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DateTimeError` if any component is out of valid range, or
+    /// `DateTimeError::InvalidWeekday` if `weekday` does not match the date.
+    pub fn new_with_weekday(
+        year: u16,
+        month: u8,
+        day_of_month: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        weekday: Weekday,
+    ) -> Result<Self, DateTimeError> {
+        let mut dt = Self::new(year, month, day_of_month, hour, minute, second)?;
+        if dt.calculate_weekday()? != weekday {
+            return Err(DateTimeError::InvalidWeekday);
+        }
+        dt.weekday = Some(weekday);
+        Ok(dt)
+    }
+
     /// Validate all datetime components.
     ///
     /// # Errors
@@ -209,6 +322,7 @@ impl DateTime {
         Self::validate_year(year)?;
         Self::validate_day(year, self.month, self.day_of_month)?;
         self.year = year;
+        self.refresh_weekday();
         Ok(())
     }
 
@@ -219,6 +333,7 @@ impl DateTime {
         Self::validate_month(month)?;
         Self::validate_day(self.year, month, self.day_of_month)?;
         self.month = month;
+        self.refresh_weekday();
         Ok(())
     }
 
@@ -226,6 +341,7 @@ impl DateTime {
     pub fn set_day_of_month(&mut self, day_of_month: u8) -> Result<(), DateTimeError> {
         Self::validate_day(self.year, self.month, day_of_month)?;
         self.day_of_month = day_of_month;
+        self.refresh_weekday();
         Ok(())
     }
 
@@ -250,209 +366,1189 @@ impl DateTime {
         Ok(())
     }
 
-    /// Calculate weekday for this DateTime
-    pub fn calculate_weekday(&self) -> Result<Weekday, DateTimeError> {
-        calculate_weekday(self.year, self.month, self.day_of_month)
+    /// Check if this is [`DateTime::MIN`].
+    ///
+    /// Useful for detecting chips that were never set and still hold
+    /// the epoch value.
+    pub fn is_min(&self) -> bool {
+        *self == Self::MIN
     }
-}
-
-/// Day of the week (1 = Sunday .. 7 = Saturday)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
-pub enum Weekday {
-    /// Sunday starts with 1
-    Sunday = 1,
-    /// Monday
-    Monday = 2,
-    /// Tuesday
-    Tuesday = 3,
-    /// Wednesday
-    Wednesday = 4,
-    /// Thursday
-    Thursday = 5,
-    /// Friday
-    Friday = 6,
-    /// Saturday
-    Saturday = 7,
-}
 
-impl Weekday {
-    /// Create a Weekday from a raw u8 (1 = Sunday .. 7 = Saturday).
-    pub fn from_number(n: u8) -> Result<Self, DateTimeError> {
-        match n {
-            1 => Ok(Self::Sunday),
-            2 => Ok(Self::Monday),
-            3 => Ok(Self::Tuesday),
-            4 => Ok(Self::Wednesday),
-            5 => Ok(Self::Thursday),
-            6 => Ok(Self::Friday),
-            7 => Ok(Self::Saturday),
-            _ => Err(DateTimeError::InvalidWeekday),
-        }
+    /// Check if this is [`DateTime::MAX`].
+    pub fn is_max(&self) -> bool {
+        *self == Self::MAX
     }
 
-    /// Get the number form (1 = Sunday .. 7 = Saturday).
-    pub fn to_number(self) -> u8 {
-        self as u8
+    /// Pack this `DateTime` into a `u64` whose numeric order matches chronological order.
+    ///
+    /// Each field gets its own bit range, from most to least significant:
+    ///
+    /// | Bits   | Field        |
+    /// |--------|--------------|
+    /// | 63..56 | unused (0)   |
+    /// | 55..40 | year         |
+    /// | 39..32 | month        |
+    /// | 31..24 | day of month |
+    /// | 23..16 | hour         |
+    /// | 15..8  | minute       |
+    /// | 7..0   | second       |
+    ///
+    /// The layout is stable, so the value can be persisted (e.g. in NVRAM or flash)
+    /// and compared without decoding.
+    pub fn to_sortable_u64(&self) -> u64 {
+        ((self.year as u64) << 40)
+            | ((self.month as u64) << 32)
+            | ((self.day_of_month as u64) << 24)
+            | ((self.hour as u64) << 16)
+            | ((self.minute as u64) << 8)
+            | (self.second as u64)
     }
 
-    /// Get the weekday name as a string slice
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Weekday::Sunday => "Sunday",
-            Weekday::Monday => "Monday",
-            Weekday::Tuesday => "Tuesday",
-            Weekday::Wednesday => "Wednesday",
-            Weekday::Thursday => "Thursday",
-            Weekday::Friday => "Friday",
-            Weekday::Saturday => "Saturday",
+    /// Unpack a value produced by [`DateTime::to_sortable_u64`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DateTimeError` if any field is out of range, or
+    /// `DateTimeError::InvalidYear` if the unused high bits are set.
+    pub fn from_sortable_u64(value: u64) -> Result<Self, DateTimeError> {
+        if value >> 56 != 0 {
+            return Err(DateTimeError::InvalidYear);
         }
+        Self::new(
+            (value >> 40) as u16,
+            (value >> 32) as u8,
+            (value >> 24) as u8,
+            (value >> 16) as u8,
+            (value >> 8) as u8,
+            value as u8,
+        )
     }
-}
-
-/// Check if a year is a leap year
-pub fn is_leap_year(year: u16) -> bool {
-    (year % 4 == 0) && (year % 100 != 0 || year % 400 == 0)
-}
 
-/// Get the number of days in a month
-pub fn days_in_month(year: u16, month: u8) -> u8 {
-    match month {
-        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-        4 | 6 | 9 | 11 => 30,
-        2 => {
-            if is_leap_year(year) {
-                29
-            } else {
-                28
-            }
-        }
-        _ => 0,
+    /// Fields from the most to the least significant, without the weekday
+    fn fields(&self) -> (u16, u8, u8, u8, u8, u8) {
+        (
+            self.year,
+            self.month,
+            self.day_of_month,
+            self.hour,
+            self.minute,
+            self.second,
+        )
     }
-}
-
-/// Calculate the day of the week using Zeller's congruence algorithm
-/// Returns 1=Sunday, 2=Monday, ..., 7=Saturday
-pub fn calculate_weekday(year: u16, month: u8, day_of_month: u8) -> Result<Weekday, DateTimeError> {
-    let (year, month) = if month < 3 {
-        (year - 1, month + 12)
-    } else {
-        (year, month)
-    };
 
-    let k = year % 100;
-    let j = year / 100;
+    /// Encode this `DateTime` into a fixed 7-byte representation for storage.
+    ///
+    /// The layout is stable across versions:
+    ///
+    /// | Byte | Field                      |
+    /// |------|----------------------------|
+    /// | 0..2 | year (`u16`, little-endian) |
+    /// | 2    | month                      |
+    /// | 3    | day of month               |
+    /// | 4    | hour                       |
+    /// | 5    | minute                     |
+    /// | 6    | second                     |
+    pub fn to_bytes(&self) -> [u8; 7] {
+        let [year_lo, year_hi] = self.year.to_le_bytes();
+        [
+            year_lo,
+            year_hi,
+            self.month,
+            self.day_of_month,
+            self.hour,
+            self.minute,
+            self.second,
+        ]
+    }
 
-    let h =
-        (day_of_month as u16 + ((13 * (month as u16 + 1)) / 5) + k + (k / 4) + (j / 4) - 2 * j) % 7;
+    /// Decode a `DateTime` produced by [`DateTime::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DateTimeError` if any field is out of range, for example
+    /// when the stored bytes were corrupted.
+    pub fn from_bytes(bytes: &[u8; 7]) -> Result<Self, DateTimeError> {
+        Self::new(
+            u16::from_le_bytes([bytes[0], bytes[1]]),
+            bytes[2],
+            bytes[3],
+            bytes[4],
+            bytes[5],
+            bytes[6],
+        )
+    }
 
-    // Convert Zeller's result (0=Saturday) to our format (1=Sunday)
-    let weekday_num = ((h + 6) % 7) + 1;
+    /// Get the number of seconds since the Unix epoch (1970-01-01 00:00:00).
+    ///
+    /// The `DateTime` is treated as UTC.
+    pub fn to_unix_timestamp(&self) -> u64 {
+        let days = days_since_epoch(self.year, self.month, self.day_of_month) as u64;
+        days * 86_400 + self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64
+    }
 
-    // This should never fail since we're calculating a valid weekday
-    Weekday::from_number(weekday_num as u8)
-}
+    /// Create a `DateTime` from the number of seconds since the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidYear` if the timestamp is past [`DateTime::MAX`].
+    pub fn from_unix_timestamp(timestamp: u64) -> Result<Self, DateTimeError> {
+        if timestamp > Self::MAX.to_unix_timestamp() {
+            return Err(DateTimeError::InvalidYear);
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let days = timestamp / 86_400;
+        let secs_of_day = timestamp % 86_400;
+        let (year, month, day_of_month) = civil_from_days(days);
 
-    #[test]
-    fn test_valid_datetime_creation() {
-        let dt = DateTime::new(2024, 3, 15, 14, 30, 45).unwrap();
-        assert_eq!(dt.year(), 2024);
-        assert_eq!(dt.month(), 3);
-        assert_eq!(dt.day_of_month(), 15);
-        assert_eq!(dt.hour(), 14);
-        assert_eq!(dt.minute(), 30);
-        assert_eq!(dt.second(), 45);
+        Ok(DateTime {
+            year,
+            month,
+            day_of_month,
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day % 3600 / 60) as u8,
+            second: (secs_of_day % 60) as u8,
+            weekday: None,
+        })
     }
 
-    #[test]
-    fn test_invalid_year() {
-        let result = DateTime::new(1969, 1, 1, 0, 0, 0);
-        assert_eq!(result.unwrap_err(), DateTimeError::InvalidYear);
+    /// Add (or with a negative value, subtract) a number of seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidYear` if the result would fall outside
+    /// [`DateTime::MIN`]..=[`DateTime::MAX`].
+    pub fn checked_add_seconds(&self, seconds: i64) -> Result<Self, DateTimeError> {
+        let timestamp = (self.to_unix_timestamp() as i64)
+            .checked_add(seconds)
+            .ok_or(DateTimeError::InvalidYear)?;
+        if timestamp < 0 {
+            return Err(DateTimeError::InvalidYear);
+        }
+        Self::from_unix_timestamp(timestamp as u64)
     }
 
-    #[test]
-    fn test_invalid_month() {
-        assert_eq!(
-            DateTime::new(2024, 0, 1, 0, 0, 0).unwrap_err(),
-            DateTimeError::InvalidMonth
-        );
-        assert_eq!(
-            DateTime::new(2024, 13, 1, 0, 0, 0).unwrap_err(),
-            DateTimeError::InvalidMonth
-        );
+    /// Add (or with a negative value, subtract) a number of whole days.
+    ///
+    /// The time of day is kept unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidYear` if the result would fall outside
+    /// [`DateTime::MIN`]..=[`DateTime::MAX`].
+    pub fn checked_add_days(&self, days: i32) -> Result<Self, DateTimeError> {
+        self.checked_add_seconds(days as i64 * 86_400)
     }
 
-    #[test]
-    fn test_invalid_day() {
-        // Test February 30th (invalid)
-        assert_eq!(
-            DateTime::new(2024, 2, 30, 0, 0, 0).unwrap_err(),
-            DateTimeError::InvalidDay
-        );
+    /// Check if this date falls on a Saturday or Sunday.
+    pub fn is_weekend(&self) -> bool {
+        matches!(
+            weekday_index(self.year, self.month, self.day_of_month),
+            0 | 6
+        )
+    }
 
-        // Test day 0
-        assert_eq!(
-            DateTime::new(2024, 1, 0, 0, 0, 0).unwrap_err(),
-            DateTimeError::InvalidDay
-        );
+    /// Get the next Monday-to-Friday date after this one.
+    ///
+    /// Skips Saturday and Sunday, rolling over month and year ends as needed.
+    /// The time of day is kept unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidYear` if the result would be past [`DateTime::MAX`].
+    pub fn next_business_day(&self) -> Result<Self, DateTimeError> {
+        let days = match weekday_index(self.year, self.month, self.day_of_month) {
+            5 => 3, // Friday -> Monday
+            6 => 2, // Saturday -> Monday
+            _ => 1,
+        };
+        self.checked_add_days(days)
+    }
 
-        // Test April 31st (invalid - April has 30 days)
-        assert_eq!(
-            DateTime::new(2024, 4, 31, 0, 0, 0).unwrap_err(),
-            DateTimeError::InvalidDay
-        );
+    /// Combine a date and a time of day.
+    pub fn from_date_time(date: Date, time: Time) -> DateTime {
+        DateTime {
+            year: date.year,
+            month: date.month,
+            day_of_month: date.day_of_month,
+            hour: time.hour,
+            minute: time.minute,
+            second: time.second,
+            weekday: None,
+        }
     }
 
-    #[test]
-    fn test_invalid_hour() {
-        assert_eq!(
-            DateTime::new(2024, 1, 1, 24, 0, 0).unwrap_err(),
-            DateTimeError::InvalidHour
-        );
+    /// Get the calendar date, without the time of day.
+    pub fn date(&self) -> Date {
+        Date {
+            year: self.year,
+            month: self.month,
+            day_of_month: self.day_of_month,
+        }
     }
 
-    #[test]
-    fn test_invalid_minute() {
-        assert_eq!(
-            DateTime::new(2024, 1, 1, 0, 60, 0).unwrap_err(),
-            DateTimeError::InvalidMinute
-        );
+    /// Get the time of day, without the date.
+    pub fn time(&self) -> Time {
+        Time {
+            hour: self.hour,
+            minute: self.minute,
+            second: self.second,
+        }
     }
 
-    #[test]
-    fn test_invalid_second() {
-        assert_eq!(
-            DateTime::new(2024, 1, 1, 0, 0, 60).unwrap_err(),
-            DateTimeError::InvalidSecond
-        );
+    /// Get midnight (00:00:00) of the same date.
+    pub fn start_of_day(&self) -> DateTime {
+        DateTime {
+            hour: 0,
+            minute: 0,
+            second: 0,
+            ..*self
+        }
     }
 
-    #[test]
-    fn test_leap_year_february_29() {
-        // 2024 is a leap year - February 29th should be valid
-        assert!(DateTime::new(2024, 2, 29, 0, 0, 0).is_ok());
+    /// Get the last calendar day of the same month.
+    ///
+    /// The time of day is kept unchanged.
+    pub fn last_day_of_month(&self) -> DateTime {
+        let mut dt = DateTime {
+            day_of_month: days_in_month(self.year, self.month),
+            ..*self
+        };
+        dt.refresh_weekday();
+        dt
+    }
 
-        // 2023 is not a leap year - February 29th should be invalid
-        assert_eq!(
-            DateTime::new(2023, 2, 29, 0, 0, 0).unwrap_err(),
-            DateTimeError::InvalidDay
-        );
+    /// Get the week of the month (1-6).
+    ///
+    /// Weeks run Sunday to Saturday, and week 1 is the week containing the 1st
+    /// of the month, so this is the row the date occupies in a Sunday-first
+    /// wall calendar. A month starting on a Saturday puts the 2nd in week 2,
+    /// and a 31-day month starting on a Friday or Saturday reaches week 6.
+    pub fn week_of_month(&self) -> u8 {
+        let first = weekday_index(self.year, self.month, 1);
+        (self.day_of_month - 1 + first) / 7 + 1
     }
 
-    #[test]
-    fn test_setters_with_validation() {
-        let mut dt = DateTime::new(2024, 1, 1, 0, 0, 0).unwrap();
+    /// Get the week of the month counted from the first Sunday (0-5).
+    ///
+    /// Week 1 starts on the first Sunday of the month and every following
+    /// Sunday starts a new week. Days before the first Sunday belong to
+    /// week 0, which is empty when the month starts on a Sunday.
+    pub fn week_of_month_from_first_sunday(&self) -> u8 {
+        let first = weekday_index(self.year, self.month, 1);
+        let first_sunday = 1 + (7 - first) % 7;
+        (self.day_of_month + 7 - first_sunday) / 7
+    }
 
-        // Valid operations
-        assert!(dt.set_year(2025).is_ok());
-        assert_eq!(dt.year(), 2025);
+    /// Get the weekday, using the carried value if there is one.
+    pub fn weekday(&self) -> Weekday {
+        self.weekday.unwrap_or_else(|| {
+            Weekday::ALL[weekday_index(self.year, self.month, self.day_of_month) as usize]
+        })
+    }
 
-        assert!(dt.set_month(12).is_ok());
-        assert_eq!(dt.month(), 12);
+    /// Recompute the carried weekday after the date changed.
+    fn refresh_weekday(&mut self) {
+        if self.weekday.is_some() {
+            self.weekday = None;
+            self.weekday = Some(self.weekday());
+        }
+    }
+
+    /// Calculate weekday for this DateTime
+    pub fn calculate_weekday(&self) -> Result<Weekday, DateTimeError> {
+        calculate_weekday(self.year, self.month, self.day_of_month)
+    }
+}
+
+/// Calendar date without a time of day.
+///
+/// Follows the same validation rules as [`DateTime`], including `year >= 1970`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    /// Year (full year, e.g., 2024)
+    year: u16,
+    /// Month (1-12)
+    month: u8,
+    /// Day of the month (1-31 depending on month/year)
+    day_of_month: u8,
+}
+
+impl Date {
+    /// Create a new `Date` instance with validation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DateTimeError` if any component is out of valid range.
+    pub fn new(year: u16, month: u8, day_of_month: u8) -> Result<Self, DateTimeError> {
+        DateTime::validate_year(year)?;
+        DateTime::validate_month(month)?;
+        DateTime::validate_day(year, month, day_of_month)?;
+        Ok(Date {
+            year,
+            month,
+            day_of_month,
+        })
+    }
+
+    /// Get the year (e.g. 2025).
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the month number (1-12).
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Get the day of the month (1-31).
+    pub fn day_of_month(&self) -> u8 {
+        self.day_of_month
+    }
+}
+
+/// Time of day without a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time {
+    /// Hour (0-23)
+    hour: u8,
+    /// Minute (0-59)
+    minute: u8,
+    /// Second (0-59)
+    second: u8,
+}
+
+impl Time {
+    /// Midnight (00:00:00).
+    pub const MIDNIGHT: Time = Time {
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+
+    /// Create a new `Time` instance with validation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DateTimeError` if any component is out of valid range.
+    pub fn new(hour: u8, minute: u8, second: u8) -> Result<Self, DateTimeError> {
+        DateTime::validate_hour(hour)?;
+        DateTime::validate_minute(minute)?;
+        DateTime::validate_second(second)?;
+        Ok(Time {
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Get the hour (0-23).
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute (0-59).
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Get the second (0-59).
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Get the number of seconds since midnight (0-86399).
+    pub fn seconds_since_midnight(&self) -> u32 {
+        self.hour as u32 * 3600 + self.minute as u32 * 60 + self.second as u32
+    }
+}
+
+/// Fixed offset from UTC, positive east of Greenwich.
+///
+/// The offset must be less than 24 hours in either direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UtcOffset {
+    /// Offset in seconds
+    seconds: i32,
+}
+
+impl UtcOffset {
+    /// The zero offset (UTC itself).
+    pub const UTC: UtcOffset = UtcOffset { seconds: 0 };
+
+    /// Create an offset from a number of seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidHour` if the offset is 24 hours or more.
+    pub fn from_seconds(seconds: i32) -> Result<Self, DateTimeError> {
+        if seconds.unsigned_abs() >= 86_400 {
+            return Err(DateTimeError::InvalidHour);
+        }
+        Ok(UtcOffset { seconds })
+    }
+
+    /// Create an offset from whole hours (e.g. `-5` for US Eastern Standard Time).
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidHour` if the offset is 24 hours or more.
+    pub fn from_hours(hours: i8) -> Result<Self, DateTimeError> {
+        Self::from_seconds(hours as i32 * 3600)
+    }
+
+    /// Create an offset from hours and minutes (e.g. `5, 30` for India).
+    ///
+    /// Both parts must have the same sign, so `-3, -30` is Newfoundland.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidMinute` if the minutes are not within -59..=59
+    /// or have the opposite sign of the hours, and `DateTimeError::InvalidHour`
+    /// if the offset is 24 hours or more.
+    pub fn from_hours_minutes(hours: i8, minutes: i8) -> Result<Self, DateTimeError> {
+        if minutes.unsigned_abs() > 59 || (hours > 0 && minutes < 0) || (hours < 0 && minutes > 0) {
+            return Err(DateTimeError::InvalidMinute);
+        }
+        Self::from_seconds(hours as i32 * 3600 + minutes as i32 * 60)
+    }
+
+    /// Get the offset in seconds.
+    pub fn as_seconds(&self) -> i32 {
+        self.seconds
+    }
+
+    /// Convert a UTC `DateTime` to local time at this offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidYear` if the result is out of range.
+    pub fn to_local(&self, utc: &DateTime) -> Result<DateTime, DateTimeError> {
+        utc.checked_add_seconds(self.seconds as i64)
+    }
+
+    /// Convert a local `DateTime` at this offset to UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidYear` if the result is out of range.
+    pub fn to_utc(&self, local: &DateTime) -> Result<DateTime, DateTimeError> {
+        local.checked_add_seconds(-(self.seconds as i64))
+    }
+}
+
+/// Day of the week (1 = Sunday .. 7 = Saturday)
+///
+/// Other numbering conventions are available through conversion methods:
+///
+/// | Day       | [`to_number`](Weekday::to_number) | [`to_iso_number`](Weekday::to_iso_number) | [`to_zero_based_sunday`](Weekday::to_zero_based_sunday) |
+/// |-----------|---|---|---|
+/// | Sunday    | 1 | 7 | 0 |
+/// | Monday    | 2 | 1 | 1 |
+/// | Tuesday   | 3 | 2 | 2 |
+/// | Wednesday | 4 | 3 | 3 |
+/// | Thursday  | 5 | 4 | 4 |
+/// | Friday    | 6 | 5 | 5 |
+/// | Saturday  | 7 | 6 | 6 |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Weekday {
+    /// Sunday starts with 1
+    Sunday = 1,
+    /// Monday
+    Monday = 2,
+    /// Tuesday
+    Tuesday = 3,
+    /// Wednesday
+    Wednesday = 4,
+    /// Thursday
+    Thursday = 5,
+    /// Friday
+    Friday = 6,
+    /// Saturday
+    Saturday = 7,
+}
+
+impl Weekday {
+    /// All seven days in numeric order, Sunday to Saturday.
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Sunday,
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+    ];
+
+    /// Iterate over all seven days in numeric order, Sunday to Saturday.
+    ///
+    /// Use `.rev()` to go from Saturday back to Sunday.
+    pub fn iter() -> WeekdayIter {
+        WeekdayIter { front: 0, back: 7 }
+    }
+
+    /// Create a Weekday from a raw u8 (1 = Sunday .. 7 = Saturday).
+    pub fn from_number(n: u8) -> Result<Self, DateTimeError> {
+        Self::try_from(n)
+    }
+
+    /// Get the number form (1 = Sunday .. 7 = Saturday).
+    pub fn to_number(self) -> u8 {
+        u8::from(self)
+    }
+
+    /// Create a Weekday from an ISO 8601 number (1 = Monday .. 7 = Sunday).
+    pub fn from_iso_number(n: u8) -> Result<Self, DateTimeError> {
+        match n {
+            1..=7 => Ok(Self::ALL[(n % 7) as usize]),
+            _ => Err(DateTimeError::InvalidWeekday),
+        }
+    }
+
+    /// Get the ISO 8601 number form (1 = Monday .. 7 = Sunday).
+    pub fn to_iso_number(self) -> u8 {
+        match self {
+            Self::Sunday => 7,
+            other => other as u8 - 1,
+        }
+    }
+
+    /// Create a Weekday from a zero-based number (0 = Sunday .. 6 = Saturday).
+    ///
+    /// This is the convention used by the PCF8563 and RV-3028.
+    pub fn from_zero_based_sunday(n: u8) -> Result<Self, DateTimeError> {
+        Self::ALL
+            .get(n as usize)
+            .copied()
+            .ok_or(DateTimeError::InvalidWeekday)
+    }
+
+    /// Get the zero-based number form (0 = Sunday .. 6 = Saturday).
+    pub fn to_zero_based_sunday(self) -> u8 {
+        self as u8 - 1
+    }
+
+    /// Get the number of days from this day until `target` (0-6).
+    ///
+    /// Returns 0 when `target` is the same day.
+    pub fn days_until(self, target: Weekday) -> u8 {
+        (target as u8 + 7 - self as u8) % 7
+    }
+
+    /// Get the number of days from this day until the next `target` (1-7).
+    ///
+    /// Unlike [`Weekday::days_until`], the same day counts as a week away.
+    ///
+    /// ```
+    /// use rtc_hal::datetime::{DateTime, Weekday};
+    ///
+    /// // Friday 2024-08-16 -> next Friday
+    /// let now = DateTime::new(2024, 8, 16, 9, 0, 0).unwrap();
+    /// let today = now.calculate_weekday().unwrap();
+    /// let next = now
+    ///     .checked_add_days(today.days_until_next(Weekday::Friday) as i32)
+    ///     .unwrap();
+    /// assert_eq!(next, DateTime::new(2024, 8, 23, 9, 0, 0).unwrap());
+    /// ```
+    pub fn days_until_next(self, target: Weekday) -> u8 {
+        match self.days_until(target) {
+            0 => 7,
+            days => days,
+        }
+    }
+
+    /// Check if this is Saturday or Sunday.
+    pub fn is_weekend(&self) -> bool {
+        matches!(self, Weekday::Saturday | Weekday::Sunday)
+    }
+
+    /// Check if this is Monday to Friday.
+    pub fn is_weekday(&self) -> bool {
+        !self.is_weekend()
+    }
+
+    /// Get the following day, wrapping from Saturday to Sunday.
+    pub fn next(self) -> Weekday {
+        self.add_days(1)
+    }
+
+    /// Get the preceding day, wrapping from Sunday to Saturday.
+    pub fn prev(self) -> Weekday {
+        self.add_days(-1)
+    }
+
+    /// Get the day `n` days later (or earlier, for negative `n`).
+    pub fn add_days(self, n: i32) -> Weekday {
+        let index = (self as i32 - 1 + n.rem_euclid(7)).rem_euclid(7);
+        Self::ALL[index as usize]
+    }
+
+    /// Get the weekday name as a string slice
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Weekday::Sunday => "Sunday",
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+        }
+    }
+
+    /// Get the three-letter English abbreviation ("Sun" .. "Sat")
+    pub const fn as_short_str(&self) -> &'static str {
+        match self {
+            Weekday::Sunday => "Sun",
+            Weekday::Monday => "Mon",
+            Weekday::Tuesday => "Tue",
+            Weekday::Wednesday => "Wed",
+            Weekday::Thursday => "Thu",
+            Weekday::Friday => "Fri",
+            Weekday::Saturday => "Sat",
+        }
+    }
+
+    /// Get the two-letter English abbreviation ("Su" .. "Sa") for narrow displays
+    pub const fn as_minimal_str(&self) -> &'static str {
+        match self {
+            Weekday::Sunday => "Su",
+            Weekday::Monday => "Mo",
+            Weekday::Tuesday => "Tu",
+            Weekday::Wednesday => "We",
+            Weekday::Thursday => "Th",
+            Weekday::Friday => "Fr",
+            Weekday::Saturday => "Sa",
+        }
+    }
+}
+
+impl core::fmt::Display for Weekday {
+    /// Writes the full name, or the three-letter abbreviation with `{:#}`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.pad(self.as_short_str())
+        } else {
+            f.pad(self.as_str())
+        }
+    }
+}
+
+/// Serialized as the full name, e.g. `"Monday"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Weekday {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Weekday {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const NAMES: [&str; 7] = [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ];
+        let index = deserializer.deserialize_str(crate::serde_names::Name::Variant(&NAMES))?;
+        Ok(Self::ALL[index])
+    }
+}
+
+impl TryFrom<u8> for Weekday {
+    type Error = DateTimeError;
+
+    /// Convert from the number form (1 = Sunday .. 7 = Saturday).
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            1 => Ok(Self::Sunday),
+            2 => Ok(Self::Monday),
+            3 => Ok(Self::Tuesday),
+            4 => Ok(Self::Wednesday),
+            5 => Ok(Self::Thursday),
+            6 => Ok(Self::Friday),
+            7 => Ok(Self::Saturday),
+            _ => Err(DateTimeError::InvalidWeekday),
+        }
+    }
+}
+
+impl From<Weekday> for u8 {
+    /// Convert to the number form (1 = Sunday .. 7 = Saturday).
+    fn from(weekday: Weekday) -> Self {
+        weekday as u8
+    }
+}
+
+/// Iterator over the days of the week, created by [`Weekday::iter`].
+#[derive(Debug, Clone)]
+pub struct WeekdayIter {
+    /// Index of the next day yielded from the front
+    front: u8,
+    /// One past the index of the next day yielded from the back
+    back: u8,
+}
+
+impl Iterator for WeekdayIter {
+    type Item = Weekday;
+
+    fn next(&mut self) -> Option<Weekday> {
+        if self.front == self.back {
+            return None;
+        }
+        let day = Weekday::ALL[self.front as usize];
+        self.front += 1;
+        Some(day)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for WeekdayIter {
+    fn next_back(&mut self) -> Option<Weekday> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(Weekday::ALL[self.back as usize])
+    }
+}
+
+impl ExactSizeIterator for WeekdayIter {}
+
+impl core::iter::FusedIterator for WeekdayIter {}
+
+/// Set of weekdays stored as a bitmask.
+///
+/// Bit 0 is Sunday through bit 6 for Saturday, matching the weekday alarm
+/// registers of chips like the RV-8803, so [`WeekdaySet::bits`] and
+/// [`WeekdaySet::from_bits`] convert directly to and from register values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WeekdaySet(u8);
+
+impl WeekdaySet {
+    /// No days.
+    pub const EMPTY: WeekdaySet = WeekdaySet(0);
+    /// Monday to Friday.
+    pub const WEEKDAYS: WeekdaySet = WeekdaySet(0b0011_1110);
+    /// Saturday and Sunday.
+    pub const WEEKEND: WeekdaySet = WeekdaySet(0b0100_0001);
+    /// All seven days.
+    pub const ALL: WeekdaySet = WeekdaySet(0b0111_1111);
+
+    /// Create a set containing a single day.
+    pub const fn single(day: Weekday) -> Self {
+        WeekdaySet(Self::bit(day))
+    }
+
+    /// Create a set from a register value (bit 0 = Sunday .. bit 6 = Saturday).
+    ///
+    /// # Errors
+    ///
+    /// Returns `DateTimeError::InvalidWeekday` if bit 7 is set.
+    pub const fn from_bits(bits: u8) -> Result<Self, DateTimeError> {
+        if bits & !Self::ALL.0 != 0 {
+            return Err(DateTimeError::InvalidWeekday);
+        }
+        Ok(WeekdaySet(bits))
+    }
+
+    /// Create a set from a register value, ignoring bit 7.
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        WeekdaySet(bits & Self::ALL.0)
+    }
+
+    /// Get the register value (bit 0 = Sunday .. bit 6 = Saturday).
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Bit for a single day
+    const fn bit(day: Weekday) -> u8 {
+        1 << (day as u8 - 1)
+    }
+
+    /// Add a day to the set.
+    pub fn insert(&mut self, day: Weekday) {
+        self.0 |= Self::bit(day);
+    }
+
+    /// Remove a day from the set.
+    pub fn remove(&mut self, day: Weekday) {
+        self.0 &= !Self::bit(day);
+    }
+
+    /// Check if the set contains a day.
+    pub const fn contains(self, day: Weekday) -> bool {
+        self.0 & Self::bit(day) != 0
+    }
+
+    /// Check if the set contains no days.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Get the number of days in the set.
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Get the days in either set.
+    pub const fn union(self, other: WeekdaySet) -> Self {
+        WeekdaySet(self.0 | other.0)
+    }
+
+    /// Get the days in both sets.
+    pub const fn intersection(self, other: WeekdaySet) -> Self {
+        WeekdaySet(self.0 & other.0)
+    }
+
+    /// Get the days in this set but not in `other`.
+    pub const fn difference(self, other: WeekdaySet) -> Self {
+        WeekdaySet(self.0 & !other.0)
+    }
+
+    /// Get the days not in this set.
+    pub const fn complement(self) -> Self {
+        WeekdaySet(!self.0 & Self::ALL.0)
+    }
+
+    /// Iterate over the days in the set, Sunday to Saturday.
+    pub fn iter(self) -> impl DoubleEndedIterator<Item = Weekday> {
+        Weekday::iter().filter(move |day| self.contains(*day))
+    }
+}
+
+impl From<Weekday> for WeekdaySet {
+    fn from(day: Weekday) -> Self {
+        Self::single(day)
+    }
+}
+
+impl FromIterator<Weekday> for WeekdaySet {
+    fn from_iter<I: IntoIterator<Item = Weekday>>(iter: I) -> Self {
+        let mut set = WeekdaySet::EMPTY;
+        for day in iter {
+            set.insert(day);
+        }
+        set
+    }
+}
+
+impl Extend<Weekday> for WeekdaySet {
+    fn extend<I: IntoIterator<Item = Weekday>>(&mut self, iter: I) {
+        for day in iter {
+            self.insert(day);
+        }
+    }
+}
+
+impl core::ops::BitOr for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn bitor(self, rhs: WeekdaySet) -> WeekdaySet {
+        self.union(rhs)
+    }
+}
+
+impl core::ops::BitAnd for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn bitand(self, rhs: WeekdaySet) -> WeekdaySet {
+        self.intersection(rhs)
+    }
+}
+
+impl core::ops::Not for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn not(self) -> WeekdaySet {
+        self.complement()
+    }
+}
+
+/// Serialized as an array of day names, Sunday to Saturday, e.g.
+/// `["Saturday"]` for the weekend without Sunday
+#[cfg(feature = "serde")]
+impl serde::Serialize for WeekdaySet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for day in self.iter() {
+            seq.serialize_element(&day)?;
+        }
+        seq.end()
+    }
+}
+
+/// Days may be in any order; repeated days are accepted
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WeekdaySet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = WeekdaySet;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "an array of weekday names")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<WeekdaySet, A::Error> {
+                let mut set = WeekdaySet::EMPTY;
+                while let Some(day) = seq.next_element()? {
+                    set.insert(day);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+/// Check if a year is a leap year
+pub fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+/// Get the number of days in a month
+pub fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// Number of days from 1970-01-01 to the given date.
+///
+/// The date must already be valid. Based on Howard Hinnant's `days_from_civil`.
+fn days_since_epoch(year: u16, month: u8, day_of_month: u8) -> u32 {
+    let year = if month <= 2 { year - 1 } else { year } as u32;
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month = month as u32;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day_of_month as u32 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_since_epoch`], returning `(year, month, day_of_month)`.
+fn civil_from_days(days: u64) -> (u16, u8, u8) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day_of_month = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as u16, month, day_of_month)
+}
+
+/// Day of the week for a date using Sakamoto's method (0 = Sunday .. 6 = Saturday).
+///
+/// The date must already be valid.
+fn weekday_index(year: u16, month: u8, day_of_month: u8) -> u8 {
+    const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 {
+        year as i32 - 1
+    } else {
+        year as i32
+    };
+    let days = year + year.div_euclid(4) - year.div_euclid(100)
+        + year.div_euclid(400)
+        + OFFSETS[(month - 1) as usize]
+        + day_of_month as i32;
+    days.rem_euclid(7) as u8
+}
+
+/// Calculate the day of the week using Sakamoto's method
+/// Returns 1=Sunday, 2=Monday, ..., 7=Saturday
+///
+/// # Errors
+///
+/// Returns `DateTimeError::InvalidMonth` if the month is not 1-12, or
+/// `DateTimeError::InvalidDay` if the day does not exist in that month.
+pub fn calculate_weekday(year: u16, month: u8, day_of_month: u8) -> Result<Weekday, DateTimeError> {
+    DateTime::validate_month(month)?;
+    DateTime::validate_day(year, month, day_of_month)?;
+
+    Weekday::from_number(weekday_index(year, month, day_of_month) + 1)
+}
+
+/// Find the Nth occurrence (1-5) of a weekday in a month.
+///
+/// For example, US daylight saving time starts on the second Sunday in March:
+/// `nth_weekday_of_month(2024, 3, Weekday::Sunday, 2)` returns 2024-03-10.
+///
+/// # Errors
+///
+/// Returns `DateTimeError::InvalidYear` or `DateTimeError::InvalidMonth` for an
+/// invalid year or month, and `DateTimeError::InvalidDay` if `n` is 0 or the
+/// month has fewer than `n` occurrences of the weekday.
+pub fn nth_weekday_of_month(
+    year: u16,
+    month: u8,
+    weekday: Weekday,
+    n: u8,
+) -> Result<Date, DateTimeError> {
+    if n == 0 {
+        return Err(DateTimeError::InvalidDay);
+    }
+    let first = calculate_weekday(year, month, 1)?;
+    let offset = (weekday.to_number() + 7 - first.to_number()) % 7;
+    let day = 1 + offset as u16 + (n as u16 - 1) * 7;
+    if day > days_in_month(year, month) as u16 {
+        return Err(DateTimeError::InvalidDay);
+    }
+    Date::new(year, month, day as u8)
+}
+
+/// Find the last occurrence of a weekday in a month.
+///
+/// For example, EU summer time ends on the last Sunday in October:
+/// `last_weekday_of_month(2024, 10, Weekday::Sunday)` returns 2024-10-27.
+///
+/// # Errors
+///
+/// Returns `DateTimeError::InvalidYear` or `DateTimeError::InvalidMonth` for an
+/// invalid year or month.
+pub fn last_weekday_of_month(
+    year: u16,
+    month: u8,
+    weekday: Weekday,
+) -> Result<Date, DateTimeError> {
+    let last_day = days_in_month(year, month);
+    let last = calculate_weekday(year, month, last_day)?;
+    let offset = (last.to_number() + 7 - weekday.to_number()) % 7;
+    Date::new(year, month, last_day - offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_datetime_creation() {
+        let dt = DateTime::new(2024, 3, 15, 14, 30, 45).unwrap();
+        assert_eq!(dt.year(), 2024);
+        assert_eq!(dt.month(), 3);
+        assert_eq!(dt.day_of_month(), 15);
+        assert_eq!(dt.hour(), 14);
+        assert_eq!(dt.minute(), 30);
+        assert_eq!(dt.second(), 45);
+    }
+
+    #[test]
+    fn test_invalid_year() {
+        let result = DateTime::new(1969, 1, 1, 0, 0, 0);
+        assert_eq!(result.unwrap_err(), DateTimeError::InvalidYear);
+    }
+
+    #[test]
+    fn test_invalid_month() {
+        assert_eq!(
+            DateTime::new(2024, 0, 1, 0, 0, 0).unwrap_err(),
+            DateTimeError::InvalidMonth
+        );
+        assert_eq!(
+            DateTime::new(2024, 13, 1, 0, 0, 0).unwrap_err(),
+            DateTimeError::InvalidMonth
+        );
+    }
+
+    #[test]
+    fn test_invalid_day() {
+        // Test February 30th (invalid)
+        assert_eq!(
+            DateTime::new(2024, 2, 30, 0, 0, 0).unwrap_err(),
+            DateTimeError::InvalidDay
+        );
+
+        // Test day 0
+        assert_eq!(
+            DateTime::new(2024, 1, 0, 0, 0, 0).unwrap_err(),
+            DateTimeError::InvalidDay
+        );
+
+        // Test April 31st (invalid - April has 30 days)
+        assert_eq!(
+            DateTime::new(2024, 4, 31, 0, 0, 0).unwrap_err(),
+            DateTimeError::InvalidDay
+        );
+    }
+
+    #[test]
+    fn test_invalid_hour() {
+        assert_eq!(
+            DateTime::new(2024, 1, 1, 24, 0, 0).unwrap_err(),
+            DateTimeError::InvalidHour
+        );
+    }
+
+    #[test]
+    fn test_invalid_minute() {
+        assert_eq!(
+            DateTime::new(2024, 1, 1, 0, 60, 0).unwrap_err(),
+            DateTimeError::InvalidMinute
+        );
+    }
+
+    #[test]
+    fn test_invalid_second() {
+        assert_eq!(
+            DateTime::new(2024, 1, 1, 0, 0, 60).unwrap_err(),
+            DateTimeError::InvalidSecond
+        );
+    }
+
+    #[test]
+    fn test_leap_year_february_29() {
+        // 2024 is a leap year - February 29th should be valid
+        assert!(DateTime::new(2024, 2, 29, 0, 0, 0).is_ok());
+
+        // 2023 is not a leap year - February 29th should be invalid
+        assert_eq!(
+            DateTime::new(2023, 2, 29, 0, 0, 0).unwrap_err(),
+            DateTimeError::InvalidDay
+        );
+    }
+
+    #[test]
+    fn test_setters_with_validation() {
+        let mut dt = DateTime::new(2024, 1, 1, 0, 0, 0).unwrap();
+
+        // Valid operations
+        assert!(dt.set_year(2025).is_ok());
+        assert_eq!(dt.year(), 2025);
+
+        assert!(dt.set_month(12).is_ok());
+        assert_eq!(dt.month(), 12);
 
         assert!(dt.set_hour(23).is_ok());
         assert_eq!(dt.hour(), 23);
@@ -464,88 +1560,702 @@ mod tests {
     }
 
     #[test]
-    fn test_leap_year_edge_cases_in_setters() {
-        let mut dt = DateTime::new(2024, 2, 29, 0, 0, 0).unwrap(); // Leap year
+    fn test_leap_year_edge_cases_in_setters() {
+        let mut dt = DateTime::new(2024, 2, 29, 0, 0, 0).unwrap(); // Leap year
+
+        // Changing to non-leap year should fail because Feb 29 becomes invalid
+        assert_eq!(dt.set_year(2023), Err(DateTimeError::InvalidDay));
+
+        // Original value should remain unchanged after failed operation
+        assert_eq!(dt.year(), 2024);
+        assert_eq!(dt.day_of_month(), 29);
+    }
+
+    #[test]
+    fn test_month_day_validation_in_setters() {
+        let mut dt = DateTime::new(2024, 1, 31, 0, 0, 0).unwrap(); // January 31st
+
+        // Changing to February should fail because Feb doesn't have 31 days
+        assert_eq!(dt.set_month(2), Err(DateTimeError::InvalidDay));
+
+        // Original value should remain unchanged
+        assert_eq!(dt.month(), 1);
+        assert_eq!(dt.day_of_month(), 31);
+
+        // But changing to March should work (March has 31 days)
+        assert!(dt.set_month(3).is_ok());
+        assert_eq!(dt.month(), 3);
+    }
+
+    #[test]
+    fn test_weekday_calculation() {
+        let dt = DateTime::new(2024, 1, 1, 0, 0, 0).unwrap(); // New Year 2024
+        let weekday = dt.calculate_weekday().unwrap();
+        assert_eq!(weekday, Weekday::Monday); // January 1, 2024 was a Monday
+
+        let dt = DateTime::new(2024, 12, 25, 0, 0, 0).unwrap();
+        let weekday = dt.calculate_weekday().unwrap();
+        assert_eq!(weekday, Weekday::Wednesday); // December 25, 2024 is a Wednesday
+    }
+
+    #[test]
+    fn test_weekday_from_number() {
+        assert_eq!(Weekday::from_number(1).unwrap(), Weekday::Sunday);
+        assert_eq!(Weekday::from_number(2).unwrap(), Weekday::Monday);
+        assert_eq!(Weekday::from_number(7).unwrap(), Weekday::Saturday);
+        assert_eq!(Weekday::from_number(3).unwrap(), Weekday::Tuesday);
+
+        assert_eq!(
+            Weekday::from_number(0).unwrap_err(),
+            DateTimeError::InvalidWeekday
+        );
+        assert_eq!(
+            Weekday::from_number(8).unwrap_err(),
+            DateTimeError::InvalidWeekday
+        );
+    }
+
+    #[test]
+    fn test_weekday_to_number() {
+        assert_eq!(Weekday::Sunday.to_number(), 1);
+        assert_eq!(Weekday::Monday.to_number(), 2);
+        assert_eq!(Weekday::Saturday.to_number(), 7);
+    }
+
+    #[test]
+    fn test_weekday_standard_conversions() {
+        assert_eq!(u8::from(Weekday::Sunday), 1);
+        assert_eq!(u8::from(Weekday::Saturday), 7);
+        assert_eq!(Weekday::try_from(1), Ok(Weekday::Sunday));
+        assert_eq!(Weekday::try_from(7), Ok(Weekday::Saturday));
+        assert_eq!(Weekday::try_from(0), Err(DateTimeError::InvalidWeekday));
+        assert_eq!(Weekday::try_from(8), Err(DateTimeError::InvalidWeekday));
+
+        for day in Weekday::iter() {
+            let n: u8 = day.into();
+            let back: Weekday = n.try_into().unwrap();
+            assert_eq!(back, day);
+            assert_eq!(n, day.to_number());
+        }
+    }
+
+    #[test]
+    fn test_weekday_as_str() {
+        assert_eq!(Weekday::Sunday.as_str(), "Sunday");
+        assert_eq!(Weekday::Monday.as_str(), "Monday");
+        assert_eq!(Weekday::Tuesday.as_str(), "Tuesday");
+        assert_eq!(Weekday::Wednesday.as_str(), "Wednesday");
+        assert_eq!(Weekday::Thursday.as_str(), "Thursday");
+        assert_eq!(Weekday::Friday.as_str(), "Friday");
+        assert_eq!(Weekday::Saturday.as_str(), "Saturday");
+    }
+
+    #[test]
+    fn test_weekday_next_prev_wrap() {
+        assert_eq!(Weekday::Sunday.next(), Weekday::Monday);
+        assert_eq!(Weekday::Saturday.next(), Weekday::Sunday);
+        assert_eq!(Weekday::Sunday.prev(), Weekday::Saturday);
+        assert_eq!(Weekday::Monday.prev(), Weekday::Sunday);
+
+        // A full cycle in either direction returns to the start
+        for n in 1..=7 {
+            let start = Weekday::from_number(n).unwrap();
+            let mut forward = start;
+            let mut backward = start;
+            for _ in 0..7 {
+                forward = forward.next();
+                backward = backward.prev();
+            }
+            assert_eq!(forward, start);
+            assert_eq!(backward, start);
+            assert_eq!(start.next().prev(), start);
+        }
+    }
+
+    #[test]
+    fn test_weekday_iter_order() {
+        let mut iter = Weekday::iter();
+        assert_eq!(iter.len(), 7);
+        for n in 1..=7 {
+            assert_eq!(iter.next(), Some(Weekday::from_number(n).unwrap()));
+            assert_eq!(iter.len(), 7 - n as usize);
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        assert!(Weekday::iter().eq(Weekday::ALL));
+        assert!(Weekday::iter().rev().eq(Weekday::ALL.into_iter().rev()));
+    }
+
+    #[test]
+    fn test_weekday_iter_both_ends() {
+        let mut iter = Weekday::iter();
+        assert_eq!(iter.next(), Some(Weekday::Sunday));
+        assert_eq!(iter.next_back(), Some(Weekday::Saturday));
+        assert_eq!(iter.next_back(), Some(Weekday::Friday));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(Weekday::Monday));
+        assert_eq!(iter.next(), Some(Weekday::Tuesday));
+        assert_eq!(iter.next(), Some(Weekday::Wednesday));
+        assert_eq!(iter.next_back(), Some(Weekday::Thursday));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_weekday_iso_number() {
+        let expected = [
+            (Weekday::Monday, 1),
+            (Weekday::Tuesday, 2),
+            (Weekday::Wednesday, 3),
+            (Weekday::Thursday, 4),
+            (Weekday::Friday, 5),
+            (Weekday::Saturday, 6),
+            (Weekday::Sunday, 7),
+        ];
+        for (day, n) in expected {
+            assert_eq!(day.to_iso_number(), n);
+            assert_eq!(Weekday::from_iso_number(n), Ok(day));
+        }
+
+        assert_eq!(
+            Weekday::from_iso_number(0),
+            Err(DateTimeError::InvalidWeekday)
+        );
+        assert_eq!(
+            Weekday::from_iso_number(8),
+            Err(DateTimeError::InvalidWeekday)
+        );
+    }
+
+    #[test]
+    fn test_weekday_zero_based_sunday() {
+        for day in Weekday::iter() {
+            let n = day.to_zero_based_sunday();
+            assert_eq!(n, day.to_number() - 1);
+            assert_eq!(Weekday::from_zero_based_sunday(n), Ok(day));
+        }
+        assert_eq!(Weekday::Sunday.to_zero_based_sunday(), 0);
+        assert_eq!(Weekday::Saturday.to_zero_based_sunday(), 6);
+
+        assert_eq!(
+            Weekday::from_zero_based_sunday(7),
+            Err(DateTimeError::InvalidWeekday)
+        );
+        assert_eq!(
+            Weekday::from_zero_based_sunday(u8::MAX),
+            Err(DateTimeError::InvalidWeekday)
+        );
+    }
+
+    #[test]
+    fn test_weekday_days_until() {
+        for from in Weekday::iter() {
+            for to in Weekday::iter() {
+                let days = from.days_until(to);
+                assert!(days <= 6);
+                assert_eq!(from.add_days(days as i32), to);
+                assert_eq!(days == 0, from == to);
+            }
+        }
+        assert_eq!(Weekday::Monday.days_until(Weekday::Friday), 4);
+        assert_eq!(Weekday::Friday.days_until(Weekday::Monday), 3);
+        assert_eq!(Weekday::Saturday.days_until(Weekday::Sunday), 1);
+    }
+
+    #[test]
+    fn test_weekday_days_until_next() {
+        for from in Weekday::iter() {
+            for to in Weekday::iter() {
+                let days = from.days_until_next(to);
+                assert!((1..=7).contains(&days));
+                assert_eq!(from.add_days(days as i32), to);
+                assert_eq!(days == 7, from == to);
+            }
+        }
+        assert_eq!(Weekday::Friday.days_until_next(Weekday::Friday), 7);
+        assert_eq!(Weekday::Sunday.days_until_next(Weekday::Saturday), 6);
+    }
+
+    #[test]
+    fn test_checked_add_days() {
+        let dt = DateTime::new(2024, 2, 28, 12, 30, 0).unwrap();
+        assert_eq!(
+            dt.checked_add_days(1),
+            DateTime::new(2024, 2, 29, 12, 30, 0)
+        );
+        assert_eq!(dt.checked_add_days(2), DateTime::new(2024, 3, 1, 12, 30, 0));
+        assert_eq!(
+            dt.checked_add_days(-59),
+            DateTime::new(2023, 12, 31, 12, 30, 0)
+        );
+        assert_eq!(
+            DateTime::MIN.checked_add_days(-1),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn test_weekday_is_weekend() {
+        for day in Weekday::iter() {
+            let weekend = day == Weekday::Saturday || day == Weekday::Sunday;
+            assert_eq!(day.is_weekend(), weekend);
+            assert_eq!(day.is_weekday(), !weekend);
+        }
+    }
+
+    #[test]
+    fn test_datetime_is_weekend() {
+        // 2024-08-16 is a Friday
+        assert!(!DateTime::new(2024, 8, 16, 0, 0, 0).unwrap().is_weekend());
+        assert!(DateTime::new(2024, 8, 17, 0, 0, 0).unwrap().is_weekend());
+        assert!(DateTime::new(2024, 8, 18, 0, 0, 0).unwrap().is_weekend());
+        assert!(!DateTime::new(2024, 8, 19, 0, 0, 0).unwrap().is_weekend());
+    }
+
+    #[test]
+    fn test_next_business_day() {
+        // Monday -> Tuesday
+        let dt = DateTime::new(2024, 8, 12, 7, 30, 0).unwrap();
+        assert_eq!(dt.next_business_day(), DateTime::new(2024, 8, 13, 7, 30, 0));
+
+        // Friday, Saturday and Sunday -> Monday
+        for day in 16..=18 {
+            let dt = DateTime::new(2024, 8, day, 7, 30, 0).unwrap();
+            assert_eq!(dt.next_business_day(), DateTime::new(2024, 8, 19, 7, 30, 0));
+        }
+
+        // Friday 2024-05-31 -> Monday 2024-06-03
+        let dt = DateTime::new(2024, 5, 31, 0, 0, 0).unwrap();
+        assert_eq!(dt.next_business_day(), DateTime::new(2024, 6, 3, 0, 0, 0));
+
+        // Saturday 2022-12-31 -> Monday 2023-01-02
+        let dt = DateTime::new(2022, 12, 31, 0, 0, 0).unwrap();
+        assert_eq!(dt.next_business_day(), DateTime::new(2023, 1, 2, 0, 0, 0));
+
+        assert_eq!(
+            DateTime::MAX.next_business_day(),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn test_weekday_set_constants() {
+        assert!(WeekdaySet::EMPTY.is_empty());
+        assert_eq!(WeekdaySet::ALL.len(), 7);
+        assert!(WeekdaySet::ALL.iter().eq(Weekday::iter()));
+        assert!(WeekdaySet::WEEKEND.iter().all(|d| d.is_weekend()));
+        assert!(WeekdaySet::WEEKDAYS.iter().all(|d| d.is_weekday()));
+        assert_eq!(WeekdaySet::WEEKEND.len(), 2);
+        assert_eq!(WeekdaySet::WEEKDAYS.len(), 5);
+        assert_eq!(WeekdaySet::WEEKDAYS | WeekdaySet::WEEKEND, WeekdaySet::ALL);
+    }
+
+    #[test]
+    fn test_weekday_set_insert_remove() {
+        let mut set = WeekdaySet::EMPTY;
+        set.insert(Weekday::Monday);
+        set.insert(Weekday::Wednesday);
+        set.insert(Weekday::Friday);
+        set.insert(Weekday::Friday);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(Weekday::Wednesday));
+        assert!(!set.contains(Weekday::Tuesday));
+
+        set.remove(Weekday::Wednesday);
+        set.remove(Weekday::Sunday);
+        assert_eq!(set.len(), 2);
+        assert!(!set.contains(Weekday::Wednesday));
+        assert!(
+            set.iter()
+                .eq([Weekday::Monday, Weekday::Friday].into_iter())
+        );
+        assert!(
+            set.iter()
+                .rev()
+                .eq([Weekday::Friday, Weekday::Monday].into_iter())
+        );
+    }
+
+    #[test]
+    fn test_weekday_set_algebra() {
+        let mwf: WeekdaySet = [Weekday::Monday, Weekday::Wednesday, Weekday::Friday]
+            .into_iter()
+            .collect();
+        let weekend = WeekdaySet::WEEKEND;
+
+        assert_eq!(mwf.union(weekend).len(), 5);
+        assert_eq!(mwf.intersection(weekend), WeekdaySet::EMPTY);
+        assert_eq!(mwf & WeekdaySet::WEEKDAYS, mwf);
+        assert_eq!(
+            WeekdaySet::WEEKDAYS.difference(mwf),
+            [Weekday::Tuesday, Weekday::Thursday].into_iter().collect()
+        );
+        assert_eq!(!WeekdaySet::WEEKDAYS, WeekdaySet::WEEKEND);
+        assert_eq!(WeekdaySet::ALL.complement(), WeekdaySet::EMPTY);
+        assert_eq!(!mwf & mwf, WeekdaySet::EMPTY);
+
+        let mut set = WeekdaySet::single(Weekday::Sunday);
+        set.extend([Weekday::Saturday]);
+        assert_eq!(set, WeekdaySet::WEEKEND);
+        assert_eq!(
+            WeekdaySet::from(Weekday::Sunday),
+            WeekdaySet::single(Weekday::Sunday)
+        );
+    }
+
+    #[test]
+    fn test_weekday_set_register_round_trip() {
+        assert_eq!(WeekdaySet::single(Weekday::Sunday).bits(), 0b0000_0001);
+        assert_eq!(WeekdaySet::single(Weekday::Saturday).bits(), 0b0100_0000);
+        assert_eq!(WeekdaySet::WEEKDAYS.bits(), 0b0011_1110);
+
+        for bits in 0..=0x7F {
+            let set = WeekdaySet::from_bits(bits).unwrap();
+            assert_eq!(set.bits(), bits);
+            assert_eq!(set.len(), bits.count_ones() as usize);
+            assert_eq!(set.iter().collect::<WeekdaySet>(), set);
+        }
+
+        assert_eq!(
+            WeekdaySet::from_bits(0x80),
+            Err(DateTimeError::InvalidWeekday)
+        );
+        assert_eq!(WeekdaySet::from_bits_truncate(0xFF), WeekdaySet::ALL);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_weekday_set_serde() {
+        use serde_test::{Token, assert_de_tokens, assert_de_tokens_error, assert_tokens};
+
+        for day in Weekday::iter() {
+            assert_tokens(&day, &[Token::Str(day.as_str())]);
+        }
+        assert_tokens(
+            &WeekdaySet::WEEKEND,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Str("Sunday"),
+                Token::Str("Saturday"),
+                Token::SeqEnd,
+            ],
+        );
+        assert_tokens(
+            &WeekdaySet::EMPTY,
+            &[Token::Seq { len: Some(0) }, Token::SeqEnd],
+        );
+        assert_de_tokens(
+            &WeekdaySet::WEEKEND,
+            &[
+                Token::Seq { len: None },
+                Token::Str("Saturday"),
+                Token::Str("Sunday"),
+                Token::Str("Saturday"),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens_error::<Weekday>(
+            &[Token::Str("Sun")],
+            "unknown variant `Sun`, expected one of `Sunday`, `Monday`, `Tuesday`, \
+             `Wednesday`, `Thursday`, `Friday`, `Saturday`",
+        );
+    }
+
+    #[test]
+    fn test_weekday_add_days() {
+        assert_eq!(Weekday::Wednesday.add_days(0), Weekday::Wednesday);
+        assert_eq!(Weekday::Wednesday.add_days(4), Weekday::Sunday);
+        assert_eq!(Weekday::Wednesday.add_days(-4), Weekday::Saturday);
+        assert_eq!(Weekday::Wednesday.add_days(7), Weekday::Wednesday);
+        assert_eq!(Weekday::Wednesday.add_days(-7), Weekday::Wednesday);
+        assert_eq!(Weekday::Sunday.add_days(365), Weekday::Monday);
+        assert_eq!(Weekday::Sunday.add_days(-365), Weekday::Saturday);
+        assert_eq!(Weekday::Sunday.add_days(i32::MAX), Weekday::Monday);
+        assert_eq!(Weekday::Sunday.add_days(i32::MIN), Weekday::Friday);
+    }
+
+    #[test]
+    fn test_weekday_display() {
+        for day in Weekday::iter() {
+            assert_eq!(format!("{day}"), day.as_str());
+            assert_eq!(format!("{day:#}"), day.as_short_str());
+        }
+        assert_eq!(format!("{:<10}|", Weekday::Monday), "Monday    |");
+        assert_eq!(format!("{:>#5}", Weekday::Friday), "  Fri");
+    }
+
+    #[test]
+    fn test_weekday_as_short_str() {
+        assert_eq!(Weekday::Sunday.as_short_str(), "Sun");
+        assert_eq!(Weekday::Monday.as_short_str(), "Mon");
+        assert_eq!(Weekday::Tuesday.as_short_str(), "Tue");
+        assert_eq!(Weekday::Wednesday.as_short_str(), "Wed");
+        assert_eq!(Weekday::Thursday.as_short_str(), "Thu");
+        assert_eq!(Weekday::Friday.as_short_str(), "Fri");
+        assert_eq!(Weekday::Saturday.as_short_str(), "Sat");
+    }
+
+    #[test]
+    fn test_weekday_as_minimal_str() {
+        assert_eq!(Weekday::Sunday.as_minimal_str(), "Su");
+        assert_eq!(Weekday::Monday.as_minimal_str(), "Mo");
+        assert_eq!(Weekday::Tuesday.as_minimal_str(), "Tu");
+        assert_eq!(Weekday::Wednesday.as_minimal_str(), "We");
+        assert_eq!(Weekday::Thursday.as_minimal_str(), "Th");
+        assert_eq!(Weekday::Friday.as_minimal_str(), "Fr");
+        assert_eq!(Weekday::Saturday.as_minimal_str(), "Sa");
+    }
+
+    #[test]
+    fn test_weekday_abbreviations_in_const_table() {
+        const HEADER: [&str; 2] = [
+            Weekday::Monday.as_short_str(),
+            Weekday::Monday.as_minimal_str(),
+        ];
+        assert_eq!(HEADER, ["Mon", "Mo"]);
+    }
+
+    #[test]
+    fn test_calculate_weekday_known_dates() {
+        // Test some known dates
+        assert_eq!(calculate_weekday(2000, 1, 1).unwrap(), Weekday::Saturday);
+        assert_eq!(calculate_weekday(2024, 1, 1).unwrap(), Weekday::Monday);
+        assert_eq!(calculate_weekday(2025, 8, 15).unwrap(), Weekday::Friday);
+
+        // Test leap year boundary
+        assert_eq!(calculate_weekday(2024, 2, 29).unwrap(), Weekday::Thursday); // Leap day 2024
+    }
+
+    #[test]
+    fn test_calculate_weekday_rejects_invalid_input() {
+        assert_eq!(
+            calculate_weekday(2024, 0, 1),
+            Err(DateTimeError::InvalidMonth)
+        );
+        assert_eq!(
+            calculate_weekday(2024, 13, 1),
+            Err(DateTimeError::InvalidMonth)
+        );
+        assert_eq!(
+            calculate_weekday(2024, 1, 0),
+            Err(DateTimeError::InvalidDay)
+        );
+        assert_eq!(
+            calculate_weekday(2023, 2, 29),
+            Err(DateTimeError::InvalidDay)
+        );
+        assert_eq!(
+            calculate_weekday(2024, 4, 31),
+            Err(DateTimeError::InvalidDay)
+        );
+    }
+
+    #[test]
+    fn test_calculate_weekday_full_year_range() {
+        // Does not panic at the edges of the u16 range
+        assert_eq!(calculate_weekday(0, 1, 1).unwrap(), Weekday::Saturday);
+        assert_eq!(calculate_weekday(1, 1, 1).unwrap(), Weekday::Monday);
+        assert_eq!(
+            calculate_weekday(65535, 12, 31).unwrap(),
+            DateTime::MAX.calculate_weekday().unwrap()
+        );
+    }
 
-        // Changing to non-leap year should fail because Feb 29 becomes invalid
-        assert_eq!(dt.set_year(2023), Err(DateTimeError::InvalidDay));
+    #[test]
+    fn test_calculate_weekday_matches_day_count() {
+        // 1970-01-01 was a Thursday (index 4 when Sunday = 0)
+        let mut days_since_epoch = 0u32;
+        for year in 1970..=2105 {
+            for month in 1..=12 {
+                for day in 1..=days_in_month(year, month) {
+                    let expected = Weekday::from_number(((days_since_epoch + 4) % 7) as u8 + 1);
+                    assert_eq!(
+                        calculate_weekday(year, month, day),
+                        expected,
+                        "{year}-{month}-{day}"
+                    );
+                    days_since_epoch += 1;
+                }
+            }
+        }
+    }
 
-        // Original value should remain unchanged after failed operation
-        assert_eq!(dt.year(), 2024);
-        assert_eq!(dt.day_of_month(), 29);
+    #[test]
+    fn test_date_new() {
+        let date = Date::new(2024, 2, 29).unwrap();
+        assert_eq!(date.year(), 2024);
+        assert_eq!(date.month(), 2);
+        assert_eq!(date.day_of_month(), 29);
+
+        assert_eq!(Date::new(1969, 1, 1), Err(DateTimeError::InvalidYear));
+        assert_eq!(Date::new(2024, 13, 1), Err(DateTimeError::InvalidMonth));
+        assert_eq!(Date::new(2023, 2, 29), Err(DateTimeError::InvalidDay));
     }
 
     #[test]
-    fn test_month_day_validation_in_setters() {
-        let mut dt = DateTime::new(2024, 1, 31, 0, 0, 0).unwrap(); // January 31st
+    fn test_time_new() {
+        let time = Time::new(23, 59, 58).unwrap();
+        assert_eq!(time.hour(), 23);
+        assert_eq!(time.minute(), 59);
+        assert_eq!(time.second(), 58);
+        assert_eq!(time.seconds_since_midnight(), 86398);
+        assert_eq!(Time::MIDNIGHT.seconds_since_midnight(), 0);
+        assert!(Time::MIDNIGHT < time);
+
+        assert_eq!(Time::new(24, 0, 0), Err(DateTimeError::InvalidHour));
+        assert_eq!(Time::new(0, 60, 0), Err(DateTimeError::InvalidMinute));
+        assert_eq!(Time::new(0, 0, 60), Err(DateTimeError::InvalidSecond));
+    }
 
-        // Changing to February should fail because Feb doesn't have 31 days
-        assert_eq!(dt.set_month(2), Err(DateTimeError::InvalidDay));
+    #[test]
+    fn test_date_and_time_projection() {
+        let dt = DateTime::new(2024, 8, 16, 12, 34, 56).unwrap();
+        assert_eq!(dt.date(), Date::new(2024, 8, 16).unwrap());
+        assert_eq!(dt.time(), Time::new(12, 34, 56).unwrap());
+        assert_eq!(dt.start_of_day().time(), Time::MIDNIGHT);
+        assert_eq!(DateTime::from_date_time(dt.date(), dt.time()), dt);
+    }
 
-        // Original value should remain unchanged
-        assert_eq!(dt.month(), 1);
-        assert_eq!(dt.day_of_month(), 31);
+    #[test]
+    fn test_nth_weekday_of_month_us_dst_dates() {
+        // US DST starts on the second Sunday in March
+        for (year, day) in [(2023, 12), (2024, 10), (2025, 9), (2026, 8)] {
+            assert_eq!(
+                nth_weekday_of_month(year, 3, Weekday::Sunday, 2),
+                Date::new(year, 3, day)
+            );
+        }
 
-        // But changing to March should work (March has 31 days)
-        assert!(dt.set_month(3).is_ok());
-        assert_eq!(dt.month(), 3);
+        // US DST ends on the first Sunday in November
+        for (year, day) in [(2023, 5), (2024, 3), (2025, 2), (2026, 1)] {
+            assert_eq!(
+                nth_weekday_of_month(year, 11, Weekday::Sunday, 1),
+                Date::new(year, 11, day)
+            );
+        }
     }
 
     #[test]
-    fn test_weekday_calculation() {
-        let dt = DateTime::new(2024, 1, 1, 0, 0, 0).unwrap(); // New Year 2024
-        let weekday = dt.calculate_weekday().unwrap();
-        assert_eq!(weekday, Weekday::Monday); // January 1, 2024 was a Monday
+    fn test_last_weekday_of_month_eu_dst_dates() {
+        // EU summer time starts on the last Sunday in March
+        for (year, day) in [(2023, 26), (2024, 31), (2025, 30), (2026, 29)] {
+            assert_eq!(
+                last_weekday_of_month(year, 3, Weekday::Sunday),
+                Date::new(year, 3, day)
+            );
+        }
 
-        let dt = DateTime::new(2024, 12, 25, 0, 0, 0).unwrap();
-        let weekday = dt.calculate_weekday().unwrap();
-        assert_eq!(weekday, Weekday::Wednesday); // December 25, 2024 is a Wednesday
+        // EU summer time ends on the last Sunday in October
+        for (year, day) in [(2023, 29), (2024, 27), (2025, 26), (2026, 25)] {
+            assert_eq!(
+                last_weekday_of_month(year, 10, Weekday::Sunday),
+                Date::new(year, 10, day)
+            );
+        }
     }
 
     #[test]
-    fn test_weekday_from_number() {
-        assert_eq!(Weekday::from_number(1).unwrap(), Weekday::Sunday);
-        assert_eq!(Weekday::from_number(2).unwrap(), Weekday::Monday);
-        assert_eq!(Weekday::from_number(7).unwrap(), Weekday::Saturday);
-        assert_eq!(Weekday::from_number(3).unwrap(), Weekday::Tuesday);
+    fn test_nth_weekday_of_month_missing_occurrence() {
+        // February 2015 has exactly four of every weekday
+        assert_eq!(
+            nth_weekday_of_month(2015, 2, Weekday::Saturday, 4),
+            Date::new(2015, 2, 28)
+        );
+        assert_eq!(
+            nth_weekday_of_month(2015, 2, Weekday::Sunday, 5),
+            Err(DateTimeError::InvalidDay)
+        );
 
+        // March 2024 has five Fridays, Saturdays and Sundays only
         assert_eq!(
-            Weekday::from_number(0).unwrap_err(),
-            DateTimeError::InvalidWeekday
+            nth_weekday_of_month(2024, 3, Weekday::Sunday, 5),
+            Date::new(2024, 3, 31)
         );
         assert_eq!(
-            Weekday::from_number(8).unwrap_err(),
-            DateTimeError::InvalidWeekday
+            nth_weekday_of_month(2024, 3, Weekday::Monday, 5),
+            Err(DateTimeError::InvalidDay)
+        );
+
+        assert_eq!(
+            nth_weekday_of_month(2024, 3, Weekday::Monday, 0),
+            Err(DateTimeError::InvalidDay)
+        );
+        assert_eq!(
+            nth_weekday_of_month(2024, 3, Weekday::Monday, u8::MAX),
+            Err(DateTimeError::InvalidDay)
+        );
+        assert_eq!(
+            nth_weekday_of_month(2024, 13, Weekday::Monday, 1),
+            Err(DateTimeError::InvalidMonth)
+        );
+        assert_eq!(
+            last_weekday_of_month(2024, 0, Weekday::Monday),
+            Err(DateTimeError::InvalidMonth)
         );
     }
 
     #[test]
-    fn test_weekday_to_number() {
-        assert_eq!(Weekday::Sunday.to_number(), 1);
-        assert_eq!(Weekday::Monday.to_number(), 2);
-        assert_eq!(Weekday::Saturday.to_number(), 7);
+    fn test_checked_add_seconds() {
+        let dt = DateTime::new(2024, 2, 28, 23, 59, 30).unwrap();
+        assert_eq!(
+            dt.checked_add_seconds(45),
+            DateTime::new(2024, 2, 29, 0, 0, 15)
+        );
+        assert_eq!(
+            dt.checked_add_seconds(-86_400),
+            DateTime::new(2024, 2, 27, 23, 59, 30)
+        );
+        assert_eq!(dt.checked_add_seconds(0), Ok(dt));
+
+        assert_eq!(
+            DateTime::MIN.checked_add_seconds(-1),
+            Err(DateTimeError::InvalidYear)
+        );
+        assert_eq!(
+            DateTime::MAX.checked_add_seconds(1),
+            Err(DateTimeError::InvalidYear)
+        );
+        assert_eq!(
+            dt.checked_add_seconds(i64::MAX),
+            Err(DateTimeError::InvalidYear)
+        );
     }
 
     #[test]
-    fn test_weekday_as_str() {
-        assert_eq!(Weekday::Sunday.as_str(), "Sunday");
-        assert_eq!(Weekday::Monday.as_str(), "Monday");
-        assert_eq!(Weekday::Tuesday.as_str(), "Tuesday");
-        assert_eq!(Weekday::Wednesday.as_str(), "Wednesday");
-        assert_eq!(Weekday::Thursday.as_str(), "Thursday");
-        assert_eq!(Weekday::Friday.as_str(), "Friday");
-        assert_eq!(Weekday::Saturday.as_str(), "Saturday");
+    fn test_utc_offset_construction() {
+        assert_eq!(UtcOffset::from_hours(-5).unwrap().as_seconds(), -18_000);
+        assert_eq!(
+            UtcOffset::from_hours_minutes(5, 30).unwrap().as_seconds(),
+            19_800
+        );
+        assert_eq!(
+            UtcOffset::from_hours_minutes(-3, -30).unwrap().as_seconds(),
+            -12_600
+        );
+        assert_eq!(UtcOffset::UTC.as_seconds(), 0);
+
+        assert_eq!(UtcOffset::from_hours(24), Err(DateTimeError::InvalidHour));
+        assert_eq!(UtcOffset::from_hours(-24), Err(DateTimeError::InvalidHour));
+        assert_eq!(
+            UtcOffset::from_hours_minutes(1, 60),
+            Err(DateTimeError::InvalidMinute)
+        );
+        assert_eq!(
+            UtcOffset::from_hours_minutes(-3, 30),
+            Err(DateTimeError::InvalidMinute)
+        );
     }
 
     #[test]
-    fn test_calculate_weekday_known_dates() {
-        // Test some known dates
-        assert_eq!(calculate_weekday(2000, 1, 1).unwrap(), Weekday::Saturday);
-        assert_eq!(calculate_weekday(2024, 1, 1).unwrap(), Weekday::Monday);
-        assert_eq!(calculate_weekday(2025, 8, 15).unwrap(), Weekday::Friday);
-
-        // Test leap year boundary
-        assert_eq!(calculate_weekday(2024, 2, 29).unwrap(), Weekday::Thursday); // Leap day 2024
+    fn test_utc_offset_conversion() {
+        let utc = DateTime::new(2024, 1, 1, 2, 0, 0).unwrap();
+        let eastern = UtcOffset::from_hours(-5).unwrap();
+
+        let local = eastern.to_local(&utc).unwrap();
+        assert_eq!(local, DateTime::new(2023, 12, 31, 21, 0, 0).unwrap());
+        assert_eq!(eastern.to_utc(&local), Ok(utc));
+        assert_eq!(UtcOffset::UTC.to_local(&utc), Ok(utc));
     }
 
     #[test]
@@ -718,6 +2428,387 @@ mod tests {
         assert_eq!(dt.minute, 10);
     }
 
+    #[test]
+    fn test_min_max_constants_validate() {
+        assert!(DateTime::MIN.validate().is_ok());
+        assert!(DateTime::MAX.validate().is_ok());
+        assert_eq!(DateTime::MIN, DateTime::new(1970, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            DateTime::MAX,
+            DateTime::new(65535, 12, 31, 23, 59, 59).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_min_max_ordering() {
+        let dt = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        assert!(DateTime::MIN < dt);
+        assert!(dt < DateTime::MAX);
+        assert!(DateTime::MIN < DateTime::MAX);
+
+        assert!(DateTime::MIN.is_min());
+        assert!(!DateTime::MIN.is_max());
+        assert!(DateTime::MAX.is_max());
+        assert!(!dt.is_min());
+        assert!(!dt.is_max());
+    }
+
+    #[test]
+    fn test_datetime_ordering_is_chronological() {
+        let a = DateTime::new(2024, 1, 31, 23, 59, 59).unwrap();
+        let b = DateTime::new(2024, 2, 1, 0, 0, 0).unwrap();
+        let c = DateTime::new(2024, 2, 1, 0, 0, 1).unwrap();
+        assert!(a < b);
+        assert!(b < c);
+        assert_eq!(a.max(c), c);
+    }
+
+    #[test]
+    fn test_week_of_month_for_each_starting_weekday() {
+        // (year, month) pairs whose 1st falls on Sunday..Saturday
+        let months = [
+            (2024, 9),  // Sunday
+            (2024, 7),  // Monday
+            (2024, 10), // Tuesday
+            (2024, 5),  // Wednesday
+            (2024, 2),  // Thursday
+            (2024, 3),  // Friday
+            (2024, 6),  // Saturday
+        ];
+
+        for (offset, (year, month)) in months.into_iter().enumerate() {
+            let first = DateTime::new(year, month, 1, 0, 0, 0).unwrap();
+            assert_eq!(
+                first.calculate_weekday().unwrap().to_number() - 1,
+                offset as u8
+            );
+            assert_eq!(first.week_of_month(), 1);
+
+            // The first Saturday always closes week 1
+            let first_saturday = 7 - offset as u8;
+            let dt = DateTime::new(year, month, first_saturday, 0, 0, 0).unwrap();
+            assert_eq!(dt.week_of_month(), 1);
+
+            // The following Sunday opens week 2
+            let dt = DateTime::new(year, month, first_saturday + 1, 0, 0, 0).unwrap();
+            assert_eq!(dt.week_of_month(), 2);
+        }
+    }
+
+    #[test]
+    fn test_week_of_month_day_31() {
+        // March 2024 starts on a Friday, so the 31st is in week 6
+        let dt = DateTime::new(2024, 3, 31, 0, 0, 0).unwrap();
+        assert_eq!(dt.week_of_month(), 6);
+
+        // December 2024 starts on a Sunday, so the 31st is in week 5
+        let dt = DateTime::new(2024, 12, 31, 0, 0, 0).unwrap();
+        assert_eq!(dt.week_of_month(), 5);
+
+        // February 2015 starts on a Sunday and has exactly four weeks
+        let dt = DateTime::new(2015, 2, 28, 0, 0, 0).unwrap();
+        assert_eq!(dt.week_of_month(), 4);
+    }
+
+    #[test]
+    fn test_week_of_month_from_first_sunday() {
+        // March 2024 starts on a Friday; the first Sunday is the 3rd
+        let week = |day| {
+            DateTime::new(2024, 3, day, 0, 0, 0)
+                .unwrap()
+                .week_of_month_from_first_sunday()
+        };
+        assert_eq!(week(1), 0);
+        assert_eq!(week(2), 0);
+        assert_eq!(week(3), 1);
+        assert_eq!(week(9), 1);
+        assert_eq!(week(10), 2);
+        assert_eq!(week(31), 5);
+
+        // September 2024 starts on a Sunday, so there is no week 0
+        let dt = DateTime::new(2024, 9, 1, 0, 0, 0).unwrap();
+        assert_eq!(dt.week_of_month_from_first_sunday(), 1);
+        let dt = DateTime::new(2024, 9, 30, 0, 0, 0).unwrap();
+        assert_eq!(dt.week_of_month_from_first_sunday(), 5);
+    }
+
+    #[test]
+    fn test_start_of_day() {
+        let dt = DateTime::new(2024, 8, 16, 12, 34, 56).unwrap();
+        let midnight = dt.start_of_day();
+        assert_eq!(midnight, DateTime::new(2024, 8, 16, 0, 0, 0).unwrap());
+        assert_eq!(midnight.start_of_day(), midnight);
+    }
+
+    #[test]
+    fn test_last_day_of_month() {
+        let dt = DateTime::new(2024, 2, 10, 8, 30, 0).unwrap();
+        assert_eq!(
+            dt.last_day_of_month(),
+            DateTime::new(2024, 2, 29, 8, 30, 0).unwrap()
+        );
+
+        let dt = DateTime::new(2023, 2, 10, 8, 30, 0).unwrap();
+        assert_eq!(
+            dt.last_day_of_month(),
+            DateTime::new(2023, 2, 28, 8, 30, 0).unwrap()
+        );
+
+        let dt = DateTime::new(2024, 12, 1, 23, 59, 59).unwrap();
+        let last = dt.last_day_of_month();
+        assert_eq!(last, DateTime::new(2024, 12, 31, 23, 59, 59).unwrap());
+        assert!(last.validate().is_ok());
+    }
+
+    /// Small xorshift generator so the property tests stay deterministic
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn range(&mut self, min: u64, max: u64) -> u64 {
+            min + self.next() % (max - min + 1)
+        }
+
+        fn datetime(&mut self) -> DateTime {
+            let year = self.range(1970, 2200) as u16;
+            let month = self.range(1, 12) as u8;
+            let day = self.range(1, days_in_month(year, month) as u64) as u8;
+            DateTime::new(
+                year,
+                month,
+                day,
+                self.range(0, 23) as u8,
+                self.range(0, 59) as u8,
+                self.range(0, 59) as u8,
+            )
+            .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_sortable_u64_layout() {
+        let dt = DateTime::new(2024, 8, 16, 12, 34, 56).unwrap();
+        assert_eq!(dt.to_sortable_u64(), 0x0007_E808_100C_2238);
+        assert_eq!(DateTime::MIN.to_sortable_u64(), 0x0007_B201_0100_0000);
+        assert_eq!(DateTime::MAX.to_sortable_u64(), 0x00FF_FF0C_1F17_3B3B);
+    }
+
+    #[test]
+    fn test_sortable_u64_round_trip() {
+        let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+        for _ in 0..1000 {
+            let dt = rng.datetime();
+            assert_eq!(DateTime::from_sortable_u64(dt.to_sortable_u64()), Ok(dt));
+        }
+        assert_eq!(
+            DateTime::from_sortable_u64(DateTime::MAX.to_sortable_u64()),
+            Ok(DateTime::MAX)
+        );
+    }
+
+    #[test]
+    fn test_sortable_u64_order_matches_datetime_order() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..1000 {
+            let a = rng.datetime();
+            let b = rng.datetime();
+            assert_eq!(
+                a.to_sortable_u64().cmp(&b.to_sortable_u64()),
+                a.cmp(&b),
+                "{a:?} vs {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_sortable_u64_rejects_invalid() {
+        let valid = DateTime::new(2024, 8, 16, 12, 34, 56)
+            .unwrap()
+            .to_sortable_u64();
+
+        assert_eq!(
+            DateTime::from_sortable_u64(valid | (1 << 56)),
+            Err(DateTimeError::InvalidYear)
+        );
+        assert_eq!(
+            DateTime::from_sortable_u64(valid | (0xFF << 32)),
+            Err(DateTimeError::InvalidMonth)
+        );
+        assert_eq!(
+            DateTime::from_sortable_u64(valid | (0x3F << 24)),
+            Err(DateTimeError::InvalidDay)
+        );
+        assert_eq!(
+            DateTime::from_sortable_u64(valid | 0xFF),
+            Err(DateTimeError::InvalidSecond)
+        );
+        assert_eq!(
+            DateTime::from_sortable_u64(0),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn test_bytes_layout() {
+        let dt = DateTime::new(2024, 8, 16, 12, 34, 56).unwrap();
+        assert_eq!(dt.to_bytes(), [0xE8, 0x07, 8, 16, 12, 34, 56]);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let mut rng = XorShift(0xA076_1D64_78BD_642F);
+        for _ in 0..1000 {
+            let dt = rng.datetime();
+            assert_eq!(DateTime::from_bytes(&dt.to_bytes()), Ok(dt));
+        }
+        assert_eq!(
+            DateTime::from_bytes(&DateTime::MIN.to_bytes()),
+            Ok(DateTime::MIN)
+        );
+        assert_eq!(
+            DateTime::from_bytes(&DateTime::MAX.to_bytes()),
+            Ok(DateTime::MAX)
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_corruption() {
+        let valid = DateTime::new(2024, 8, 16, 12, 34, 56).unwrap().to_bytes();
+
+        let mut bytes = valid;
+        bytes[2] = 0xFF;
+        assert_eq!(
+            DateTime::from_bytes(&bytes),
+            Err(DateTimeError::InvalidMonth)
+        );
+
+        let mut bytes = valid;
+        bytes[3] = 0;
+        assert_eq!(DateTime::from_bytes(&bytes), Err(DateTimeError::InvalidDay));
+
+        let mut bytes = valid;
+        bytes[4] = 24;
+        assert_eq!(
+            DateTime::from_bytes(&bytes),
+            Err(DateTimeError::InvalidHour)
+        );
+
+        let mut bytes = valid;
+        bytes[5] = 0xFF;
+        assert_eq!(
+            DateTime::from_bytes(&bytes),
+            Err(DateTimeError::InvalidMinute)
+        );
+
+        let mut bytes = valid;
+        bytes[6] = 60;
+        assert_eq!(
+            DateTime::from_bytes(&bytes),
+            Err(DateTimeError::InvalidSecond)
+        );
+
+        // Erased memory
+        assert_eq!(
+            DateTime::from_bytes(&[0xFF; 7]),
+            Err(DateTimeError::InvalidMonth)
+        );
+        assert_eq!(
+            DateTime::from_bytes(&[0x00; 7]),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn test_unix_timestamp_known_values() {
+        assert_eq!(DateTime::MIN.to_unix_timestamp(), 0);
+        let dt = DateTime::new(2000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(dt.to_unix_timestamp(), 946_684_800);
+        let dt = DateTime::new(2024, 2, 29, 12, 0, 0).unwrap();
+        assert_eq!(dt.to_unix_timestamp(), 1_709_208_000);
+        let dt = DateTime::new(2038, 1, 19, 3, 14, 8).unwrap();
+        assert_eq!(dt.to_unix_timestamp(), 1 << 31);
+
+        assert_eq!(
+            DateTime::from_unix_timestamp(1_709_208_000),
+            DateTime::new(2024, 2, 29, 12, 0, 0)
+        );
+        assert_eq!(DateTime::from_unix_timestamp(0), Ok(DateTime::MIN));
+    }
+
+    #[test]
+    fn test_unix_timestamp_round_trip() {
+        let mut rng = XorShift(0xD1B5_4A32_D192_ED03);
+        for _ in 0..1000 {
+            let dt = rng.datetime();
+            assert_eq!(
+                DateTime::from_unix_timestamp(dt.to_unix_timestamp()),
+                Ok(dt)
+            );
+        }
+
+        let max = DateTime::MAX.to_unix_timestamp();
+        assert_eq!(DateTime::from_unix_timestamp(max), Ok(DateTime::MAX));
+        assert_eq!(
+            DateTime::from_unix_timestamp(max + 1),
+            Err(DateTimeError::InvalidYear)
+        );
+        assert_eq!(
+            DateTime::from_unix_timestamp(u64::MAX),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn test_new_with_weekday() {
+        // 2024-08-16 is a Friday
+        let dt = DateTime::new_with_weekday(2024, 8, 16, 12, 0, 0, Weekday::Friday).unwrap();
+        assert_eq!(dt.weekday(), Weekday::Friday);
+        assert_eq!(dt, DateTime::new(2024, 8, 16, 12, 0, 0).unwrap());
+
+        assert_eq!(
+            DateTime::new_with_weekday(2024, 8, 16, 12, 0, 0, Weekday::Monday),
+            Err(DateTimeError::InvalidWeekday)
+        );
+        assert_eq!(
+            DateTime::new_with_weekday(2024, 2, 30, 12, 0, 0, Weekday::Friday),
+            Err(DateTimeError::InvalidDay)
+        );
+    }
+
+    #[test]
+    fn test_weekday_computed_without_carried_value() {
+        let dt = DateTime::new(2024, 12, 25, 0, 0, 0).unwrap();
+        assert_eq!(dt.weekday(), Weekday::Wednesday);
+        assert_eq!(DateTime::MIN.weekday(), Weekday::Thursday);
+    }
+
+    #[test]
+    fn test_setters_keep_carried_weekday_consistent() {
+        let mut dt = DateTime::new_with_weekday(2024, 8, 16, 12, 0, 0, Weekday::Friday).unwrap();
+
+        dt.set_day_of_month(17).unwrap();
+        assert_eq!(dt.weekday(), Weekday::Saturday);
+
+        dt.set_month(9).unwrap();
+        assert_eq!(dt.weekday(), Weekday::Tuesday);
+
+        dt.set_year(2025).unwrap();
+        assert_eq!(dt.weekday(), Weekday::Wednesday);
+
+        // Failed setters leave the weekday untouched
+        assert!(dt.set_day_of_month(31).is_err());
+        assert_eq!(dt.weekday(), Weekday::Wednesday);
+
+        assert_eq!(dt.last_day_of_month().weekday(), Weekday::Tuesday);
+        assert_eq!(dt.weekday(), dt.calculate_weekday().unwrap());
+    }
+
     #[test]
     fn test_set_second() {
         let mut dt = DateTime::new(2024, 5, 15, 12, 30, 45).unwrap();
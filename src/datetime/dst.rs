@@ -0,0 +1,199 @@
+//! # Daylight Saving Time Rules
+//!
+//! This module implements the EU and US daylight saving time (DST) rules without
+//! a time zone database, so a fixed standard offset plus one of these rules is
+//! enough to show local time on small devices.
+//!
+//! Enabled with the `dst` feature.
+//!
+//! ## Rules
+//! - **EU**: DST runs from the last Sunday in March at 01:00 UTC to the last
+//!   Sunday in October at 01:00 UTC. The switch happens at the same instant in
+//!   every EU time zone, so the rule is evaluated on UTC.
+//! - **US**: DST runs from the second Sunday in March at 02:00 local standard time
+//!   to the first Sunday in November at 02:00 local daylight time (01:00 local
+//!   standard time). The rule is evaluated on local standard time.
+//!
+//! ## Transition Instants
+//! The start instant is inclusive and the end instant is exclusive: exactly at
+//! the start DST is active, and exactly at the end it is not.
+//!
+//! ## Example
+//! ```
+//! use rtc_hal::datetime::{DateTime, UtcOffset};
+//! use rtc_hal::datetime::dst::{apply_dst, DstRule};
+//!
+//! let utc = DateTime::new(2024, 7, 1, 12, 0, 0).unwrap();
+//! let berlin = apply_dst(&utc, UtcOffset::from_hours(1).unwrap(), DstRule::Eu).unwrap();
+//! assert_eq!(berlin, DateTime::new(2024, 7, 1, 14, 0, 0).unwrap());
+//! ```
+
+use crate::datetime::{
+    Date, DateTime, DateTimeError, UtcOffset, Weekday, last_weekday_of_month, nth_weekday_of_month,
+};
+
+/// Daylight saving time rule set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DstRule {
+    /// European Union rules (last Sunday of March/October at 01:00 UTC)
+    Eu,
+    /// United States rules (second Sunday of March/first Sunday of November at 02:00 local)
+    Us,
+}
+
+/// Combine a transition date with the hour it happens at.
+fn transition(date: Result<Date, DateTimeError>, hour: u8) -> Option<DateTime> {
+    let date = date.ok()?;
+    DateTime::new(date.year(), date.month(), date.day_of_month(), hour, 0, 0).ok()
+}
+
+/// Check whether EU summer time is in effect.
+///
+/// `utc` must be in UTC. Returns `true` from the last Sunday in March at
+/// 01:00:00 UTC (inclusive) until the last Sunday in October at 01:00:00 UTC
+/// (exclusive).
+pub fn eu_dst_active(utc: &DateTime) -> bool {
+    let year = utc.year();
+    let start = transition(last_weekday_of_month(year, 3, Weekday::Sunday), 1);
+    let end = transition(last_weekday_of_month(year, 10, Weekday::Sunday), 1);
+
+    match (start, end) {
+        (Some(start), Some(end)) => *utc >= start && *utc < end,
+        _ => false,
+    }
+}
+
+/// Check whether US daylight saving time is in effect.
+///
+/// `local_standard` must be local *standard* time, i.e. UTC plus the zone's
+/// standard offset with no DST applied. Returns `true` from the second Sunday
+/// in March at 02:00:00 (inclusive) until the first Sunday in November at
+/// 01:00:00 standard time (exclusive), which is 02:00:00 on the wall clock.
+pub fn us_dst_active(local_standard: &DateTime) -> bool {
+    let year = local_standard.year();
+    let start = transition(nth_weekday_of_month(year, 3, Weekday::Sunday, 2), 2);
+    let end = transition(nth_weekday_of_month(year, 11, Weekday::Sunday, 1), 1);
+
+    match (start, end) {
+        (Some(start), Some(end)) => *local_standard >= start && *local_standard < end,
+        _ => false,
+    }
+}
+
+/// Convert UTC to local wall-clock time, applying the given DST rule.
+///
+/// `standard_offset` is the zone's offset outside of DST (e.g. +1 hour for
+/// Central European Time, -5 hours for US Eastern Time). One hour is added
+/// on top of it while DST is active.
+///
+/// # Errors
+///
+/// Returns `DateTimeError::InvalidYear` if the result is out of range.
+pub fn apply_dst(
+    utc: &DateTime,
+    standard_offset: UtcOffset,
+    rule: DstRule,
+) -> Result<DateTime, DateTimeError> {
+    let local_standard = standard_offset.to_local(utc)?;
+    let active = match rule {
+        DstRule::Eu => eu_dst_active(utc),
+        DstRule::Us => us_dst_active(&local_standard),
+    };
+
+    if active {
+        local_standard.checked_add_seconds(3600)
+    } else {
+        Ok(local_standard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn test_eu_dst_transitions() {
+        // 2024: March 31 to October 27
+        assert!(!eu_dst_active(&dt(2024, 3, 31, 0, 59, 59)));
+        assert!(eu_dst_active(&dt(2024, 3, 31, 1, 0, 0)));
+        assert!(eu_dst_active(&dt(2024, 10, 27, 0, 59, 59)));
+        assert!(!eu_dst_active(&dt(2024, 10, 27, 1, 0, 0)));
+
+        assert!(!eu_dst_active(&dt(2024, 1, 15, 12, 0, 0)));
+        assert!(eu_dst_active(&dt(2024, 7, 15, 12, 0, 0)));
+        assert!(!eu_dst_active(&dt(2024, 12, 15, 12, 0, 0)));
+    }
+
+    #[test]
+    fn test_us_dst_transitions() {
+        // 2024: March 10 to November 3
+        assert!(!us_dst_active(&dt(2024, 3, 10, 1, 59, 59)));
+        assert!(us_dst_active(&dt(2024, 3, 10, 2, 0, 0)));
+        assert!(us_dst_active(&dt(2024, 11, 3, 0, 59, 59)));
+        assert!(!us_dst_active(&dt(2024, 11, 3, 1, 0, 0)));
+
+        assert!(!us_dst_active(&dt(2025, 3, 9, 1, 59, 59)));
+        assert!(us_dst_active(&dt(2025, 3, 9, 2, 0, 0)));
+        assert!(!us_dst_active(&dt(2025, 11, 2, 1, 0, 0)));
+    }
+
+    #[test]
+    fn test_apply_dst_eu() {
+        let cet = UtcOffset::from_hours(1).unwrap();
+        assert_eq!(
+            apply_dst(&dt(2024, 1, 1, 12, 0, 0), cet, DstRule::Eu),
+            Ok(dt(2024, 1, 1, 13, 0, 0))
+        );
+        assert_eq!(
+            apply_dst(&dt(2024, 7, 1, 12, 0, 0), cet, DstRule::Eu),
+            Ok(dt(2024, 7, 1, 14, 0, 0))
+        );
+
+        // Wall clock jumps from 01:59:59 to 03:00:00 CET
+        assert_eq!(
+            apply_dst(&dt(2024, 3, 31, 0, 59, 59), cet, DstRule::Eu),
+            Ok(dt(2024, 3, 31, 1, 59, 59))
+        );
+        assert_eq!(
+            apply_dst(&dt(2024, 3, 31, 1, 0, 0), cet, DstRule::Eu),
+            Ok(dt(2024, 3, 31, 3, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_apply_dst_us() {
+        let eastern = UtcOffset::from_hours(-5).unwrap();
+        assert_eq!(
+            apply_dst(&dt(2024, 1, 1, 12, 0, 0), eastern, DstRule::Us),
+            Ok(dt(2024, 1, 1, 7, 0, 0))
+        );
+        assert_eq!(
+            apply_dst(&dt(2024, 7, 1, 12, 0, 0), eastern, DstRule::Us),
+            Ok(dt(2024, 7, 1, 8, 0, 0))
+        );
+
+        // Wall clock jumps from 01:59:59 to 03:00:00 EST
+        assert_eq!(
+            apply_dst(&dt(2024, 3, 10, 6, 59, 59), eastern, DstRule::Us),
+            Ok(dt(2024, 3, 10, 1, 59, 59))
+        );
+        assert_eq!(
+            apply_dst(&dt(2024, 3, 10, 7, 0, 0), eastern, DstRule::Us),
+            Ok(dt(2024, 3, 10, 3, 0, 0))
+        );
+
+        // Wall clock falls back from 01:59:59 EDT to 01:00:00 EST
+        assert_eq!(
+            apply_dst(&dt(2024, 11, 3, 5, 59, 59), eastern, DstRule::Us),
+            Ok(dt(2024, 11, 3, 1, 59, 59))
+        );
+        assert_eq!(
+            apply_dst(&dt(2024, 11, 3, 6, 0, 0), eastern, DstRule::Us),
+            Ok(dt(2024, 11, 3, 1, 0, 0))
+        );
+    }
+}
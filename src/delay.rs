@@ -0,0 +1,205 @@
+//! # Delays timed by an RTC
+//!
+//! [`RtcDelay`] implements `embedded_hal::delay::DelayNs` by polling an
+//! [`Rtc`] until the requested number of seconds has passed. It is meant for
+//! long delays on systems without a usable hardware timer, such as deep-sleep
+//! bootloaders.
+//!
+//! Enabled with the `embedded-hal` feature.
+//!
+//! ## Granularity
+//!
+//! The RTC only counts whole seconds, and the delay can start anywhere within
+//! a second. Requests are rounded up to whole seconds, and the actual delay is
+//! up to one second shorter than that: a request for `n` seconds waits for `n`
+//! ticks of the seconds counter, so it lasts between `n - 1` and `n` seconds.
+//! Sub-second requests are rounded up to one tick, which may come at any time
+//! within the next second. A request for zero returns immediately.
+//!
+//! Time is compared as seconds since the Unix epoch, so crossing midnight or
+//! the end of a month does not disturb the count. If the reading jumps
+//! backwards (e.g. a chip whose two-digit year wraps from 99 to 00), the jump
+//! counts as a single tick.
+use embedded_hal::delay::DelayNs;
+
+use crate::rtc::Rtc;
+
+/// Delay provider that busy-polls an [`Rtc`]
+///
+/// `DelayNs` cannot report errors. If reading the RTC fails, the delay ends
+/// early and the error is kept until [`RtcDelay::take_error`] is called. Use
+/// [`RtcDelay::delay_secs`] directly to handle errors as they happen.
+pub struct RtcDelay<R: Rtc> {
+    rtc: R,
+    error: Option<R::Error>,
+}
+
+impl<R: Rtc> RtcDelay<R> {
+    /// Create a delay provider that polls the given RTC
+    pub fn new(rtc: R) -> Self {
+        Self { rtc, error: None }
+    }
+
+    /// Release the RTC
+    pub fn into_inner(self) -> R {
+        self.rtc
+    }
+
+    /// Take the error that ended the last delay early, if any
+    pub fn take_error(&mut self) -> Option<R::Error> {
+        self.error.take()
+    }
+
+    /// Wait until the RTC seconds counter has ticked `secs` times
+    ///
+    /// # Errors
+    ///
+    /// Returns `R::Error` if reading the RTC fails. The delay ends at that point.
+    pub fn delay_secs(&mut self, secs: u64) -> Result<(), R::Error> {
+        if secs == 0 {
+            return Ok(());
+        }
+        let mut last = self.rtc.get_unix_time()?;
+        let mut ticks = 0;
+        while ticks < secs {
+            let now = self.rtc.get_unix_time()?;
+            if now > last {
+                ticks += now - last;
+            } else if now < last {
+                ticks += 1;
+            }
+            last = now;
+        }
+        Ok(())
+    }
+
+    fn delay_rounded(&mut self, amount: u32, per_second: u32) {
+        let secs = (amount as u64).div_ceil(per_second as u64);
+        if let Err(e) = self.delay_secs(secs) {
+            self.error = Some(e);
+        }
+    }
+}
+
+impl<R: Rtc> DelayNs for RtcDelay<R> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delay_rounded(ns, 1_000_000_000);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.delay_rounded(us, 1_000_000);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay_rounded(ms, 1_000);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::DateTime;
+    use crate::error::{ErrorKind, ErrorType};
+
+    /// Replays a script of readings, one per call
+    struct Scripted<'a> {
+        script: &'a [Result<DateTime, ErrorKind>],
+        reads: usize,
+    }
+
+    impl<'a> Scripted<'a> {
+        fn new(script: &'a [Result<DateTime, ErrorKind>]) -> Self {
+            Self { script, reads: 0 }
+        }
+    }
+
+    impl ErrorType for Scripted<'_> {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for Scripted<'_> {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            let reading = self.script[self.reads];
+            self.reads += 1;
+            reading
+        }
+
+        fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn at(h: u8, m: u8, s: u8) -> Result<DateTime, ErrorKind> {
+        Ok(DateTime::new(2024, 8, 16, h, m, s).unwrap())
+    }
+
+    #[test]
+    fn test_delay_ms_rounds_up_to_seconds() {
+        let script = [
+            at(12, 0, 0),
+            at(12, 0, 0),
+            at(12, 0, 1),
+            at(12, 0, 1),
+            at(12, 0, 2),
+        ];
+        let mut delay = RtcDelay::new(Scripted::new(&script));
+        delay.delay_ms(1500);
+        assert_eq!(delay.take_error(), None);
+        assert_eq!(delay.into_inner().reads, 5);
+    }
+
+    #[test]
+    fn test_sub_second_waits_one_tick() {
+        let script = [at(12, 0, 0), at(12, 0, 0), at(12, 0, 1)];
+        let mut delay = RtcDelay::new(Scripted::new(&script));
+        delay.delay_ns(1);
+        assert_eq!(delay.into_inner().reads, 3);
+
+        let mut delay = RtcDelay::new(Scripted::new(&script));
+        delay.delay_us(0);
+        assert_eq!(delay.into_inner().reads, 0);
+    }
+
+    #[test]
+    fn test_delay_across_midnight() {
+        let script = [
+            Ok(DateTime::new(2024, 12, 31, 23, 59, 59).unwrap()),
+            Ok(DateTime::new(2025, 1, 1, 0, 0, 0).unwrap()),
+            Ok(DateTime::new(2025, 1, 1, 0, 0, 1).unwrap()),
+        ];
+        let mut delay = RtcDelay::new(Scripted::new(&script));
+        assert_eq!(delay.delay_secs(2), Ok(()));
+        assert_eq!(delay.into_inner().reads, 3);
+    }
+
+    #[test]
+    fn test_delay_across_era_rollover() {
+        // Two-digit year wraps from 2099 back to 2000
+        let script = [
+            Ok(DateTime::new(2099, 12, 31, 23, 59, 59).unwrap()),
+            Ok(DateTime::new(2000, 1, 1, 0, 0, 0).unwrap()),
+            Ok(DateTime::new(2000, 1, 1, 0, 0, 1).unwrap()),
+        ];
+        let mut delay = RtcDelay::new(Scripted::new(&script));
+        assert_eq!(delay.delay_secs(2), Ok(()));
+        assert_eq!(delay.into_inner().reads, 3);
+    }
+
+    #[test]
+    fn test_skipped_seconds_count() {
+        // A slow poll can see the counter jump by more than one
+        let script = [at(12, 0, 0), at(12, 0, 3)];
+        let mut delay = RtcDelay::new(Scripted::new(&script));
+        assert_eq!(delay.delay_secs(3), Ok(()));
+        assert_eq!(delay.into_inner().reads, 2);
+    }
+
+    #[test]
+    fn test_error_ends_delay() {
+        let script = [at(12, 0, 0), Err(ErrorKind::Bus)];
+        let mut delay = RtcDelay::new(Scripted::new(&script));
+        delay.delay_ms(5000);
+        assert_eq!(delay.take_error(), Some(ErrorKind::Bus));
+        assert_eq!(delay.take_error(), None);
+    }
+}
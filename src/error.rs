@@ -3,7 +3,29 @@
 //! This module provides a standardized error handling framework for RTC drivers,
 //! allowing consistent error categorization across different RTC hardware implementations.
 
+use crate::alarm::AlarmConfigError;
+use crate::datetime::DateTimeError;
+use crate::square_wave::SquareWaveError;
+
 /// Common categories of errors for RTC drivers
+///
+/// This enum is `#[non_exhaustive]`, so new categories can be added without a
+/// breaking release. Code matching on it outside this crate needs a wildcard
+/// arm, which should treat unknown kinds the same as `Other`:
+///
+/// ```
+/// use rtc_hal::error::ErrorKind;
+///
+/// fn should_retry(kind: ErrorKind) -> bool {
+///     match kind {
+///         ErrorKind::Bus | ErrorKind::Busy => true,
+///         ErrorKind::ClockIntegrity => false,
+///         _ => false,
+///     }
+/// }
+///
+/// assert!(should_retry(ErrorKind::Busy));
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
@@ -26,10 +48,93 @@ pub enum ErrorKind {
     /// NVRAM is write protected
     NvramWriteProtected,
 
+    // Errors related to device state
+    /// The oscillator stopped or the time was lost, so the time cannot be trusted
+    ClockIntegrity,
+    /// A power failure or supply switchover was detected
+    PowerFailure,
+    /// The device is busy (e.g. an EEPROM write is in progress)
+    Busy,
+    /// The operation is not supported by this RTC
+    Unsupported,
+
     /// Any other error not covered above
     Other,
 }
 
+/// Categories of bus errors, mirroring the I2C and SPI error kinds of `embedded-hal`
+///
+/// Reported by [`Error::bus_kind`] to tell apart failures that call for
+/// different handling, e.g. a missing device (`NoAcknowledge`) versus a
+/// transient `ArbitrationLoss` worth retrying.
+///
+/// With the `embedded-hal` feature enabled, `From` impls for
+/// `embedded_hal::i2c::ErrorKind` and `embedded_hal::spi::ErrorKind` are provided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum BusErrorKind {
+    /// Bus error, e.g. a misplaced START or STOP condition (I2C)
+    Bus,
+    /// Bus arbitration was lost (I2C)
+    ArbitrationLoss,
+    /// The device did not acknowledge its address or a data byte (I2C)
+    NoAcknowledge,
+    /// The peripheral receive buffer was overrun (I2C, SPI)
+    Overrun,
+    /// Multiple devices on the bus are trying to drive it (SPI)
+    ModeFault,
+    /// Received data does not conform to the peripheral configuration (SPI)
+    FrameFormat,
+    /// An error occurred while asserting or deasserting chip select (SPI)
+    ChipSelectFault,
+    /// Any other bus error
+    Other,
+}
+
+impl core::fmt::Display for BusErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus => write!(f, "Bus error occurred"),
+            Self::ArbitrationLoss => write!(f, "Bus arbitration was lost"),
+            Self::NoAcknowledge => write!(f, "The device did not acknowledge"),
+            Self::Overrun => write!(f, "The peripheral receive buffer was overrun"),
+            Self::ModeFault => write!(f, "Multiple devices are trying to drive the bus"),
+            Self::FrameFormat => write!(f, "Received data has an invalid frame format"),
+            Self::ChipSelectFault => write!(f, "Chip select fault"),
+            Self::Other => write!(f, "A different bus error occurred"),
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl From<embedded_hal::i2c::ErrorKind> for BusErrorKind {
+    fn from(kind: embedded_hal::i2c::ErrorKind) -> Self {
+        use embedded_hal::i2c::ErrorKind as I2cErrorKind;
+        match kind {
+            I2cErrorKind::Bus => Self::Bus,
+            I2cErrorKind::ArbitrationLoss => Self::ArbitrationLoss,
+            I2cErrorKind::NoAcknowledge(_) => Self::NoAcknowledge,
+            I2cErrorKind::Overrun => Self::Overrun,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl From<embedded_hal::spi::ErrorKind> for BusErrorKind {
+    fn from(kind: embedded_hal::spi::ErrorKind) -> Self {
+        use embedded_hal::spi::ErrorKind as SpiErrorKind;
+        match kind {
+            SpiErrorKind::Overrun => Self::Overrun,
+            SpiErrorKind::ModeFault => Self::ModeFault,
+            SpiErrorKind::FrameFormat => Self::FrameFormat,
+            SpiErrorKind::ChipSelectFault => Self::ChipSelectFault,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// Trait that RTC driver error types should implement.
 ///
 /// Allows converting driver-specific errors into standard categories.
@@ -37,8 +142,205 @@ pub enum ErrorKind {
 pub trait Error: core::fmt::Debug {
     /// Map a driver-specific error into a general category
     fn kind(&self) -> ErrorKind;
+
+    /// Detail for errors of kind [`ErrorKind::Bus`]
+    ///
+    /// Returns `None` by default. Drivers wrapping an `embedded-hal` bus error
+    /// can report it with `Some(bus_error.kind().into())`.
+    fn bus_kind(&self) -> Option<BusErrorKind> {
+        None
+    }
+
+    /// What the driver was doing when the error occurred
+    ///
+    /// Returns `None` by default. Drivers can return context directly from
+    /// their error type, or wrap errors in [`WithContext`].
+    fn context(&self) -> Option<ErrorContext> {
+        None
+    }
+}
+
+/// Human-readable message for any driver error, even without a `Display` impl
+///
+/// Prints the [`ErrorKind`] message followed by the `Debug` output of the
+/// error, and the [`ErrorContext`] if the error has one.
+///
+/// ```
+/// use rtc_hal::error::{Error, ErrorKind, Report};
+///
+/// #[derive(Debug)]
+/// struct I2cNack;
+///
+/// impl Error for I2cNack {
+///     fn kind(&self) -> ErrorKind {
+///         ErrorKind::Bus
+///     }
+/// }
+///
+/// assert_eq!(
+///     format!("{}", Report(&I2cNack)),
+///     "Underlying bus error occurred (I2cNack)"
+/// );
+/// ```
+pub struct Report<'a, E: Error>(pub &'a E);
+
+impl<E: Error> core::fmt::Display for Report<'_, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} ({:?})", self.0.kind(), self.0)?;
+        if let Some(context) = self.0.context() {
+            write!(f, " while {context}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Extension methods for checking the [`ErrorKind`] of a `Result`
+///
+/// ```
+/// use rtc_hal::error::{ErrorKind, RtcResultExt};
+///
+/// let res: Result<(), ErrorKind> = Err(ErrorKind::NvramWriteProtected);
+/// if res.is_kind(ErrorKind::NvramWriteProtected) {
+///     // unlock and retry
+/// }
+/// assert_eq!(res.kind(), Some(ErrorKind::NvramWriteProtected));
+/// assert_eq!(Ok::<u8, ErrorKind>(1).kind(), None);
+/// ```
+pub trait RtcResultExt {
+    /// The error kind, or `None` if the result is `Ok`
+    fn kind(&self) -> Option<ErrorKind>;
+
+    /// Whether the result is an error of the given kind
+    fn is_kind(&self, kind: ErrorKind) -> bool {
+        self.kind() == Some(kind)
+    }
+}
+
+impl<T, E: Error> RtcResultExt for Result<T, E> {
+    #[inline]
+    fn kind(&self) -> Option<ErrorKind> {
+        self.as_ref().err().map(Error::kind)
+    }
+}
+
+/// Driver operation during which an error occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Operation {
+    /// Reading the date and time
+    ReadTime,
+    /// Writing the date and time
+    WriteTime,
+    /// Reading NVRAM
+    ReadNvram,
+    /// Writing NVRAM
+    WriteNvram,
+    /// Configuring an alarm
+    ConfigureAlarm,
+    /// Reading or clearing alarm status
+    AlarmStatus,
+    /// Configuring the square wave output
+    ConfigureSquareWave,
+    /// Starting or halting the clock
+    PowerControl,
+    /// Reading or writing other control registers
+    Control,
+}
+
+impl core::fmt::Display for Operation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ReadTime => write!(f, "reading the time"),
+            Self::WriteTime => write!(f, "writing the time"),
+            Self::ReadNvram => write!(f, "reading NVRAM"),
+            Self::WriteNvram => write!(f, "writing NVRAM"),
+            Self::ConfigureAlarm => write!(f, "configuring the alarm"),
+            Self::AlarmStatus => write!(f, "accessing the alarm status"),
+            Self::ConfigureSquareWave => write!(f, "configuring the square wave"),
+            Self::PowerControl => write!(f, "controlling the clock"),
+            Self::Control => write!(f, "accessing control registers"),
+        }
+    }
+}
+
+/// Where an error occurred: the operation and, if known, the register address
+///
+/// Displays as e.g. `configuring the alarm at register 0x07`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ErrorContext {
+    /// The operation that failed
+    pub operation: Operation,
+    /// The register address being accessed, if known
+    pub address: Option<u8>,
+}
+
+impl ErrorContext {
+    /// Create a context without a register address
+    pub const fn new(operation: Operation) -> Self {
+        Self {
+            operation,
+            address: None,
+        }
+    }
+
+    /// Create a context for an access to the register at `address`
+    pub const fn at(operation: Operation, address: u8) -> Self {
+        Self {
+            operation,
+            address: Some(address),
+        }
+    }
 }
 
+impl core::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.operation)?;
+        if let Some(address) = self.address {
+            write!(f, " at register {address:#04x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error together with the [`ErrorContext`] it occurred in
+///
+/// Forwards [`Error::kind`] and [`Error::bus_kind`] to the wrapped error and
+/// reports the context through [`Error::context`]. Displays as the wrapped
+/// error followed by the context, e.g.
+/// `Underlying bus error occurred while configuring the alarm at register 0x07`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WithContext<E> {
+    /// The original error
+    pub error: E,
+    /// Where the error occurred
+    pub context: ErrorContext,
+}
+
+impl<E: Error> Error for WithContext<E> {
+    fn kind(&self) -> ErrorKind {
+        self.error.kind()
+    }
+
+    fn bus_kind(&self) -> Option<BusErrorKind> {
+        self.error.bus_kind()
+    }
+
+    fn context(&self) -> Option<ErrorContext> {
+        Some(self.context)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for WithContext<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} while {}", self.error, self.context)
+    }
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> core::error::Error for WithContext<E> {}
+
 /// RTC error type trait.
 ///
 /// This just defines the error type, to be used by the other traits.
@@ -62,6 +364,174 @@ impl<T: ErrorType + ?Sized> ErrorType for &mut T {
     type Error = T::Error;
 }
 
+#[cfg(feature = "alloc")]
+impl<T: ErrorType + ?Sized> ErrorType for alloc::boxed::Box<T> {
+    type Error = T::Error;
+}
+
+/// Every `DateTimeError` maps to `ErrorKind::InvalidDateTime`.
+///
+/// This lets drivers that use `ErrorKind` as their error type validate with `?`:
+///
+/// ```
+/// use rtc_hal::bcd::{self, RegisterLayout};
+/// use rtc_hal::datetime::DateTime;
+/// use rtc_hal::error::{ErrorKind, ErrorType};
+/// use rtc_hal::rtc::Rtc;
+///
+/// struct Driver {
+///     registers: [u8; 7],
+/// }
+///
+/// impl ErrorType for Driver {
+///     type Error = ErrorKind;
+/// }
+///
+/// impl Rtc for Driver {
+///     fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+///         bcd::decode_datetime(&self.registers, RegisterLayout::Ds1307, 2000)
+///             .map_err(ErrorKind::from)
+///     }
+///
+///     fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+///         datetime.validate().map_err(ErrorKind::from)?;
+///         bcd::encode_datetime(datetime, &mut self.registers, RegisterLayout::Ds1307);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut rtc = Driver { registers: [0; 7] };
+/// rtc.set_datetime(&DateTime::new(2024, 8, 16, 12, 0, 0)?)?;
+/// assert_eq!(rtc.get_datetime()?, DateTime::new(2024, 8, 16, 12, 0, 0)?);
+/// # Ok::<(), ErrorKind>(())
+/// ```
+impl From<DateTimeError> for ErrorKind {
+    fn from(_: DateTimeError) -> Self {
+        ErrorKind::InvalidDateTime
+    }
+}
+
+/// Allows `DateTimeError` to be used where an RTC `Error` is expected.
+impl Error for DateTimeError {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::InvalidDateTime
+    }
+}
+
+/// Every `AlarmConfigError` maps to `ErrorKind::InvalidAlarmConfig`.
+impl From<AlarmConfigError> for ErrorKind {
+    fn from(_: AlarmConfigError) -> Self {
+        ErrorKind::InvalidAlarmConfig
+    }
+}
+
+/// Allows `AlarmConfigError` to be used where an RTC `Error` is expected.
+impl Error for AlarmConfigError {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::InvalidAlarmConfig
+    }
+}
+
+/// Every `SquareWaveError` maps to `ErrorKind::UnsupportedSqwFrequency`.
+impl From<SquareWaveError> for ErrorKind {
+    fn from(_: SquareWaveError) -> Self {
+        ErrorKind::UnsupportedSqwFrequency
+    }
+}
+
+/// Allows `SquareWaveError` to be used where an RTC `Error` is expected.
+impl Error for SquareWaveError {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::UnsupportedSqwFrequency
+    }
+}
+
+impl ErrorKind {
+    /// Every `ErrorKind` variant, in declaration order
+    const ALL: [ErrorKind; 12] = [
+        Self::Bus,
+        Self::InvalidDateTime,
+        Self::InvalidAlarmConfig,
+        Self::UnsupportedSqwFrequency,
+        Self::InvalidAddress,
+        Self::NvramOutOfBounds,
+        Self::NvramWriteProtected,
+        Self::ClockIntegrity,
+        Self::PowerFailure,
+        Self::Busy,
+        Self::Unsupported,
+        Self::Other,
+    ];
+
+    /// Stable name of the kind, identical to the variant name (e.g. `"NvramOutOfBounds"`)
+    ///
+    /// These names are used by the `serde` impls and will not change, so they
+    /// are safe to store or send as telemetry. Use `Display` for human-readable
+    /// messages instead.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bus => "Bus",
+            Self::InvalidDateTime => "InvalidDateTime",
+            Self::InvalidAlarmConfig => "InvalidAlarmConfig",
+            Self::UnsupportedSqwFrequency => "UnsupportedSqwFrequency",
+            Self::InvalidAddress => "InvalidAddress",
+            Self::NvramOutOfBounds => "NvramOutOfBounds",
+            Self::NvramWriteProtected => "NvramWriteProtected",
+            Self::ClockIntegrity => "ClockIntegrity",
+            Self::PowerFailure => "PowerFailure",
+            Self::Busy => "Busy",
+            Self::Unsupported => "Unsupported",
+            Self::Other => "Other",
+        }
+    }
+
+    /// Look up a kind by its [`as_str`](Self::as_str) name
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == name)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ErrorKind {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = ErrorKind;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "an RTC error kind name")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<ErrorKind, E> {
+                const NAMES: [&str; 12] = {
+                    let mut names = [""; 12];
+                    let mut i = 0;
+                    while i < names.len() {
+                        names[i] = ErrorKind::ALL[i].as_str();
+                        i += 1;
+                    }
+                    names
+                };
+                ErrorKind::from_name(name).ok_or_else(|| E::unknown_variant(name, &NAMES))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
 impl core::fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -75,6 +545,10 @@ impl core::fmt::Display for ErrorKind {
             Self::InvalidAddress => write!(f, "Invalid register address"),
             Self::NvramOutOfBounds => write!(f, "NVRAM address out of bounds"),
             Self::NvramWriteProtected => write!(f, "NVRAM is write protected"),
+            Self::ClockIntegrity => write!(f, "Clock integrity lost, time is not trustworthy"),
+            Self::PowerFailure => write!(f, "Power failure detected"),
+            Self::Busy => write!(f, "Device is busy"),
+            Self::Unsupported => write!(f, "Operation not supported by the RTC"),
             Self::Other => write!(
                 f,
                 "A different error occurred. The original error may contain more information"
@@ -83,6 +557,269 @@ impl core::fmt::Display for ErrorKind {
     }
 }
 
+/// Ready-made error type for drivers: a bus error or one of the standard categories
+///
+/// Drivers can declare `type Error = RtcError<I2C::Error>` instead of writing
+/// their own enum and `Error` impl. `DateTimeError` converts with `?`. Bus
+/// errors are wrapped with `map_err(RtcError::Bus)`, since a blanket
+/// `From<E>` impl would overlap with `From<DateTimeError>`.
+///
+/// ```
+/// use rtc_hal::bcd::{self, RegisterLayout};
+/// use rtc_hal::datetime::DateTime;
+/// use rtc_hal::error::{ErrorType, RtcError};
+/// use rtc_hal::rtc::Rtc;
+///
+/// // Stand-in for an I2C bus
+/// trait Bus {
+///     type Error: core::fmt::Debug;
+///     fn write_read(&mut self, write: &[u8], read: &mut [u8]) -> Result<(), Self::Error>;
+///     fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+/// }
+///
+/// struct Ds1307<B> {
+///     bus: B,
+/// }
+///
+/// impl<B: Bus> ErrorType for Ds1307<B> {
+///     type Error = RtcError<B::Error>;
+/// }
+///
+/// impl<B: Bus> Rtc for Ds1307<B> {
+///     fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+///         let mut registers = [0; 7];
+///         self.bus.write_read(&[0x00], &mut registers).map_err(RtcError::Bus)?;
+///         Ok(bcd::decode_datetime(&registers, RegisterLayout::Ds1307, 2000)?)
+///     }
+///
+///     fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+///         if !(2000..=2099).contains(&datetime.year()) {
+///             return Err(RtcError::InvalidDateTime(
+///                 rtc_hal::datetime::DateTimeError::InvalidYear,
+///             ));
+///         }
+///         let mut bytes = [0; 8];
+///         let registers: &mut [u8; 7] = (&mut bytes[1..]).try_into().unwrap();
+///         bcd::encode_datetime(datetime, registers, RegisterLayout::Ds1307);
+///         self.bus.write(&bytes).map_err(RtcError::Bus)
+///     }
+/// }
+///
+/// struct Nack;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct NackError;
+///
+/// impl Bus for Nack {
+///     type Error = NackError;
+///     fn write_read(&mut self, _: &[u8], _: &mut [u8]) -> Result<(), NackError> {
+///         Err(NackError)
+///     }
+///     fn write(&mut self, _: &[u8]) -> Result<(), NackError> {
+///         Err(NackError)
+///     }
+/// }
+///
+/// let mut rtc = Ds1307 { bus: Nack };
+/// assert_eq!(rtc.get_datetime(), Err(RtcError::Bus(NackError)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum RtcError<E> {
+    /// Underlying bus error (I2C, SPI, etc.)
+    Bus(E),
+    /// Invalid date/time value provided or read from the device
+    InvalidDateTime(DateTimeError),
+    /// Invalid alarm configuration
+    InvalidAlarmConfig,
+    /// The specified square wave frequency is not supported by the RTC
+    UnsupportedSqwFrequency,
+    /// Invalid register address
+    InvalidAddress,
+    /// NVRAM address out of bounds
+    NvramOutOfBounds,
+    /// NVRAM is write protected
+    NvramWriteProtected,
+    /// The oscillator stopped or the time was lost, so the time cannot be trusted
+    ClockIntegrity,
+    /// A power failure or supply switchover was detected
+    PowerFailure,
+    /// The device is busy (e.g. an EEPROM write is in progress)
+    Busy,
+    /// The operation is not supported by this RTC
+    Unsupported,
+    /// Any other error not covered above
+    Other,
+}
+
+impl<E> From<DateTimeError> for RtcError<E> {
+    fn from(error: DateTimeError) -> Self {
+        RtcError::InvalidDateTime(error)
+    }
+}
+
+impl<E> From<AlarmConfigError> for RtcError<E> {
+    fn from(_: AlarmConfigError) -> Self {
+        RtcError::InvalidAlarmConfig
+    }
+}
+
+impl<E> From<SquareWaveError> for RtcError<E> {
+    fn from(_: SquareWaveError) -> Self {
+        RtcError::UnsupportedSqwFrequency
+    }
+}
+
+impl<E: core::fmt::Debug> Error for RtcError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Bus(_) => ErrorKind::Bus,
+            Self::InvalidDateTime(_) => ErrorKind::InvalidDateTime,
+            Self::InvalidAlarmConfig => ErrorKind::InvalidAlarmConfig,
+            Self::UnsupportedSqwFrequency => ErrorKind::UnsupportedSqwFrequency,
+            Self::InvalidAddress => ErrorKind::InvalidAddress,
+            Self::NvramOutOfBounds => ErrorKind::NvramOutOfBounds,
+            Self::NvramWriteProtected => ErrorKind::NvramWriteProtected,
+            Self::ClockIntegrity => ErrorKind::ClockIntegrity,
+            Self::PowerFailure => ErrorKind::PowerFailure,
+            Self::Busy => ErrorKind::Busy,
+            Self::Unsupported => ErrorKind::Unsupported,
+            Self::Other => ErrorKind::Other,
+        }
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for RtcError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(e) => write!(f, "Underlying bus error occurred: {e}"),
+            Self::InvalidDateTime(e) => write!(f, "Invalid datetime value provided: {e}"),
+            Self::InvalidAlarmConfig => write!(f, "{}", ErrorKind::InvalidAlarmConfig),
+            Self::UnsupportedSqwFrequency => write!(f, "{}", ErrorKind::UnsupportedSqwFrequency),
+            Self::InvalidAddress => write!(f, "{}", ErrorKind::InvalidAddress),
+            Self::NvramOutOfBounds => write!(f, "{}", ErrorKind::NvramOutOfBounds),
+            Self::NvramWriteProtected => write!(f, "{}", ErrorKind::NvramWriteProtected),
+            Self::ClockIntegrity => write!(f, "{}", ErrorKind::ClockIntegrity),
+            Self::PowerFailure => write!(f, "{}", ErrorKind::PowerFailure),
+            Self::Busy => write!(f, "{}", ErrorKind::Busy),
+            Self::Unsupported => write!(f, "{}", ErrorKind::Unsupported),
+            Self::Other => write!(f, "{}", ErrorKind::Other),
+        }
+    }
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> core::error::Error for RtcError<E> {}
+
+/// Implement [`Error`] for a driver error enum by mapping variants to [`ErrorKind`]s
+///
+/// Each arm is a variant of the enum (written without the enum name),
+/// optionally followed by a pattern for its fields, and the name of an
+/// `ErrorKind` variant. A final `_` arm covers the remaining variants. Generic
+/// enums list their type parameters, which must implement `Debug`. Adding
+/// `#[display]` also implements `Display` using the `ErrorKind` message.
+///
+/// ```
+/// use rtc_hal::error::{Error, ErrorKind};
+///
+/// #[derive(Debug)]
+/// enum DriverError<E> {
+///     I2c(E),
+///     BadTime,
+///     Eeprom { busy: bool },
+///     Checksum,
+/// }
+///
+/// rtc_hal::impl_error! {
+///     #[display]
+///     DriverError<E> {
+///         I2c(_) => Bus,
+///         BadTime => InvalidDateTime,
+///         Eeprom { busy: true } => Busy,
+///         _ => Other,
+///     }
+/// }
+///
+/// assert_eq!(DriverError::I2c(()).kind(), ErrorKind::Bus);
+/// assert_eq!(DriverError::<()>::Eeprom { busy: true }.kind(), ErrorKind::Busy);
+/// assert_eq!(DriverError::<()>::Checksum.kind(), ErrorKind::Other);
+/// assert_eq!(format!("{}", DriverError::<()>::BadTime), "Invalid datetime value provided");
+/// ```
+///
+/// Unknown kinds are rejected at compile time:
+///
+/// ```compile_fail
+/// #[derive(Debug)]
+/// enum DriverError {
+///     I2c,
+/// }
+///
+/// rtc_hal::impl_error! {
+///     DriverError {
+///         I2c => I2cBus,
+///     }
+/// }
+/// ```
+///
+/// So are unknown variants, since every arm is matched as
+/// `DriverError::Variant` rather than a bare name that could bind anything:
+///
+/// ```compile_fail
+/// #[derive(Debug)]
+/// enum DriverError {
+///     I2c,
+///     Checksum,
+/// }
+///
+/// rtc_hal::impl_error! {
+///     DriverError {
+///         I2C => Bus,
+///         Checksum => Other,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_error {
+    (
+        #[display]
+        $name:ident $(< $($generic:ident),+ >)? { $($arms:tt)* }
+    ) => {
+        $crate::impl_error! { $name $(< $($generic),+ >)? { $($arms)* } }
+
+        impl $(< $($generic: ::core::fmt::Debug),+ >)? ::core::fmt::Display
+            for $name $(< $($generic),+ >)?
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&$crate::error::Error::kind(self), f)
+            }
+        }
+    };
+    (
+        $name:ident $(< $($generic:ident),+ >)? {
+            $(
+                $variant:ident $(( $($tuple:tt)* ))? $({ $($fields:tt)* })?
+                    => $kind:ident
+            ),+
+            $(, _ => $default:ident)?
+            $(,)?
+        }
+    ) => {
+        impl $(< $($generic: ::core::fmt::Debug),+ >)? $crate::error::Error
+            for $name $(< $($generic),+ >)?
+        {
+            fn kind(&self) -> $crate::error::ErrorKind {
+                match self {
+                    $(
+                        $name::$variant $(( $($tuple)* ))? $({ $($fields)* })?
+                            => $crate::error::ErrorKind::$kind,
+                    )+
+                    $(_ => $crate::error::ErrorKind::$default,)?
+                }
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,10 +834,21 @@ mod tests {
         InvalidRegisterAddress,
         NvramAddressOutOfBounds,
         NvramWriteProtected,
+        OscillatorStopped,
+        PowerFailed,
+        EepromBusy,
+        NotSupported,
         UnknownError,
     }
 
     impl Error for MockRtcError {
+        fn bus_kind(&self) -> Option<BusErrorKind> {
+            match self {
+                MockRtcError::I2cError => Some(BusErrorKind::NoAcknowledge),
+                _ => None,
+            }
+        }
+
         fn kind(&self) -> ErrorKind {
             match self {
                 MockRtcError::I2cError => ErrorKind::Bus,
@@ -110,6 +858,10 @@ mod tests {
                 MockRtcError::InvalidRegisterAddress => ErrorKind::InvalidAddress,
                 MockRtcError::NvramAddressOutOfBounds => ErrorKind::NvramOutOfBounds,
                 MockRtcError::NvramWriteProtected => ErrorKind::NvramWriteProtected,
+                MockRtcError::OscillatorStopped => ErrorKind::ClockIntegrity,
+                MockRtcError::PowerFailed => ErrorKind::PowerFailure,
+                MockRtcError::EepromBusy => ErrorKind::Busy,
+                MockRtcError::NotSupported => ErrorKind::Unsupported,
                 _ => ErrorKind::Other,
             }
         }
@@ -142,9 +894,348 @@ mod tests {
             MockRtcError::NvramWriteProtected.kind(),
             ErrorKind::NvramWriteProtected
         );
+        assert_eq!(
+            MockRtcError::OscillatorStopped.kind(),
+            ErrorKind::ClockIntegrity
+        );
+        assert_eq!(MockRtcError::PowerFailed.kind(), ErrorKind::PowerFailure);
+        assert_eq!(MockRtcError::EepromBusy.kind(), ErrorKind::Busy);
+        assert_eq!(MockRtcError::NotSupported.kind(), ErrorKind::Unsupported);
         assert_eq!(MockRtcError::UnknownError.kind(), ErrorKind::Other);
     }
 
+    #[test]
+    fn test_bus_kind() {
+        assert_eq!(
+            MockRtcError::I2cError.bus_kind(),
+            Some(BusErrorKind::NoAcknowledge)
+        );
+        assert_eq!(MockRtcError::InvalidDateTime.bus_kind(), None);
+        assert_eq!(ErrorKind::Bus.bus_kind(), None);
+    }
+
+    #[test]
+    fn test_bus_error_kind_display_messages() {
+        assert_eq!(format!("{}", BusErrorKind::Bus), "Bus error occurred");
+        assert_eq!(
+            format!("{}", BusErrorKind::ArbitrationLoss),
+            "Bus arbitration was lost"
+        );
+        assert_eq!(
+            format!("{}", BusErrorKind::NoAcknowledge),
+            "The device did not acknowledge"
+        );
+        assert_eq!(
+            format!("{}", BusErrorKind::Overrun),
+            "The peripheral receive buffer was overrun"
+        );
+        assert_eq!(
+            format!("{}", BusErrorKind::ModeFault),
+            "Multiple devices are trying to drive the bus"
+        );
+        assert_eq!(
+            format!("{}", BusErrorKind::FrameFormat),
+            "Received data has an invalid frame format"
+        );
+        assert_eq!(
+            format!("{}", BusErrorKind::ChipSelectFault),
+            "Chip select fault"
+        );
+        assert_eq!(
+            format!("{}", BusErrorKind::Other),
+            "A different bus error occurred"
+        );
+    }
+
+    #[cfg(feature = "embedded-hal")]
+    #[test]
+    fn test_bus_error_kind_from_embedded_hal() {
+        use embedded_hal::{i2c, spi};
+
+        assert_eq!(BusErrorKind::from(i2c::ErrorKind::Bus), BusErrorKind::Bus);
+        assert_eq!(
+            BusErrorKind::from(i2c::ErrorKind::ArbitrationLoss),
+            BusErrorKind::ArbitrationLoss
+        );
+        assert_eq!(
+            BusErrorKind::from(i2c::ErrorKind::NoAcknowledge(
+                i2c::NoAcknowledgeSource::Address
+            )),
+            BusErrorKind::NoAcknowledge
+        );
+        assert_eq!(
+            BusErrorKind::from(i2c::ErrorKind::Overrun),
+            BusErrorKind::Overrun
+        );
+        assert_eq!(
+            BusErrorKind::from(i2c::ErrorKind::Other),
+            BusErrorKind::Other
+        );
+
+        assert_eq!(
+            BusErrorKind::from(spi::ErrorKind::Overrun),
+            BusErrorKind::Overrun
+        );
+        assert_eq!(
+            BusErrorKind::from(spi::ErrorKind::ModeFault),
+            BusErrorKind::ModeFault
+        );
+        assert_eq!(
+            BusErrorKind::from(spi::ErrorKind::FrameFormat),
+            BusErrorKind::FrameFormat
+        );
+        assert_eq!(
+            BusErrorKind::from(spi::ErrorKind::ChipSelectFault),
+            BusErrorKind::ChipSelectFault
+        );
+        assert_eq!(
+            BusErrorKind::from(spi::ErrorKind::Other),
+            BusErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn test_rtc_error_kind_mapping() {
+        type E = RtcError<MockRtcError>;
+        assert_eq!(E::Bus(MockRtcError::I2cError).kind(), ErrorKind::Bus);
+        assert_eq!(
+            E::InvalidDateTime(DateTimeError::InvalidDay).kind(),
+            ErrorKind::InvalidDateTime
+        );
+        assert_eq!(E::InvalidAlarmConfig.kind(), ErrorKind::InvalidAlarmConfig);
+        assert_eq!(
+            E::UnsupportedSqwFrequency.kind(),
+            ErrorKind::UnsupportedSqwFrequency
+        );
+        assert_eq!(E::InvalidAddress.kind(), ErrorKind::InvalidAddress);
+        assert_eq!(E::NvramOutOfBounds.kind(), ErrorKind::NvramOutOfBounds);
+        assert_eq!(
+            E::NvramWriteProtected.kind(),
+            ErrorKind::NvramWriteProtected
+        );
+        assert_eq!(E::ClockIntegrity.kind(), ErrorKind::ClockIntegrity);
+        assert_eq!(E::PowerFailure.kind(), ErrorKind::PowerFailure);
+        assert_eq!(E::Busy.kind(), ErrorKind::Busy);
+        assert_eq!(E::Unsupported.kind(), ErrorKind::Unsupported);
+        assert_eq!(E::Other.kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_rtc_error_question_mark() {
+        fn bus(ok: bool) -> Result<(), MockRtcError> {
+            if ok {
+                Ok(())
+            } else {
+                Err(MockRtcError::I2cError)
+            }
+        }
+        fn read(bus_ok: bool) -> Result<crate::datetime::DateTime, RtcError<MockRtcError>> {
+            bus(bus_ok).map_err(RtcError::Bus)?;
+            Ok(crate::datetime::DateTime::new(2024, 13, 1, 0, 0, 0)?)
+        }
+        assert_eq!(read(false), Err(RtcError::Bus(MockRtcError::I2cError)));
+        assert_eq!(
+            read(true),
+            Err(RtcError::InvalidDateTime(DateTimeError::InvalidMonth))
+        );
+    }
+
+    #[test]
+    fn test_rtc_error_display() {
+        assert_eq!(
+            format!("{}", RtcError::Bus(ErrorKind::Busy)),
+            "Underlying bus error occurred: Device is busy"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                RtcError::<ErrorKind>::InvalidDateTime(DateTimeError::InvalidHour)
+            ),
+            "Invalid datetime value provided: invalid hour"
+        );
+        assert_eq!(
+            format!("{}", RtcError::<ErrorKind>::NvramWriteProtected),
+            "NVRAM is write protected"
+        );
+    }
+
+    #[test]
+    fn test_error_context_display() {
+        assert_eq!(
+            format!("{}", ErrorContext::new(Operation::ReadTime)),
+            "reading the time"
+        );
+        assert_eq!(
+            format!("{}", ErrorContext::at(Operation::WriteNvram, 0x3F)),
+            "writing NVRAM at register 0x3f"
+        );
+        assert_eq!(MockRtcError::I2cError.context(), None);
+    }
+
+    #[test]
+    fn test_with_context_through_driver() {
+        struct Driver;
+
+        impl ErrorType for Driver {
+            type Error = WithContext<ErrorKind>;
+        }
+
+        impl Driver {
+            fn set_alarm(&mut self) -> Result<(), <Self as ErrorType>::Error> {
+                Err(WithContext {
+                    error: ErrorKind::Bus,
+                    context: ErrorContext::at(Operation::ConfigureAlarm, 0x07),
+                })
+            }
+        }
+
+        let err = Driver.set_alarm().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Bus);
+        assert_eq!(
+            err.context(),
+            Some(ErrorContext::at(Operation::ConfigureAlarm, 0x07))
+        );
+        assert_eq!(
+            format!("{err}"),
+            "Underlying bus error occurred while configuring the alarm at register 0x07"
+        );
+    }
+
+    #[test]
+    fn test_impl_error_macro() {
+        #[derive(Debug)]
+        enum DriverError {
+            I2c,
+            BadTime(u8),
+            NotFound,
+        }
+
+        crate::impl_error! {
+            DriverError {
+                I2c => Bus,
+                BadTime(60..) => InvalidDateTime,
+                _ => Other,
+            }
+        }
+
+        assert_eq!(DriverError::I2c.kind(), ErrorKind::Bus);
+        assert_eq!(DriverError::BadTime(61).kind(), ErrorKind::InvalidDateTime);
+        assert_eq!(DriverError::BadTime(30).kind(), ErrorKind::Other);
+        assert_eq!(DriverError::NotFound.kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_impl_error_macro_generic_with_display() {
+        #[derive(Debug)]
+        enum DriverError<E> {
+            Bus(E),
+            Locked,
+        }
+
+        crate::impl_error! {
+            #[display]
+            DriverError<E> {
+                Bus(_) => Bus,
+                Locked => NvramWriteProtected
+            }
+        }
+
+        assert_eq!(
+            DriverError::Bus(MockRtcError::I2cError).kind(),
+            ErrorKind::Bus
+        );
+        assert_eq!(
+            DriverError::<MockRtcError>::Locked.kind(),
+            ErrorKind::NvramWriteProtected
+        );
+        assert_eq!(
+            format!("{}", DriverError::<()>::Locked),
+            "NVRAM is write protected"
+        );
+    }
+
+    #[test]
+    fn test_error_kind_names() {
+        let expected = [
+            (ErrorKind::Bus, "Bus"),
+            (ErrorKind::InvalidDateTime, "InvalidDateTime"),
+            (ErrorKind::InvalidAlarmConfig, "InvalidAlarmConfig"),
+            (
+                ErrorKind::UnsupportedSqwFrequency,
+                "UnsupportedSqwFrequency",
+            ),
+            (ErrorKind::InvalidAddress, "InvalidAddress"),
+            (ErrorKind::NvramOutOfBounds, "NvramOutOfBounds"),
+            (ErrorKind::NvramWriteProtected, "NvramWriteProtected"),
+            (ErrorKind::ClockIntegrity, "ClockIntegrity"),
+            (ErrorKind::PowerFailure, "PowerFailure"),
+            (ErrorKind::Busy, "Busy"),
+            (ErrorKind::Unsupported, "Unsupported"),
+            (ErrorKind::Other, "Other"),
+        ];
+        assert_eq!(expected.len(), ErrorKind::ALL.len());
+        for (kind, name) in expected {
+            assert_eq!(kind.as_str(), name);
+            assert_eq!(ErrorKind::from_name(name), Some(kind));
+        }
+        assert_eq!(ErrorKind::from_name("bus"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_error_kind_serde() {
+        use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+        for kind in ErrorKind::ALL {
+            assert_tokens(&kind, &[Token::Str(kind.as_str())]);
+        }
+        assert_de_tokens_error::<ErrorKind>(
+            &[Token::Str("Overheated")],
+            "unknown variant `Overheated`, expected one of `Bus`, `InvalidDateTime`, \
+             `InvalidAlarmConfig`, `UnsupportedSqwFrequency`, `InvalidAddress`, \
+             `NvramOutOfBounds`, `NvramWriteProtected`, `ClockIntegrity`, `PowerFailure`, \
+             `Busy`, `Unsupported`, `Other`",
+        );
+    }
+
+    #[test]
+    fn test_result_ext() {
+        let bus: Result<u8, MockRtcError> = Err(MockRtcError::I2cError);
+        assert_eq!(bus.kind(), Some(ErrorKind::Bus));
+        assert!(bus.is_kind(ErrorKind::Bus));
+        assert!(!bus.is_kind(ErrorKind::Other));
+
+        let locked: Result<(), MockRtcError> = Err(MockRtcError::NvramWriteProtected);
+        assert!(locked.is_kind(ErrorKind::NvramWriteProtected));
+
+        let ok: Result<u8, MockRtcError> = Ok(7);
+        assert_eq!(ok.kind(), None);
+        assert!(!ok.is_kind(ErrorKind::Bus));
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            format!("{}", Report(&MockRtcError::I2cError)),
+            "Underlying bus error occurred (I2cError)"
+        );
+        assert_eq!(
+            format!("{}", Report(&MockRtcError::NvramWriteProtected)),
+            "NVRAM is write protected (NvramWriteProtected)"
+        );
+
+        let with_context = WithContext {
+            error: MockRtcError::InvalidDateTime,
+            context: ErrorContext::new(Operation::WriteTime),
+        };
+        assert_eq!(
+            format!("{}", Report(&with_context)),
+            "Invalid datetime value provided (WithContext { error: InvalidDateTime, \
+             context: ErrorContext { operation: WriteTime, address: None } }) \
+             while writing the time"
+        );
+    }
+
     #[test]
     fn test_error_kind_equality() {
         assert_eq!(ErrorKind::Bus, ErrorKind::Bus);
@@ -162,6 +1253,52 @@ mod tests {
         assert_eq!(error.kind(), ErrorKind::Other);
     }
 
+    #[test]
+    fn test_datetime_error_maps_to_invalid_datetime() {
+        let all = [
+            DateTimeError::InvalidMonth,
+            DateTimeError::InvalidDay,
+            DateTimeError::InvalidHour,
+            DateTimeError::InvalidMinute,
+            DateTimeError::InvalidSecond,
+            DateTimeError::InvalidWeekday,
+            DateTimeError::InvalidYear,
+        ];
+        for error in all {
+            assert_eq!(ErrorKind::from(error), ErrorKind::InvalidDateTime);
+            assert_eq!(error.kind(), ErrorKind::InvalidDateTime);
+        }
+    }
+
+    #[test]
+    fn test_square_wave_error_question_mark() {
+        use crate::square_wave::SquareWaveFreq;
+
+        fn parse<E: From<SquareWaveError>>(hz: u32) -> Result<SquareWaveFreq, E> {
+            Ok(SquareWaveFreq::try_from_hz(hz)?)
+        }
+        assert_eq!(
+            parse::<ErrorKind>(0),
+            Err(ErrorKind::UnsupportedSqwFrequency)
+        );
+        assert_eq!(
+            parse::<RtcError<()>>(40_000),
+            Err(RtcError::UnsupportedSqwFrequency)
+        );
+        assert_eq!(
+            SquareWaveError::ZeroFrequency.kind(),
+            ErrorKind::UnsupportedSqwFrequency
+        );
+    }
+
+    #[test]
+    fn test_datetime_error_question_mark() {
+        fn build() -> Result<crate::datetime::DateTime, ErrorKind> {
+            Ok(crate::datetime::DateTime::new(2024, 2, 30, 0, 0, 0)?)
+        }
+        assert_eq!(build(), Err(ErrorKind::InvalidDateTime));
+    }
+
     #[test]
     fn test_error_kind_display_messages() {
         assert_eq!(
@@ -199,6 +1336,23 @@ mod tests {
             "NVRAM is write protected"
         );
 
+        assert_eq!(
+            format!("{}", ErrorKind::ClockIntegrity),
+            "Clock integrity lost, time is not trustworthy"
+        );
+
+        assert_eq!(
+            format!("{}", ErrorKind::PowerFailure),
+            "Power failure detected"
+        );
+
+        assert_eq!(format!("{}", ErrorKind::Busy), "Device is busy");
+
+        assert_eq!(
+            format!("{}", ErrorKind::Unsupported),
+            "Operation not supported by the RTC"
+        );
+
         assert_eq!(
             format!("{}", ErrorKind::Other),
             "A different error occurred. The original error may contain more information"
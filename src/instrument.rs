@@ -0,0 +1,1091 @@
+//! # Operation tracing
+//!
+//! [`Instrumented`] wraps a driver and emits a trace-level record for every
+//! operation, with the values involved and the outcome, through `log` and/or
+//! `defmt` depending on which features are enabled:
+//!
+//! ```text
+//! rtc.set_datetime 2024-08-16T12:00:00 -> Ok
+//! rtc.get_datetime -> 2024-08-16T12:00:03
+//! rtc.read_nvram offset=0 len=4 -> Err(Bus)
+//! ```
+//!
+//! Records are formatted without allocating. Failures are reported by their
+//! [`ErrorKind`], since driver error types need not be printable.
+use crate::{
+    alarm::{Alarm, AlarmCapabilities, AlarmConfig, AlarmId},
+    control::{ClockIntegrity, RtcPowerControl},
+    datetime::{Date, DateTime, DateTimeError, Time},
+    error::{Error, ErrorKind, ErrorType},
+    nvram::{NvramProtect, RtcNvram},
+    periodic::{PeriodicInterrupt, TickPeriod},
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, DutyCycle, Output32kHz, OutputPin, OutputPinConfig, SquareWave,
+        SquareWaveBatteryBacked, SquareWaveConfig, SquareWaveDuty, SquareWaveFreq, SquareWavePins,
+    },
+    timestamp::RtcTimestamp,
+};
+
+/// Emit a trace record through every enabled logging backend
+macro_rules! emit {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::trace!($($arg)*);
+        #[cfg(feature = "defmt")]
+        defmt::trace!($($arg)*);
+    }};
+}
+
+/// Argument or result of an operation, as it appears in a record
+enum Value {
+    None,
+    DateTime(DateTime),
+    Date(Date),
+    Time(Time),
+    Seconds(u64),
+    Bool(bool),
+    Freq(SquareWaveFreq),
+    Nvram { offset: u32, len: usize },
+    Alarm(AlarmId),
+    AlarmConfig(AlarmId, AlarmConfig),
+    AlarmIn(AlarmId, u32),
+    Tick(TickPeriod),
+    Duty(DutyCycle),
+    Pin(OutputPin),
+    PinFreq(OutputPin, SquareWaveFreq),
+    Fill { offset: u32, len: u32, value: u8 },
+    OutputPin(OutputPinConfig),
+    Level(DisabledOutputLevel),
+    SquareWave(SquareWaveConfig),
+}
+
+/// A frequency shown in Hz, with millihertz only for fractional values and
+/// `1/60Hz` for one pulse per minute
+struct Hz(SquareWaveFreq);
+
+impl core::fmt::Display for Hz {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0.to_millihz() {
+            _ if self.0 == SquareWaveFreq::PerMinute => write!(f, "1/60Hz"),
+            millihz if millihz.is_multiple_of(1000) => write!(f, "{}Hz", millihz / 1000),
+            millihz => write!(f, "{}.{:03}Hz", millihz / 1000, millihz % 1000),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Hz {
+    fn format(&self, f: defmt::Formatter) {
+        match self.0.to_millihz() {
+            _ if self.0 == SquareWaveFreq::PerMinute => defmt::write!(f, "1/60Hz"),
+            millihz if millihz.is_multiple_of(1000) => defmt::write!(f, "{}Hz", millihz / 1000),
+            millihz => defmt::write!(f, "{}.{:03}Hz", millihz / 1000, millihz % 1000),
+        }
+    }
+}
+
+/// An alarm match field, `*` when it matches any value
+struct Field(Option<u8>);
+
+impl core::fmt::Display for Field {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(value) => write!(f, "{value:02}"),
+            None => write!(f, "*"),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Field {
+    fn format(&self, f: defmt::Formatter) {
+        match self.0 {
+            Some(value) => defmt::write!(f, "{=u8:02}", value),
+            None => defmt::write!(f, "*"),
+        }
+    }
+}
+
+fn level_name(level: DisabledOutputLevel) -> &'static str {
+    match level {
+        DisabledOutputLevel::Low => "low",
+        DisabledOutputLevel::High => "high",
+        DisabledOutputLevel::HighImpedance => "high_z",
+    }
+}
+
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Value::None => Ok(()),
+            Value::DateTime(dt) => write!(
+                f,
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                dt.year(),
+                dt.month(),
+                dt.day_of_month(),
+                dt.hour(),
+                dt.minute(),
+                dt.second()
+            ),
+            Value::Date(d) => write!(
+                f,
+                "{:04}-{:02}-{:02}",
+                d.year(),
+                d.month(),
+                d.day_of_month()
+            ),
+            Value::Time(t) => write!(f, "{:02}:{:02}:{:02}", t.hour(), t.minute(), t.second()),
+            Value::Seconds(secs) => write!(f, "{secs}"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Freq(freq) => write!(f, "{}", Hz(*freq)),
+            Value::Nvram { offset, len } => write!(f, "offset={offset} len={len}"),
+            Value::Alarm(id) => write!(f, "id={}", id.0),
+            Value::AlarmConfig(id, config) => {
+                write!(
+                    f,
+                    "id={} at={}:{}:{}",
+                    id.0,
+                    Field(config.hour),
+                    Field(config.minute),
+                    Field(config.second)
+                )?;
+                if let Some(day) = config.day_of_month {
+                    write!(f, " day={day}")?;
+                }
+                if let Some(weekdays) = config.weekdays {
+                    write!(f, " weekdays={:#04x}", weekdays.bits())?;
+                }
+                Ok(())
+            }
+            Value::AlarmIn(id, seconds) => write!(f, "id={} in={seconds}s", id.0),
+            Value::Tick(TickPeriod::Second) => write!(f, "every_second"),
+            Value::Tick(TickPeriod::Minute) => write!(f, "every_minute"),
+            Value::Tick(TickPeriod::Custom(hz)) => write!(f, "{hz}Hz"),
+            Value::Duty(duty) => write!(f, "{duty}"),
+            Value::Pin(pin) => write!(f, "pin={}", pin.0),
+            Value::PinFreq(pin, freq) => write!(f, "pin={} {}", pin.0, Hz(*freq)),
+            Value::Fill { offset, len, value } => {
+                write!(f, "offset={offset} len={len} value={value:#04x}")
+            }
+            Value::OutputPin(OutputPinConfig::SquareWave(freq)) => {
+                write!(f, "square_wave {}", Hz(*freq))
+            }
+            Value::OutputPin(OutputPinConfig::AlarmInterrupt) => write!(f, "alarm_interrupt"),
+            Value::OutputPin(OutputPinConfig::Disabled { level }) => {
+                write!(f, "disabled {}", level_name(*level))
+            }
+            Value::Level(level) => write!(f, "{}", level_name(*level)),
+            Value::SquareWave(config) => write!(
+                f,
+                "{} {} idle {}",
+                if config.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                Hz(config.freq),
+                level_name(config.disabled_level)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Value {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Value::None => {}
+            Value::DateTime(dt) => defmt::write!(
+                f,
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                dt.year(),
+                dt.month(),
+                dt.day_of_month(),
+                dt.hour(),
+                dt.minute(),
+                dt.second()
+            ),
+            Value::Date(d) => defmt::write!(
+                f,
+                "{:04}-{:02}-{:02}",
+                d.year(),
+                d.month(),
+                d.day_of_month()
+            ),
+            Value::Time(t) => {
+                defmt::write!(f, "{:02}:{:02}:{:02}", t.hour(), t.minute(), t.second())
+            }
+            Value::Seconds(secs) => defmt::write!(f, "{}", secs),
+            Value::Bool(b) => defmt::write!(f, "{}", b),
+            Value::Freq(freq) => defmt::write!(f, "{}", Hz(*freq)),
+            Value::Nvram { offset, len } => defmt::write!(f, "offset={} len={}", offset, len),
+            Value::Alarm(id) => defmt::write!(f, "id={}", id.0),
+            Value::AlarmConfig(id, config) => {
+                defmt::write!(
+                    f,
+                    "id={} at={}:{}:{}",
+                    id.0,
+                    Field(config.hour),
+                    Field(config.minute),
+                    Field(config.second)
+                );
+                if let Some(day) = config.day_of_month {
+                    defmt::write!(f, " day={}", day);
+                }
+                if let Some(weekdays) = config.weekdays {
+                    defmt::write!(f, " weekdays={=u8:#04x}", weekdays.bits());
+                }
+            }
+            Value::AlarmIn(id, seconds) => defmt::write!(f, "id={} in={}s", id.0, seconds),
+            Value::Tick(TickPeriod::Second) => defmt::write!(f, "every_second"),
+            Value::Tick(TickPeriod::Minute) => defmt::write!(f, "every_minute"),
+            Value::Tick(TickPeriod::Custom(hz)) => defmt::write!(f, "{}Hz", hz),
+            Value::Duty(duty) => defmt::write!(f, "{}%", duty.percent()),
+            Value::Pin(pin) => defmt::write!(f, "pin={}", pin.0),
+            Value::PinFreq(pin, freq) => defmt::write!(f, "pin={} {}", pin.0, Hz(*freq)),
+            Value::Fill { offset, len, value } => {
+                defmt::write!(f, "offset={} len={} value={=u8:#04x}", offset, len, value)
+            }
+            Value::OutputPin(OutputPinConfig::SquareWave(freq)) => {
+                defmt::write!(f, "square_wave {}", Hz(*freq))
+            }
+            Value::OutputPin(OutputPinConfig::AlarmInterrupt) => {
+                defmt::write!(f, "alarm_interrupt")
+            }
+            Value::OutputPin(OutputPinConfig::Disabled { level }) => {
+                defmt::write!(f, "disabled {}", level_name(*level))
+            }
+            Value::Level(level) => defmt::write!(f, "{}", level_name(*level)),
+            Value::SquareWave(config) => defmt::write!(
+                f,
+                "{} {} idle {}",
+                if config.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                Hz(config.freq),
+                level_name(config.disabled_level)
+            ),
+        }
+    }
+}
+
+/// Outcome of an operation, as it appears in a record
+enum Outcome {
+    Ok(Value),
+    Err(ErrorKind),
+}
+
+impl core::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Outcome::Ok(Value::None) => write!(f, "Ok"),
+            Outcome::Ok(value) => write!(f, "{value}"),
+            Outcome::Err(kind) => write!(f, "Err({})", kind.as_str()),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Outcome {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Outcome::Ok(Value::None) => defmt::write!(f, "Ok"),
+            Outcome::Ok(value) => defmt::write!(f, "{}", value),
+            Outcome::Err(kind) => defmt::write!(f, "Err({})", kind.as_str()),
+        }
+    }
+}
+
+/// Emit the record for one operation and pass its result through
+fn record<T, E: Error>(
+    op: &'static str,
+    input: Value,
+    result: Result<T, E>,
+    output: impl FnOnce(&T) -> Value,
+) -> Result<T, E> {
+    let outcome = match &result {
+        Ok(value) => Outcome::Ok(output(value)),
+        Err(e) => Outcome::Err(e.kind()),
+    };
+    match input {
+        Value::None => emit!("rtc.{} -> {}", op, outcome),
+        input => emit!("rtc.{} {} -> {}", op, input, outcome),
+    }
+    result
+}
+
+/// Wrapper that traces every operation of the inner driver
+///
+/// Implements every trait the inner driver implements, delegating each call
+/// unchanged. See the [module documentation](self) for the record format.
+pub struct Instrumented<R> {
+    rtc: R,
+}
+
+impl<R> Instrumented<R> {
+    /// Wrap a driver
+    pub fn new(rtc: R) -> Self {
+        Self { rtc }
+    }
+
+    /// Access the wrapped driver without tracing
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.rtc
+    }
+
+    /// Release the wrapped driver
+    pub fn into_inner(self) -> R {
+        self.rtc
+    }
+}
+
+impl<R: ErrorType> ErrorType for Instrumented<R> {
+    type Error = R::Error;
+}
+
+impl<R: Rtc> Rtc for Instrumented<R> {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let result = self.rtc.get_datetime();
+        record("get_datetime", Value::None, result, |dt| {
+            Value::DateTime(*dt)
+        })
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        let result = self.rtc.set_datetime(datetime);
+        record("set_datetime", Value::DateTime(*datetime), result, |_| {
+            Value::None
+        })
+    }
+
+    fn get_time(&mut self) -> Result<Time, Self::Error> {
+        let result = self.rtc.get_time();
+        record("get_time", Value::None, result, |t| Value::Time(*t))
+    }
+
+    fn get_date(&mut self) -> Result<Date, Self::Error> {
+        let result = self.rtc.get_date();
+        record("get_date", Value::None, result, |d| Value::Date(*d))
+    }
+
+    fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+        let result = self.rtc.set_time(time);
+        record("set_time", Value::Time(*time), result, |_| Value::None)
+    }
+
+    fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+        let result = self.rtc.set_date(date);
+        record("set_date", Value::Date(*date), result, |_| Value::None)
+    }
+}
+
+impl<R: RtcNvram> RtcNvram for Instrumented<R> {
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        let len = buffer.len();
+        let result = self.rtc.read_nvram(offset, buffer);
+        record("read_nvram", Value::Nvram { offset, len }, result, |_| {
+            Value::None
+        })
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        let len = data.len();
+        let result = self.rtc.write_nvram(offset, data);
+        record("write_nvram", Value::Nvram { offset, len }, result, |_| {
+            Value::None
+        })
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.rtc.nvram_size()
+    }
+
+    fn fill_nvram(&mut self, offset: u32, len: u32, value: u8) -> Result<(), Self::Error> {
+        let result = self.rtc.fill_nvram(offset, len, value);
+        let input = Value::Fill { offset, len, value };
+        record("fill_nvram", input, result, |_| Value::None)
+    }
+
+    fn erase_nvram(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.erase_nvram();
+        record("erase_nvram", Value::None, result, |_| Value::None)
+    }
+}
+
+impl<R: NvramProtect> NvramProtect for Instrumented<R> {
+    fn nvram_write_protected(&mut self) -> Result<bool, Self::Error> {
+        let result = self.rtc.nvram_write_protected();
+        record("nvram_write_protected", Value::None, result, |b| {
+            Value::Bool(*b)
+        })
+    }
+
+    fn set_nvram_write_protect(&mut self, protect: bool) -> Result<(), Self::Error> {
+        let result = self.rtc.set_nvram_write_protect(protect);
+        record(
+            "set_nvram_write_protect",
+            Value::Bool(protect),
+            result,
+            |_| Value::None,
+        )
+    }
+}
+
+impl<R: RtcPowerControl> RtcPowerControl for Instrumented<R> {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.start_clock();
+        record("start_clock", Value::None, result, |_| Value::None)
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.halt_clock();
+        record("halt_clock", Value::None, result, |_| Value::None)
+    }
+}
+
+impl<R: ClockIntegrity> ClockIntegrity for Instrumented<R> {
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+        let result = self.rtc.datetime_valid();
+        record("datetime_valid", Value::None, result, |b| Value::Bool(*b))
+    }
+
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.clear_integrity_flag();
+        record("clear_integrity_flag", Value::None, result, |_| Value::None)
+    }
+}
+
+impl<R: SquareWave> SquareWave for Instrumented<R> {
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        let result = self.rtc.configure_output_pin(config);
+        record(
+            "configure_output_pin",
+            Value::OutputPin(config),
+            result,
+            |_| Value::None,
+        )
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        let result = self.rtc.start_square_wave(freq);
+        record("start_square_wave", Value::Freq(freq), result, |_| {
+            Value::None
+        })
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.enable_square_wave();
+        record("enable_square_wave", Value::None, result, |_| Value::None)
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_square_wave();
+        record("disable_square_wave", Value::None, result, |_| Value::None)
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        let result = self.rtc.set_square_wave_frequency(freq);
+        record(
+            "set_square_wave_frequency",
+            Value::Freq(freq),
+            result,
+            |_| Value::None,
+        )
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        let result = self.rtc.set_disabled_output_level(level);
+        record(
+            "set_disabled_output_level",
+            Value::Level(level),
+            result,
+            |_| Value::None,
+        )
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        let result = self.rtc.square_wave_config();
+        record("square_wave_config", Value::None, result, |config| {
+            Value::SquareWave(*config)
+        })
+    }
+
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        let result = self.rtc.current_pin_mode();
+        record("current_pin_mode", Value::None, result, |mode| {
+            Value::OutputPin(*mode)
+        })
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        self.rtc.supported_square_wave_frequencies()
+    }
+}
+
+impl<R: SquareWaveDuty> SquareWaveDuty for Instrumented<R> {
+    fn set_duty_cycle(&mut self, duty: DutyCycle) -> Result<(), Self::Error> {
+        let result = self.rtc.set_duty_cycle(duty);
+        record("set_duty_cycle", Value::Duty(duty), result, |_| Value::None)
+    }
+
+    fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error> {
+        let result = self.rtc.duty_cycle();
+        record("duty_cycle", Value::None, result, |duty| Value::Duty(*duty))
+    }
+}
+
+impl<R: SquareWaveBatteryBacked> SquareWaveBatteryBacked for Instrumented<R> {
+    fn enable_square_wave_on_battery(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        let result = self.rtc.enable_square_wave_on_battery(enabled);
+        record(
+            "enable_square_wave_on_battery",
+            Value::Bool(enabled),
+            result,
+            |_| Value::None,
+        )
+    }
+
+    fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error> {
+        let result = self.rtc.is_square_wave_on_battery_enabled();
+        record(
+            "is_square_wave_on_battery_enabled",
+            Value::None,
+            result,
+            |b| Value::Bool(*b),
+        )
+    }
+}
+
+impl<R: SquareWavePins> SquareWavePins for Instrumented<R> {
+    fn output_pin_count(&self) -> u8 {
+        self.rtc.output_pin_count()
+    }
+
+    fn start_square_wave_on(
+        &mut self,
+        pin: OutputPin,
+        freq: SquareWaveFreq,
+    ) -> Result<(), Self::Error> {
+        let result = self.rtc.start_square_wave_on(pin, freq);
+        record(
+            "start_square_wave_on",
+            Value::PinFreq(pin, freq),
+            result,
+            |_| Value::None,
+        )
+    }
+
+    fn disable_square_wave_on(&mut self, pin: OutputPin) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_square_wave_on(pin);
+        record("disable_square_wave_on", Value::Pin(pin), result, |_| {
+            Value::None
+        })
+    }
+}
+
+impl<R: Output32kHz> Output32kHz for Instrumented<R> {
+    fn enable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.enable_32khz_output();
+        record("enable_32khz_output", Value::None, result, |_| Value::None)
+    }
+
+    fn disable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_32khz_output();
+        record("disable_32khz_output", Value::None, result, |_| Value::None)
+    }
+
+    fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error> {
+        let result = self.rtc.is_32khz_output_enabled();
+        record("is_32khz_output_enabled", Value::None, result, |b| {
+            Value::Bool(*b)
+        })
+    }
+}
+
+impl<R: RtcTimestamp> RtcTimestamp for Instrumented<R> {
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        let result = self.rtc.get_timestamp();
+        record("get_timestamp", Value::None, result, |secs| {
+            Value::Seconds(*secs)
+        })
+    }
+
+    fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+        let result = self.rtc.set_timestamp(secs);
+        record("set_timestamp", Value::Seconds(secs), result, |_| {
+            Value::None
+        })
+    }
+}
+
+impl<R: Alarm> Alarm for Instrumented<R> {
+    fn alarm_count(&self) -> u8 {
+        self.rtc.alarm_count()
+    }
+
+    fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+        self.rtc.alarm_capabilities(id)
+    }
+
+    fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+        let result = self.rtc.set_alarm(id, config);
+        record("set_alarm", Value::AlarmConfig(id, *config), result, |_| {
+            Value::None
+        })
+    }
+
+    fn disable_alarm(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_alarm(id);
+        record("disable_alarm", Value::Alarm(id), result, |_| Value::None)
+    }
+
+    fn alarm_triggered(&mut self, id: AlarmId) -> Result<bool, Self::Error> {
+        let result = self.rtc.alarm_triggered(id);
+        record("alarm_triggered", Value::Alarm(id), result, |b| {
+            Value::Bool(*b)
+        })
+    }
+
+    fn clear_alarm_flag(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        let result = self.rtc.clear_alarm_flag(id);
+        record("clear_alarm_flag", Value::Alarm(id), result, |_| {
+            Value::None
+        })
+    }
+
+    fn enable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        let result = self.rtc.enable_alarm_interrupt(id);
+        record("enable_alarm_interrupt", Value::Alarm(id), result, |_| {
+            Value::None
+        })
+    }
+
+    fn disable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_alarm_interrupt(id);
+        record("disable_alarm_interrupt", Value::Alarm(id), result, |_| {
+            Value::None
+        })
+    }
+
+    fn disable_all_alarms(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_all_alarms();
+        record("disable_all_alarms", Value::None, result, |_| Value::None)
+    }
+
+    fn set_alarm_in_seconds(&mut self, id: AlarmId, seconds: u32) -> Result<DateTime, Self::Error>
+    where
+        Self::Error: From<DateTimeError>,
+    {
+        let result = self.rtc.set_alarm_in_seconds(id, seconds);
+        record(
+            "set_alarm_in_seconds",
+            Value::AlarmIn(id, seconds),
+            result,
+            |dt| Value::DateTime(*dt),
+        )
+    }
+}
+
+impl<R: PeriodicInterrupt> PeriodicInterrupt for Instrumented<R> {
+    fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+        let result = self.rtc.enable_tick(period);
+        record("enable_tick", Value::Tick(period), result, |_| Value::None)
+    }
+
+    fn disable_tick(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_tick();
+        record("disable_tick", Value::None, result, |_| Value::None)
+    }
+
+    fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+        let result = self.rtc.tick_flag();
+        record("tick_flag", Value::None, result, |b| Value::Bool(*b))
+    }
+
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.clear_tick_flag();
+        record("clear_tick_flag", Value::None, result, |_| Value::None)
+    }
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, string::String, sync::Once, vec::Vec};
+
+    std::thread_local! {
+        static CAPTURED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Collects records per thread so parallel tests do not interfere
+    struct CaptureLogger;
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Trace
+        }
+
+        fn log(&self, record: &log::Record) {
+            CAPTURED.with(|lines| lines.borrow_mut().push(record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn captured() -> Vec<String> {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&CaptureLogger).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        CAPTURED.with(|lines| lines.borrow_mut().drain(..).collect())
+    }
+
+    struct MockRtc {
+        datetime: DateTime,
+        nvram: [u8; 8],
+    }
+
+    impl ErrorType for MockRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MockRtc {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(self.datetime)
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.datetime = *datetime;
+            Ok(())
+        }
+    }
+
+    impl RtcNvram for MockRtc {
+        fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+            let start = offset as usize;
+            let src = self
+                .nvram
+                .get(start..start + buffer.len())
+                .ok_or(ErrorKind::NvramOutOfBounds)?;
+            buffer.copy_from_slice(src);
+            Ok(())
+        }
+
+        fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+            crate::nvram::check_bounds(offset, data.len(), self.nvram_size())?;
+            Err(ErrorKind::Bus)
+        }
+
+        fn nvram_size(&self) -> u32 {
+            self.nvram.len() as u32
+        }
+    }
+
+    impl Alarm for MockRtc {
+        fn set_alarm(&mut self, _: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+            Ok(config.validate()?)
+        }
+
+        fn disable_alarm(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn alarm_triggered(&mut self, _: AlarmId) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+
+        fn clear_alarm_flag(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn enable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn disable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+            Err(ErrorKind::Bus)
+        }
+    }
+
+    impl PeriodicInterrupt for MockRtc {
+        fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+            match period {
+                TickPeriod::Custom(4096) => Err(ErrorKind::Unsupported),
+                _ => Ok(()),
+            }
+        }
+
+        fn disable_tick(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+            Ok(false)
+        }
+
+        fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn mock() -> Instrumented<MockRtc> {
+        Instrumented::new(MockRtc {
+            datetime: DateTime::MIN,
+            nvram: [0; 8],
+        })
+    }
+
+    #[test]
+    fn test_datetime_records() {
+        captured();
+        let mut rtc = mock();
+        let dt = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        rtc.set_datetime(&dt).unwrap();
+        assert_eq!(rtc.get_datetime().unwrap(), dt);
+        rtc.get_time().unwrap();
+        assert_eq!(
+            captured(),
+            [
+                "rtc.set_datetime 2024-08-16T12:00:00 -> Ok",
+                "rtc.get_datetime -> 2024-08-16T12:00:00",
+                "rtc.get_time -> 12:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failures_log_error_kind() {
+        captured();
+        let mut rtc = mock();
+        let mut buffer = [0; 4];
+        assert_eq!(
+            rtc.read_nvram(6, &mut buffer),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(rtc.write_nvram(0, &[1, 2]), Err(ErrorKind::Bus));
+        rtc.read_nvram(0, &mut buffer).unwrap();
+        assert_eq!(rtc.nvram_size(), 8);
+        assert_eq!(
+            captured(),
+            [
+                "rtc.read_nvram offset=6 len=4 -> Err(NvramOutOfBounds)",
+                "rtc.write_nvram offset=0 len=2 -> Err(Bus)",
+                "rtc.read_nvram offset=0 len=4 -> Ok",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_traced_as_one_operation() {
+        captured();
+        let mut rtc = mock();
+        assert_eq!(rtc.fill_nvram(4, 8, 0xFF), Err(ErrorKind::NvramOutOfBounds));
+        assert_eq!(rtc.erase_nvram(), Err(ErrorKind::Bus));
+        assert_eq!(
+            captured(),
+            [
+                "rtc.fill_nvram offset=4 len=8 value=0xff -> Err(NvramOutOfBounds)",
+                "rtc.erase_nvram -> Err(Bus)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alarm_records() {
+        use crate::datetime::Weekday;
+
+        captured();
+        let mut rtc = mock();
+        let id = AlarmId::ALARM1;
+        rtc.set_alarm(id, &AlarmConfig::daily_at(7, 30, 0)).unwrap();
+        rtc.set_alarm(AlarmId(1), &AlarmConfig::every_minute_at(5))
+            .unwrap();
+        rtc.set_alarm(id, &AlarmConfig::monthly_at(15, 6, 0, 0))
+            .unwrap();
+        rtc.set_alarm(id, &AlarmConfig::weekly_at(Weekday::Monday, 8, 0, 0))
+            .unwrap();
+        assert!(rtc.alarm_triggered(id).unwrap());
+        rtc.clear_alarm_flag(id).unwrap();
+        assert_eq!(rtc.disable_alarm_interrupt(id), Err(ErrorKind::Bus));
+        rtc.set_alarm_in_seconds(id, 90).unwrap();
+        assert_eq!(rtc.alarm_count(), 1);
+        assert_eq!(
+            captured(),
+            [
+                "rtc.set_alarm id=0 at=07:30:00 -> Ok",
+                "rtc.set_alarm id=1 at=*:*:05 -> Ok",
+                "rtc.set_alarm id=0 at=06:00:00 day=15 -> Ok",
+                "rtc.set_alarm id=0 at=08:00:00 weekdays=0x02 -> Ok",
+                "rtc.alarm_triggered id=0 -> true",
+                "rtc.clear_alarm_flag id=0 -> Ok",
+                "rtc.disable_alarm_interrupt id=0 -> Err(Bus)",
+                "rtc.set_alarm_in_seconds id=0 in=90s -> 1970-01-01T00:01:30",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tick_records() {
+        captured();
+        let mut rtc = mock();
+        rtc.enable_tick(TickPeriod::Minute).unwrap();
+        rtc.enable_tick(TickPeriod::Second).unwrap();
+        assert_eq!(
+            rtc.enable_tick(TickPeriod::Custom(4096)),
+            Err(ErrorKind::Unsupported)
+        );
+        assert!(!rtc.tick_flag().unwrap());
+        rtc.disable_tick().unwrap();
+        assert_eq!(
+            captured(),
+            [
+                "rtc.enable_tick every_minute -> Ok",
+                "rtc.enable_tick every_second -> Ok",
+                "rtc.enable_tick 4096Hz -> Err(Unsupported)",
+                "rtc.tick_flag -> false",
+                "rtc.disable_tick -> Ok",
+            ]
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_duty_cycle_records() {
+        use crate::mock::Transaction;
+
+        captured();
+        let quarter = DutyCycle::new(25).unwrap();
+        let mut rtc = Instrumented::new(crate::mock::MockRtc::new(&[
+            Transaction::set_duty_cycle(quarter),
+            Transaction::duty_cycle(quarter),
+            Transaction::set_duty_cycle(DutyCycle::HALF).with_error(ErrorKind::Unsupported),
+        ]));
+        rtc.set_duty_cycle(quarter).unwrap();
+        assert_eq!(rtc.duty_cycle(), Ok(quarter));
+        assert_eq!(
+            rtc.set_duty_cycle(DutyCycle::HALF),
+            Err(ErrorKind::Unsupported)
+        );
+        assert_eq!(
+            captured(),
+            [
+                "rtc.set_duty_cycle 25% -> Ok",
+                "rtc.duty_cycle -> 25%",
+                "rtc.set_duty_cycle 50% -> Err(Unsupported)",
+            ]
+        );
+        rtc.into_inner().done();
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_battery_backed_records() {
+        use crate::mock::Transaction;
+
+        captured();
+        let mut rtc = Instrumented::new(crate::mock::MockRtc::new(&[
+            Transaction::enable_square_wave_on_battery(true),
+            Transaction::is_square_wave_on_battery_enabled(true),
+        ]));
+        rtc.enable_square_wave_on_battery(true).unwrap();
+        assert_eq!(rtc.is_square_wave_on_battery_enabled(), Ok(true));
+        assert_eq!(
+            captured(),
+            [
+                "rtc.enable_square_wave_on_battery true -> Ok",
+                "rtc.is_square_wave_on_battery_enabled -> true",
+            ]
+        );
+        rtc.into_inner().done();
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_32khz_output_records() {
+        use crate::mock::Transaction;
+
+        captured();
+        let mut rtc = Instrumented::new(crate::mock::MockRtc::new(&[
+            Transaction::is_32khz_output_enabled(false),
+            Transaction::enable_32khz_output(),
+            Transaction::disable_32khz_output().with_error(ErrorKind::Bus),
+        ]));
+        assert_eq!(rtc.is_32khz_output_enabled(), Ok(false));
+        rtc.enable_32khz_output().unwrap();
+        assert_eq!(rtc.disable_32khz_output(), Err(ErrorKind::Bus));
+        assert_eq!(
+            captured(),
+            [
+                "rtc.is_32khz_output_enabled -> false",
+                "rtc.enable_32khz_output -> Ok",
+                "rtc.disable_32khz_output -> Err(Bus)",
+            ]
+        );
+        rtc.into_inner().done();
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_output_pin_records() {
+        use crate::mock::Transaction;
+
+        captured();
+        let mut inner = crate::mock::MockRtc::new(&[
+            Transaction::start_square_wave_on(OutputPin::SECONDARY, SquareWaveFreq::Hz1024),
+            Transaction::disable_square_wave_on(OutputPin(2)).with_error(ErrorKind::InvalidAddress),
+        ]);
+        inner.set_output_pin_count(2);
+        let mut rtc = Instrumented::new(inner);
+        assert_eq!(rtc.output_pin_count(), 2);
+        rtc.start_square_wave_on(OutputPin::SECONDARY, SquareWaveFreq::Hz1024)
+            .unwrap();
+        assert_eq!(
+            rtc.disable_square_wave_on(OutputPin(2)),
+            Err(ErrorKind::InvalidAddress)
+        );
+        assert_eq!(
+            captured(),
+            [
+                "rtc.start_square_wave_on pin=1 1024Hz -> Ok",
+                "rtc.disable_square_wave_on pin=2 -> Err(InvalidAddress)",
+            ]
+        );
+        rtc.into_inner().done();
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_nvram_protect_records() {
+        use crate::mock::Transaction;
+
+        captured();
+        let mut rtc = Instrumented::new(crate::mock::MockRtc::new(&[
+            Transaction::nvram_write_protected(true),
+            Transaction::set_nvram_write_protect(false),
+        ]));
+        assert_eq!(rtc.nvram_write_protected(), Ok(true));
+        rtc.set_nvram_write_protect(false).unwrap();
+        assert_eq!(
+            captured(),
+            [
+                "rtc.nvram_write_protected -> true",
+                "rtc.set_nvram_write_protect false -> Ok",
+            ]
+        );
+        rtc.into_inner().done();
+    }
+
+    #[test]
+    fn test_frequencies_show_millihertz_only_when_fractional() {
+        assert_eq!(format!("{}", Hz(SquareWaveFreq::Hz1024)), "1024Hz");
+        assert_eq!(format!("{}", Hz(SquareWaveFreq::MilliHz(2000))), "2Hz");
+        assert_eq!(format!("{}", Hz(SquareWaveFreq::MilliHz(1500))), "1.500Hz");
+        assert_eq!(format!("{}", Hz(SquareWaveFreq::MilliHz(17))), "0.017Hz");
+        assert_eq!(format!("{}", Hz(SquareWaveFreq::PerMinute)), "1/60Hz");
+    }
+}
@@ -4,15 +4,40 @@
 //! in a platform-agnostic way, following the embedded-hal design patterns.
 //!
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![deny(unsafe_code)]
 #![warn(missing_docs)]
 
+#[cfg(any(test, feature = "alloc"))]
+extern crate alloc;
+
 pub mod alarm;
 pub mod bcd;
+pub mod calibration;
+#[cfg(any(feature = "fugit", feature = "embedded-time"))]
+pub mod clock;
 pub mod control;
 pub mod datetime;
+#[cfg(feature = "embedded-hal")]
+pub mod delay;
 pub mod error;
+#[cfg(any(feature = "log", feature = "defmt"))]
+pub mod instrument;
+#[cfg(all(feature = "linux", target_os = "linux"))]
+pub mod linux;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod nvram;
+pub mod periodic;
+pub mod prelude;
+pub mod retry;
 pub mod rtc;
+#[cfg(feature = "serde")]
+mod serde_names;
+pub mod shared;
 pub mod square_wave;
+#[cfg(feature = "std")]
+pub mod system;
+#[cfg(any(test, feature = "mock"))]
+pub mod testing;
+pub mod timestamp;
@@ -0,0 +1,322 @@
+//! # Linux kernel RTC
+//!
+//! [`LinuxRtc`] implements [`Rtc`] on a Linux RTC character device such as
+//! `/dev/rtc0`, through the `RTC_RD_TIME` and `RTC_SET_TIME` ioctls. This lets
+//! application code written against the RTC traits run unchanged on
+//! Raspberry-Pi-class gateways, using whatever chip the kernel drives.
+//!
+//! The kernel RTC holds UTC by convention. Opening the device usually needs
+//! root or membership in a group with access to it; setting the time needs
+//! `CAP_SYS_TIME`.
+//!
+//! Enabled with the `linux` feature on Linux targets.
+//!
+//! ```no_run
+//! use rtc_hal::linux::LinuxRtc;
+//! use rtc_hal::rtc::Rtc;
+//!
+//! let mut rtc = LinuxRtc::open()?;
+//! let now = rtc.get_datetime()?;
+//! # Ok::<(), rtc_hal::linux::LinuxRtcError>(())
+//! ```
+//!
+//! ## Unsafe code
+//!
+//! The crate denies `unsafe_code`. The two ioctl calls in this module are the
+//! only exception: there is no safe wrapper for the RTC ioctls, and each call
+//! passes a valid descriptor and a pointer to a live, correctly sized
+//! `struct rtc_time`.
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::fd::AsRawFd;
+use std::path::Path;
+
+use crate::datetime::{DateTime, days_in_month};
+use crate::error::{Error, ErrorKind, ErrorType};
+use crate::rtc::Rtc;
+
+/// Default RTC device
+pub const DEFAULT_PATH: &str = "/dev/rtc0";
+
+/// `struct rtc_time` from `<linux/rtc.h>`
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct RtcTime {
+    tm_sec: libc::c_int,
+    tm_min: libc::c_int,
+    tm_hour: libc::c_int,
+    tm_mday: libc::c_int,
+    /// Months since January (0-11)
+    tm_mon: libc::c_int,
+    /// Years since 1900
+    tm_year: libc::c_int,
+    /// Days since Sunday (0-6), ignored by the kernel
+    tm_wday: libc::c_int,
+    /// Days since January 1 (0-365), ignored by the kernel
+    tm_yday: libc::c_int,
+    tm_isdst: libc::c_int,
+}
+
+// ioctl request encoding, as in <asm-generic/ioctl.h> and its overrides
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips64",
+    target_arch = "powerpc",
+    target_arch = "powerpc64",
+    target_arch = "sparc64"
+))]
+mod ioc {
+    pub const WRITE: u32 = 4;
+    pub const READ: u32 = 2;
+    pub const SIZEBITS: u32 = 13;
+}
+#[cfg(not(any(
+    target_arch = "mips",
+    target_arch = "mips64",
+    target_arch = "powerpc",
+    target_arch = "powerpc64",
+    target_arch = "sparc64"
+)))]
+mod ioc {
+    pub const WRITE: u32 = 1;
+    pub const READ: u32 = 2;
+    pub const SIZEBITS: u32 = 14;
+}
+
+const fn request(dir: u32, nr: u32) -> u32 {
+    let size = core::mem::size_of::<RtcTime>() as u32;
+    (dir << (16 + ioc::SIZEBITS)) | (size << 16) | ((b'p' as u32) << 8) | nr
+}
+
+/// `_IOR('p', 0x09, struct rtc_time)`
+const RTC_RD_TIME: u32 = request(ioc::READ, 0x09);
+/// `_IOW('p', 0x0a, struct rtc_time)`
+const RTC_SET_TIME: u32 = request(ioc::WRITE, 0x0a);
+
+impl RtcTime {
+    fn to_datetime(self) -> Result<DateTime, ErrorKind> {
+        let field =
+            |value: libc::c_int| u8::try_from(value).map_err(|_| ErrorKind::InvalidDateTime);
+        let year = self
+            .tm_year
+            .checked_add(1900)
+            .and_then(|year| u16::try_from(year).ok())
+            .ok_or(ErrorKind::InvalidDateTime)?;
+        Ok(DateTime::new(
+            year,
+            field(self.tm_mon)? + 1,
+            field(self.tm_mday)?,
+            field(self.tm_hour)?,
+            field(self.tm_min)?,
+            field(self.tm_sec)?,
+        )?)
+    }
+
+    fn from_datetime(datetime: &DateTime) -> Self {
+        let yday = (1..datetime.month())
+            .map(|month| days_in_month(datetime.year(), month) as libc::c_int)
+            .sum::<libc::c_int>()
+            + datetime.day_of_month() as libc::c_int
+            - 1;
+        Self {
+            tm_sec: datetime.second().into(),
+            tm_min: datetime.minute().into(),
+            tm_hour: datetime.hour().into(),
+            tm_mday: datetime.day_of_month().into(),
+            tm_mon: (datetime.month() - 1).into(),
+            tm_year: libc::c_int::from(datetime.year()) - 1900,
+            tm_wday: (datetime.weekday().to_number() - 1).into(),
+            tm_yday: yday,
+            tm_isdst: 0,
+        }
+    }
+}
+
+/// Error from a [`LinuxRtc`]: the underlying I/O error
+#[derive(Debug)]
+pub struct LinuxRtcError(pub io::Error);
+
+impl Error for LinuxRtcError {
+    fn kind(&self) -> ErrorKind {
+        match self.0.raw_os_error() {
+            Some(libc::EIO) => ErrorKind::Bus,
+            Some(libc::EBUSY) => ErrorKind::Busy,
+            // The kernel rejects times the chip cannot hold with EINVAL
+            Some(libc::EINVAL) | Some(libc::ERANGE) => ErrorKind::InvalidDateTime,
+            Some(libc::ENOTTY) | Some(libc::EOPNOTSUPP) => ErrorKind::Unsupported,
+            _ => match self.0.kind() {
+                io::ErrorKind::InvalidData => ErrorKind::InvalidDateTime,
+                _ => ErrorKind::Other,
+            },
+        }
+    }
+}
+
+impl From<io::Error> for LinuxRtcError {
+    fn from(error: io::Error) -> Self {
+        Self(error)
+    }
+}
+
+impl From<ErrorKind> for LinuxRtcError {
+    fn from(kind: ErrorKind) -> Self {
+        Self(io::Error::new(io::ErrorKind::InvalidData, kind.as_str()))
+    }
+}
+
+impl core::fmt::Display for LinuxRtcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for LinuxRtcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// RTC backed by a Linux RTC device
+#[derive(Debug)]
+pub struct LinuxRtc {
+    device: File,
+}
+
+impl LinuxRtc {
+    /// Open [`DEFAULT_PATH`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device cannot be opened.
+    pub fn open() -> Result<Self, LinuxRtcError> {
+        Self::open_path(DEFAULT_PATH)
+    }
+
+    /// Open the RTC device at `path`, e.g. `/dev/rtc1`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device cannot be opened.
+    pub fn open_path(path: impl AsRef<Path>) -> Result<Self, LinuxRtcError> {
+        let device = OpenOptions::new().read(true).open(path)?;
+        Ok(Self { device })
+    }
+
+    /// Release the device file
+    pub fn into_inner(self) -> File {
+        self.device
+    }
+}
+
+impl ErrorType for LinuxRtc {
+    type Error = LinuxRtcError;
+}
+
+impl Rtc for LinuxRtc {
+    #[allow(unsafe_code)]
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let mut time = RtcTime::default();
+        // SAFETY: the descriptor is open for the lifetime of `self.device`, and
+        // RTC_RD_TIME writes exactly one `struct rtc_time` to the pointer.
+        let ret = unsafe {
+            libc::ioctl(
+                self.device.as_raw_fd(),
+                RTC_RD_TIME as _,
+                &mut time as *mut RtcTime,
+            )
+        };
+        if ret < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(time.to_datetime()?)
+    }
+
+    #[allow(unsafe_code)]
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        let time = RtcTime::from_datetime(datetime);
+        // SAFETY: the descriptor is open for the lifetime of `self.device`, and
+        // RTC_SET_TIME reads exactly one `struct rtc_time` from the pointer.
+        let ret = unsafe {
+            libc::ioctl(
+                self.device.as_raw_fd(),
+                RTC_SET_TIME as _,
+                &time as *const RtcTime,
+            )
+        };
+        if ret < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_codes() {
+        // Values from <linux/rtc.h> on x86, ARM and RISC-V
+        #[cfg(any(
+            target_arch = "x86_64",
+            target_arch = "aarch64",
+            target_arch = "arm",
+            target_arch = "riscv64"
+        ))]
+        {
+            assert_eq!(RTC_RD_TIME, 0x8024_7009);
+            assert_eq!(RTC_SET_TIME, 0x4024_700a);
+        }
+        assert_eq!(core::mem::size_of::<RtcTime>(), 36);
+    }
+
+    #[test]
+    fn test_rtc_time_round_trip() {
+        let dt = DateTime::new(2024, 3, 1, 6, 30, 15).unwrap();
+        let time = RtcTime::from_datetime(&dt);
+        assert_eq!(
+            time,
+            RtcTime {
+                tm_sec: 15,
+                tm_min: 30,
+                tm_hour: 6,
+                tm_mday: 1,
+                tm_mon: 2,
+                tm_year: 124,
+                tm_wday: 5,
+                tm_yday: 60,
+                tm_isdst: 0,
+            }
+        );
+        assert_eq!(time.to_datetime(), Ok(dt));
+    }
+
+    #[test]
+    fn test_invalid_rtc_time() {
+        let time = RtcTime {
+            tm_mon: 12,
+            tm_mday: 1,
+            tm_year: 124,
+            ..RtcTime::default()
+        };
+        assert_eq!(time.to_datetime(), Err(ErrorKind::InvalidDateTime));
+        let time = RtcTime {
+            tm_mday: 1,
+            tm_year: -1,
+            ..RtcTime::default()
+        };
+        assert_eq!(time.to_datetime(), Err(ErrorKind::InvalidDateTime));
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let kind = |errno| LinuxRtcError(io::Error::from_raw_os_error(errno)).kind();
+        assert_eq!(kind(libc::EIO), ErrorKind::Bus);
+        assert_eq!(kind(libc::EINVAL), ErrorKind::InvalidDateTime);
+        assert_eq!(kind(libc::EACCES), ErrorKind::Other);
+        assert_eq!(
+            LinuxRtcError::from(ErrorKind::InvalidDateTime).kind(),
+            ErrorKind::InvalidDateTime
+        );
+    }
+}
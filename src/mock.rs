@@ -0,0 +1,961 @@
+//! # Mock RTC for unit tests
+//!
+//! [`MockRtc`] replays a script of expected calls, in the style of the
+//! `embedded-hal-mock` bus mocks. Each [`Transaction`] states the call the code
+//! under test should make next, with its arguments, and what the mock returns.
+//! Any call that does not match the script panics, and [`MockRtc::done`] panics
+//! if expected calls were never made.
+//!
+//! For tests that only need a clock to read, [`FakeRtc`] keeps a time that
+//! advances only when the test says so, and [`SimulatedRtc`] adds a rate
+//! error for long-horizon simulations.
+//!
+//! [`FaultyRtc`] wraps either of them, or a real driver, and injects errors
+//! on a schedule to exercise fault paths. [`RecordingRtc`] records every call
+//! made through it for assertions after the fact.
+//!
+//! [`NoopRtc`] and [`PanicRtc`] are placeholders implementing every trait,
+//! for configurations without an RTC and for code that must not use one.
+//!
+//! Driver crates can generate a conformance test battery with
+//! [`test_suite!`](crate::test_suite).
+//!
+//! Enabled with the `mock` feature, which also enables `alloc`.
+//!
+//! ```
+//! use rtc_hal::datetime::DateTime;
+//! use rtc_hal::error::ErrorKind;
+//! use rtc_hal::mock::{MockRtc, Transaction};
+//! use rtc_hal::rtc::Rtc;
+//!
+//! let noon = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+//! let mut rtc = MockRtc::new(&[
+//!     Transaction::get_datetime(DateTime::MIN).with_error(ErrorKind::Bus),
+//!     Transaction::get_datetime(noon),
+//!     Transaction::set_datetime(noon),
+//! ]);
+//!
+//! assert_eq!(rtc.get_datetime(), Err(ErrorKind::Bus));
+//! let now = rtc.get_datetime().unwrap();
+//! rtc.set_datetime(&now).unwrap();
+//! rtc.done();
+//! ```
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::{
+    control::RtcPowerControl,
+    datetime::DateTime,
+    error::{ErrorKind, ErrorType},
+    nvram::{NvramProtect, RtcNvram},
+    periodic::{PeriodicInterrupt, TickPeriod},
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, DutyCycle, Output32kHz, OutputPin, OutputPinConfig, SquareWave,
+        SquareWaveBatteryBacked, SquareWaveConfig, SquareWaveDuty, SquareWaveFreq, SquareWavePins,
+    },
+};
+
+mod fake;
+mod faulty;
+mod recording;
+mod simulated;
+mod stubs;
+mod test_suite;
+
+pub use fake::FakeRtc;
+pub use faulty::FaultyRtc;
+pub use recording::{Call, RecordingRtc};
+pub use simulated::SimulatedRtc;
+pub use stubs::{NoopRtc, PanicRtc};
+
+/// Expected call, with its arguments or return value
+#[derive(Debug, Clone, PartialEq)]
+enum Expected {
+    GetDateTime(DateTime),
+    SetDateTime(DateTime),
+    ReadNvram { offset: u32, data: Vec<u8> },
+    WriteNvram { offset: u32, data: Vec<u8> },
+    NvramWriteProtected(bool),
+    SetNvramWriteProtect(bool),
+    ConfigureOutputPin(OutputPinConfig),
+    StartSquareWave(SquareWaveFreq),
+    EnableSquareWave,
+    DisableSquareWave,
+    SetSquareWaveFrequency(SquareWaveFreq),
+    SetDisabledOutputLevel(DisabledOutputLevel),
+    SquareWaveConfig(SquareWaveConfig),
+    CurrentPinMode(OutputPinConfig),
+    SetDutyCycle(DutyCycle),
+    DutyCycle(DutyCycle),
+    EnableSquareWaveOnBattery(bool),
+    SquareWaveOnBatteryEnabled(bool),
+    StartSquareWaveOn(OutputPin, SquareWaveFreq),
+    DisableSquareWaveOn(OutputPin),
+    Enable32kHzOutput,
+    Disable32kHzOutput,
+    Output32kHzEnabled(bool),
+    StartClock,
+    HaltClock,
+    EnableTick(TickPeriod),
+    DisableTick,
+    TickFlag(bool),
+    ClearTickFlag,
+}
+
+/// One expected call on a [`MockRtc`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+    call: Expected,
+    error: Option<ErrorKind>,
+}
+
+impl Transaction {
+    fn new(call: Expected) -> Self {
+        Self { call, error: None }
+    }
+
+    /// Expect [`Rtc::get_datetime`], returning `datetime`
+    pub fn get_datetime(datetime: DateTime) -> Self {
+        Self::new(Expected::GetDateTime(datetime))
+    }
+
+    /// Expect [`Rtc::set_datetime`] with `datetime`
+    pub fn set_datetime(datetime: DateTime) -> Self {
+        Self::new(Expected::SetDateTime(datetime))
+    }
+
+    /// Expect [`RtcNvram::read_nvram`] at `offset` into a buffer of
+    /// `data.len()` bytes, filling it with `data`
+    pub fn read_nvram(offset: u32, data: &[u8]) -> Self {
+        Self::new(Expected::ReadNvram {
+            offset,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Expect [`RtcNvram::write_nvram`] of `data` at `offset`
+    pub fn write_nvram(offset: u32, data: &[u8]) -> Self {
+        Self::new(Expected::WriteNvram {
+            offset,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Expect [`NvramProtect::nvram_write_protected`], returning `protected`
+    pub fn nvram_write_protected(protected: bool) -> Self {
+        Self::new(Expected::NvramWriteProtected(protected))
+    }
+
+    /// Expect [`NvramProtect::set_nvram_write_protect`] with `protect`
+    pub fn set_nvram_write_protect(protect: bool) -> Self {
+        Self::new(Expected::SetNvramWriteProtect(protect))
+    }
+
+    /// Expect [`SquareWave::configure_output_pin`] with `config`
+    pub fn configure_output_pin(config: OutputPinConfig) -> Self {
+        Self::new(Expected::ConfigureOutputPin(config))
+    }
+
+    /// Expect [`SquareWave::start_square_wave`] with `freq`
+    pub fn start_square_wave(freq: SquareWaveFreq) -> Self {
+        Self::new(Expected::StartSquareWave(freq))
+    }
+
+    /// Expect [`SquareWave::enable_square_wave`]
+    pub fn enable_square_wave() -> Self {
+        Self::new(Expected::EnableSquareWave)
+    }
+
+    /// Expect [`SquareWave::disable_square_wave`]
+    pub fn disable_square_wave() -> Self {
+        Self::new(Expected::DisableSquareWave)
+    }
+
+    /// Expect [`SquareWave::set_square_wave_frequency`] with `freq`
+    pub fn set_square_wave_frequency(freq: SquareWaveFreq) -> Self {
+        Self::new(Expected::SetSquareWaveFrequency(freq))
+    }
+
+    /// Expect [`SquareWave::set_disabled_output_level`] with `level`
+    pub fn set_disabled_output_level(level: DisabledOutputLevel) -> Self {
+        Self::new(Expected::SetDisabledOutputLevel(level))
+    }
+
+    /// Expect [`SquareWave::square_wave_config`], returning `config`
+    pub fn square_wave_config(config: SquareWaveConfig) -> Self {
+        Self::new(Expected::SquareWaveConfig(config))
+    }
+
+    /// Expect [`SquareWave::current_pin_mode`], returning `mode`
+    pub fn current_pin_mode(mode: OutputPinConfig) -> Self {
+        Self::new(Expected::CurrentPinMode(mode))
+    }
+
+    /// Expect [`SquareWaveDuty::set_duty_cycle`] with `duty`
+    pub fn set_duty_cycle(duty: DutyCycle) -> Self {
+        Self::new(Expected::SetDutyCycle(duty))
+    }
+
+    /// Expect [`SquareWaveDuty::duty_cycle`], returning `duty`
+    pub fn duty_cycle(duty: DutyCycle) -> Self {
+        Self::new(Expected::DutyCycle(duty))
+    }
+
+    /// Expect [`SquareWaveBatteryBacked::enable_square_wave_on_battery`] with `enabled`
+    pub fn enable_square_wave_on_battery(enabled: bool) -> Self {
+        Self::new(Expected::EnableSquareWaveOnBattery(enabled))
+    }
+
+    /// Expect [`SquareWaveBatteryBacked::is_square_wave_on_battery_enabled`],
+    /// returning `enabled`
+    pub fn is_square_wave_on_battery_enabled(enabled: bool) -> Self {
+        Self::new(Expected::SquareWaveOnBatteryEnabled(enabled))
+    }
+
+    /// Expect [`SquareWavePins::start_square_wave_on`] with `pin` and `freq`
+    pub fn start_square_wave_on(pin: OutputPin, freq: SquareWaveFreq) -> Self {
+        Self::new(Expected::StartSquareWaveOn(pin, freq))
+    }
+
+    /// Expect [`SquareWavePins::disable_square_wave_on`] with `pin`
+    pub fn disable_square_wave_on(pin: OutputPin) -> Self {
+        Self::new(Expected::DisableSquareWaveOn(pin))
+    }
+
+    /// Expect [`Output32kHz::enable_32khz_output`]
+    pub fn enable_32khz_output() -> Self {
+        Self::new(Expected::Enable32kHzOutput)
+    }
+
+    /// Expect [`Output32kHz::disable_32khz_output`]
+    pub fn disable_32khz_output() -> Self {
+        Self::new(Expected::Disable32kHzOutput)
+    }
+
+    /// Expect [`Output32kHz::is_32khz_output_enabled`], returning `enabled`
+    pub fn is_32khz_output_enabled(enabled: bool) -> Self {
+        Self::new(Expected::Output32kHzEnabled(enabled))
+    }
+
+    /// Expect [`RtcPowerControl::start_clock`]
+    pub fn start_clock() -> Self {
+        Self::new(Expected::StartClock)
+    }
+
+    /// Expect [`RtcPowerControl::halt_clock`]
+    pub fn halt_clock() -> Self {
+        Self::new(Expected::HaltClock)
+    }
+
+    /// Expect [`PeriodicInterrupt::enable_tick`] with `period`
+    pub fn enable_tick(period: TickPeriod) -> Self {
+        Self::new(Expected::EnableTick(period))
+    }
+
+    /// Expect [`PeriodicInterrupt::disable_tick`]
+    pub fn disable_tick() -> Self {
+        Self::new(Expected::DisableTick)
+    }
+
+    /// Expect [`PeriodicInterrupt::tick_flag`], returning `flag`
+    pub fn tick_flag(flag: bool) -> Self {
+        Self::new(Expected::TickFlag(flag))
+    }
+
+    /// Expect [`PeriodicInterrupt::clear_tick_flag`]
+    pub fn clear_tick_flag() -> Self {
+        Self::new(Expected::ClearTickFlag)
+    }
+
+    /// Fail the call with `kind` instead of succeeding
+    ///
+    /// The call and its arguments are still checked against the expectation.
+    pub fn with_error(mut self, kind: ErrorKind) -> Self {
+        self.error = Some(kind);
+        self
+    }
+}
+
+/// Scripted RTC that checks every call against a list of [`Transaction`]s
+///
+/// Implements [`Rtc`], [`RtcNvram`], [`NvramProtect`], [`SquareWave`], [`SquareWaveDuty`],
+/// [`SquareWaveBatteryBacked`], [`SquareWavePins`], [`Output32kHz`],
+/// [`RtcPowerControl`] and [`PeriodicInterrupt`] with [`ErrorKind`] as the
+/// error type.
+///
+/// # Panics
+///
+/// Every method panics if the call or its arguments differ from the next
+/// expected transaction, or if no transactions are left.
+#[derive(Debug)]
+pub struct MockRtc {
+    expected: VecDeque<Transaction>,
+    nvram_size: u32,
+    square_wave_frequencies: &'static [SquareWaveFreq],
+    output_pin_count: u8,
+}
+
+impl MockRtc {
+    /// Create a mock expecting `transactions` in order
+    pub fn new(transactions: &[Transaction]) -> Self {
+        Self {
+            expected: transactions.iter().cloned().collect(),
+            nvram_size: 0,
+            square_wave_frequencies: &[],
+            output_pin_count: 1,
+        }
+    }
+
+    /// Replace the remaining expectations with `transactions`
+    ///
+    /// # Panics
+    ///
+    /// Panics if expected calls from the previous script were never made.
+    pub fn update_expectations(&mut self, transactions: &[Transaction]) {
+        self.done();
+        self.expected = transactions.iter().cloned().collect();
+    }
+
+    /// Set the value returned by [`RtcNvram::nvram_size`], 0 by default
+    ///
+    /// The size is not enforced, since reads and writes are scripted.
+    pub fn set_nvram_size(&mut self, size: u32) {
+        self.nvram_size = size;
+    }
+
+    /// Set the frequencies returned by
+    /// [`SquareWave::supported_square_wave_frequencies`], none by default
+    ///
+    /// As with the NVRAM size, they are not enforced.
+    pub fn set_square_wave_frequencies(&mut self, freqs: &'static [SquareWaveFreq]) {
+        self.square_wave_frequencies = freqs;
+    }
+
+    /// Set the value returned by [`SquareWavePins::output_pin_count`], 1 by
+    /// default
+    ///
+    /// Pins are not checked against it, so unknown pins can be scripted with
+    /// an error.
+    pub fn set_output_pin_count(&mut self, count: u8) {
+        self.output_pin_count = count;
+    }
+
+    /// Check that every expected call was made
+    ///
+    /// # Panics
+    ///
+    /// Panics if any transactions are left.
+    pub fn done(&mut self) {
+        assert!(
+            self.expected.is_empty(),
+            "mock RTC: {} expected call(s) not made, next: {:?}",
+            self.expected.len(),
+            self.expected.front().unwrap(),
+        );
+    }
+
+    /// Pop the next transaction, panicking if none is left
+    fn next(&mut self, actual: &str) -> Transaction {
+        match self.expected.pop_front() {
+            Some(transaction) => transaction,
+            None => panic!("mock RTC: unexpected call {actual}, no transactions left"),
+        }
+    }
+
+    /// Pop the next transaction, which must be exactly `call`
+    fn expect(&mut self, call: Expected) -> Result<(), ErrorKind> {
+        let transaction = self.next(&alloc::format!("{call:?}"));
+        assert_eq!(transaction.call, call, "mock RTC: call mismatch");
+        transaction.error.map_or(Ok(()), Err)
+    }
+}
+
+impl ErrorType for MockRtc {
+    type Error = ErrorKind;
+}
+
+impl Rtc for MockRtc {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let transaction = self.next("GetDateTime");
+        match transaction.call {
+            Expected::GetDateTime(datetime) => transaction.error.map_or(Ok(datetime), Err),
+            expected => panic!("mock RTC: expected {expected:?}, got GetDateTime"),
+        }
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.expect(Expected::SetDateTime(*datetime))
+    }
+}
+
+impl RtcNvram for MockRtc {
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        let transaction = self.next("ReadNvram");
+        match transaction.call {
+            Expected::ReadNvram {
+                offset: expected_offset,
+                data,
+            } => {
+                assert_eq!(
+                    (offset, buffer.len()),
+                    (expected_offset, data.len()),
+                    "mock RTC: read_nvram (offset, len) mismatch"
+                );
+                if let Some(kind) = transaction.error {
+                    return Err(kind);
+                }
+                buffer.copy_from_slice(&data);
+                Ok(())
+            }
+            expected => panic!("mock RTC: expected {expected:?}, got ReadNvram"),
+        }
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        self.expect(Expected::WriteNvram {
+            offset,
+            data: data.to_vec(),
+        })
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.nvram_size
+    }
+}
+
+impl NvramProtect for MockRtc {
+    fn nvram_write_protected(&mut self) -> Result<bool, Self::Error> {
+        let transaction = self.next("NvramWriteProtected");
+        match transaction.call {
+            Expected::NvramWriteProtected(protected) => {
+                transaction.error.map_or(Ok(protected), Err)
+            }
+            expected => panic!("mock RTC: expected {expected:?}, got NvramWriteProtected"),
+        }
+    }
+
+    fn set_nvram_write_protect(&mut self, protect: bool) -> Result<(), Self::Error> {
+        self.expect(Expected::SetNvramWriteProtect(protect))
+    }
+}
+
+impl SquareWave for MockRtc {
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        self.expect(Expected::ConfigureOutputPin(config))
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.expect(Expected::StartSquareWave(freq))
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::EnableSquareWave)
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::DisableSquareWave)
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.expect(Expected::SetSquareWaveFrequency(freq))
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        self.expect(Expected::SetDisabledOutputLevel(level))
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        let transaction = self.next("SquareWaveConfig");
+        match transaction.call {
+            Expected::SquareWaveConfig(config) => transaction.error.map_or(Ok(config), Err),
+            expected => panic!("mock RTC: expected {expected:?}, got SquareWaveConfig"),
+        }
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        self.square_wave_frequencies
+    }
+
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        let transaction = self.next("CurrentPinMode");
+        match transaction.call {
+            Expected::CurrentPinMode(mode) => transaction.error.map_or(Ok(mode), Err),
+            expected => panic!("mock RTC: expected {expected:?}, got CurrentPinMode"),
+        }
+    }
+}
+
+impl SquareWaveDuty for MockRtc {
+    fn set_duty_cycle(&mut self, duty: DutyCycle) -> Result<(), Self::Error> {
+        self.expect(Expected::SetDutyCycle(duty))
+    }
+
+    fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error> {
+        let transaction = self.next("DutyCycle");
+        match transaction.call {
+            Expected::DutyCycle(duty) => transaction.error.map_or(Ok(duty), Err),
+            expected => panic!("mock RTC: expected {expected:?}, got DutyCycle"),
+        }
+    }
+}
+
+impl SquareWaveBatteryBacked for MockRtc {
+    fn enable_square_wave_on_battery(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        self.expect(Expected::EnableSquareWaveOnBattery(enabled))
+    }
+
+    fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error> {
+        let transaction = self.next("SquareWaveOnBatteryEnabled");
+        match transaction.call {
+            Expected::SquareWaveOnBatteryEnabled(enabled) => {
+                transaction.error.map_or(Ok(enabled), Err)
+            }
+            expected => panic!("mock RTC: expected {expected:?}, got SquareWaveOnBatteryEnabled"),
+        }
+    }
+}
+
+impl SquareWavePins for MockRtc {
+    fn output_pin_count(&self) -> u8 {
+        self.output_pin_count
+    }
+
+    fn start_square_wave_on(
+        &mut self,
+        pin: OutputPin,
+        freq: SquareWaveFreq,
+    ) -> Result<(), Self::Error> {
+        self.expect(Expected::StartSquareWaveOn(pin, freq))
+    }
+
+    fn disable_square_wave_on(&mut self, pin: OutputPin) -> Result<(), Self::Error> {
+        self.expect(Expected::DisableSquareWaveOn(pin))
+    }
+}
+
+impl Output32kHz for MockRtc {
+    fn enable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::Enable32kHzOutput)
+    }
+
+    fn disable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::Disable32kHzOutput)
+    }
+
+    fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error> {
+        let transaction = self.next("Output32kHzEnabled");
+        match transaction.call {
+            Expected::Output32kHzEnabled(enabled) => transaction.error.map_or(Ok(enabled), Err),
+            expected => panic!("mock RTC: expected {expected:?}, got Output32kHzEnabled"),
+        }
+    }
+}
+
+impl RtcPowerControl for MockRtc {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::StartClock)
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::HaltClock)
+    }
+}
+
+impl PeriodicInterrupt for MockRtc {
+    fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+        self.expect(Expected::EnableTick(period))
+    }
+
+    fn disable_tick(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::DisableTick)
+    }
+
+    fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+        let transaction = self.next("TickFlag");
+        match transaction.call {
+            Expected::TickFlag(flag) => transaction.error.map_or(Ok(flag), Err),
+            expected => panic!("mock RTC: expected {expected:?}, got TickFlag"),
+        }
+    }
+
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+        self.expect(Expected::ClearTickFlag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    fn noon() -> DateTime {
+        DateTime::new(2024, 8, 16, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_datetime_transactions() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::get_datetime(noon()),
+            Transaction::set_datetime(DateTime::MIN),
+            Transaction::set_datetime(noon()).with_error(ErrorKind::Bus),
+        ]);
+        assert_eq!(rtc.get_datetime(), Ok(noon()));
+        assert_eq!(rtc.set_datetime(&DateTime::MIN), Ok(()));
+        assert_eq!(rtc.set_datetime(&noon()), Err(ErrorKind::Bus));
+        rtc.done();
+    }
+
+    #[test]
+    fn test_nvram_transactions() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::write_nvram(4, &[1, 2, 3]),
+            Transaction::read_nvram(4, &[1, 2, 3]),
+            Transaction::read_nvram(0, &[0; 2]).with_error(ErrorKind::NvramOutOfBounds),
+        ]);
+        rtc.set_nvram_size(56);
+        assert_eq!(rtc.nvram_size(), 56);
+        assert!(rtc.supported_square_wave_frequencies().is_empty());
+        rtc.set_square_wave_frequencies(&[SquareWaveFreq::Hz1]);
+        assert!(rtc.supports_frequency(SquareWaveFreq::Hz1));
+        assert!(!rtc.supports_frequency(SquareWaveFreq::Hz4096));
+        rtc.write_nvram(4, &[1, 2, 3]).unwrap();
+        let mut buffer = [0; 3];
+        rtc.read_nvram(4, &mut buffer).unwrap();
+        assert_eq!(buffer, [1, 2, 3]);
+        assert_eq!(
+            rtc.read_nvram(0, &mut [0; 2]),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        rtc.done();
+    }
+
+    #[test]
+    fn test_fill_nvram_chunks() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::write_nvram(3, &[0xAA; 16]),
+            Transaction::write_nvram(19, &[0xAA; 16]),
+            Transaction::write_nvram(35, &[0xAA; 5]),
+            Transaction::write_nvram(0, &[0; 16]),
+            Transaction::write_nvram(16, &[0; 16]),
+            Transaction::write_nvram(32, &[0; 8]),
+        ]);
+        rtc.set_nvram_size(40);
+        rtc.fill_nvram(3, 37, 0xAA).unwrap();
+        rtc.fill_nvram(40, 0, 0xAA).unwrap();
+        rtc.erase_nvram().unwrap();
+        rtc.done();
+
+        // A size that is a multiple of the chunk, and no NVRAM at all
+        rtc.update_expectations(&[
+            Transaction::write_nvram(0, &[0; 16]),
+            Transaction::write_nvram(16, &[0; 16]),
+        ]);
+        rtc.set_nvram_size(32);
+        rtc.erase_nvram().unwrap();
+        rtc.set_nvram_size(0);
+        rtc.erase_nvram().unwrap();
+        rtc.done();
+    }
+
+    #[test]
+    fn test_fill_nvram_checks_bounds_first() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::write_nvram(40, &[0]).with_error(ErrorKind::NvramOutOfBounds),
+            Transaction::write_nvram(40, &[0]).with_error(ErrorKind::NvramOutOfBounds),
+            Transaction::write_nvram(50, &[0]).with_error(ErrorKind::NvramOutOfBounds),
+            Transaction::write_nvram(0, &[1; 16]),
+            Transaction::write_nvram(16, &[1; 4]).with_error(ErrorKind::Bus),
+        ]);
+        rtc.set_nvram_size(40);
+        // When the end is past the NVRAM or wraps, only a write past the end
+        // reaches the RTC, which reports it
+        assert_eq!(rtc.fill_nvram(30, 11, 0), Err(ErrorKind::NvramOutOfBounds));
+        assert_eq!(
+            rtc.fill_nvram(1, u32::MAX, 0),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(rtc.fill_nvram(50, 0, 0), Err(ErrorKind::NvramOutOfBounds));
+        // A failed chunk stops the fill
+        assert_eq!(rtc.fill_nvram(0, 20, 1), Err(ErrorKind::Bus));
+        rtc.done();
+    }
+
+    /// Write `data`, lifting the write protection if it is on
+    fn write_unprotected<R: NvramProtect>(
+        rtc: &mut R,
+        offset: u32,
+        data: &[u8],
+    ) -> Result<(), R::Error> {
+        match rtc.write_nvram(offset, data) {
+            Err(e) if e.kind() == ErrorKind::NvramWriteProtected => {
+                rtc.set_nvram_write_protect(false)?;
+                rtc.write_nvram(offset, data)
+            }
+            result => result,
+        }
+    }
+
+    #[test]
+    fn test_nvram_write_protect_transactions() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::nvram_write_protected(true),
+            Transaction::write_nvram(0, &[1, 2]).with_error(ErrorKind::NvramWriteProtected),
+            Transaction::set_nvram_write_protect(false),
+            Transaction::write_nvram(0, &[1, 2]),
+            Transaction::set_nvram_write_protect(true).with_error(ErrorKind::Unsupported),
+        ]);
+        assert_eq!(rtc.nvram_write_protected(), Ok(true));
+        write_unprotected(&mut rtc, 0, &[1, 2]).unwrap();
+        assert_eq!(
+            rtc.set_nvram_write_protect(true),
+            Err(ErrorKind::Unsupported)
+        );
+        rtc.done();
+    }
+
+    #[test]
+    fn test_square_wave_and_power_transactions() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::start_square_wave(SquareWaveFreq::Hz1),
+            Transaction::set_square_wave_frequency(SquareWaveFreq::Hz4096),
+            Transaction::disable_square_wave(),
+            Transaction::enable_square_wave().with_error(ErrorKind::UnsupportedSqwFrequency),
+            Transaction::configure_output_pin(OutputPinConfig::AlarmInterrupt),
+            Transaction::halt_clock(),
+            Transaction::start_clock(),
+        ]);
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        rtc.set_square_wave_frequency(SquareWaveFreq::Hz4096)
+            .unwrap();
+        rtc.disable_square_wave().unwrap();
+        assert_eq!(
+            rtc.enable_square_wave(),
+            Err(ErrorKind::UnsupportedSqwFrequency)
+        );
+        rtc.configure_output_pin(OutputPinConfig::AlarmInterrupt)
+            .unwrap();
+        rtc.halt_clock().unwrap();
+        rtc.start_clock().unwrap();
+        rtc.done();
+    }
+
+    #[test]
+    fn test_disabled_output_level_transactions() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::disable_square_wave(),
+            Transaction::set_disabled_output_level(DisabledOutputLevel::Low),
+            Transaction::set_disabled_output_level(DisabledOutputLevel::HighImpedance)
+                .with_error(ErrorKind::Unsupported),
+        ]);
+        rtc.disable_square_wave().unwrap();
+        rtc.set_disabled_output_level(DisabledOutputLevel::Low)
+            .unwrap();
+        assert_eq!(
+            rtc.set_disabled_output_level(DisabledOutputLevel::HighImpedance),
+            Err(ErrorKind::Unsupported)
+        );
+        rtc.done();
+    }
+
+    #[test]
+    fn test_pin_mode_transactions() {
+        /// Restart the 1 Hz tick if an alarm interrupt took the pin
+        fn keep_ticking<R: SquareWave>(rtc: &mut R) -> Result<(), R::Error> {
+            if !matches!(rtc.current_pin_mode()?, OutputPinConfig::SquareWave(_)) {
+                rtc.start_square_wave(SquareWaveFreq::Hz1)?;
+            }
+            Ok(())
+        }
+
+        // The tick runs, then an alarm interrupt takes the shared pin, which
+        // a driver must report rather than keep the square wave mode
+        let mut rtc = MockRtc::new(&[
+            Transaction::current_pin_mode(OutputPinConfig::SquareWave(SquareWaveFreq::Hz1)),
+            Transaction::current_pin_mode(OutputPinConfig::AlarmInterrupt),
+            Transaction::start_square_wave(SquareWaveFreq::Hz1),
+            Transaction::current_pin_mode(OutputPinConfig::AlarmInterrupt)
+                .with_error(ErrorKind::Bus),
+        ]);
+        keep_ticking(&mut rtc).unwrap();
+        keep_ticking(&mut rtc).unwrap();
+        assert_eq!(keep_ticking(&mut rtc), Err(ErrorKind::Bus));
+        rtc.done();
+    }
+
+    #[test]
+    fn test_square_wave_config_transactions() {
+        // Start-up code that only writes the output when it differs
+        fn ensure(rtc: &mut MockRtc, wanted: SquareWaveConfig) -> Result<(), ErrorKind> {
+            if rtc.square_wave_config()? != wanted {
+                rtc.start_square_wave(wanted.freq)?;
+            }
+            Ok(())
+        }
+
+        let wanted = SquareWaveConfig {
+            enabled: true,
+            freq: SquareWaveFreq::Hz1,
+            disabled_level: DisabledOutputLevel::High,
+        };
+        let mut rtc = MockRtc::new(&[
+            Transaction::square_wave_config(SquareWaveConfig {
+                enabled: false,
+                ..wanted
+            }),
+            Transaction::start_square_wave(SquareWaveFreq::Hz1),
+            Transaction::square_wave_config(wanted),
+            Transaction::square_wave_config(wanted).with_error(ErrorKind::Unsupported),
+        ]);
+        ensure(&mut rtc, wanted).unwrap();
+        ensure(&mut rtc, wanted).unwrap();
+        assert_eq!(ensure(&mut rtc, wanted), Err(ErrorKind::Unsupported));
+        rtc.done();
+    }
+
+    #[test]
+    fn test_duty_cycle_transactions() {
+        let quarter = DutyCycle::new(25).unwrap();
+        let mut rtc = MockRtc::new(&[
+            Transaction::set_duty_cycle(quarter),
+            Transaction::duty_cycle(quarter),
+            Transaction::set_duty_cycle(DutyCycle::new(1).unwrap())
+                .with_error(ErrorKind::Unsupported),
+            Transaction::duty_cycle(quarter).with_error(ErrorKind::Bus),
+        ]);
+        rtc.set_duty_cycle(quarter).unwrap();
+        assert_eq!(rtc.duty_cycle(), Ok(quarter));
+        assert_eq!(
+            rtc.set_duty_cycle(DutyCycle::new(1).unwrap()),
+            Err(ErrorKind::Unsupported)
+        );
+        assert_eq!(rtc.duty_cycle(), Err(ErrorKind::Bus));
+        rtc.done();
+    }
+
+    #[test]
+    fn test_square_wave_on_battery_transactions() {
+        // Set up a 1 Hz tick that survives power loss, then turn it off
+        let mut rtc = MockRtc::new(&[
+            Transaction::start_square_wave(SquareWaveFreq::Hz1),
+            Transaction::enable_square_wave_on_battery(true),
+            Transaction::is_square_wave_on_battery_enabled(true),
+            Transaction::disable_square_wave(),
+            Transaction::is_square_wave_on_battery_enabled(true),
+            Transaction::enable_square_wave_on_battery(false).with_error(ErrorKind::Bus),
+        ]);
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        rtc.enable_square_wave_on_battery(true).unwrap();
+        assert_eq!(rtc.is_square_wave_on_battery_enabled(), Ok(true));
+        // Disabling the output leaves the battery setting alone
+        rtc.disable_square_wave().unwrap();
+        assert_eq!(rtc.is_square_wave_on_battery_enabled(), Ok(true));
+        assert_eq!(
+            rtc.enable_square_wave_on_battery(false),
+            Err(ErrorKind::Bus)
+        );
+        rtc.done();
+    }
+
+    #[test]
+    fn test_output_pin_transactions() {
+        // A two-pin chip: the plain square wave methods drive the primary pin
+        let mut rtc = MockRtc::new(&[
+            Transaction::start_square_wave_on(OutputPin::SECONDARY, SquareWaveFreq::Hz32768),
+            Transaction::start_square_wave(SquareWaveFreq::Hz1),
+            Transaction::disable_square_wave_on(OutputPin::SECONDARY),
+            Transaction::start_square_wave_on(OutputPin(2), SquareWaveFreq::Hz1)
+                .with_error(ErrorKind::InvalidAddress),
+        ]);
+        rtc.set_output_pin_count(2);
+        assert_eq!(rtc.output_pin_count(), 2);
+        rtc.start_square_wave_on(OutputPin::SECONDARY, SquareWaveFreq::Hz32768)
+            .unwrap();
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        rtc.disable_square_wave_on(OutputPin::SECONDARY).unwrap();
+        assert_eq!(
+            rtc.start_square_wave_on(OutputPin(2), SquareWaveFreq::Hz1),
+            Err(ErrorKind::InvalidAddress)
+        );
+        rtc.done();
+    }
+
+    #[test]
+    fn test_32khz_output_transactions() {
+        // The 32 kHz output is scripted separately from the square wave
+        let mut rtc = MockRtc::new(&[
+            Transaction::is_32khz_output_enabled(false),
+            Transaction::enable_32khz_output(),
+            Transaction::disable_square_wave(),
+            Transaction::is_32khz_output_enabled(true),
+            Transaction::disable_32khz_output().with_error(ErrorKind::Bus),
+        ]);
+        assert_eq!(rtc.is_32khz_output_enabled(), Ok(false));
+        rtc.enable_32khz_output().unwrap();
+        rtc.disable_square_wave().unwrap();
+        assert_eq!(rtc.is_32khz_output_enabled(), Ok(true));
+        assert_eq!(rtc.disable_32khz_output(), Err(ErrorKind::Bus));
+        rtc.done();
+    }
+
+    #[test]
+    fn test_tick_transactions() {
+        let mut rtc = MockRtc::new(&[
+            Transaction::enable_tick(TickPeriod::Custom(1000)).with_error(ErrorKind::Unsupported),
+            Transaction::enable_tick(TickPeriod::Second),
+            Transaction::tick_flag(false),
+            Transaction::tick_flag(true),
+            Transaction::clear_tick_flag(),
+            Transaction::tick_flag(false).with_error(ErrorKind::Bus),
+            Transaction::disable_tick(),
+        ]);
+        assert_eq!(
+            rtc.enable_tick(TickPeriod::Custom(1000)),
+            Err(ErrorKind::Unsupported)
+        );
+        rtc.enable_tick(TickPeriod::Second).unwrap();
+        assert_eq!(rtc.tick_flag(), Ok(false));
+        assert_eq!(rtc.tick_flag(), Ok(true));
+        rtc.clear_tick_flag().unwrap();
+        assert_eq!(rtc.tick_flag(), Err(ErrorKind::Bus));
+        rtc.disable_tick().unwrap();
+        rtc.done();
+    }
+
+    #[test]
+    fn test_update_expectations() {
+        let mut rtc = MockRtc::new(&[Transaction::start_clock()]);
+        rtc.start_clock().unwrap();
+        rtc.update_expectations(&[Transaction::halt_clock()]);
+        rtc.halt_clock().unwrap();
+        rtc.done();
+    }
+
+    #[test]
+    #[should_panic(expected = "call mismatch")]
+    fn test_argument_mismatch_panics() {
+        let mut rtc = MockRtc::new(&[Transaction::set_datetime(noon())]);
+        let _ = rtc.set_datetime(&DateTime::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected StartClock, got GetDateTime")]
+    fn test_wrong_call_panics() {
+        let mut rtc = MockRtc::new(&[Transaction::start_clock()]);
+        let _ = rtc.get_datetime();
+    }
+
+    #[test]
+    #[should_panic(expected = "no transactions left")]
+    fn test_unexpected_call_panics() {
+        let mut rtc = MockRtc::new(&[]);
+        let _ = rtc.halt_clock();
+    }
+
+    #[test]
+    #[should_panic(expected = "1 expected call(s) not made")]
+    fn test_leftover_transaction_panics() {
+        let mut rtc = MockRtc::new(&[Transaction::get_datetime(noon())]);
+        rtc.done();
+    }
+}
@@ -0,0 +1,1262 @@
+//! Stateful fake RTC with manually advanced time
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    alarm::{Alarm, AlarmCapabilities, AlarmConfig, AlarmId},
+    control::RtcPowerControl,
+    datetime::DateTime,
+    error::{ErrorKind, ErrorType},
+    nvram::{self, NvramProtect, RtcNvram},
+    periodic::{PeriodicInterrupt, TickPeriod},
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, DutyCycle, Output32kHz, OutputPin, OutputPinConfig, SquareWave,
+        SquareWaveBatteryBacked, SquareWaveConfig, SquareWaveDuty, SquareWaveFreq, SquareWavePins,
+    },
+};
+
+/// RTC that holds a time and only advances when told to
+///
+/// Unlike [`MockRtc`](super::MockRtc), calls are not scripted: every
+/// [`get_datetime`](Rtc::get_datetime) returns the current value, which moves
+/// only through [`set_datetime`](Rtc::set_datetime) and
+/// [`advance_seconds`](FakeRtc::advance_seconds), so tests are deterministic.
+///
+/// While the clock is halted through [`RtcPowerControl`], advancing has no
+/// effect.
+///
+/// The fake also implements [`RtcNvram`] with RAM-backed storage, empty unless
+/// a size is given with [`with_nvram`](Self::with_nvram). Accesses past the end
+/// fail with [`ErrorKind::NvramOutOfBounds`], and writes fail with
+/// [`ErrorKind::NvramWriteProtected`] while protected, either by the test with
+/// [`set_write_protected`](Self::set_write_protected) or by the code under test
+/// through [`NvramProtect`].
+///
+/// ## Alarms
+///
+/// Any number of alarms, identified by index, can be armed with an
+/// [`AlarmConfig`] through [`arm_alarm`](Self::arm_alarm). An alarm fires for
+/// every matching second that [`advance_seconds`](Self::advance_seconds) moves
+/// the time onto: its pending flag is set until
+/// [`clear_alarm_pending`](Self::clear_alarm_pending), and each firing is
+/// queued for [`take_fired_alarms`](Self::take_fired_alarms). While the flag is
+/// set and the alarm's interrupt is enabled,
+/// [`interrupt_asserted`](Self::interrupt_asserted) reports the interrupt pin
+/// as asserted, unless the pin is outputting a square wave. Like hardware that compares
+/// on every tick, an alarm does not fire when the time is jumped past a match
+/// with [`set_datetime`](Rtc::set_datetime), or while the clock is halted.
+///
+/// The [`Alarm`] trait drives the same alarms, with `AlarmId(n)` being alarm
+/// `n`. Through the trait the fake has a single alarm unless more are given
+/// with [`with_alarms`](Self::with_alarms); other ids fail with
+/// [`ErrorKind::InvalidAlarmConfig`]. Every alarm has every
+/// [`AlarmCapabilities`] flag.
+///
+/// ## Output pin
+///
+/// Like the DS3231 INT/SQW pin, one [`output_pin`](Self::output_pin) carries
+/// either the alarm interrupt, which it starts with, or the square wave of
+/// [`SquareWave`]. Starting or enabling the square wave takes the pin from the
+/// alarms, and [`Alarm::enable_alarm_interrupt`] takes it back.
+/// [`disable_square_wave`](SquareWave::disable_square_wave) holds the pin at
+/// the [disabled level](SquareWave::set_disabled_output_level), high unless
+/// changed. Any level is accepted unless the pin is made push-pull with
+/// [`with_push_pull_output`](Self::with_push_pull_output), which rejects
+/// [`DisabledOutputLevel::HighImpedance`] with [`ErrorKind::Unsupported`].
+/// The square wave runs at any of [`SquareWaveFreq::STANDARD`] unless other
+/// frequencies are given with
+/// [`with_square_wave_frequencies`](Self::with_square_wave_frequencies); others
+/// fail with [`ErrorKind::UnsupportedSqwFrequency`]. Through [`SquareWaveDuty`]
+/// any duty cycle is accepted, starting at [`DutyCycle::HALF`]. Through
+/// [`SquareWavePins`] it has a single pin, [`OutputPin::PRIMARY`]; other pins
+/// fail with [`ErrorKind::InvalidAddress`].
+///
+/// [`set_on_battery`](Self::set_on_battery) simulates losing main power. The
+/// square wave then stops, as reported by
+/// [`square_wave_running`](Self::square_wave_running), unless kept running
+/// with [`SquareWaveBatteryBacked`]. Nothing else changes on battery.
+///
+/// Like the DS3231 32kHz pin, the [`Output32kHz`] output starts enabled and is
+/// independent of the square wave pin.
+///
+/// ## Tick
+///
+/// [`PeriodicInterrupt`] sets the tick flag whenever
+/// [`advance_seconds`](Self::advance_seconds) moves the time onto a tick: any
+/// advance for [`TickPeriod::Second`] and [`TickPeriod::Custom`], which tick at
+/// least once a second, and an advance onto or past a new minute for
+/// [`TickPeriod::Minute`]. `Custom(0)` fails with [`ErrorKind::Unsupported`].
+///
+/// ```
+/// use rtc_hal::datetime::DateTime;
+/// use rtc_hal::mock::FakeRtc;
+/// use rtc_hal::rtc::Rtc;
+///
+/// let mut rtc = FakeRtc::new(DateTime::new(2024, 12, 31, 23, 59, 59).unwrap());
+/// rtc.advance_seconds(1);
+/// assert_eq!(rtc.get_datetime().unwrap(), DateTime::new(2025, 1, 1, 0, 0, 0).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeRtc {
+    datetime: DateTime,
+    halted: bool,
+    error: Option<ErrorKind>,
+    nvram: Vec<u8>,
+    write_protected: bool,
+    alarms: Vec<FakeAlarm>,
+    alarm_count: u8,
+    fired: Vec<(usize, DateTime)>,
+    output_pin: OutputPinConfig,
+    square_wave_freq: SquareWaveFreq,
+    square_wave_freqs: &'static [SquareWaveFreq],
+    disabled_level: DisabledOutputLevel,
+    push_pull: bool,
+    on_battery: bool,
+    square_wave_on_battery: bool,
+    output_32khz: bool,
+    duty_cycle: DutyCycle,
+    tick: Option<TickPeriod>,
+    tick_pending: bool,
+}
+
+/// State of one alarm of a [`FakeRtc`]
+#[derive(Debug, Clone, Copy, Default)]
+struct FakeAlarm {
+    config: Option<AlarmConfig>,
+    pending: bool,
+    interrupt: bool,
+}
+
+impl FakeRtc {
+    /// Create a running fake set to `datetime`
+    pub fn new(datetime: DateTime) -> Self {
+        Self {
+            datetime,
+            halted: false,
+            error: None,
+            nvram: Vec::new(),
+            write_protected: false,
+            alarms: Vec::new(),
+            alarm_count: 1,
+            fired: Vec::new(),
+            output_pin: OutputPinConfig::AlarmInterrupt,
+            square_wave_freq: SquareWaveFreq::Hz1,
+            square_wave_freqs: &SquareWaveFreq::STANDARD,
+            disabled_level: DisabledOutputLevel::High,
+            push_pull: false,
+            on_battery: false,
+            square_wave_on_battery: false,
+            output_32khz: true,
+            duty_cycle: DutyCycle::HALF,
+            tick: None,
+            tick_pending: false,
+        }
+    }
+
+    /// Give the fake `size` bytes of zeroed NVRAM, e.g. 56 to emulate a DS1307
+    pub fn with_nvram(mut self, size: u32) -> Self {
+        self.nvram = vec![0; size as usize];
+        self
+    }
+
+    /// Report `count` alarms through [`Alarm::alarm_count`], e.g. 2 to emulate a DS3231
+    pub fn with_alarms(mut self, count: u8) -> Self {
+        self.alarm_count = count;
+        self
+    }
+
+    /// Support only `freqs` on the square wave output, e.g. those of a DS1307
+    ///
+    /// If the current frequency is not among them, the first one is selected.
+    pub fn with_square_wave_frequencies(mut self, freqs: &'static [SquareWaveFreq]) -> Self {
+        self.square_wave_freqs = freqs;
+        if !self.supports_frequency(self.square_wave_freq) {
+            self.square_wave_freq = freqs.first().copied().unwrap_or(SquareWaveFreq::Hz1);
+        }
+        self
+    }
+
+    /// Make the output pin push-pull, so that it cannot float while disabled
+    pub fn with_push_pull_output(mut self) -> Self {
+        self.push_pull = true;
+        self
+    }
+
+    /// Make NVRAM writes fail with [`ErrorKind::NvramWriteProtected`]
+    pub fn set_write_protected(&mut self, protected: bool) {
+        self.write_protected = protected;
+    }
+
+    /// Current NVRAM contents
+    pub fn nvram_contents(&self) -> &[u8] {
+        &self.nvram
+    }
+
+    /// Move the time forward by `seconds`, unless the clock is halted
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time would go past [`DateTime::MAX`].
+    pub fn advance_seconds(&mut self, seconds: u32) {
+        if self.halted {
+            return;
+        }
+        let from = self.datetime;
+        self.datetime = from
+            .checked_add_seconds(seconds.into())
+            .expect("FakeRtc advanced past DateTime::MAX");
+
+        let first = self.fired.len();
+        for (id, alarm) in self.alarms.iter_mut().enumerate() {
+            let Some(config) = alarm.config else {
+                continue;
+            };
+            let mut last = from;
+            while let Ok(at) = config.next_trigger(&last)
+                && at <= self.datetime
+            {
+                alarm.pending = true;
+                self.fired.push((id, at));
+                last = at;
+            }
+        }
+        self.fired[first..].sort_by_key(|&(id, at)| (at, id));
+
+        let ticked = match self.tick {
+            None => false,
+            Some(TickPeriod::Minute) => {
+                from.to_unix_timestamp() / 60 != self.datetime.to_unix_timestamp() / 60
+            }
+            Some(TickPeriod::Second | TickPeriod::Custom(_)) => seconds > 0,
+        };
+        self.tick_pending |= ticked;
+    }
+
+    /// Arm alarm `id` with `config`, replacing its previous configuration
+    ///
+    /// The pending flag is left as it is. A configuration that fails
+    /// [`AlarmConfig::validate`] never fires.
+    pub fn arm_alarm(&mut self, id: usize, config: AlarmConfig) {
+        self.alarm_mut(id).config = Some(config);
+    }
+
+    /// Disarm alarm `id`; the pending flag is left as it is
+    pub fn disarm_alarm(&mut self, id: usize) {
+        if let Some(alarm) = self.alarms.get_mut(id) {
+            alarm.config = None;
+        }
+    }
+
+    /// Whether alarm `id` has fired since its flag was last cleared
+    pub fn alarm_pending(&self, id: usize) -> bool {
+        self.alarms.get(id).is_some_and(|alarm| alarm.pending)
+    }
+
+    /// Clear the pending flag of alarm `id`
+    pub fn clear_alarm_pending(&mut self, id: usize) {
+        if let Some(alarm) = self.alarms.get_mut(id) {
+            alarm.pending = false;
+        }
+    }
+
+    /// Let alarm `id` assert the interrupt pin while its flag is set
+    pub fn set_alarm_interrupt(&mut self, id: usize, enabled: bool) {
+        self.alarm_mut(id).interrupt = enabled;
+    }
+
+    /// Whether the interrupt pin is asserted, i.e. it is in alarm interrupt
+    /// mode and an alarm with its interrupt enabled has its flag set
+    pub fn interrupt_asserted(&self) -> bool {
+        self.output_pin == OutputPinConfig::AlarmInterrupt
+            && self
+                .alarms
+                .iter()
+                .any(|alarm| alarm.pending && alarm.interrupt)
+    }
+
+    /// What the output pin currently carries
+    pub fn output_pin(&self) -> OutputPinConfig {
+        self.output_pin
+    }
+
+    /// Switch to the backup supply, or back to main power
+    pub fn set_on_battery(&mut self, on_battery: bool) {
+        self.on_battery = on_battery;
+    }
+
+    /// Whether a square wave is actually output, i.e. it is enabled and either
+    /// on main power or kept running on battery
+    pub fn square_wave_running(&self) -> bool {
+        matches!(self.output_pin, OutputPinConfig::SquareWave(_))
+            && (!self.on_battery || self.square_wave_on_battery)
+    }
+
+    /// Remove and return the alarms fired so far as `(id, instant)`, in firing order
+    pub fn take_fired_alarms(&mut self) -> Vec<(usize, DateTime)> {
+        core::mem::take(&mut self.fired)
+    }
+
+    /// Make the next call fail with `kind`
+    ///
+    /// The failed call has no other effect. Later calls succeed again.
+    pub fn set_error(&mut self, kind: ErrorKind) {
+        self.error = Some(kind);
+    }
+
+    /// Whether the clock is halted
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Current time, without going through the [`Rtc`] trait or a pending error
+    pub fn datetime(&self) -> DateTime {
+        self.datetime
+    }
+
+    fn alarm_mut(&mut self, id: usize) -> &mut FakeAlarm {
+        if id >= self.alarms.len() {
+            self.alarms.resize(id + 1, FakeAlarm::default());
+        }
+        &mut self.alarms[id]
+    }
+
+    fn check_error(&mut self) -> Result<(), ErrorKind> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+
+    /// NVRAM bytes covered by an access, if it stays within bounds
+    fn nvram_range(&self, offset: u32, len: usize) -> Result<core::ops::Range<usize>, ErrorKind> {
+        nvram::check_bounds(offset, len, self.nvram_size())?;
+        let start = offset as usize;
+        Ok(start..start + len)
+    }
+}
+
+impl ErrorType for FakeRtc {
+    type Error = ErrorKind;
+}
+
+impl Rtc for FakeRtc {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        self.check_error()?;
+        Ok(self.datetime)
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.datetime = *datetime;
+        Ok(())
+    }
+}
+
+impl RtcNvram for FakeRtc {
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.check_error()?;
+        let range = self.nvram_range(offset, buffer.len())?;
+        buffer.copy_from_slice(&self.nvram[range]);
+        Ok(())
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        self.check_error()?;
+        let range = self.nvram_range(offset, data.len())?;
+        if self.write_protected {
+            return Err(ErrorKind::NvramWriteProtected);
+        }
+        self.nvram[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.nvram.len() as u32
+    }
+}
+
+impl NvramProtect for FakeRtc {
+    fn nvram_write_protected(&mut self) -> Result<bool, Self::Error> {
+        self.check_error()?;
+        Ok(self.write_protected)
+    }
+
+    fn set_nvram_write_protect(&mut self, protect: bool) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.write_protected = protect;
+        Ok(())
+    }
+}
+
+impl RtcPowerControl for FakeRtc {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.halted = false;
+        Ok(())
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.halted = true;
+        Ok(())
+    }
+}
+
+impl Alarm for FakeRtc {
+    fn alarm_count(&self) -> u8 {
+        self.alarm_count
+    }
+
+    fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+        if id.check(self.alarm_count).is_err() {
+            return AlarmCapabilities::default();
+        }
+        AlarmCapabilities {
+            seconds_match: true,
+            weekday_match: true,
+            date_match: true,
+            interrupt_output: true,
+        }
+    }
+
+    fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+        self.check_error()?;
+        let id = id.check(self.alarm_count)?;
+        config.validate()?;
+        self.arm_alarm(id.0.into(), *config);
+        Ok(())
+    }
+
+    fn disable_alarm(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        self.check_error()?;
+        let id = id.check(self.alarm_count)?;
+        self.disarm_alarm(id.0.into());
+        Ok(())
+    }
+
+    fn alarm_triggered(&mut self, id: AlarmId) -> Result<bool, Self::Error> {
+        self.check_error()?;
+        let id = id.check(self.alarm_count)?;
+        Ok(self.alarm_pending(id.0.into()))
+    }
+
+    fn clear_alarm_flag(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        self.check_error()?;
+        let id = id.check(self.alarm_count)?;
+        self.clear_alarm_pending(id.0.into());
+        Ok(())
+    }
+
+    fn enable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        self.check_error()?;
+        let id = id.check(self.alarm_count)?;
+        self.set_alarm_interrupt(id.0.into(), true);
+        self.output_pin = OutputPinConfig::AlarmInterrupt;
+        Ok(())
+    }
+
+    fn disable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+        self.check_error()?;
+        let id = id.check(self.alarm_count)?;
+        self.set_alarm_interrupt(id.0.into(), false);
+        Ok(())
+    }
+}
+
+impl PeriodicInterrupt for FakeRtc {
+    fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+        self.check_error()?;
+        if period == TickPeriod::Custom(0) {
+            return Err(ErrorKind::Unsupported);
+        }
+        self.tick = Some(period);
+        Ok(())
+    }
+
+    fn disable_tick(&mut self) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.tick = None;
+        Ok(())
+    }
+
+    fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+        self.check_error()?;
+        Ok(self.tick_pending)
+    }
+
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.tick_pending = false;
+        Ok(())
+    }
+}
+
+impl SquareWave for FakeRtc {
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        self.check_error()?;
+        match config {
+            OutputPinConfig::SquareWave(freq) => {
+                if !self.supports_frequency(freq) {
+                    return Err(ErrorKind::UnsupportedSqwFrequency);
+                }
+                self.square_wave_freq = freq;
+            }
+            OutputPinConfig::Disabled { level } => {
+                if self.push_pull && level == DisabledOutputLevel::HighImpedance {
+                    return Err(ErrorKind::Unsupported);
+                }
+                self.disabled_level = level;
+            }
+            OutputPinConfig::AlarmInterrupt => {}
+        }
+        self.output_pin = config;
+        Ok(())
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.configure_output_pin(OutputPinConfig::SquareWave(freq))
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.configure_output_pin(OutputPinConfig::SquareWave(self.square_wave_freq))
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.configure_output_pin(OutputPinConfig::Disabled {
+            level: self.disabled_level,
+        })
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.check_error()?;
+        if !self.supports_frequency(freq) {
+            return Err(ErrorKind::UnsupportedSqwFrequency);
+        }
+        self.square_wave_freq = freq;
+        if let OutputPinConfig::SquareWave(_) = self.output_pin {
+            self.output_pin = OutputPinConfig::SquareWave(freq);
+        }
+        Ok(())
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        self.check_error()?;
+        if self.push_pull && level == DisabledOutputLevel::HighImpedance {
+            return Err(ErrorKind::Unsupported);
+        }
+        self.disabled_level = level;
+        if let OutputPinConfig::Disabled { .. } = self.output_pin {
+            self.output_pin = OutputPinConfig::Disabled { level };
+        }
+        Ok(())
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        self.check_error()?;
+        Ok(SquareWaveConfig {
+            enabled: matches!(self.output_pin, OutputPinConfig::SquareWave(_)),
+            freq: self.square_wave_freq,
+            disabled_level: self.disabled_level,
+        })
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        self.square_wave_freqs
+    }
+
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        self.check_error()?;
+        Ok(self.output_pin)
+    }
+}
+
+impl SquareWaveBatteryBacked for FakeRtc {
+    fn enable_square_wave_on_battery(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.square_wave_on_battery = enabled;
+        Ok(())
+    }
+
+    fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error> {
+        self.check_error()?;
+        Ok(self.square_wave_on_battery)
+    }
+}
+
+impl SquareWavePins for FakeRtc {
+    fn output_pin_count(&self) -> u8 {
+        1
+    }
+
+    fn start_square_wave_on(
+        &mut self,
+        pin: OutputPin,
+        freq: SquareWaveFreq,
+    ) -> Result<(), Self::Error> {
+        pin.check(self.output_pin_count())?;
+        self.start_square_wave(freq)
+    }
+
+    fn disable_square_wave_on(&mut self, pin: OutputPin) -> Result<(), Self::Error> {
+        pin.check(self.output_pin_count())?;
+        self.disable_square_wave()
+    }
+}
+
+impl Output32kHz for FakeRtc {
+    fn enable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.output_32khz = true;
+        Ok(())
+    }
+
+    fn disable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.output_32khz = false;
+        Ok(())
+    }
+
+    fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error> {
+        self.check_error()?;
+        Ok(self.output_32khz)
+    }
+}
+
+impl SquareWaveDuty for FakeRtc {
+    fn set_duty_cycle(&mut self, duty: DutyCycle) -> Result<(), Self::Error> {
+        self.check_error()?;
+        self.duty_cycle = duty;
+        Ok(())
+    }
+
+    fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error> {
+        self.check_error()?;
+        Ok(self.duty_cycle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollover_across_midnight() {
+        let mut rtc = FakeRtc::new(DateTime::new(2024, 2, 28, 23, 59, 58).unwrap());
+        rtc.advance_seconds(1);
+        assert_eq!(
+            rtc.get_datetime(),
+            DateTime::new(2024, 2, 28, 23, 59, 59).map_err(Into::into)
+        );
+        rtc.advance_seconds(2);
+        assert_eq!(
+            rtc.get_datetime(),
+            DateTime::new(2024, 2, 29, 0, 0, 1).map_err(Into::into)
+        );
+    }
+
+    #[test]
+    fn test_halt_freezes_time() {
+        let start = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        let mut rtc = FakeRtc::new(start);
+        rtc.halt_clock().unwrap();
+        assert!(rtc.is_halted());
+        rtc.advance_seconds(3_600);
+        assert_eq!(rtc.get_datetime(), Ok(start));
+
+        rtc.start_clock().unwrap();
+        rtc.advance_seconds(60);
+        assert_eq!(
+            rtc.get_datetime(),
+            start.checked_add_seconds(60).map_err(Into::into)
+        );
+    }
+
+    #[test]
+    fn test_set_error_fails_next_call_only() {
+        let mut rtc = FakeRtc::new(DateTime::MIN);
+        rtc.set_error(ErrorKind::Bus);
+        assert_eq!(rtc.set_datetime(&DateTime::MAX), Err(ErrorKind::Bus));
+        assert_eq!(rtc.datetime(), DateTime::MIN);
+        assert_eq!(rtc.get_datetime(), Ok(DateTime::MIN));
+    }
+
+    #[test]
+    fn test_nvram_boundaries() {
+        let mut rtc = FakeRtc::new(DateTime::MIN).with_nvram(56);
+        assert_eq!(rtc.nvram_size(), 56);
+
+        // Last byte
+        rtc.write_nvram(55, &[0xAB]).unwrap();
+        let mut byte = [0];
+        rtc.read_nvram(55, &mut byte).unwrap();
+        assert_eq!(byte, [0xAB]);
+        rtc.write_nvram(54, &[1, 2]).unwrap();
+
+        // One past it
+        assert_eq!(rtc.write_nvram(56, &[0]), Err(ErrorKind::NvramOutOfBounds));
+        assert_eq!(
+            rtc.write_nvram(55, &[1, 2]),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(
+            rtc.read_nvram(56, &mut byte),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(
+            rtc.read_nvram(0, &mut [0; 57]),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+
+        assert_eq!(rtc.nvram_contents()[53..], [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_nvram_past_256_bytes() {
+        // PCF2127-sized RAM, addressed past the range of a `u8` offset
+        let mut rtc = FakeRtc::new(DateTime::MIN).with_nvram(512);
+        assert_eq!(rtc.nvram_size(), 512);
+        rtc.write_nvram(255, &[1, 2]).unwrap();
+        rtc.write_nvram(511, &[3]).unwrap();
+        assert_eq!(rtc.nvram_contents()[255..257], [1, 2]);
+        assert_eq!(rtc.nvram_contents()[511], 3);
+
+        assert_eq!(
+            rtc.write_nvram(511, &[0, 0]),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(
+            rtc.read_nvram(u32::MAX, &mut [0; 2]),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(rtc.nvram_contents()[511], 3);
+    }
+
+    #[test]
+    fn test_nvram_write_protection() {
+        let mut rtc = FakeRtc::new(DateTime::MIN).with_nvram(8);
+        rtc.write_nvram(0, &[1, 2, 3]).unwrap();
+        rtc.set_write_protected(true);
+        assert_eq!(
+            rtc.write_nvram(0, &[9]),
+            Err(ErrorKind::NvramWriteProtected)
+        );
+        // Bounds are checked first
+        assert_eq!(rtc.write_nvram(8, &[9]), Err(ErrorKind::NvramOutOfBounds));
+        let mut buffer = [0; 3];
+        rtc.read_nvram(0, &mut buffer).unwrap();
+        assert_eq!(buffer, [1, 2, 3]);
+
+        rtc.set_write_protected(false);
+        rtc.write_nvram(0, &[9]).unwrap();
+        assert_eq!(rtc.nvram_contents(), [9, 2, 3, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_fill_and_erase_nvram() {
+        let mut rtc = FakeRtc::new(DateTime::MIN).with_nvram(56);
+        rtc.fill_nvram(1, 50, 0xFF).unwrap();
+        assert_eq!(rtc.nvram_contents()[0], 0);
+        assert!(rtc.nvram_contents()[1..51].iter().all(|&b| b == 0xFF));
+        assert_eq!(rtc.nvram_contents()[51..], [0; 5]);
+
+        assert_eq!(
+            rtc.fill_nvram(51, 6, 0xFF),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(rtc.nvram_contents()[51..], [0; 5]);
+
+        rtc.set_write_protected(true);
+        assert_eq!(rtc.erase_nvram(), Err(ErrorKind::NvramWriteProtected));
+        rtc.set_write_protected(false);
+        rtc.erase_nvram().unwrap();
+        assert_eq!(rtc.nvram_contents(), [0; 56]);
+    }
+
+    #[test]
+    fn test_nvram_protect_trait() {
+        let mut rtc = FakeRtc::new(DateTime::MIN).with_nvram(4);
+        assert_eq!(rtc.nvram_write_protected(), Ok(false));
+        rtc.set_nvram_write_protect(true).unwrap();
+        assert_eq!(rtc.nvram_write_protected(), Ok(true));
+        assert_eq!(
+            rtc.write_nvram(0, &[1]),
+            Err(ErrorKind::NvramWriteProtected)
+        );
+        assert_eq!(rtc.nvram_contents(), [0; 4]);
+
+        // The test-side switch and the trait share the same lock
+        rtc.set_write_protected(false);
+        assert_eq!(rtc.nvram_write_protected(), Ok(false));
+        rtc.write_nvram(0, &[1]).unwrap();
+        assert_eq!(rtc.nvram_contents(), [1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_no_nvram_by_default() {
+        let mut rtc = FakeRtc::new(DateTime::MIN);
+        assert_eq!(rtc.nvram_size(), 0);
+        assert_eq!(rtc.read_nvram(0, &mut []), Ok(()));
+        assert_eq!(
+            rtc.read_nvram(0, &mut [0]),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+    }
+
+    fn at(hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(2024, 8, 16, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn test_alarm_fires_at_exact_boundary() {
+        let mut rtc = FakeRtc::new(at(6, 29, 58));
+        rtc.arm_alarm(0, AlarmConfig::daily_at(6, 30, 0));
+        rtc.advance_seconds(1);
+        assert!(!rtc.alarm_pending(0));
+        rtc.advance_seconds(1);
+        assert!(rtc.alarm_pending(0));
+        assert_eq!(rtc.take_fired_alarms(), [(0, at(6, 30, 0))]);
+
+        // The flag stays set until cleared, and the alarm does not fire again today
+        rtc.advance_seconds(60);
+        assert!(rtc.alarm_pending(0));
+        assert!(rtc.take_fired_alarms().is_empty());
+        rtc.clear_alarm_pending(0);
+        assert!(!rtc.alarm_pending(0));
+    }
+
+    #[test]
+    fn test_daily_alarm_repeats() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        rtc.arm_alarm(0, AlarmConfig::daily_at(6, 30, 0));
+        rtc.advance_seconds(2 * 86_400);
+        let tomorrow = at(6, 30, 0).checked_add_days(1).unwrap();
+        assert_eq!(rtc.take_fired_alarms(), [(0, at(6, 30, 0)), (0, tomorrow)]);
+    }
+
+    #[test]
+    fn test_multiple_alarms() {
+        let mut rtc = FakeRtc::new(at(12, 0, 0));
+        rtc.arm_alarm(2, AlarmConfig::daily_at(12, 0, 10));
+        rtc.arm_alarm(0, AlarmConfig::daily_at(12, 0, 20));
+        rtc.arm_alarm(1, AlarmConfig::daily_at(12, 0, 10));
+        rtc.arm_alarm(3, AlarmConfig::daily_at(13, 0, 0));
+        rtc.disarm_alarm(3);
+        rtc.advance_seconds(3_600);
+        assert_eq!(
+            rtc.take_fired_alarms(),
+            [(1, at(12, 0, 10)), (2, at(12, 0, 10)), (0, at(12, 0, 20))]
+        );
+        assert!(rtc.alarm_pending(0) && rtc.alarm_pending(1) && rtc.alarm_pending(2));
+        assert!(!rtc.alarm_pending(3));
+        rtc.clear_alarm_pending(1);
+        assert!(rtc.alarm_pending(0) && !rtc.alarm_pending(1) && rtc.alarm_pending(2));
+    }
+
+    #[test]
+    fn test_alarm_in_the_past_or_jumped_over() {
+        let mut rtc = FakeRtc::new(at(12, 0, 0));
+        rtc.arm_alarm(0, AlarmConfig::daily_at(11, 0, 0));
+        rtc.arm_alarm(1, AlarmConfig::daily_at(12, 0, 0));
+        rtc.advance_seconds(3_600);
+        assert!(rtc.take_fired_alarms().is_empty());
+
+        rtc.arm_alarm(0, AlarmConfig::daily_at(14, 0, 0));
+        rtc.set_datetime(&at(15, 0, 0)).unwrap();
+        rtc.advance_seconds(1);
+        assert!(!rtc.alarm_pending(0));
+    }
+
+    #[test]
+    fn test_alarm_skipped_while_halted() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        rtc.arm_alarm(0, AlarmConfig::daily_at(6, 30, 0));
+        rtc.halt_clock().unwrap();
+        rtc.advance_seconds(3_600);
+        assert!(!rtc.alarm_pending(0));
+
+        // Resumes from the frozen time, so the alarm is still ahead
+        rtc.start_clock().unwrap();
+        rtc.advance_seconds(1_800);
+        assert_eq!(rtc.take_fired_alarms(), [(0, at(6, 30, 0))]);
+    }
+
+    #[test]
+    fn test_alarm_trait_arm_trigger_clear() {
+        let alarm = AlarmId::ALARM1;
+        let mut rtc = FakeRtc::new(at(23, 59, 0));
+        assert_eq!(
+            rtc.set_alarm(alarm, &AlarmConfig::daily_at(0, 60, 0)),
+            Err(ErrorKind::InvalidAlarmConfig)
+        );
+        rtc.set_alarm(alarm, &AlarmConfig::every_minute_at(30))
+            .unwrap();
+        rtc.advance_seconds(29);
+        assert_eq!(rtc.alarm_triggered(alarm), Ok(false));
+        rtc.advance_seconds(1);
+        for _ in 0..3 {
+            assert_eq!(rtc.alarm_triggered(alarm), Ok(true));
+        }
+        rtc.clear_alarm_flag(alarm).unwrap();
+        assert_eq!(rtc.alarm_triggered(alarm), Ok(false));
+
+        // Matches again across midnight
+        rtc.advance_seconds(60);
+        assert_eq!(rtc.alarm_triggered(alarm), Ok(true));
+        assert_eq!(rtc.take_fired_alarms().len(), 2);
+        rtc.clear_alarm_flag(alarm).unwrap();
+        rtc.disable_alarm(alarm).unwrap();
+        rtc.advance_seconds(600);
+        assert_eq!(rtc.alarm_triggered(alarm), Ok(false));
+    }
+
+    #[test]
+    fn test_interrupt_gating() {
+        let mut rtc = FakeRtc::new(at(6, 29, 0)).with_alarms(2);
+        rtc.set_alarm(AlarmId::ALARM1, &AlarmConfig::daily_at(6, 29, 30))
+            .unwrap();
+        rtc.set_alarm(AlarmId::ALARM2, &AlarmConfig::daily_at(6, 30, 0))
+            .unwrap();
+        rtc.enable_alarm_interrupt(AlarmId::ALARM2).unwrap();
+
+        // Configured with the interrupt disabled: the flag is still set
+        rtc.advance_seconds(30);
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+        assert!(!rtc.interrupt_asserted());
+
+        rtc.advance_seconds(30);
+        assert!(rtc.interrupt_asserted());
+        rtc.clear_alarm_flag(AlarmId::ALARM2).unwrap();
+        assert!(!rtc.interrupt_asserted());
+
+        // Enabling while the flag is set asserts the pin at once
+        rtc.enable_alarm_interrupt(AlarmId::ALARM1).unwrap();
+        assert!(rtc.interrupt_asserted());
+        rtc.disable_alarm_interrupt(AlarmId::ALARM1).unwrap();
+        assert!(!rtc.interrupt_asserted());
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+    }
+
+    #[test]
+    fn test_output_pin_modes_are_exclusive() {
+        let mut rtc = FakeRtc::new(at(6, 29, 0));
+        assert_eq!(rtc.output_pin(), OutputPinConfig::AlarmInterrupt);
+        rtc.set_alarm(AlarmId::ALARM1, &AlarmConfig::daily_at(6, 30, 0))
+            .unwrap();
+        rtc.enable_alarm_interrupt(AlarmId::ALARM1).unwrap();
+
+        // The square wave takes the pin: the alarm still fires, silently
+        rtc.start_square_wave(SquareWaveFreq::Hz1024).unwrap();
+        assert_eq!(
+            rtc.current_pin_mode(),
+            Ok(OutputPinConfig::SquareWave(SquareWaveFreq::Hz1024))
+        );
+        rtc.advance_seconds(60);
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+        assert!(!rtc.interrupt_asserted());
+        rtc.set_square_wave_frequency(SquareWaveFreq::Hz4096)
+            .unwrap();
+        assert_eq!(
+            rtc.output_pin(),
+            OutputPinConfig::SquareWave(SquareWaveFreq::Hz4096)
+        );
+
+        // Re-enabling the alarm interrupt takes it back and asserts the pending flag
+        rtc.enable_alarm_interrupt(AlarmId::ALARM1).unwrap();
+        assert_eq!(rtc.current_pin_mode(), Ok(OutputPinConfig::AlarmInterrupt));
+        assert!(rtc.interrupt_asserted());
+
+        let low = OutputPinConfig::Disabled {
+            level: DisabledOutputLevel::Low,
+        };
+        rtc.configure_output_pin(low).unwrap();
+        assert!(!rtc.interrupt_asserted());
+        rtc.set_square_wave_frequency(SquareWaveFreq::Hz1).unwrap();
+        assert_eq!(rtc.output_pin(), low);
+
+        // Enabling resumes the last frequency, disabling the last level
+        rtc.enable_square_wave().unwrap();
+        assert_eq!(
+            rtc.output_pin(),
+            OutputPinConfig::SquareWave(SquareWaveFreq::Hz1)
+        );
+        rtc.disable_square_wave().unwrap();
+        assert_eq!(rtc.output_pin(), low);
+        rtc.configure_output_pin(OutputPinConfig::AlarmInterrupt)
+            .unwrap();
+        assert!(rtc.interrupt_asserted());
+    }
+
+    #[test]
+    fn test_square_wave_config_reads_back() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        let config = |enabled, freq| {
+            Ok(SquareWaveConfig {
+                enabled,
+                freq,
+                disabled_level: DisabledOutputLevel::High,
+            })
+        };
+        assert_eq!(rtc.square_wave_config(), config(false, SquareWaveFreq::Hz1));
+
+        rtc.start_square_wave(SquareWaveFreq::Hz4096).unwrap();
+        assert_eq!(
+            rtc.square_wave_config(),
+            config(true, SquareWaveFreq::Hz4096)
+        );
+        rtc.disable_square_wave().unwrap();
+        rtc.set_square_wave_frequency(SquareWaveFreq::Hz1024)
+            .unwrap();
+        assert_eq!(
+            rtc.square_wave_config(),
+            config(false, SquareWaveFreq::Hz1024)
+        );
+        rtc.enable_square_wave().unwrap();
+        assert_eq!(
+            rtc.square_wave_config(),
+            config(true, SquareWaveFreq::Hz1024)
+        );
+
+        // The alarm interrupt takes the pin but the frequency is kept
+        rtc.enable_alarm_interrupt(AlarmId::ALARM1).unwrap();
+        assert_eq!(
+            rtc.square_wave_config(),
+            config(false, SquareWaveFreq::Hz1024)
+        );
+        rtc.set_error(ErrorKind::Bus);
+        assert_eq!(rtc.square_wave_config(), Err(ErrorKind::Bus));
+    }
+
+    #[test]
+    fn test_unsupported_square_wave_frequencies_fail() {
+        const DS3231: [SquareWaveFreq; 4] = [
+            SquareWaveFreq::Hz1,
+            SquareWaveFreq::Hz1024,
+            SquareWaveFreq::Hz4096,
+            SquareWaveFreq::Hz8192,
+        ];
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        assert_eq!(
+            rtc.supported_square_wave_frequencies(),
+            SquareWaveFreq::STANDARD
+        );
+        assert_eq!(
+            rtc.start_square_wave(SquareWaveFreq::Custom(2)),
+            Err(ErrorKind::UnsupportedSqwFrequency)
+        );
+
+        let mut rtc = rtc.with_square_wave_frequencies(&DS3231);
+        rtc.start_square_wave(SquareWaveFreq::Custom(1024)).unwrap();
+        assert_eq!(
+            rtc.set_square_wave_frequency(SquareWaveFreq::Hz32768),
+            Err(ErrorKind::UnsupportedSqwFrequency)
+        );
+        assert_eq!(
+            rtc.configure_output_pin(OutputPinConfig::SquareWave(SquareWaveFreq::Hz32768)),
+            Err(ErrorKind::UnsupportedSqwFrequency)
+        );
+        assert_eq!(
+            rtc.output_pin(),
+            OutputPinConfig::SquareWave(SquareWaveFreq::Custom(1024))
+        );
+
+        let rtc = FakeRtc::new(at(6, 0, 0)).with_square_wave_frequencies(&DS3231[1..]);
+        assert_eq!(
+            rtc.clone().square_wave_config().map(|config| config.freq),
+            Ok(SquareWaveFreq::Hz1024)
+        );
+    }
+
+    #[test]
+    fn test_square_wave_on_battery() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        assert!(rtc.square_wave_running());
+        assert_eq!(rtc.is_square_wave_on_battery_enabled(), Ok(false));
+
+        // Stops on battery by default, and resumes with main power
+        rtc.set_on_battery(true);
+        assert!(!rtc.square_wave_running());
+        rtc.set_on_battery(false);
+        assert!(rtc.square_wave_running());
+
+        rtc.enable_square_wave_on_battery(true).unwrap();
+        rtc.set_on_battery(true);
+        assert!(rtc.square_wave_running());
+
+        // Disabling the output still stops it on battery
+        rtc.disable_square_wave().unwrap();
+        assert!(!rtc.square_wave_running());
+        rtc.enable_square_wave().unwrap();
+        assert!(rtc.square_wave_running());
+
+        rtc.enable_square_wave_on_battery(false).unwrap();
+        assert!(!rtc.square_wave_running());
+        assert_eq!(
+            rtc.square_wave_config().map(|config| config.enabled),
+            Ok(true)
+        );
+        rtc.set_error(ErrorKind::Bus);
+        assert_eq!(rtc.enable_square_wave_on_battery(true), Err(ErrorKind::Bus));
+        assert_eq!(rtc.is_square_wave_on_battery_enabled(), Ok(false));
+    }
+
+    #[test]
+    fn test_single_output_pin() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        rtc.start_square_wave_on(OutputPin::PRIMARY, SquareWaveFreq::Hz4096)
+            .unwrap();
+        assert_eq!(
+            rtc.start_square_wave_on(OutputPin::SECONDARY, SquareWaveFreq::Hz1),
+            Err(ErrorKind::InvalidAddress)
+        );
+        assert_eq!(
+            rtc.disable_square_wave_on(OutputPin::SECONDARY),
+            Err(ErrorKind::InvalidAddress)
+        );
+        assert!(rtc.square_wave_running());
+        assert_eq!(
+            rtc.square_wave_config().unwrap().freq,
+            SquareWaveFreq::Hz4096
+        );
+        rtc.disable_square_wave_on(OutputPin::PRIMARY).unwrap();
+        assert!(!rtc.square_wave_running());
+    }
+
+    #[test]
+    fn test_32khz_output_independent_of_square_wave() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        assert_eq!(rtc.is_32khz_output_enabled(), Ok(true));
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        rtc.disable_32khz_output().unwrap();
+        assert_eq!(rtc.is_32khz_output_enabled(), Ok(false));
+        assert!(rtc.square_wave_running());
+
+        rtc.disable_square_wave().unwrap();
+        rtc.enable_32khz_output().unwrap();
+        assert_eq!(rtc.is_32khz_output_enabled(), Ok(true));
+        assert!(!rtc.square_wave_running());
+    }
+
+    #[test]
+    fn test_duty_cycle_kept_across_frequencies() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        assert_eq!(rtc.duty_cycle(), Ok(DutyCycle::HALF));
+        let quarter = DutyCycle::new(25).unwrap();
+        rtc.set_duty_cycle(quarter).unwrap();
+        rtc.start_square_wave(SquareWaveFreq::Hz4096).unwrap();
+        assert_eq!(rtc.duty_cycle(), Ok(quarter));
+        rtc.set_error(ErrorKind::Bus);
+        assert_eq!(rtc.set_duty_cycle(DutyCycle::HALF), Err(ErrorKind::Bus));
+        assert_eq!(rtc.duty_cycle(), Ok(quarter));
+    }
+
+    #[test]
+    fn test_disabled_output_level() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+
+        // Stored while the square wave runs, applied when it stops
+        rtc.set_disabled_output_level(DisabledOutputLevel::Low)
+            .unwrap();
+        assert_eq!(
+            rtc.output_pin(),
+            OutputPinConfig::SquareWave(SquareWaveFreq::Hz1)
+        );
+        rtc.disable_square_wave().unwrap();
+        let disabled = |level| OutputPinConfig::Disabled { level };
+        assert_eq!(rtc.output_pin(), disabled(DisabledOutputLevel::Low));
+        rtc.set_disabled_output_level(DisabledOutputLevel::HighImpedance)
+            .unwrap();
+        assert_eq!(
+            rtc.output_pin(),
+            disabled(DisabledOutputLevel::HighImpedance)
+        );
+        assert_eq!(
+            rtc.square_wave_config().map(|config| config.disabled_level),
+            Ok(DisabledOutputLevel::HighImpedance)
+        );
+
+        let mut rtc = FakeRtc::new(at(6, 0, 0)).with_push_pull_output();
+        rtc.disable_square_wave().unwrap();
+        assert_eq!(
+            rtc.set_disabled_output_level(DisabledOutputLevel::HighImpedance),
+            Err(ErrorKind::Unsupported)
+        );
+        assert_eq!(
+            rtc.configure_output_pin(disabled(DisabledOutputLevel::HighImpedance)),
+            Err(ErrorKind::Unsupported)
+        );
+        assert_eq!(rtc.output_pin(), disabled(DisabledOutputLevel::High));
+        rtc.set_disabled_output_level(DisabledOutputLevel::Low)
+            .unwrap();
+        assert_eq!(rtc.output_pin(), disabled(DisabledOutputLevel::Low));
+    }
+
+    #[test]
+    fn test_alarm_trait_ids() {
+        let mut rtc = FakeRtc::new(at(6, 0, 0));
+        assert_eq!(rtc.alarm_count(), 1);
+        assert_eq!(
+            rtc.set_alarm(AlarmId::ALARM2, &AlarmConfig::daily_at(7, 0, 0)),
+            Err(ErrorKind::InvalidAlarmConfig)
+        );
+
+        let mut rtc = rtc.with_alarms(2);
+        rtc.set_alarm(AlarmId::ALARM1, &AlarmConfig::daily_at(6, 30, 0))
+            .unwrap();
+        rtc.set_alarm(AlarmId::ALARM2, &AlarmConfig::daily_at(7, 0, 0))
+            .unwrap();
+        rtc.advance_seconds(1_800);
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM1), Ok(true));
+        assert_eq!(rtc.alarm_triggered(AlarmId::ALARM2), Ok(false));
+        rtc.advance_seconds(1_800);
+        assert_eq!(
+            rtc.take_fired_alarms(),
+            [(0, at(6, 30, 0)), (1, at(7, 0, 0))]
+        );
+        assert_eq!(
+            rtc.alarm_triggered(AlarmId(2)),
+            Err(ErrorKind::InvalidAlarmConfig)
+        );
+    }
+
+    #[test]
+    fn test_tick_enable_flag_clear() {
+        let mut rtc = FakeRtc::new(at(6, 0, 58));
+        rtc.advance_seconds(1);
+        assert_eq!(rtc.tick_flag(), Ok(false));
+
+        rtc.enable_tick(TickPeriod::Second).unwrap();
+        rtc.advance_seconds(1);
+        assert_eq!(rtc.tick_flag(), Ok(true));
+        rtc.clear_tick_flag().unwrap();
+        assert_eq!(rtc.tick_flag(), Ok(false));
+
+        // Only the rollover to a new minute ticks
+        rtc.enable_tick(TickPeriod::Minute).unwrap();
+        rtc.advance_seconds(59);
+        assert_eq!(rtc.tick_flag(), Ok(false));
+        rtc.advance_seconds(1);
+        assert_eq!(rtc.tick_flag(), Ok(true));
+
+        // Disabling keeps a pending flag
+        rtc.disable_tick().unwrap();
+        assert_eq!(rtc.tick_flag(), Ok(true));
+        rtc.clear_tick_flag().unwrap();
+        rtc.advance_seconds(120);
+        assert_eq!(rtc.tick_flag(), Ok(false));
+
+        assert_eq!(
+            rtc.enable_tick(TickPeriod::Custom(0)),
+            Err(ErrorKind::Unsupported)
+        );
+        rtc.enable_tick(TickPeriod::Custom(64)).unwrap();
+        rtc.halt_clock().unwrap();
+        rtc.advance_seconds(1);
+        assert_eq!(rtc.tick_flag(), Ok(false));
+    }
+
+    #[test]
+    #[should_panic(expected = "past DateTime::MAX")]
+    fn test_advance_past_max_panics() {
+        FakeRtc::new(DateTime::MAX).advance_seconds(1);
+    }
+}
@@ -0,0 +1,396 @@
+//! Decorator injecting errors on a schedule
+use crate::{
+    control::{ClockIntegrity, RtcPowerControl},
+    datetime::{Date, DateTime, Time},
+    error::{ErrorKind, ErrorType, Operation},
+    nvram::RtcNvram,
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, OutputPinConfig, SquareWave, SquareWaveConfig, SquareWaveFreq,
+    },
+    timestamp::RtcTimestamp,
+};
+
+/// When calls of one operation class fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fault {
+    EveryNth(u32, ErrorKind),
+    After(u32, ErrorKind),
+    Always(ErrorKind),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Schedule {
+    fault: Option<Fault>,
+    calls: u32,
+}
+
+const CLASSES: usize = 9;
+
+/// Index of an operation class in the schedule table
+fn slot(operation: Operation) -> usize {
+    match operation {
+        Operation::ReadTime => 0,
+        Operation::WriteTime => 1,
+        Operation::ReadNvram => 2,
+        Operation::WriteNvram => 3,
+        Operation::ConfigureAlarm => 4,
+        Operation::AlarmStatus => 5,
+        Operation::ConfigureSquareWave => 6,
+        Operation::PowerControl => 7,
+        Operation::Control => 8,
+    }
+}
+
+/// Wrapper that makes calls to the inner RTC fail on a programmable schedule
+///
+/// Each operation class, identified by an [`Operation`], has its own call
+/// counter and at most one fault. A failing call returns the scheduled
+/// [`ErrorKind`] without reaching the inner RTC. Classes are assigned as:
+///
+/// | Calls | Class |
+/// |-------|-------|
+/// | `get_datetime`, `get_time`, `get_date`, `get_timestamp` | [`Operation::ReadTime`] |
+/// | `set_datetime`, `set_time`, `set_date`, `set_timestamp` | [`Operation::WriteTime`] |
+/// | `read_nvram` | [`Operation::ReadNvram`] |
+/// | `write_nvram` | [`Operation::WriteNvram`] |
+/// | [`SquareWave`] methods | [`Operation::ConfigureSquareWave`] |
+/// | `start_clock`, `halt_clock` | [`Operation::PowerControl`] |
+/// | [`ClockIntegrity`] methods | [`Operation::Control`] |
+///
+/// The inner error type must be constructible from an `ErrorKind`, as it is
+/// for [`FakeRtc`](super::FakeRtc) and [`MockRtc`](super::MockRtc). Wrapping
+/// the result in a [`RetryRtc`](crate::retry::RetryRtc) exercises retry logic:
+///
+/// ```
+/// use rtc_hal::datetime::DateTime;
+/// use rtc_hal::error::{ErrorKind, Operation};
+/// use rtc_hal::mock::{FakeRtc, FaultyRtc};
+/// use rtc_hal::retry::RetryRtc;
+/// use rtc_hal::rtc::Rtc;
+///
+/// let mut faulty = FaultyRtc::new(FakeRtc::new(DateTime::MIN));
+/// faulty.fail_nth(Operation::ReadTime, 2, ErrorKind::Bus);
+/// let mut rtc = RetryRtc::<_, 1>::new(faulty);
+/// for _ in 0..4 {
+///     assert_eq!(rtc.get_datetime(), Ok(DateTime::MIN));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FaultyRtc<R> {
+    rtc: R,
+    schedules: [Schedule; CLASSES],
+}
+
+impl<R> FaultyRtc<R> {
+    /// Wrap `rtc` with no faults scheduled
+    pub fn new(rtc: R) -> Self {
+        Self {
+            rtc,
+            schedules: [Schedule::default(); CLASSES],
+        }
+    }
+
+    /// Fail every `n`th call of `operation` (the `n`th, `2n`th, ...) with `kind`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn fail_nth(&mut self, operation: Operation, n: u32, kind: ErrorKind) {
+        assert!(n > 0, "n must be at least 1");
+        self.schedule(operation, Fault::EveryNth(n, kind));
+    }
+
+    /// Let the first `count` calls of `operation` through, then fail all later ones with `kind`
+    pub fn fail_after(&mut self, operation: Operation, count: u32, kind: ErrorKind) {
+        self.schedule(operation, Fault::After(count, kind));
+    }
+
+    /// Fail every call of `operation` with `kind`
+    pub fn always(&mut self, operation: Operation, kind: ErrorKind) {
+        self.schedule(operation, Fault::Always(kind));
+    }
+
+    /// Remove the fault for `operation`, so its calls reach the inner RTC again
+    pub fn clear(&mut self, operation: Operation) {
+        self.schedules[slot(operation)] = Schedule::default();
+    }
+
+    /// Number of calls of `operation` so far, including failed ones
+    pub fn calls(&self, operation: Operation) -> u32 {
+        self.schedules[slot(operation)].calls
+    }
+
+    /// Access the inner RTC, e.g. to advance a [`FakeRtc`](super::FakeRtc)
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.rtc
+    }
+
+    /// Release the inner RTC
+    pub fn into_inner(self) -> R {
+        self.rtc
+    }
+
+    /// Install a fault, restarting the call count
+    fn schedule(&mut self, operation: Operation, fault: Fault) {
+        self.schedules[slot(operation)] = Schedule {
+            fault: Some(fault),
+            calls: 0,
+        };
+    }
+
+    /// Count a call and return the scheduled error, if it should fail
+    fn check(&mut self, operation: Operation) -> Result<(), ErrorKind> {
+        let schedule = &mut self.schedules[slot(operation)];
+        schedule.calls += 1;
+        let error = match schedule.fault {
+            Some(Fault::EveryNth(n, kind)) if schedule.calls.is_multiple_of(n) => Some(kind),
+            Some(Fault::After(count, kind)) if schedule.calls > count => Some(kind),
+            Some(Fault::Always(kind)) => Some(kind),
+            _ => None,
+        };
+        error.map_or(Ok(()), Err)
+    }
+}
+
+impl<R: ErrorType> ErrorType for FaultyRtc<R> {
+    type Error = R::Error;
+}
+
+impl<R: Rtc> Rtc for FaultyRtc<R>
+where
+    R::Error: From<ErrorKind>,
+{
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        self.check(Operation::ReadTime)?;
+        self.rtc.get_datetime()
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.check(Operation::WriteTime)?;
+        self.rtc.set_datetime(datetime)
+    }
+
+    fn get_time(&mut self) -> Result<Time, Self::Error> {
+        self.check(Operation::ReadTime)?;
+        self.rtc.get_time()
+    }
+
+    fn get_date(&mut self) -> Result<Date, Self::Error> {
+        self.check(Operation::ReadTime)?;
+        self.rtc.get_date()
+    }
+
+    fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+        self.check(Operation::WriteTime)?;
+        self.rtc.set_time(time)
+    }
+
+    fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+        self.check(Operation::WriteTime)?;
+        self.rtc.set_date(date)
+    }
+}
+
+impl<R: RtcNvram> RtcNvram for FaultyRtc<R>
+where
+    R::Error: From<ErrorKind>,
+{
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.check(Operation::ReadNvram)?;
+        self.rtc.read_nvram(offset, buffer)
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        self.check(Operation::WriteNvram)?;
+        self.rtc.write_nvram(offset, data)
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.rtc.nvram_size()
+    }
+
+    fn fill_nvram(&mut self, offset: u32, len: u32, value: u8) -> Result<(), Self::Error> {
+        self.check(Operation::WriteNvram)?;
+        self.rtc.fill_nvram(offset, len, value)
+    }
+
+    fn erase_nvram(&mut self) -> Result<(), Self::Error> {
+        self.check(Operation::WriteNvram)?;
+        self.rtc.erase_nvram()
+    }
+}
+
+impl<R: RtcPowerControl> RtcPowerControl for FaultyRtc<R>
+where
+    R::Error: From<ErrorKind>,
+{
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        self.check(Operation::PowerControl)?;
+        self.rtc.start_clock()
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        self.check(Operation::PowerControl)?;
+        self.rtc.halt_clock()
+    }
+}
+
+impl<R: ClockIntegrity> ClockIntegrity for FaultyRtc<R>
+where
+    R::Error: From<ErrorKind>,
+{
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+        self.check(Operation::Control)?;
+        self.rtc.datetime_valid()
+    }
+
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+        self.check(Operation::Control)?;
+        self.rtc.clear_integrity_flag()
+    }
+}
+
+impl<R: SquareWave> SquareWave for FaultyRtc<R>
+where
+    R::Error: From<ErrorKind>,
+{
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.configure_output_pin(config)
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.start_square_wave(freq)
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.enable_square_wave()
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.disable_square_wave()
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.set_square_wave_frequency(freq)
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.set_disabled_output_level(level)
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.square_wave_config()
+    }
+
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        self.check(Operation::ConfigureSquareWave)?;
+        self.rtc.current_pin_mode()
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        self.rtc.supported_square_wave_frequencies()
+    }
+}
+
+impl<R: RtcTimestamp> RtcTimestamp for FaultyRtc<R>
+where
+    R::Error: From<ErrorKind>,
+{
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        self.check(Operation::ReadTime)?;
+        self.rtc.get_timestamp()
+    }
+
+    fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+        self.check(Operation::WriteTime)?;
+        self.rtc.set_timestamp(secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{FakeRtc, MockRtc, Transaction};
+    use crate::retry::RetryRtc;
+
+    fn fake() -> FaultyRtc<FakeRtc> {
+        FaultyRtc::new(FakeRtc::new(DateTime::MIN))
+    }
+
+    #[test]
+    fn test_fail_nth() {
+        let mut rtc = fake();
+        rtc.fail_nth(Operation::ReadTime, 3, ErrorKind::Bus);
+        let results: [_; 6] = core::array::from_fn(|_| rtc.get_datetime().is_ok());
+        assert_eq!(results, [true, true, false, true, true, false]);
+        // Other classes are unaffected
+        assert_eq!(rtc.set_datetime(&DateTime::MIN), Ok(()));
+        assert_eq!(rtc.calls(Operation::ReadTime), 6);
+    }
+
+    #[test]
+    fn test_fail_after() {
+        let mut rtc = FaultyRtc::new(MockRtc::new(&[
+            Transaction::write_nvram(0, &[1]),
+            Transaction::write_nvram(1, &[2]),
+        ]));
+        rtc.fail_after(Operation::WriteNvram, 2, ErrorKind::NvramWriteProtected);
+        assert_eq!(rtc.write_nvram(0, &[1]), Ok(()));
+        assert_eq!(rtc.write_nvram(1, &[2]), Ok(()));
+        assert_eq!(
+            rtc.write_nvram(2, &[3]),
+            Err(ErrorKind::NvramWriteProtected)
+        );
+        // Failed calls never reach the inner RTC
+        rtc.into_inner().done();
+    }
+
+    #[test]
+    fn test_fill_counts_as_one_write() {
+        let mut rtc = FaultyRtc::new(FakeRtc::new(DateTime::MIN).with_nvram(40));
+        rtc.fail_nth(Operation::WriteNvram, 2, ErrorKind::Bus);
+        rtc.fill_nvram(0, 40, 0xFF).unwrap();
+        assert_eq!(rtc.erase_nvram(), Err(ErrorKind::Bus));
+        assert_eq!(rtc.inner_mut().nvram_contents(), [0xFF; 40]);
+        assert_eq!(rtc.calls(Operation::WriteNvram), 2);
+    }
+
+    #[test]
+    fn test_always_and_clear() {
+        let mut rtc = fake();
+        rtc.always(Operation::PowerControl, ErrorKind::ClockIntegrity);
+        assert_eq!(rtc.halt_clock(), Err(ErrorKind::ClockIntegrity));
+        assert_eq!(rtc.start_clock(), Err(ErrorKind::ClockIntegrity));
+        assert!(!rtc.inner_mut().is_halted());
+
+        rtc.clear(Operation::PowerControl);
+        assert_eq!(rtc.halt_clock(), Ok(()));
+        assert!(rtc.inner_mut().is_halted());
+    }
+
+    #[test]
+    fn test_with_retry() {
+        let mut faulty = fake();
+        faulty.fail_nth(Operation::WriteTime, 2, ErrorKind::Bus);
+        faulty.always(Operation::ReadTime, ErrorKind::Bus);
+        let mut rtc = RetryRtc::<_, 2>::new(faulty);
+
+        for _ in 0..3 {
+            assert_eq!(rtc.set_datetime(&DateTime::MAX), Ok(()));
+        }
+        assert_eq!(rtc.get_datetime(), Err(ErrorKind::Bus));
+
+        let faulty = rtc.into_inner();
+        // Calls 1, 2 (fails, retried as 3), 4 (fails, retried as 5)
+        assert_eq!(faulty.calls(Operation::WriteTime), 5);
+        assert_eq!(faulty.calls(Operation::ReadTime), 3);
+    }
+}
@@ -0,0 +1,342 @@
+//! Decorator recording every call for later assertions
+use alloc::vec::Vec;
+
+use crate::{
+    control::RtcPowerControl,
+    datetime::DateTime,
+    error::{Error, ErrorKind, ErrorType},
+    nvram::RtcNvram,
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, OutputPinConfig, SquareWave, SquareWaveConfig, SquareWaveFreq,
+    },
+};
+
+/// One call recorded by [`RecordingRtc`], with its arguments and result
+///
+/// Errors are recorded by their [`ErrorKind`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+    /// [`Rtc::get_datetime`]
+    GetDateTime(Result<DateTime, ErrorKind>),
+    /// [`Rtc::set_datetime`]
+    SetDateTime(DateTime, Result<(), ErrorKind>),
+    /// [`RtcNvram::read_nvram`] at an offset, with the bytes read
+    ReadNvram(u32, Result<Vec<u8>, ErrorKind>),
+    /// [`RtcNvram::write_nvram`] at an offset, with the bytes written
+    WriteNvram(u32, Vec<u8>, Result<(), ErrorKind>),
+    /// [`RtcNvram::fill_nvram`] at an offset, with the length and value
+    FillNvram(u32, u32, u8, Result<(), ErrorKind>),
+    /// [`RtcNvram::erase_nvram`]
+    EraseNvram(Result<(), ErrorKind>),
+    /// [`SquareWave::configure_output_pin`]
+    ConfigureOutputPin(OutputPinConfig, Result<(), ErrorKind>),
+    /// [`SquareWave::start_square_wave`]
+    StartSquareWave(SquareWaveFreq, Result<(), ErrorKind>),
+    /// [`SquareWave::enable_square_wave`]
+    EnableSquareWave(Result<(), ErrorKind>),
+    /// [`SquareWave::disable_square_wave`]
+    DisableSquareWave(Result<(), ErrorKind>),
+    /// [`SquareWave::set_square_wave_frequency`]
+    SetSquareWaveFrequency(SquareWaveFreq, Result<(), ErrorKind>),
+    /// [`SquareWave::set_disabled_output_level`]
+    SetDisabledOutputLevel(DisabledOutputLevel, Result<(), ErrorKind>),
+    /// [`SquareWave::square_wave_config`]
+    SquareWaveConfig(Result<SquareWaveConfig, ErrorKind>),
+    /// [`SquareWave::current_pin_mode`]
+    CurrentPinMode(Result<OutputPinConfig, ErrorKind>),
+    /// [`RtcPowerControl::start_clock`]
+    StartClock(Result<(), ErrorKind>),
+    /// [`RtcPowerControl::halt_clock`]
+    HaltClock(Result<(), ErrorKind>),
+}
+
+/// Wrapper that delegates to the inner RTC and records every call
+///
+/// Unlike [`MockRtc`](super::MockRtc), nothing is checked while the code under
+/// test runs; assertions are made on [`operations`](Self::operations)
+/// afterwards.
+///
+/// The provided [`Rtc`] methods such as [`Rtc::set_time`] use their default
+/// implementations and are recorded as the `get_datetime`/`set_datetime` calls
+/// they make.
+///
+/// ```
+/// use rtc_hal::datetime::DateTime;
+/// use rtc_hal::mock::{Call, FakeRtc, RecordingRtc};
+/// use rtc_hal::rtc::Rtc;
+///
+/// let mut rtc = RecordingRtc::new(FakeRtc::new(DateTime::MIN));
+/// rtc.set_datetime(&DateTime::new(2024, 8, 16, 12, 0, 0).unwrap()).unwrap();
+///
+/// let sets = rtc
+///     .operations()
+///     .iter()
+///     .filter(|call| matches!(call, Call::SetDateTime(dt, Ok(())) if dt.second() == 0))
+///     .count();
+/// assert_eq!(sets, 1);
+/// ```
+#[derive(Debug)]
+pub struct RecordingRtc<R> {
+    rtc: R,
+    calls: Vec<Call>,
+}
+
+impl<R> RecordingRtc<R> {
+    /// Wrap `rtc` with an empty record
+    pub fn new(rtc: R) -> Self {
+        Self {
+            rtc,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Calls recorded so far, oldest first
+    pub fn operations(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Forget all recorded calls
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    /// Access the inner RTC; calls made through it are not recorded
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.rtc
+    }
+
+    /// Release the inner RTC
+    pub fn into_inner(self) -> R {
+        self.rtc
+    }
+}
+
+/// Convert a result for recording, keeping only the error kind
+fn kind_of<T: Clone, E: Error>(result: &Result<T, E>) -> Result<T, ErrorKind> {
+    match result {
+        Ok(value) => Ok(value.clone()),
+        Err(e) => Err(e.kind()),
+    }
+}
+
+impl<R: ErrorType> ErrorType for RecordingRtc<R> {
+    type Error = R::Error;
+}
+
+impl<R: Rtc> Rtc for RecordingRtc<R> {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let result = self.rtc.get_datetime();
+        self.calls.push(Call::GetDateTime(kind_of(&result)));
+        result
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        let result = self.rtc.set_datetime(datetime);
+        self.calls
+            .push(Call::SetDateTime(*datetime, kind_of(&result)));
+        result
+    }
+}
+
+impl<R: RtcNvram> RtcNvram for RecordingRtc<R> {
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        let result = self.rtc.read_nvram(offset, buffer);
+        let data = kind_of(&result).map(|()| buffer.to_vec());
+        self.calls.push(Call::ReadNvram(offset, data));
+        result
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        let result = self.rtc.write_nvram(offset, data);
+        self.calls
+            .push(Call::WriteNvram(offset, data.to_vec(), kind_of(&result)));
+        result
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.rtc.nvram_size()
+    }
+
+    fn fill_nvram(&mut self, offset: u32, len: u32, value: u8) -> Result<(), Self::Error> {
+        let result = self.rtc.fill_nvram(offset, len, value);
+        self.calls
+            .push(Call::FillNvram(offset, len, value, kind_of(&result)));
+        result
+    }
+
+    fn erase_nvram(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.erase_nvram();
+        self.calls.push(Call::EraseNvram(kind_of(&result)));
+        result
+    }
+}
+
+impl<R: SquareWave> SquareWave for RecordingRtc<R> {
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        let result = self.rtc.configure_output_pin(config);
+        self.calls
+            .push(Call::ConfigureOutputPin(config, kind_of(&result)));
+        result
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        let result = self.rtc.start_square_wave(freq);
+        self.calls
+            .push(Call::StartSquareWave(freq, kind_of(&result)));
+        result
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.enable_square_wave();
+        self.calls.push(Call::EnableSquareWave(kind_of(&result)));
+        result
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.disable_square_wave();
+        self.calls.push(Call::DisableSquareWave(kind_of(&result)));
+        result
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        let result = self.rtc.set_square_wave_frequency(freq);
+        self.calls
+            .push(Call::SetSquareWaveFrequency(freq, kind_of(&result)));
+        result
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        let result = self.rtc.set_disabled_output_level(level);
+        self.calls
+            .push(Call::SetDisabledOutputLevel(level, kind_of(&result)));
+        result
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        let result = self.rtc.square_wave_config();
+        self.calls.push(Call::SquareWaveConfig(kind_of(&result)));
+        result
+    }
+
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        let result = self.rtc.current_pin_mode();
+        self.calls.push(Call::CurrentPinMode(kind_of(&result)));
+        result
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        self.rtc.supported_square_wave_frequencies()
+    }
+}
+
+impl<R: RtcPowerControl> RtcPowerControl for RecordingRtc<R> {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.start_clock();
+        self.calls.push(Call::StartClock(kind_of(&result)));
+        result
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        let result = self.rtc.halt_clock();
+        self.calls.push(Call::HaltClock(kind_of(&result)));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::Time;
+    use crate::mock::{MockRtc, Transaction};
+    use alloc::vec;
+
+    #[test]
+    fn test_records_scenario() {
+        let noon = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        let later = DateTime::new(2024, 8, 16, 13, 30, 0).unwrap();
+        let mut inner = MockRtc::new(&[
+            Transaction::halt_clock(),
+            Transaction::get_datetime(noon),
+            Transaction::set_datetime(later),
+            Transaction::start_clock(),
+            Transaction::write_nvram(0, &[7, 8]),
+            Transaction::read_nvram(0, &[7, 8]),
+            Transaction::read_nvram(60, &[0]).with_error(ErrorKind::NvramOutOfBounds),
+            Transaction::start_square_wave(SquareWaveFreq::Hz1),
+            Transaction::set_square_wave_frequency(SquareWaveFreq::Hz4096),
+            Transaction::disable_square_wave(),
+            Transaction::enable_square_wave().with_error(ErrorKind::Bus),
+        ]);
+        inner.set_nvram_size(56);
+        let mut rtc = RecordingRtc::new(inner);
+
+        // Set the time of day while the clock is halted
+        rtc.halt_clock().unwrap();
+        rtc.set_time(&Time::new(13, 30, 0).unwrap()).unwrap();
+        rtc.start_clock().unwrap();
+
+        rtc.write_nvram(0, &[7, 8]).unwrap();
+        let mut buffer = [0; 2];
+        rtc.read_nvram(0, &mut buffer).unwrap();
+        let _ = rtc.read_nvram(60, &mut [0]);
+        assert_eq!(rtc.nvram_size(), 56);
+
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        rtc.set_square_wave_frequency(SquareWaveFreq::Hz4096)
+            .unwrap();
+        rtc.disable_square_wave().unwrap();
+        let _ = rtc.enable_square_wave();
+
+        assert_eq!(
+            rtc.operations(),
+            [
+                Call::HaltClock(Ok(())),
+                Call::GetDateTime(Ok(noon)),
+                Call::SetDateTime(later, Ok(())),
+                Call::StartClock(Ok(())),
+                Call::WriteNvram(0, vec![7, 8], Ok(())),
+                Call::ReadNvram(0, Ok(vec![7, 8])),
+                Call::ReadNvram(60, Err(ErrorKind::NvramOutOfBounds)),
+                Call::StartSquareWave(SquareWaveFreq::Hz1, Ok(())),
+                Call::SetSquareWaveFrequency(SquareWaveFreq::Hz4096, Ok(())),
+                Call::DisableSquareWave(Ok(())),
+                Call::EnableSquareWave(Err(ErrorKind::Bus)),
+            ]
+        );
+        let sets = rtc
+            .operations()
+            .iter()
+            .filter(|call| matches!(call, Call::SetDateTime(dt, _) if dt.second() == 0))
+            .count();
+        assert_eq!(sets, 1);
+
+        rtc.clear();
+        assert!(rtc.operations().is_empty());
+        rtc.into_inner().done();
+    }
+
+    #[test]
+    fn test_fill_recorded_as_one_call() {
+        let mut inner = MockRtc::new(&[
+            Transaction::write_nvram(0, &[0; 16]),
+            Transaction::write_nvram(16, &[0; 4]),
+            Transaction::write_nvram(20, &[0xFF]).with_error(ErrorKind::NvramOutOfBounds),
+        ]);
+        inner.set_nvram_size(20);
+        let mut rtc = RecordingRtc::new(inner);
+        rtc.erase_nvram().unwrap();
+        assert_eq!(
+            rtc.fill_nvram(19, 2, 0xFF),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(
+            rtc.operations(),
+            [
+                Call::EraseNvram(Ok(())),
+                Call::FillNvram(19, 2, 0xFF, Err(ErrorKind::NvramOutOfBounds)),
+            ]
+        );
+        rtc.into_inner().done();
+    }
+}
@@ -0,0 +1,171 @@
+//! Simulated RTC with drift
+use crate::{
+    control::ClockIntegrity,
+    datetime::DateTime,
+    error::{ErrorKind, ErrorType},
+    rtc::Rtc,
+};
+
+/// RTC driven by simulated time, running fast or slow by a fixed rate
+///
+/// Simulated (true) time moves forward only through [`tick`](Self::tick). The
+/// RTC counts that time with a rate error of
+/// [`with_drift_ppm`](Self::with_drift_ppm) parts per million, accumulated
+/// exactly and truncated to whole seconds on every read, like a hardware
+/// counter. Setting the time restarts the accumulation from the new value.
+///
+/// [`power_loss`](Self::power_loss) simulates the backup supply failing: the
+/// time resets to [`DateTime::MIN`] and [`ClockIntegrity::datetime_valid`]
+/// reports `false` until the flag is cleared.
+///
+/// ```
+/// use rtc_hal::datetime::DateTime;
+/// use rtc_hal::mock::SimulatedRtc;
+/// use rtc_hal::rtc::Rtc;
+///
+/// let start = DateTime::new(2024, 1, 1, 0, 0, 0).unwrap();
+/// let mut rtc = SimulatedRtc::new(start).with_drift_ppm(20);
+/// // A year of 20 ppm gains 630.72 seconds
+/// rtc.tick(365 * 86_400);
+/// let expected = DateTime::new(2024, 12, 31, 0, 10, 30).unwrap();
+/// assert_eq!(rtc.get_datetime().unwrap(), expected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SimulatedRtc {
+    base: DateTime,
+    elapsed: u64,
+    drift_ppm: i32,
+    valid: bool,
+}
+
+impl SimulatedRtc {
+    /// Create a valid, drift-free RTC set to `start`
+    pub fn new(start: DateTime) -> Self {
+        Self {
+            base: start,
+            elapsed: 0,
+            drift_ppm: 0,
+            valid: true,
+        }
+    }
+
+    /// Make the RTC run fast (positive) or slow (negative) by `ppm` parts per million
+    pub fn with_drift_ppm(mut self, ppm: i32) -> Self {
+        self.drift_ppm = ppm;
+        self
+    }
+
+    /// Advance simulated time by `sim_seconds`
+    pub fn tick(&mut self, sim_seconds: u64) {
+        self.elapsed = self.elapsed.saturating_add(sim_seconds);
+    }
+
+    /// Simulate a loss of power: reset the time and mark it invalid
+    pub fn power_loss(&mut self) {
+        self.base = DateTime::MIN;
+        self.elapsed = 0;
+        self.valid = false;
+    }
+
+    /// Seconds counted by the RTC since its time was last set
+    fn counted(&self) -> i128 {
+        let elapsed = self.elapsed as i128;
+        elapsed + (elapsed * self.drift_ppm as i128).div_euclid(1_000_000)
+    }
+}
+
+impl ErrorType for SimulatedRtc {
+    type Error = ErrorKind;
+}
+
+impl Rtc for SimulatedRtc {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let counted = i64::try_from(self.counted()).map_err(|_| ErrorKind::InvalidDateTime)?;
+        Ok(self.base.checked_add_seconds(counted)?)
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.base = *datetime;
+        self.elapsed = 0;
+        Ok(())
+    }
+}
+
+impl ClockIntegrity for SimulatedRtc {
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.valid)
+    }
+
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+        self.valid = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86_400;
+
+    fn offset_after(ppm: i32, days: u64) -> i64 {
+        let start = DateTime::new(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut rtc = SimulatedRtc::new(start).with_drift_ppm(ppm);
+        for _ in 0..days {
+            rtc.tick(DAY);
+        }
+        let now = rtc.get_datetime().unwrap();
+        now.to_unix_timestamp() as i64 - start.to_unix_timestamp() as i64 - (days * DAY) as i64
+    }
+
+    #[test]
+    fn test_accumulated_offset_matches_ppm() {
+        // 86400 s * 20e-6 = 1.728 s per day
+        assert_eq!(offset_after(20, 1), 1);
+        assert_eq!(offset_after(20, 10), 17);
+        assert_eq!(offset_after(20, 365), 630);
+        // Truncation rounds towards the past when running slow
+        assert_eq!(offset_after(-20, 1), -2);
+        assert_eq!(offset_after(-20, 365), -631);
+        assert_eq!(offset_after(0, 365), 0);
+    }
+
+    #[test]
+    fn test_fractions_accumulate_across_ticks() {
+        // 0.5 s of gain per 1e5 s; small ticks must not lose it
+        let mut rtc = SimulatedRtc::new(DateTime::MIN).with_drift_ppm(5);
+        for _ in 0..200_000 {
+            rtc.tick(1);
+        }
+        assert_eq!(rtc.get_datetime().unwrap().to_unix_timestamp(), 200_001);
+    }
+
+    #[test]
+    fn test_set_datetime_restarts_accumulation() {
+        let mut rtc = SimulatedRtc::new(DateTime::MIN).with_drift_ppm(500_000);
+        rtc.tick(10);
+        assert_eq!(rtc.get_datetime().unwrap().to_unix_timestamp(), 15);
+        let noon = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        rtc.set_datetime(&noon).unwrap();
+        assert_eq!(rtc.get_datetime(), Ok(noon));
+        rtc.tick(2);
+        assert_eq!(
+            rtc.get_datetime(),
+            noon.checked_add_seconds(3).map_err(Into::into)
+        );
+    }
+
+    #[test]
+    fn test_power_loss() {
+        let mut rtc = SimulatedRtc::new(DateTime::new(2024, 8, 16, 12, 0, 0).unwrap());
+        rtc.tick(DAY);
+        assert_eq!(rtc.datetime_valid(), Ok(true));
+
+        rtc.power_loss();
+        assert_eq!(rtc.datetime_valid(), Ok(false));
+        assert_eq!(rtc.get_datetime(), Ok(DateTime::MIN));
+
+        rtc.clear_integrity_flag().unwrap();
+        assert_eq!(rtc.datetime_valid(), Ok(true));
+    }
+}
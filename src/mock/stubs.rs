@@ -0,0 +1,527 @@
+//! Placeholder implementations
+use crate::{
+    alarm::{Alarm, AlarmCapabilities, AlarmConfig, AlarmId},
+    control::{ClockIntegrity, RtcPowerControl},
+    datetime::DateTime,
+    error::{ErrorKind, ErrorType},
+    nvram::{NvramProtect, RtcNvram},
+    periodic::{PeriodicInterrupt, TickPeriod},
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, DutyCycle, Output32kHz, OutputPin, OutputPinConfig, SquareWave,
+        SquareWaveBatteryBacked, SquareWaveConfig, SquareWaveDuty, SquareWaveFreq, SquareWavePins,
+    },
+    timestamp::RtcTimestamp,
+};
+
+/// RTC that always reads a fixed time and ignores every change
+///
+/// Stands in for a missing RTC, e.g. in a build configuration without one.
+/// Every call succeeds: writes are discarded, the clock reads as valid, the
+/// alarm and the tick never trigger, the square wave reads as disabled high at
+/// 1 Hz with a 50% duty cycle, the 32 kHz output reads as disabled, and NVRAM
+/// has size 0 but reads as zeros and is never write protected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoopRtc {
+    datetime: DateTime,
+}
+
+impl NoopRtc {
+    /// Create a stub that always reads `datetime`
+    pub fn new(datetime: DateTime) -> Self {
+        Self { datetime }
+    }
+}
+
+impl ErrorType for NoopRtc {
+    type Error = ErrorKind;
+}
+
+impl Rtc for NoopRtc {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        Ok(self.datetime)
+    }
+
+    fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl RtcNvram for NoopRtc {
+    fn read_nvram(&mut self, _: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        buffer.fill(0);
+        Ok(())
+    }
+
+    fn write_nvram(&mut self, _: u32, _: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn nvram_size(&self) -> u32 {
+        0
+    }
+}
+
+impl NvramProtect for NoopRtc {
+    fn nvram_write_protected(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn set_nvram_write_protect(&mut self, _: bool) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl RtcPowerControl for NoopRtc {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ClockIntegrity for NoopRtc {
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl SquareWave for NoopRtc {
+    fn configure_output_pin(&mut self, _: OutputPinConfig) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn start_square_wave(&mut self, _: SquareWaveFreq) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_square_wave_frequency(&mut self, _: SquareWaveFreq) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_disabled_output_level(&mut self, _: DisabledOutputLevel) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        Ok(SquareWaveConfig {
+            enabled: false,
+            freq: SquareWaveFreq::Hz1,
+            disabled_level: DisabledOutputLevel::High,
+        })
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        &SquareWaveFreq::STANDARD
+    }
+}
+
+impl Alarm for NoopRtc {
+    fn set_alarm(&mut self, _: AlarmId, _: &AlarmConfig) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn disable_alarm(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn alarm_triggered(&mut self, _: AlarmId) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn clear_alarm_flag(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn enable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn disable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl RtcTimestamp for NoopRtc {
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        Ok(self.datetime.to_unix_timestamp())
+    }
+
+    fn set_timestamp(&mut self, _: u64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl PeriodicInterrupt for NoopRtc {
+    fn enable_tick(&mut self, _: TickPeriod) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn disable_tick(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl SquareWaveBatteryBacked for NoopRtc {
+    fn enable_square_wave_on_battery(&mut self, _: bool) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+impl SquareWavePins for NoopRtc {
+    fn output_pin_count(&self) -> u8 {
+        1
+    }
+
+    fn start_square_wave_on(&mut self, _: OutputPin, _: SquareWaveFreq) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn disable_square_wave_on(&mut self, _: OutputPin) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Output32kHz for NoopRtc {
+    fn enable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn disable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+impl SquareWaveDuty for NoopRtc {
+    fn set_duty_cycle(&mut self, _: DutyCycle) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error> {
+        Ok(DutyCycle::HALF)
+    }
+}
+
+/// RTC that panics on every call
+///
+/// For tests where touching the clock is a bug. The panic message names the
+/// method that was called.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PanicRtc;
+
+/// Panic naming the RTC method that must not be called
+fn called(method: &str) -> ! {
+    panic!("PanicRtc: {method} called, but this code must not use the RTC")
+}
+
+impl ErrorType for PanicRtc {
+    type Error = ErrorKind;
+}
+
+impl Rtc for PanicRtc {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        called("get_datetime")
+    }
+
+    fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+        called("set_datetime")
+    }
+}
+
+impl RtcNvram for PanicRtc {
+    fn read_nvram(&mut self, _: u32, _: &mut [u8]) -> Result<(), Self::Error> {
+        called("read_nvram")
+    }
+
+    fn write_nvram(&mut self, _: u32, _: &[u8]) -> Result<(), Self::Error> {
+        called("write_nvram")
+    }
+
+    fn nvram_size(&self) -> u32 {
+        called("nvram_size")
+    }
+}
+
+impl NvramProtect for PanicRtc {
+    fn nvram_write_protected(&mut self) -> Result<bool, Self::Error> {
+        called("nvram_write_protected")
+    }
+
+    fn set_nvram_write_protect(&mut self, _: bool) -> Result<(), Self::Error> {
+        called("set_nvram_write_protect")
+    }
+}
+
+impl RtcPowerControl for PanicRtc {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        called("start_clock")
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        called("halt_clock")
+    }
+}
+
+impl ClockIntegrity for PanicRtc {
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+        called("datetime_valid")
+    }
+
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+        called("clear_integrity_flag")
+    }
+}
+
+impl SquareWave for PanicRtc {
+    fn configure_output_pin(&mut self, _: OutputPinConfig) -> Result<(), Self::Error> {
+        called("configure_output_pin")
+    }
+
+    fn start_square_wave(&mut self, _: SquareWaveFreq) -> Result<(), Self::Error> {
+        called("start_square_wave")
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        called("enable_square_wave")
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        called("disable_square_wave")
+    }
+
+    fn set_square_wave_frequency(&mut self, _: SquareWaveFreq) -> Result<(), Self::Error> {
+        called("set_square_wave_frequency")
+    }
+    fn set_disabled_output_level(&mut self, _: DisabledOutputLevel) -> Result<(), Self::Error> {
+        called("set_disabled_output_level")
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        called("square_wave_config")
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        called("supported_square_wave_frequencies")
+    }
+
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        called("current_pin_mode")
+    }
+}
+
+impl Alarm for PanicRtc {
+    fn alarm_count(&self) -> u8 {
+        called("alarm_count")
+    }
+
+    fn alarm_capabilities(&self, _: AlarmId) -> AlarmCapabilities {
+        called("alarm_capabilities")
+    }
+
+    fn set_alarm(&mut self, _: AlarmId, _: &AlarmConfig) -> Result<(), Self::Error> {
+        called("set_alarm")
+    }
+
+    fn disable_alarm(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        called("disable_alarm")
+    }
+
+    fn alarm_triggered(&mut self, _: AlarmId) -> Result<bool, Self::Error> {
+        called("alarm_triggered")
+    }
+
+    fn clear_alarm_flag(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        called("clear_alarm_flag")
+    }
+
+    fn enable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        called("enable_alarm_interrupt")
+    }
+
+    fn disable_alarm_interrupt(&mut self, _: AlarmId) -> Result<(), Self::Error> {
+        called("disable_alarm_interrupt")
+    }
+}
+
+impl RtcTimestamp for PanicRtc {
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        called("get_timestamp")
+    }
+
+    fn set_timestamp(&mut self, _: u64) -> Result<(), Self::Error> {
+        called("set_timestamp")
+    }
+}
+
+impl PeriodicInterrupt for PanicRtc {
+    fn enable_tick(&mut self, _: TickPeriod) -> Result<(), Self::Error> {
+        called("enable_tick")
+    }
+
+    fn disable_tick(&mut self) -> Result<(), Self::Error> {
+        called("disable_tick")
+    }
+
+    fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+        called("tick_flag")
+    }
+
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+        called("clear_tick_flag")
+    }
+}
+
+impl SquareWaveBatteryBacked for PanicRtc {
+    fn enable_square_wave_on_battery(&mut self, _: bool) -> Result<(), Self::Error> {
+        called("enable_square_wave_on_battery")
+    }
+
+    fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error> {
+        called("is_square_wave_on_battery_enabled")
+    }
+}
+
+impl SquareWavePins for PanicRtc {
+    fn output_pin_count(&self) -> u8 {
+        called("output_pin_count")
+    }
+
+    fn start_square_wave_on(&mut self, _: OutputPin, _: SquareWaveFreq) -> Result<(), Self::Error> {
+        called("start_square_wave_on")
+    }
+
+    fn disable_square_wave_on(&mut self, _: OutputPin) -> Result<(), Self::Error> {
+        called("disable_square_wave_on")
+    }
+}
+
+impl Output32kHz for PanicRtc {
+    fn enable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        called("enable_32khz_output")
+    }
+
+    fn disable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        called("disable_32khz_output")
+    }
+
+    fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error> {
+        called("is_32khz_output_enabled")
+    }
+}
+
+impl SquareWaveDuty for PanicRtc {
+    fn set_duty_cycle(&mut self, _: DutyCycle) -> Result<(), Self::Error> {
+        called("set_duty_cycle")
+    }
+
+    fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error> {
+        called("duty_cycle")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accepts anything implementing every trait of the crate
+    fn exercise<T>(rtc: &mut T) -> Result<DateTime, T::Error>
+    where
+        T: RtcNvram
+            + NvramProtect
+            + RtcPowerControl
+            + ClockIntegrity
+            + SquareWave
+            + RtcTimestamp
+            + Alarm
+            + PeriodicInterrupt
+            + SquareWaveDuty
+            + SquareWaveBatteryBacked
+            + SquareWavePins
+            + Output32kHz,
+    {
+        rtc.start_clock()?;
+        rtc.set_datetime(&DateTime::MAX)?;
+        rtc.clear_integrity_flag()?;
+        assert!(rtc.datetime_valid()?);
+        rtc.set_nvram_write_protect(false)?;
+        assert!(!rtc.nvram_write_protected()?);
+        rtc.write_nvram(0, &[1, 2])?;
+        let mut buffer = [0xFF; 2];
+        rtc.read_nvram(0, &mut buffer)?;
+        assert_eq!(buffer, [0, 0]);
+        rtc.start_square_wave(SquareWaveFreq::Hz1)?;
+        rtc.disable_square_wave()?;
+        rtc.set_timestamp(0)?;
+        rtc.set_alarm(AlarmId::ALARM1, &AlarmConfig::daily_at(7, 0, 0))?;
+        assert!(!rtc.alarm_triggered(AlarmId::ALARM1)?);
+        rtc.enable_tick(TickPeriod::Second)?;
+        assert!(!rtc.tick_flag()?);
+        rtc.set_duty_cycle(DutyCycle::HALF)?;
+        rtc.enable_square_wave_on_battery(true)?;
+        rtc.start_square_wave_on(OutputPin::PRIMARY, SquareWaveFreq::Hz1)?;
+        rtc.disable_square_wave_on(OutputPin::PRIMARY)?;
+        rtc.enable_32khz_output()?;
+        rtc.get_datetime()
+    }
+
+    /// Code that is only given a clock to pass around, never to read
+    fn holds_rtc<T: Rtc>(rtc: T) -> T {
+        rtc
+    }
+
+    #[test]
+    fn test_noop_substitutes_anywhere() {
+        let noon = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        let mut rtc = NoopRtc::new(noon);
+        assert_eq!(exercise(&mut rtc), Ok(noon));
+        assert_eq!(rtc.get_timestamp(), Ok(noon.to_unix_timestamp()));
+        assert_eq!(rtc.nvram_size(), 0);
+    }
+
+    #[test]
+    fn test_panic_rtc_untouched() {
+        holds_rtc(PanicRtc);
+    }
+
+    #[test]
+    #[should_panic(expected = "PanicRtc: start_clock called")]
+    fn test_panic_rtc_panics() {
+        let _ = exercise(&mut PanicRtc);
+    }
+
+    #[test]
+    #[should_panic(expected = "PanicRtc: nvram_size called")]
+    fn test_panic_rtc_panics_on_infallible_call() {
+        PanicRtc.nvram_size();
+    }
+}
@@ -0,0 +1,241 @@
+//! Conformance test battery for drivers
+
+/// Generate conformance tests for an RTC driver
+///
+/// Driver crates invoke this in a test module with a constructor for their
+/// driver, usually over a simulated bus. The macro expands to a module of
+/// `#[test]` functions checking behavior every implementation should share:
+///
+/// - `round_trip`: [`set_datetime`](crate::rtc::Rtc::set_datetime) followed by
+///   [`get_datetime`](crate::rtc::Rtc::get_datetime) returns the same value,
+///   at both ends of the supported year range and on the first and last
+///   Feb 29 in it.
+/// - `rejects_out_of_range_years`: setting a year just outside the supported
+///   range fails with [`ErrorKind::InvalidDateTime`](crate::error::ErrorKind::InvalidDateTime).
+///
+/// The simulated bus must return what was written and must not advance the
+/// time between the two calls.
+///
+/// ## Opting into optional traits
+///
+/// Tests for the optional traits are generated only when the matching line is
+/// present, so a driver lists exactly the traits it implements:
+///
+/// - `nvram;` for [`RtcNvram`](crate::nvram::RtcNvram): reads and writes at
+///   the last byte succeed and accesses crossing
+///   [`nvram_size`](crate::nvram::RtcNvram::nvram_size) fail with
+///   [`ErrorKind::NvramOutOfBounds`](crate::error::ErrorKind::NvramOutOfBounds),
+///   as does an access whose end overflows `u32`, and
+///   [`fill_nvram`](crate::nvram::RtcNvram::fill_nvram) across the end fails
+///   without writing the last byte.
+/// - `square_wave: supported [..], unsupported [..];` for
+///   [`SquareWave`](crate::square_wave::SquareWave): each supported frequency
+///   is advertised by
+///   [`supports_frequency`](crate::square_wave::SquareWave::supports_frequency)
+///   and can be started, read back (also through
+///   [`current_pin_mode`](crate::square_wave::SquareWave::current_pin_mode))
+///   and set, and each unsupported one is not
+///   advertised and fails with
+///   [`ErrorKind::UnsupportedSqwFrequency`](crate::error::ErrorKind::UnsupportedSqwFrequency).
+///   Drivers whose [`square_wave_config`](crate::square_wave::SquareWave::square_wave_config)
+///   fails with [`ErrorKind::Unsupported`](crate::error::ErrorKind::Unsupported)
+///   skip the read-back.
+///
+/// The generated module imports the parent module with `use super::*`, so the
+/// constructor may refer to anything in scope at the invocation.
+///
+/// ## Example
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///     use rtc_hal::square_wave::SquareWaveFreq;
+///
+///     rtc_hal::test_suite! {
+///         mod conformance;
+///         new: || Ds1307::new(SimulatedBus::new());
+///         years: 2000..=2099;
+///         nvram;
+///         square_wave: supported [SquareWaveFreq::Hz1, SquareWaveFreq::Hz4096],
+///             unsupported [SquareWaveFreq::Hz1024, SquareWaveFreq::Custom(2)];
+///     }
+/// }
+/// ```
+///
+/// Enabled with the `mock` feature.
+#[macro_export]
+macro_rules! test_suite {
+    (
+        mod $name:ident;
+        new: $new:expr;
+        years: $min:literal ..= $max:literal;
+        $($rest:tt)*
+    ) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+            use $crate::datetime::DateTime;
+            use $crate::error::{Error as _, ErrorKind};
+            use $crate::rtc::Rtc as _;
+
+            const MIN_YEAR: u16 = $min;
+            const MAX_YEAR: u16 = $max;
+
+            fn round_trip_at(datetime: DateTime) {
+                let mut rtc = ($new)();
+                rtc.set_datetime(&datetime)
+                    .unwrap_or_else(|e| panic!("set_datetime({datetime:?}) failed: {:?}", e.kind()));
+                let read = rtc
+                    .get_datetime()
+                    .unwrap_or_else(|e| panic!("get_datetime failed: {:?}", e.kind()));
+                assert_eq!(read, datetime, "round trip changed the time");
+            }
+
+            fn expect_invalid(datetime: DateTime) {
+                let mut rtc = ($new)();
+                match rtc.set_datetime(&datetime) {
+                    Ok(()) => panic!("set_datetime({datetime:?}) accepted a year out of range"),
+                    Err(e) => assert_eq!(e.kind(), ErrorKind::InvalidDateTime),
+                }
+            }
+
+            #[test]
+            fn round_trip() {
+                round_trip_at(DateTime::new(MIN_YEAR, 1, 1, 0, 0, 0).unwrap());
+                round_trip_at(DateTime::new(MAX_YEAR, 12, 31, 23, 59, 59).unwrap());
+                let is_leap = |year: &u16| $crate::datetime::is_leap_year(*year);
+                if let Some(year) = (MIN_YEAR..=MAX_YEAR).find(is_leap) {
+                    round_trip_at(DateTime::new(year, 2, 29, 12, 34, 56).unwrap());
+                }
+                if let Some(year) = (MIN_YEAR..=MAX_YEAR).rev().find(is_leap) {
+                    round_trip_at(DateTime::new(year, 2, 29, 23, 59, 59).unwrap());
+                }
+            }
+
+            #[test]
+            fn rejects_out_of_range_years() {
+                if MIN_YEAR > DateTime::MIN.year() {
+                    expect_invalid(DateTime::new(MIN_YEAR - 1, 12, 31, 23, 59, 59).unwrap());
+                }
+                if MAX_YEAR < DateTime::MAX.year() {
+                    expect_invalid(DateTime::new(MAX_YEAR + 1, 1, 1, 0, 0, 0).unwrap());
+                }
+            }
+
+            $crate::test_suite!(@optional $new; $($rest)*);
+        }
+    };
+
+    (@optional $new:expr;) => {};
+
+    (@optional $new:expr; nvram; $($rest:tt)*) => {
+        #[test]
+        fn nvram_bounds() {
+            use $crate::nvram::RtcNvram as _;
+
+            let mut rtc = ($new)();
+            let size = rtc.nvram_size();
+            if size > 0 {
+                let last = size - 1;
+                rtc.write_nvram(last, &[0xA5])
+                    .unwrap_or_else(|e| panic!("write_nvram at offset {last} failed: {:?}", e.kind()));
+                let mut byte = [0];
+                rtc.read_nvram(last, &mut byte)
+                    .unwrap_or_else(|e| panic!("read_nvram at offset {last} failed: {:?}", e.kind()));
+                assert_eq!(byte, [0xA5], "NVRAM byte did not read back");
+            }
+
+            // Smallest accesses that end one byte past the NVRAM, and one
+            // whose end wraps around
+            let mut buffer = [0u8; 2];
+            for (offset, len) in [(size, 1), (size.saturating_sub(1), 2), (u32::MAX, 2)] {
+                let buffer = &mut buffer[..len];
+                let err = rtc.read_nvram(offset, buffer).err();
+                assert_eq!(
+                    err.map(|e| e.kind()),
+                    Some(ErrorKind::NvramOutOfBounds),
+                    "read_nvram at offset {offset}, {len} bytes"
+                );
+                let err = rtc.write_nvram(offset, buffer).err();
+                assert_eq!(
+                    err.map(|e| e.kind()),
+                    Some(ErrorKind::NvramOutOfBounds),
+                    "write_nvram at offset {offset}, {len} bytes"
+                );
+            }
+
+            let err = rtc.fill_nvram(size.saturating_sub(1), 2, 0).err();
+            assert_eq!(
+                err.map(|e| e.kind()),
+                Some(ErrorKind::NvramOutOfBounds),
+                "fill_nvram across the end"
+            );
+            if size > 0 {
+                let mut byte = [0];
+                rtc.read_nvram(size - 1, &mut byte)
+                    .unwrap_or_else(|e| panic!("read_nvram at offset {} failed: {:?}", size - 1, e.kind()));
+                assert_eq!(byte, [0xA5], "fill_nvram across the end wrote the last byte");
+            }
+        }
+
+        $crate::test_suite!(@optional $new; $($rest)*);
+    };
+
+    (
+        @optional $new:expr;
+        square_wave: supported [$($supported:expr),* $(,)?], unsupported [$($unsupported:expr),* $(,)?];
+        $($rest:tt)*
+    ) => {
+        #[test]
+        fn square_wave_frequencies() {
+            use $crate::square_wave::SquareWave as _;
+
+            let mut rtc = ($new)();
+            for freq in [$($supported),*] {
+                assert!(rtc.supports_frequency(freq), "supports_frequency({freq:?})");
+                rtc.start_square_wave(freq)
+                    .unwrap_or_else(|e| panic!("start_square_wave({freq:?}) failed: {:?}", e.kind()));
+                match rtc.square_wave_config() {
+                    Ok(config) => assert!(
+                        config.enabled && config.freq.same_frequency(&freq),
+                        "square_wave_config() after start_square_wave({freq:?}) is {config:?}"
+                    ),
+                    Err(e) if e.kind() == ErrorKind::Unsupported => {}
+                    Err(e) => panic!("square_wave_config() failed: {:?}", e.kind()),
+                }
+                match rtc.current_pin_mode() {
+                    Ok($crate::square_wave::OutputPinConfig::SquareWave(mode_freq)) => assert!(
+                        mode_freq.same_frequency(&freq),
+                        "current_pin_mode() after start_square_wave({freq:?}) is {mode_freq:?}"
+                    ),
+                    Ok(mode) => panic!(
+                        "current_pin_mode() after start_square_wave({freq:?}) is {mode:?}"
+                    ),
+                    Err(e) if e.kind() == ErrorKind::Unsupported => {}
+                    Err(e) => panic!("current_pin_mode() failed: {:?}", e.kind()),
+                }
+                rtc.set_square_wave_frequency(freq).unwrap_or_else(|e| {
+                    panic!("set_square_wave_frequency({freq:?}) failed: {:?}", e.kind())
+                });
+            }
+            for freq in [$($unsupported),*] {
+                assert!(!rtc.supports_frequency(freq), "supports_frequency({freq:?})");
+                let err = rtc.start_square_wave(freq).err();
+                assert_eq!(
+                    err.map(|e| e.kind()),
+                    Some(ErrorKind::UnsupportedSqwFrequency),
+                    "start_square_wave({freq:?})"
+                );
+                let err = rtc.set_square_wave_frequency(freq).err();
+                assert_eq!(
+                    err.map(|e| e.kind()),
+                    Some(ErrorKind::UnsupportedSqwFrequency),
+                    "set_square_wave_frequency({freq:?})"
+                );
+            }
+        }
+
+        $crate::test_suite!(@optional $new; $($rest)*);
+    };
+}
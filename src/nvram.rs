@@ -1,8 +1,19 @@
 //! Traits for RTC with non-volatile memory (NVRAM/SRAM) access
 
+use crate::error::ErrorKind;
 use crate::rtc::Rtc;
 
+/// Largest write issued by [`RtcNvram::fill_nvram`], sized for the stack
+const FILL_CHUNK: usize = 16;
+
 /// RTC with non-volatile memory (NVRAM/SRAM) access
+///
+/// Offsets and the size are `u32`, so memories past 256 bytes, such as the
+/// 512 bytes of the PCF2127 or the EEPROM of the MCP79410, are addressed
+/// directly. Accesses that would run past [`nvram_size`](RtcNvram::nvram_size)
+/// fail with
+/// [`ErrorKind::NvramOutOfBounds`] without reading or writing anything;
+/// drivers can check this with [`check_bounds`].
 pub trait RtcNvram: Rtc {
     /// Read data from NVRAM starting at the given offset
     ///
@@ -13,7 +24,7 @@ pub trait RtcNvram: Rtc {
     /// # Returns
     /// * `Ok(())` on success
     /// * `Err(Self::Error)` if offset or length is invalid, or read fails
-    fn read_nvram(&mut self, offset: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error>;
 
     /// Write data to NVRAM starting at the given offset
     ///
@@ -24,11 +35,183 @@ pub trait RtcNvram: Rtc {
     /// # Returns
     /// * `Ok(())` on success
     /// * `Err(Self::Error)` if offset or length is invalid, or write fails
-    fn write_nvram(&mut self, offset: u8, data: &[u8]) -> Result<(), Self::Error>;
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error>;
 
     /// Get the size of available NVRAM in bytes
     ///
     /// # Returns
-    /// Total NVRAM size (e.g., 56 for DS1307, 0 for DS3231)
-    fn nvram_size(&self) -> u16;
+    /// Total NVRAM size (e.g., 56 for DS1307, 512 for PCF2127, 0 for DS3231)
+    fn nvram_size(&self) -> u32;
+
+    /// Set `len` bytes starting at `offset` to `value`
+    ///
+    /// The default implementation calls
+    /// [`write_nvram`](RtcNvram::write_nvram) with at most 16 bytes at a time
+    /// from a stack buffer, so it needs no allocation. Drivers for chips with
+    /// a hardware clear may override it.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the driver's [`ErrorKind::NvramOutOfBounds`] error before
+    /// anything is written if the range runs past
+    /// [`nvram_size`](RtcNvram::nvram_size). The default gets that error from
+    /// a one-byte [`write_nvram`](RtcNvram::write_nvram) just past the end,
+    /// which the driver rejects without writing. An error from a later chunk
+    /// leaves the earlier chunks written.
+    fn fill_nvram(&mut self, offset: u32, len: u32, value: u8) -> Result<(), Self::Error> {
+        let chunk = [value; FILL_CHUNK];
+        let size = self.nvram_size();
+        if offset.checked_add(len).is_none_or(|end| end > size) {
+            return self.write_nvram(offset.max(size), &chunk[..1]);
+        }
+        let mut offset = offset;
+        let mut remaining = len;
+        while remaining > 0 {
+            let len = remaining.min(FILL_CHUNK as u32);
+            self.write_nvram(offset, &chunk[..len as usize])?;
+            offset += len;
+            remaining -= len;
+        }
+        Ok(())
+    }
+
+    /// Set the whole NVRAM to zero, e.g. for a factory reset
+    ///
+    /// Equivalent to [`fill_nvram`](RtcNvram::fill_nvram) over
+    /// [`nvram_size`](RtcNvram::nvram_size) bytes.
+    fn erase_nvram(&mut self) -> Result<(), Self::Error> {
+        self.fill_nvram(0, self.nvram_size(), 0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: RtcNvram + ?Sized> RtcNvram for alloc::boxed::Box<T> {
+    #[inline]
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        T::read_nvram(self, offset, buffer)
+    }
+
+    #[inline]
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        T::write_nvram(self, offset, data)
+    }
+
+    #[inline]
+    fn nvram_size(&self) -> u32 {
+        T::nvram_size(self)
+    }
+
+    #[inline]
+    fn fill_nvram(&mut self, offset: u32, len: u32, value: u8) -> Result<(), Self::Error> {
+        T::fill_nvram(self, offset, len, value)
+    }
+
+    #[inline]
+    fn erase_nvram(&mut self) -> Result<(), Self::Error> {
+        T::erase_nvram(self)
+    }
+}
+
+/// NVRAM with a software write protection
+///
+/// The PCF2127 and battery-backed SRAM modules can lock their memory against
+/// writes. While protection is on, [`write_nvram`](RtcNvram::write_nvram)
+/// fails with [`ErrorKind::NvramWriteProtected`] and leaves the memory as it
+/// was; reads are unaffected. Drivers for a chip family where only some parts
+/// have the lock return [`ErrorKind::Unsupported`] on the others.
+///
+/// ```
+/// use rtc_hal::nvram::NvramProtect;
+///
+/// /// Store calibration data, leaving the memory locked afterwards
+/// fn store_locked<R: NvramProtect>(rtc: &mut R, data: &[u8]) -> Result<(), R::Error> {
+///     rtc.set_nvram_write_protect(false)?;
+///     let written = rtc.write_nvram(0, data);
+///     // Lock again even if the write failed
+///     rtc.set_nvram_write_protect(true)?;
+///     written
+/// }
+/// ```
+pub trait NvramProtect: RtcNvram {
+    /// Check whether NVRAM writes are currently rejected
+    fn nvram_write_protected(&mut self) -> Result<bool, Self::Error>;
+
+    /// Turn the write protection on or off
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::Unsupported`] if the chip has no
+    /// write protection, or `Self::Error` if communication fails.
+    fn set_nvram_write_protect(&mut self, protect: bool) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: NvramProtect + ?Sized> NvramProtect for alloc::boxed::Box<T> {
+    #[inline]
+    fn nvram_write_protected(&mut self) -> Result<bool, Self::Error> {
+        T::nvram_write_protected(self)
+    }
+
+    #[inline]
+    fn set_nvram_write_protect(&mut self, protect: bool) -> Result<(), Self::Error> {
+        T::set_nvram_write_protect(self, protect)
+    }
+}
+
+/// Check that an access of `len` bytes at `offset` fits in an NVRAM of `size`
+/// bytes.
+///
+/// The end of the access is computed with checked arithmetic, so offsets near
+/// `u32::MAX` fail instead of wrapping around to a valid address. Lets drivers
+/// reject accesses with `?` before touching the bus.
+///
+/// # Errors
+///
+/// Returns `ErrorKind::NvramOutOfBounds` if `offset + len` is above `size`.
+pub fn check_bounds(offset: u32, len: usize, size: u32) -> Result<(), ErrorKind> {
+    let end = u32::try_from(len)
+        .ok()
+        .and_then(|len| offset.checked_add(len));
+    match end {
+        Some(end) if end <= size => Ok(()),
+        _ => Err(ErrorKind::NvramOutOfBounds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_bounds_at_the_end() {
+        assert_eq!(check_bounds(0, 512, 512), Ok(()));
+        assert_eq!(check_bounds(511, 1, 512), Ok(()));
+        assert_eq!(check_bounds(300, 212, 512), Ok(()));
+        assert_eq!(check_bounds(512, 0, 512), Ok(()));
+        assert_eq!(check_bounds(511, 2, 512), Err(ErrorKind::NvramOutOfBounds));
+        assert_eq!(check_bounds(512, 1, 512), Err(ErrorKind::NvramOutOfBounds));
+        assert_eq!(check_bounds(513, 0, 512), Err(ErrorKind::NvramOutOfBounds));
+        assert_eq!(check_bounds(0, 513, 512), Err(ErrorKind::NvramOutOfBounds));
+        assert_eq!(check_bounds(0, 0, 0), Ok(()));
+        assert_eq!(check_bounds(0, 1, 0), Err(ErrorKind::NvramOutOfBounds));
+    }
+
+    #[test]
+    fn test_check_bounds_does_not_wrap() {
+        let size = u32::MAX;
+        assert_eq!(check_bounds(u32::MAX - 1, 1, size), Ok(()));
+        assert_eq!(check_bounds(u32::MAX, 0, size), Ok(()));
+        assert_eq!(
+            check_bounds(u32::MAX, 1, size),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(
+            check_bounds(u32::MAX - 1, 3, size),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+        assert_eq!(
+            check_bounds(1, usize::MAX, size),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+    }
 }
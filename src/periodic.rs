@@ -0,0 +1,202 @@
+//! Periodic "tick" interrupts
+use crate::rtc::Rtc;
+
+/// How often a [`PeriodicInterrupt`] ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TickPeriod {
+    /// Once a second, when the seconds change
+    Second,
+    /// Once a minute, when the seconds roll over to 0
+    Minute,
+    /// The given number of ticks per second, for chips with a programmable
+    /// divider, e.g. 64 for the PCF8563 timer clocked at 64 Hz
+    Custom(u32),
+}
+
+/// This trait extends [`Rtc`] with a periodic interrupt that is independent
+/// of the alarms.
+///
+/// Chips such as the PCF8563 (timer), RV-3028 (periodic time update) and
+/// M41T62 can flag every second or minute, which suits waking up to refresh a
+/// display without arming and re-arming an alarm.
+///
+/// Like an alarm, each tick sets a flag that stays set until
+/// [`clear_tick_flag`](PeriodicInterrupt::clear_tick_flag). Whether the flag
+/// also drives an interrupt pin, and whether that pin is shared with the
+/// alarms or the square wave, is chip specific and should be documented by
+/// drivers.
+///
+/// ```
+/// use rtc_hal::periodic::{PeriodicInterrupt, TickPeriod};
+///
+/// fn refresh_every_minute<R: PeriodicInterrupt>(
+///     rtc: &mut R,
+///     mut redraw: impl FnMut(),
+/// ) -> Result<(), R::Error> {
+///     rtc.enable_tick(TickPeriod::Minute)?;
+///     loop {
+///         // ... sleep until the interrupt ...
+///         if rtc.tick_flag()? {
+///             rtc.clear_tick_flag()?;
+///             redraw();
+///         }
+///     }
+/// }
+/// ```
+pub trait PeriodicInterrupt: Rtc {
+    /// Start ticking every `period`, replacing the previous period.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind
+    /// [`ErrorKind::Unsupported`](crate::error::ErrorKind::Unsupported) for a
+    /// period the chip cannot generate, or `Self::Error` if communication fails.
+    fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error>;
+
+    /// Stop ticking; the flag is left as it is.
+    fn disable_tick(&mut self) -> Result<(), Self::Error>;
+
+    /// Check whether a tick occurred since the flag was last cleared.
+    fn tick_flag(&mut self) -> Result<bool, Self::Error>;
+
+    /// Clear the tick flag, releasing the interrupt pin if the tick drives it.
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error>;
+}
+
+/// blanket impl for all `&mut T`
+impl<T: PeriodicInterrupt + ?Sized> PeriodicInterrupt for &mut T {
+    #[inline]
+    fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+        T::enable_tick(self, period)
+    }
+
+    #[inline]
+    fn disable_tick(&mut self) -> Result<(), Self::Error> {
+        T::disable_tick(self)
+    }
+
+    #[inline]
+    fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+        T::tick_flag(self)
+    }
+
+    #[inline]
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+        T::clear_tick_flag(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PeriodicInterrupt + ?Sized> PeriodicInterrupt for alloc::boxed::Box<T> {
+    #[inline]
+    fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+        T::enable_tick(self, period)
+    }
+
+    #[inline]
+    fn disable_tick(&mut self) -> Result<(), Self::Error> {
+        T::disable_tick(self)
+    }
+
+    #[inline]
+    fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+        T::tick_flag(self)
+    }
+
+    #[inline]
+    fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+        T::clear_tick_flag(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::DateTime;
+    use crate::error::{ErrorKind, ErrorType};
+
+    /// PCF8563-like timer: 4096, 64 and 1 Hz, or once a minute
+    #[derive(Default)]
+    struct Timer {
+        period: Option<TickPeriod>,
+        flag: bool,
+    }
+
+    impl ErrorType for Timer {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for Timer {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(DateTime::MIN)
+        }
+
+        fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl PeriodicInterrupt for Timer {
+        fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+            match period {
+                TickPeriod::Second | TickPeriod::Minute | TickPeriod::Custom(1 | 64 | 4096) => {
+                    self.period = Some(period);
+                    Ok(())
+                }
+                TickPeriod::Custom(_) => Err(ErrorKind::Unsupported),
+            }
+        }
+
+        fn disable_tick(&mut self) -> Result<(), Self::Error> {
+            self.period = None;
+            Ok(())
+        }
+
+        fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.flag)
+        }
+
+        fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+            self.flag = false;
+            Ok(())
+        }
+    }
+
+    /// Enable the fastest of `periods` the chip supports
+    fn fastest<R: PeriodicInterrupt<Error = ErrorKind>>(
+        mut rtc: R,
+        periods: &[TickPeriod],
+    ) -> Option<TickPeriod> {
+        periods
+            .iter()
+            .copied()
+            .find(|period| rtc.enable_tick(*period).is_ok())
+    }
+
+    #[test]
+    fn test_unsupported_periods_fall_through() {
+        let mut timer = Timer::default();
+        let periods = [
+            TickPeriod::Custom(1000),
+            TickPeriod::Custom(64),
+            TickPeriod::Second,
+        ];
+        assert_eq!(fastest(&mut timer, &periods), Some(TickPeriod::Custom(64)));
+        assert_eq!(timer.period, Some(TickPeriod::Custom(64)));
+        assert_eq!(fastest(&mut timer, &periods[..1]), None);
+        assert_eq!(timer.period, Some(TickPeriod::Custom(64)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_boxed_forwarding() {
+        let mut timer: alloc::boxed::Box<Timer> = alloc::boxed::Box::default();
+        timer.enable_tick(TickPeriod::Minute).unwrap();
+        timer.flag = true;
+        assert_eq!(timer.tick_flag(), Ok(true));
+        timer.clear_tick_flag().unwrap();
+        timer.disable_tick().unwrap();
+        assert_eq!((timer.period, timer.flag), (None, false));
+    }
+}
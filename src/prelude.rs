@@ -0,0 +1,17 @@
+//! Commonly used traits and types
+//!
+//! ```
+//! use rtc_hal::prelude::*;
+//! ```
+pub use crate::alarm::{Alarm, AlarmConfig, AlarmId};
+pub use crate::control::{ClockIntegrity, RtcPowerControl};
+pub use crate::datetime::{Date, DateTime, Time, Weekday};
+pub use crate::error::{Error, ErrorKind, ErrorType};
+pub use crate::nvram::{NvramProtect, RtcNvram};
+pub use crate::periodic::{PeriodicInterrupt, TickPeriod};
+pub use crate::rtc::Rtc;
+pub use crate::square_wave::{
+    DisabledOutputLevel, DutyCycle, Output32kHz, OutputPin, OutputPinConfig, SquareWave,
+    SquareWaveBatteryBacked, SquareWaveConfig, SquareWaveDuty, SquareWaveFreq, SquareWavePins,
+};
+pub use crate::timestamp::RtcTimestamp;
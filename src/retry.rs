@@ -0,0 +1,335 @@
+//! # Retrying transient errors
+//!
+//! [`RetryRtc`] wraps a driver and repeats operations that fail with a
+//! transient error, such as a NACK on a long I2C cable, before giving up.
+//!
+//! ```
+//! use rtc_hal::retry::RetryRtc;
+//! use rtc_hal::rtc::Rtc;
+//! # use rtc_hal::{datetime::DateTime, error::{ErrorKind, ErrorType}};
+//! # struct Ds1307;
+//! # impl ErrorType for Ds1307 { type Error = ErrorKind; }
+//! # impl Rtc for Ds1307 {
+//! #     fn get_datetime(&mut self) -> Result<DateTime, ErrorKind> { Ok(DateTime::MIN) }
+//! #     fn set_datetime(&mut self, _: &DateTime) -> Result<(), ErrorKind> { Ok(()) }
+//! # }
+//!
+//! // Up to 3 retries, backing off a little more after each failure
+//! let mut rtc = RetryRtc::<_, 3, _>::with_hook(Ds1307, |attempt, _kind| {
+//!     // delay.delay_ms(10 * attempt as u32);
+//! });
+//! let now = rtc.get_datetime()?;
+//! # Ok::<(), ErrorKind>(())
+//! ```
+use crate::{
+    control::{ClockIntegrity, RtcPowerControl},
+    datetime::{Date, DateTime, Time},
+    error::{Error, ErrorKind, ErrorType},
+    nvram::RtcNvram,
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, OutputPinConfig, SquareWave, SquareWaveConfig, SquareWaveFreq,
+    },
+    timestamp::RtcTimestamp,
+};
+
+/// Whether an error of this kind may go away if the operation is repeated
+fn is_transient(kind: ErrorKind) -> bool {
+    matches!(kind, ErrorKind::Bus | ErrorKind::Busy)
+}
+
+/// Wrapper that retries operations failing with a transient error up to `N` times
+///
+/// Errors of kind [`ErrorKind::Bus`] and [`ErrorKind::Busy`] are retried; all
+/// others are returned immediately. After `N` retries (`N + 1` attempts in
+/// total) the last error is returned.
+///
+/// The hook is called before each retry with the retry number (starting at 1)
+/// and the kind of the error, which is the place to add a backoff delay.
+pub struct RetryRtc<R, const N: usize, H = fn(usize, ErrorKind)> {
+    rtc: R,
+    hook: H,
+}
+
+impl<R, const N: usize> RetryRtc<R, N> {
+    /// Wrap a driver, retrying without delay
+    pub fn new(rtc: R) -> Self {
+        Self {
+            rtc,
+            hook: |_, _| {},
+        }
+    }
+}
+
+impl<R, const N: usize, H: FnMut(usize, ErrorKind)> RetryRtc<R, N, H> {
+    /// Wrap a driver, calling `hook` before each retry
+    pub fn with_hook(rtc: R, hook: H) -> Self {
+        Self { rtc, hook }
+    }
+
+    /// Release the wrapped driver
+    pub fn into_inner(self) -> R {
+        self.rtc
+    }
+
+    fn retry<T, E: Error>(&mut self, mut op: impl FnMut(&mut R) -> Result<T, E>) -> Result<T, E> {
+        let mut retries = 0;
+        loop {
+            match op(&mut self.rtc) {
+                Err(e) if retries < N && is_transient(e.kind()) => {
+                    retries += 1;
+                    (self.hook)(retries, e.kind());
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<R: ErrorType, const N: usize, H> ErrorType for RetryRtc<R, N, H> {
+    type Error = R::Error;
+}
+
+impl<R: Rtc, const N: usize, H: FnMut(usize, ErrorKind)> Rtc for RetryRtc<R, N, H> {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        self.retry(|rtc| rtc.get_datetime())
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.set_datetime(datetime))
+    }
+
+    fn get_time(&mut self) -> Result<Time, Self::Error> {
+        self.retry(|rtc| rtc.get_time())
+    }
+
+    fn get_date(&mut self) -> Result<Date, Self::Error> {
+        self.retry(|rtc| rtc.get_date())
+    }
+
+    fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.set_time(time))
+    }
+
+    fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.set_date(date))
+    }
+}
+
+impl<R: RtcNvram, const N: usize, H: FnMut(usize, ErrorKind)> RtcNvram for RetryRtc<R, N, H> {
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.read_nvram(offset, buffer))
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.write_nvram(offset, data))
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.rtc.nvram_size()
+    }
+
+    fn fill_nvram(&mut self, offset: u32, len: u32, value: u8) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.fill_nvram(offset, len, value))
+    }
+
+    fn erase_nvram(&mut self) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.erase_nvram())
+    }
+}
+
+impl<R: RtcPowerControl, const N: usize, H: FnMut(usize, ErrorKind)> RtcPowerControl
+    for RetryRtc<R, N, H>
+{
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.start_clock())
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.halt_clock())
+    }
+}
+
+impl<R: ClockIntegrity, const N: usize, H: FnMut(usize, ErrorKind)> ClockIntegrity
+    for RetryRtc<R, N, H>
+{
+    fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+        self.retry(|rtc| rtc.datetime_valid())
+    }
+
+    fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.clear_integrity_flag())
+    }
+}
+
+impl<R: SquareWave, const N: usize, H: FnMut(usize, ErrorKind)> SquareWave for RetryRtc<R, N, H> {
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.configure_output_pin(config))
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.start_square_wave(freq))
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.enable_square_wave())
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.disable_square_wave())
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.set_square_wave_frequency(freq))
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.set_disabled_output_level(level))
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        self.retry(|rtc| rtc.square_wave_config())
+    }
+
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        self.retry(|rtc| rtc.current_pin_mode())
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        self.rtc.supported_square_wave_frequencies()
+    }
+}
+
+impl<R: RtcTimestamp, const N: usize, H: FnMut(usize, ErrorKind)> RtcTimestamp
+    for RetryRtc<R, N, H>
+{
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        self.retry(|rtc| rtc.get_timestamp())
+    }
+
+    fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+        self.retry(|rtc| rtc.set_timestamp(secs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fails the first `failures` calls with `error`, then succeeds
+    struct Flaky {
+        failures: usize,
+        error: ErrorKind,
+        calls: usize,
+        nvram: [u8; 4],
+    }
+
+    impl Flaky {
+        fn new(failures: usize, error: ErrorKind) -> Self {
+            Self {
+                failures,
+                error,
+                calls: 0,
+                nvram: [0; 4],
+            }
+        }
+
+        fn call(&mut self) -> Result<(), ErrorKind> {
+            self.calls += 1;
+            if self.calls <= self.failures {
+                Err(self.error)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl ErrorType for Flaky {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for Flaky {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            self.call()?;
+            Ok(DateTime::MIN)
+        }
+
+        fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+            self.call()
+        }
+    }
+
+    impl RtcNvram for Flaky {
+        fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+            self.call()?;
+            let start = offset as usize;
+            buffer.copy_from_slice(&self.nvram[start..start + buffer.len()]);
+            Ok(())
+        }
+
+        fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+            self.call()?;
+            let start = offset as usize;
+            self.nvram[start..start + data.len()].copy_from_slice(data);
+            Ok(())
+        }
+
+        fn nvram_size(&self) -> u32 {
+            self.nvram.len() as u32
+        }
+    }
+
+    #[test]
+    fn test_fails_twice_then_succeeds() {
+        let mut rtc = RetryRtc::<_, 3>::new(Flaky::new(2, ErrorKind::Bus));
+        assert_eq!(rtc.get_datetime(), Ok(DateTime::MIN));
+        assert_eq!(rtc.into_inner().calls, 3);
+    }
+
+    #[test]
+    fn test_always_fails() {
+        let mut rtc = RetryRtc::<_, 3>::new(Flaky::new(usize::MAX, ErrorKind::Busy));
+        assert_eq!(rtc.set_datetime(&DateTime::MIN), Err(ErrorKind::Busy));
+        assert_eq!(rtc.into_inner().calls, 4);
+    }
+
+    #[test]
+    fn test_non_transient_error_not_retried() {
+        let mut rtc = RetryRtc::<_, 3>::new(Flaky::new(1, ErrorKind::InvalidDateTime));
+        assert_eq!(rtc.get_datetime(), Err(ErrorKind::InvalidDateTime));
+        assert_eq!(rtc.into_inner().calls, 1);
+    }
+
+    #[test]
+    fn test_zero_retries() {
+        let mut rtc = RetryRtc::<_, 0>::new(Flaky::new(1, ErrorKind::Bus));
+        assert_eq!(rtc.get_datetime(), Err(ErrorKind::Bus));
+        assert_eq!(rtc.get_datetime(), Ok(DateTime::MIN));
+    }
+
+    #[test]
+    fn test_hook_called_before_each_retry() {
+        let mut seen = [None; 4];
+        let mut rtc = RetryRtc::<_, 3, _>::with_hook(Flaky::new(2, ErrorKind::Bus), |n, kind| {
+            seen[n] = Some(kind);
+        });
+        rtc.write_nvram(1, &[0xAA, 0xBB]).unwrap();
+        let mut buffer = [0; 2];
+        rtc.read_nvram(1, &mut buffer).unwrap();
+        assert_eq!(buffer, [0xAA, 0xBB]);
+        assert_eq!(rtc.into_inner().nvram_size(), 4);
+        assert_eq!(
+            seen,
+            [None, Some(ErrorKind::Bus), Some(ErrorKind::Bus), None]
+        );
+    }
+
+    #[test]
+    fn test_fill_retried_as_a_whole() {
+        let mut rtc = RetryRtc::<_, 3>::new(Flaky::new(1, ErrorKind::Bus));
+        rtc.fill_nvram(1, 3, 0xFF).unwrap();
+        let flaky = rtc.into_inner();
+        assert_eq!(flaky.nvram, [0, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(flaky.calls, 2);
+    }
+}
@@ -30,7 +30,13 @@
 //! let now = rtc.get_datetime()?;
 //! rtc.set_datetime(&DateTime::new(2024, 8, 16, 12, 0, 0)?)?;
 //! ```
-use crate::{datetime::DateTime, error::ErrorType};
+use crate::{
+    datetime::{Date, DateTime, DateTimeError, Time},
+    error::ErrorType,
+};
+
+#[cfg(feature = "async")]
+pub mod asynch;
 
 /// Core trait for Real-Time Clock (RTC) devices.
 ///
@@ -65,6 +71,144 @@ pub trait Rtc: ErrorType {
     /// Returns `Self::Error` if communication with the RTC fails or
     /// if the provided `DateTime` is out of range for this device.
     fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error>;
+
+    /// Get the current time of day.
+    ///
+    /// The default implementation reads the full date and time with
+    /// [`get_datetime`](Rtc::get_datetime). Drivers can override it to read
+    /// only the time registers, which is a shorter bus transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails.
+    fn get_time(&mut self) -> Result<Time, Self::Error> {
+        Ok(self.get_datetime()?.time())
+    }
+
+    /// Get the current calendar date.
+    ///
+    /// The default implementation reads the full date and time with
+    /// [`get_datetime`](Rtc::get_datetime). Drivers can override it to read
+    /// only the date registers.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails.
+    fn get_date(&mut self) -> Result<Date, Self::Error> {
+        Ok(self.get_datetime()?.date())
+    }
+
+    /// Set the time of day, keeping the current date.
+    ///
+    /// The default implementation reads the date and time, replaces the time
+    /// and writes everything back. If the date rolls over between the read and
+    /// the write (e.g. at midnight), the old date is written back and the clock
+    /// ends up a day behind. Drivers should override this to write only the
+    /// time registers.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails or
+    /// if the resulting `DateTime` is out of range for this device.
+    fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+        let date = self.get_datetime()?.date();
+        self.set_datetime(&DateTime::from_date_time(date, *time))
+    }
+
+    /// Set the calendar date, keeping the current time of day.
+    ///
+    /// The default implementation reads the date and time, replaces the date
+    /// and writes everything back, so the time falls behind by however long
+    /// that takes (up to a second lost to the reset of the sub-second counter
+    /// on many chips). Drivers should override this to write only the date
+    /// registers.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails or
+    /// if the resulting `DateTime` is out of range for this device.
+    fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+        let time = self.get_datetime()?.time();
+        self.set_datetime(&DateTime::from_date_time(*date, time))
+    }
+
+    /// Get the current time as seconds since the Unix epoch (1970-01-01 00:00:00).
+    ///
+    /// The default implementation converts the result of [`get_datetime`](Rtc::get_datetime).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails.
+    fn get_unix_time(&mut self) -> Result<u64, Self::Error> {
+        Ok(self.get_datetime()?.to_unix_timestamp())
+    }
+
+    /// Set the current time from seconds since the Unix epoch (1970-01-01 00:00:00).
+    ///
+    /// The default implementation converts `secs` and calls
+    /// [`set_datetime`](Rtc::set_datetime).
+    ///
+    /// Only available when the driver's error type can be built from a
+    /// `DateTimeError`, which reports a timestamp past [`DateTime::MAX`].
+    /// `ErrorKind` and [`RtcError`](crate::error::RtcError) support this; drivers
+    /// with custom errors should implement `From<DateTimeError>` and map it to
+    /// an error of kind `InvalidDateTime`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if `secs` is not representable as a `DateTime`,
+    /// is out of range for this device, or communication with the RTC fails.
+    fn set_unix_time(&mut self, secs: u64) -> Result<(), Self::Error>
+    where
+        Self::Error: From<DateTimeError>,
+    {
+        self.set_datetime(&DateTime::from_unix_timestamp(secs)?)
+    }
+}
+
+/// Returned by [`elapsed_seconds`] when the RTC reads earlier than the reference time.
+///
+/// This usually means the backup battery failed and the RTC restarted from its
+/// reset value, or that the clock was deliberately set back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimeWentBackwards {
+    /// How many seconds the RTC reading is behind the reference time
+    pub seconds: u64,
+}
+
+/// Read the RTC and get the number of seconds elapsed since `since`.
+///
+/// The outer `Result` carries communication errors from the RTC, while the
+/// inner one reports whether time went backwards relative to `since`.
+///
+/// # Example
+///
+/// ```ignore
+/// let last_sync = load_timestamp_from_nvram();
+/// match elapsed_seconds(&mut rtc, &last_sync)? {
+///     Ok(secs) => info!("{} seconds since last sync", secs),
+///     Err(TimeWentBackwards { .. }) => resync_from_network(&mut rtc),
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns `R::Error` if communication with the RTC fails.
+pub fn elapsed_seconds<R: Rtc>(
+    rtc: &mut R,
+    since: &DateTime,
+) -> Result<Result<u64, TimeWentBackwards>, R::Error> {
+    let now = rtc.get_datetime()?.to_unix_timestamp();
+    let since = since.to_unix_timestamp();
+
+    if now >= since {
+        Ok(Ok(now - since))
+    } else {
+        Ok(Err(TimeWentBackwards {
+            seconds: since - now,
+        }))
+    }
 }
 
 /// blanket impl for all `&mut T`
@@ -78,4 +222,480 @@ impl<T: Rtc + ?Sized> Rtc for &mut T {
     fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
         T::set_datetime(self, datetime)
     }
+
+    #[inline]
+    fn get_time(&mut self) -> Result<Time, Self::Error> {
+        T::get_time(self)
+    }
+
+    #[inline]
+    fn get_date(&mut self) -> Result<Date, Self::Error> {
+        T::get_date(self)
+    }
+
+    #[inline]
+    fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+        T::set_time(self, time)
+    }
+
+    #[inline]
+    fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+        T::set_date(self, date)
+    }
+
+    #[inline]
+    fn get_unix_time(&mut self) -> Result<u64, Self::Error> {
+        T::get_unix_time(self)
+    }
+
+    #[inline]
+    fn set_unix_time(&mut self, secs: u64) -> Result<(), Self::Error>
+    where
+        Self::Error: From<DateTimeError>,
+    {
+        T::set_unix_time(self, secs)
+    }
+}
+
+/// blanket impl for all `Box<T>`, e.g. `Box<dyn Rtc<Error = ErrorKind>>`
+///
+/// There is no impl for `Rc<RefCell<T>>`: the traits take `&mut self`, and
+/// borrowing through a `RefCell` can panic at runtime. Share an RTC with a
+/// wrapper type that decides how to handle a conflicting borrow instead.
+#[cfg(feature = "alloc")]
+impl<T: Rtc + ?Sized> Rtc for alloc::boxed::Box<T> {
+    #[inline]
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        T::get_datetime(self)
+    }
+
+    #[inline]
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        T::set_datetime(self, datetime)
+    }
+
+    #[inline]
+    fn get_time(&mut self) -> Result<Time, Self::Error> {
+        T::get_time(self)
+    }
+
+    #[inline]
+    fn get_date(&mut self) -> Result<Date, Self::Error> {
+        T::get_date(self)
+    }
+
+    #[inline]
+    fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+        T::set_time(self, time)
+    }
+
+    #[inline]
+    fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+        T::set_date(self, date)
+    }
+
+    #[inline]
+    fn get_unix_time(&mut self) -> Result<u64, Self::Error> {
+        T::get_unix_time(self)
+    }
+
+    #[inline]
+    fn set_unix_time(&mut self, secs: u64) -> Result<(), Self::Error>
+    where
+        Self::Error: From<DateTimeError>,
+    {
+        T::set_unix_time(self, secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    #[cfg(feature = "alloc")]
+    use alloc::boxed::Box;
+
+    struct MockRtc {
+        now: Result<DateTime, ErrorKind>,
+    }
+
+    impl ErrorType for MockRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MockRtc {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            self.now
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.now = Ok(*datetime);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_elapsed_seconds_forward() {
+        let since = DateTime::new(2024, 8, 16, 23, 59, 0).unwrap();
+        let mut rtc = MockRtc {
+            now: Ok(DateTime::new(2024, 8, 17, 0, 1, 30).unwrap()),
+        };
+        assert_eq!(elapsed_seconds(&mut rtc, &since), Ok(Ok(150)));
+        let now = rtc.now.unwrap();
+        assert_eq!(elapsed_seconds(&mut rtc, &now), Ok(Ok(0)));
+    }
+
+    #[test]
+    fn test_elapsed_seconds_backwards() {
+        let since = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        let mut rtc = MockRtc {
+            now: Ok(DateTime::MIN),
+        };
+        assert_eq!(
+            elapsed_seconds(&mut rtc, &since),
+            Ok(Err(TimeWentBackwards {
+                seconds: since.to_unix_timestamp()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_elapsed_seconds_bus_error() {
+        let since = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        let mut rtc = MockRtc {
+            now: Err(ErrorKind::Bus),
+        };
+        assert_eq!(elapsed_seconds(&mut rtc, &since), Err(ErrorKind::Bus));
+    }
+
+    #[test]
+    fn test_unix_time() {
+        let mut rtc = MockRtc {
+            now: Ok(DateTime::new(2024, 8, 16, 12, 0, 0).unwrap()),
+        };
+        assert_eq!(rtc.get_unix_time(), Ok(1_723_809_600));
+
+        rtc.set_unix_time(0).unwrap();
+        assert_eq!(rtc.now, Ok(DateTime::MIN));
+        rtc.set_unix_time(1_000_000_000).unwrap();
+        assert_eq!(rtc.now, Ok(DateTime::new(2001, 9, 9, 1, 46, 40).unwrap()));
+        let mut by_ref = &mut rtc;
+        assert_eq!(Rtc::get_unix_time(&mut by_ref), Ok(1_000_000_000));
+
+        let past_max = DateTime::MAX.to_unix_timestamp() + 1;
+        assert_eq!(rtc.set_unix_time(past_max), Err(ErrorKind::InvalidDateTime));
+        assert_eq!(rtc.get_unix_time(), Ok(1_000_000_000));
+    }
+
+    #[test]
+    fn test_get_time_and_date_default() {
+        let mut rtc = MockRtc {
+            now: Ok(DateTime::new(2024, 8, 16, 12, 34, 56).unwrap()),
+        };
+        assert_eq!(rtc.get_time(), Ok(Time::new(12, 34, 56).unwrap()));
+        assert_eq!(rtc.get_date(), Ok(Date::new(2024, 8, 16).unwrap()));
+
+        rtc.now = Err(ErrorKind::Bus);
+        assert_eq!(rtc.get_time(), Err(ErrorKind::Bus));
+        assert_eq!(rtc.get_date(), Err(ErrorKind::Bus));
+    }
+
+    #[test]
+    fn test_get_time_and_date_overridden() {
+        // Counts register reads: 7 for the full block, 3 for time only, 4 for date only
+        struct Counting {
+            bytes_read: usize,
+        }
+
+        impl ErrorType for Counting {
+            type Error = ErrorKind;
+        }
+
+        impl Rtc for Counting {
+            fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+                self.bytes_read += 7;
+                Ok(DateTime::new(2024, 8, 16, 12, 34, 56)?)
+            }
+
+            fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn get_time(&mut self) -> Result<Time, Self::Error> {
+                self.bytes_read += 3;
+                Ok(Time::new(12, 34, 56)?)
+            }
+
+            fn get_date(&mut self) -> Result<Date, Self::Error> {
+                self.bytes_read += 4;
+                Ok(Date::new(2024, 8, 16)?)
+            }
+        }
+
+        let mut rtc = Counting { bytes_read: 0 };
+        assert_eq!(rtc.get_time(), Ok(Time::new(12, 34, 56).unwrap()));
+        assert_eq!(rtc.bytes_read, 3);
+
+        // The `&mut T` blanket impl forwards to the override
+        let mut by_ref = &mut rtc;
+        assert_eq!(
+            Rtc::get_date(&mut by_ref),
+            Ok(Date::new(2024, 8, 16).unwrap())
+        );
+        assert_eq!(rtc.bytes_read, 7);
+    }
+
+    #[test]
+    fn test_set_time_and_date_default() {
+        // Records every full read and write
+        struct Logging {
+            now: DateTime,
+            reads: usize,
+            writes: usize,
+        }
+
+        impl ErrorType for Logging {
+            type Error = ErrorKind;
+        }
+
+        impl Rtc for Logging {
+            fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+                self.reads += 1;
+                Ok(self.now)
+            }
+
+            fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+                self.writes += 1;
+                self.now = *datetime;
+                Ok(())
+            }
+        }
+
+        let mut rtc = Logging {
+            now: DateTime::new(2024, 8, 16, 12, 34, 56).unwrap(),
+            reads: 0,
+            writes: 0,
+        };
+        rtc.set_time(&Time::new(1, 2, 3).unwrap()).unwrap();
+        assert_eq!(rtc.now, DateTime::new(2024, 8, 16, 1, 2, 3).unwrap());
+        assert_eq!((rtc.reads, rtc.writes), (1, 1));
+
+        rtc.set_date(&Date::new(2025, 1, 31).unwrap()).unwrap();
+        assert_eq!(rtc.now, DateTime::new(2025, 1, 31, 1, 2, 3).unwrap());
+        assert_eq!((rtc.reads, rtc.writes), (2, 2));
+    }
+
+    #[test]
+    fn test_set_time_and_date_overridden() {
+        // Separate time and date registers; only `set_time`/`set_date` touch one bank
+        struct Banked {
+            time: Time,
+            date: Date,
+            full_writes: usize,
+        }
+
+        impl ErrorType for Banked {
+            type Error = ErrorKind;
+        }
+
+        impl Rtc for Banked {
+            fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+                Ok(DateTime::from_date_time(self.date, self.time))
+            }
+
+            fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+                self.full_writes += 1;
+                self.time = datetime.time();
+                self.date = datetime.date();
+                Ok(())
+            }
+
+            fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+                self.time = *time;
+                Ok(())
+            }
+
+            fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+                self.date = *date;
+                Ok(())
+            }
+        }
+
+        let mut rtc = Banked {
+            time: Time::MIDNIGHT,
+            date: Date::new(2024, 8, 16).unwrap(),
+            full_writes: 0,
+        };
+        let mut by_ref = &mut rtc;
+        Rtc::set_time(&mut by_ref, &Time::new(23, 0, 0).unwrap()).unwrap();
+        Rtc::set_date(&mut by_ref, &Date::new(2024, 8, 17).unwrap()).unwrap();
+        assert_eq!(
+            rtc.get_datetime(),
+            Ok(DateTime::new(2024, 8, 17, 23, 0, 0).unwrap())
+        );
+        assert_eq!(rtc.full_writes, 0);
+    }
+
+    #[test]
+    fn test_unix_time_bus_error() {
+        let mut rtc = MockRtc {
+            now: Err(ErrorKind::Bus),
+        };
+        assert_eq!(rtc.get_unix_time(), Err(ErrorKind::Bus));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_boxed_dyn_rtc() {
+        let mut rtc: Box<dyn Rtc<Error = ErrorKind>> = Box::new(MockRtc {
+            now: Ok(DateTime::MIN),
+        });
+        let datetime = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        rtc.set_datetime(&datetime).unwrap();
+        assert_eq!(rtc.get_datetime(), Ok(datetime));
+
+        // Usable wherever a generic `Rtc` is expected
+        assert_eq!(elapsed_seconds(&mut rtc, &datetime), Ok(Ok(0)));
+
+        rtc.set_unix_time(0).unwrap();
+        assert_eq!(rtc.get_unix_time(), Ok(0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_boxed_extension_traits() {
+        use crate::control::RtcPowerControl;
+        use crate::nvram::RtcNvram;
+        use crate::square_wave::{
+            DisabledOutputLevel, OutputPinConfig, SquareWave, SquareWaveConfig, SquareWaveFreq,
+        };
+
+        struct Full {
+            running: bool,
+            sqw: Option<SquareWaveFreq>,
+            nvram: [u8; 4],
+        }
+
+        impl ErrorType for Full {
+            type Error = ErrorKind;
+        }
+
+        impl Rtc for Full {
+            fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+                Ok(DateTime::MIN)
+            }
+
+            fn set_datetime(&mut self, _: &DateTime) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        impl RtcPowerControl for Full {
+            fn start_clock(&mut self) -> Result<(), Self::Error> {
+                self.running = true;
+                Ok(())
+            }
+
+            fn halt_clock(&mut self) -> Result<(), Self::Error> {
+                self.running = false;
+                Ok(())
+            }
+        }
+
+        impl SquareWave for Full {
+            fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+                match config {
+                    OutputPinConfig::SquareWave(freq) => self.start_square_wave(freq),
+                    _ => self.disable_square_wave(),
+                }
+            }
+
+            fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+                self.sqw = Some(freq);
+                Ok(())
+            }
+
+            fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+                self.sqw = None;
+                Ok(())
+            }
+
+            fn set_square_wave_frequency(
+                &mut self,
+                freq: SquareWaveFreq,
+            ) -> Result<(), Self::Error> {
+                self.sqw = Some(freq);
+                Ok(())
+            }
+
+            fn set_disabled_output_level(
+                &mut self,
+                _: DisabledOutputLevel,
+            ) -> Result<(), Self::Error> {
+                Err(ErrorKind::Unsupported)
+            }
+
+            fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+                Err(ErrorKind::Unsupported)
+            }
+
+            fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+                &SquareWaveFreq::STANDARD
+            }
+        }
+
+        impl RtcNvram for Full {
+            fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+                let start = offset as usize;
+                let src = self
+                    .nvram
+                    .get(start..start + buffer.len())
+                    .ok_or(ErrorKind::NvramOutOfBounds)?;
+                buffer.copy_from_slice(src);
+                Ok(())
+            }
+
+            fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+                let start = offset as usize;
+                self.nvram
+                    .get_mut(start..start + data.len())
+                    .ok_or(ErrorKind::NvramOutOfBounds)?
+                    .copy_from_slice(data);
+                Ok(())
+            }
+
+            fn nvram_size(&self) -> u32 {
+                self.nvram.len() as u32
+            }
+        }
+
+        let full = || Full {
+            running: false,
+            sqw: None,
+            nvram: [0; 4],
+        };
+
+        let mut power: Box<dyn RtcPowerControl<Error = ErrorKind>> = Box::new(full());
+        power.start_clock().unwrap();
+        power.halt_clock().unwrap();
+
+        let mut sqw: Box<dyn SquareWave<Error = ErrorKind>> = Box::new(full());
+        sqw.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        sqw.disable_square_wave().unwrap();
+
+        let mut nvram: Box<dyn RtcNvram<Error = ErrorKind>> = Box::new(full());
+        assert_eq!(nvram.nvram_size(), 4);
+        nvram.write_nvram(1, &[0xAB, 0xCD]).unwrap();
+        let mut buffer = [0; 2];
+        nvram.read_nvram(1, &mut buffer).unwrap();
+        assert_eq!(buffer, [0xAB, 0xCD]);
+        assert_eq!(
+            nvram.write_nvram(3, &[1, 2]),
+            Err(ErrorKind::NvramOutOfBounds)
+        );
+    }
 }
@@ -0,0 +1,101 @@
+//! # Async RTC Trait Interface
+//!
+//! Async counterpart of [`Rtc`](crate::rtc::Rtc), for drivers on an async bus
+//! (e.g. `embedded-hal-async` I2C under Embassy). It shares [`ErrorType`], so
+//! the same error type serves both the blocking and the async driver.
+//!
+//! Enabled with the `async` feature.
+use crate::{datetime::DateTime, error::ErrorType};
+
+/// Async version of [`Rtc`](crate::rtc::Rtc).
+///
+/// # Example
+///
+/// ```ignore
+/// let mut rtc = Ds1307::new(i2c);
+/// let now = rtc.get_datetime().await?;
+/// rtc.set_datetime(&DateTime::new(2024, 8, 16, 12, 0, 0)?).await?;
+/// ```
+#[allow(async_fn_in_trait)]
+pub trait Rtc: ErrorType {
+    /// Get the current date and time atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails.
+    async fn get_datetime(&mut self) -> Result<DateTime, Self::Error>;
+
+    /// Set the current date and time atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails or
+    /// if the provided `DateTime` is out of range for this device.
+    async fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error>;
+}
+
+/// blanket impl for all `&mut T`
+impl<T: Rtc + ?Sized> Rtc for &mut T {
+    #[inline]
+    async fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        T::get_datetime(self).await
+    }
+
+    #[inline]
+    async fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        T::set_datetime(self, datetime).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    struct MockRtc {
+        now: DateTime,
+    }
+
+    impl ErrorType for MockRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MockRtc {
+        async fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(self.now)
+        }
+
+        async fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            if datetime.year() > 2099 {
+                return Err(ErrorKind::InvalidDateTime);
+            }
+            self.now = *datetime;
+            Ok(())
+        }
+    }
+
+    async fn read<R: Rtc>(mut rtc: R) -> Result<DateTime, R::Error> {
+        rtc.get_datetime().await
+    }
+
+    #[test]
+    fn test_async_get_set() {
+        let mut rtc = MockRtc { now: DateTime::MIN };
+        let datetime = DateTime::new(2024, 8, 16, 12, 0, 0).unwrap();
+        pollster::block_on(rtc.set_datetime(&datetime)).unwrap();
+        assert_eq!(pollster::block_on(rtc.get_datetime()), Ok(datetime));
+
+        let too_late = DateTime::new(2100, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            pollster::block_on(rtc.set_datetime(&too_late)),
+            Err(ErrorKind::InvalidDateTime)
+        );
+    }
+
+    #[test]
+    fn test_async_mut_ref_blanket_impl() {
+        let mut rtc = MockRtc { now: DateTime::MIN };
+        assert_eq!(pollster::block_on(read(&mut rtc)), Ok(DateTime::MIN));
+        assert_eq!(rtc.now, DateTime::MIN);
+    }
+}
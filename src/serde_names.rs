@@ -0,0 +1,50 @@
+//! Name lookup shared by the hand-written `serde` impls
+use core::fmt;
+
+use serde::de::{self, DeserializeSeed, Deserializer, Visitor};
+
+/// Deserializes one of a fixed list of names as its index in the list
+///
+/// Used for the variants of enums stored by name and for struct field names.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Name {
+    /// An enum variant; other names are `unknown_variant` errors
+    Variant(&'static [&'static str]),
+    /// A struct field; other names are `unknown_field` errors
+    Field(&'static [&'static str]),
+}
+
+impl Name {
+    fn names(self) -> &'static [&'static str] {
+        match self {
+            Self::Variant(names) | Self::Field(names) => names,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Name {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl Visitor<'_> for Name {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "one of {:?}", self.names())
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<usize, E> {
+        let names = self.names();
+        names
+            .iter()
+            .position(|known| *known == name)
+            .ok_or_else(|| match self {
+                Self::Variant(_) => E::unknown_variant(name, names),
+                Self::Field(_) => E::unknown_field(name, names),
+            })
+    }
+}
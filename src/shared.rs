@@ -0,0 +1,545 @@
+//! # Sharing one RTC between several owners
+//!
+//! Applications should normally own their `Rtc` (see [`crate::rtc`]). When
+//! several parts of a program need the same chip, e.g. a display, a logger
+//! and an alarm manager, these adapters give each of them an owned handle that
+//! implements the RTC traits and serializes access internally, in the same
+//! way as the `embedded-hal-bus` device types.
+//!
+//! - [`RefCellRtc`] shares through a [`RefCell`]. It is not `Sync`, so all
+//!   handles must live in the same thread or task.
+//! - [`CriticalSectionRtc`] shares through a `critical_section::Mutex`, so
+//!   handles can be used from different tasks and interrupt handlers. Enabled
+//!   with the `critical-section` feature.
+//!
+//! Each handle forwards every trait the inner RTC implements: [`Rtc`],
+//! [`RtcNvram`], [`NvramProtect`], [`RtcPowerControl`], [`ClockIntegrity`],
+//! [`SquareWave`], [`SquareWaveDuty`], [`SquareWaveBatteryBacked`],
+//! [`SquareWavePins`], [`Output32kHz`], [`RtcTimestamp`], [`Alarm`] and
+//! [`PeriodicInterrupt`].
+//!
+//! ```
+//! use core::cell::RefCell;
+//! use rtc_hal::shared::RefCellRtc;
+//! use rtc_hal::rtc::Rtc;
+//! # use rtc_hal::{datetime::DateTime, error::{ErrorKind, ErrorType}};
+//! # struct Ds1307(DateTime);
+//! # impl ErrorType for Ds1307 { type Error = ErrorKind; }
+//! # impl Rtc for Ds1307 {
+//! #     fn get_datetime(&mut self) -> Result<DateTime, ErrorKind> { Ok(self.0) }
+//! #     fn set_datetime(&mut self, dt: &DateTime) -> Result<(), ErrorKind> { self.0 = *dt; Ok(()) }
+//! # }
+//!
+//! let rtc = RefCell::new(Ds1307(DateTime::MIN));
+//! let mut display = RefCellRtc::new(&rtc);
+//! let mut logger = RefCellRtc::new(&rtc);
+//!
+//! logger.set_datetime(&DateTime::new(2024, 8, 16, 12, 0, 0)?)?;
+//! assert_eq!(display.get_datetime()?, DateTime::new(2024, 8, 16, 12, 0, 0)?);
+//! # Ok::<(), ErrorKind>(())
+//! ```
+use core::cell::RefCell;
+
+use crate::{
+    alarm::{Alarm, AlarmCapabilities, AlarmConfig, AlarmId},
+    control::{ClockIntegrity, RtcPowerControl},
+    datetime::{Date, DateTime, DateTimeError, Time},
+    error::ErrorType,
+    nvram::{NvramProtect, RtcNvram},
+    periodic::{PeriodicInterrupt, TickPeriod},
+    rtc::Rtc,
+    square_wave::{
+        DisabledOutputLevel, DutyCycle, Output32kHz, OutputPin, OutputPinConfig, SquareWave,
+        SquareWaveBatteryBacked, SquareWaveConfig, SquareWaveDuty, SquareWaveFreq, SquareWavePins,
+    },
+    timestamp::RtcTimestamp,
+};
+
+/// Forward the RTC traits through a shared handle.
+///
+/// `$with` is a method `fn(&self, impl FnOnce(&mut T) -> U) -> U` giving
+/// exclusive access to the inner RTC.
+macro_rules! impl_shared_traits {
+    ($handle:ident, $with:ident) => {
+        impl<T: ErrorType> ErrorType for $handle<'_, T> {
+            type Error = T::Error;
+        }
+
+        impl<T: Rtc> Rtc for $handle<'_, T> {
+            #[inline]
+            fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+                self.$with(|rtc| rtc.get_datetime())
+            }
+
+            #[inline]
+            fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_datetime(datetime))
+            }
+
+            #[inline]
+            fn get_time(&mut self) -> Result<Time, Self::Error> {
+                self.$with(|rtc| rtc.get_time())
+            }
+
+            #[inline]
+            fn get_date(&mut self) -> Result<Date, Self::Error> {
+                self.$with(|rtc| rtc.get_date())
+            }
+
+            #[inline]
+            fn set_time(&mut self, time: &Time) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_time(time))
+            }
+
+            #[inline]
+            fn set_date(&mut self, date: &Date) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_date(date))
+            }
+
+            #[inline]
+            fn get_unix_time(&mut self) -> Result<u64, Self::Error> {
+                self.$with(|rtc| rtc.get_unix_time())
+            }
+
+            #[inline]
+            fn set_unix_time(&mut self, secs: u64) -> Result<(), Self::Error>
+            where
+                Self::Error: From<DateTimeError>,
+            {
+                self.$with(|rtc| rtc.set_unix_time(secs))
+            }
+        }
+
+        impl<T: RtcNvram> RtcNvram for $handle<'_, T> {
+            #[inline]
+            fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.read_nvram(offset, buffer))
+            }
+
+            #[inline]
+            fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.write_nvram(offset, data))
+            }
+
+            #[inline]
+            fn nvram_size(&self) -> u32 {
+                self.$with(|rtc| rtc.nvram_size())
+            }
+
+            #[inline]
+            fn fill_nvram(&mut self, offset: u32, len: u32, value: u8) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.fill_nvram(offset, len, value))
+            }
+
+            #[inline]
+            fn erase_nvram(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.erase_nvram())
+            }
+        }
+
+        impl<T: NvramProtect> NvramProtect for $handle<'_, T> {
+            #[inline]
+            fn nvram_write_protected(&mut self) -> Result<bool, Self::Error> {
+                self.$with(|rtc| rtc.nvram_write_protected())
+            }
+
+            #[inline]
+            fn set_nvram_write_protect(&mut self, protect: bool) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_nvram_write_protect(protect))
+            }
+        }
+
+        impl<T: RtcPowerControl> RtcPowerControl for $handle<'_, T> {
+            #[inline]
+            fn start_clock(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.start_clock())
+            }
+
+            #[inline]
+            fn halt_clock(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.halt_clock())
+            }
+        }
+
+        impl<T: ClockIntegrity> ClockIntegrity for $handle<'_, T> {
+            #[inline]
+            fn datetime_valid(&mut self) -> Result<bool, Self::Error> {
+                self.$with(|rtc| rtc.datetime_valid())
+            }
+
+            #[inline]
+            fn clear_integrity_flag(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.clear_integrity_flag())
+            }
+        }
+
+        impl<T: SquareWave> SquareWave for $handle<'_, T> {
+            #[inline]
+            fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.configure_output_pin(config))
+            }
+
+            #[inline]
+            fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.start_square_wave(freq))
+            }
+
+            #[inline]
+            fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.enable_square_wave())
+            }
+
+            #[inline]
+            fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.disable_square_wave())
+            }
+
+            #[inline]
+            fn set_square_wave_frequency(
+                &mut self,
+                freq: SquareWaveFreq,
+            ) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_square_wave_frequency(freq))
+            }
+
+            #[inline]
+            fn set_disabled_output_level(
+                &mut self,
+                level: DisabledOutputLevel,
+            ) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_disabled_output_level(level))
+            }
+
+            #[inline]
+            fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+                self.$with(|rtc| rtc.square_wave_config())
+            }
+
+            #[inline]
+            fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+                self.$with(|rtc| rtc.supported_square_wave_frequencies())
+            }
+
+            #[inline]
+            fn supports_frequency(&self, freq: SquareWaveFreq) -> bool {
+                self.$with(|rtc| rtc.supports_frequency(freq))
+            }
+
+            #[inline]
+            fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+                self.$with(|rtc| rtc.current_pin_mode())
+            }
+        }
+
+        impl<T: RtcTimestamp> RtcTimestamp for $handle<'_, T> {
+            #[inline]
+            fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+                self.$with(|rtc| rtc.get_timestamp())
+            }
+
+            #[inline]
+            fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_timestamp(secs))
+            }
+        }
+
+        impl<T: Alarm> Alarm for $handle<'_, T> {
+            #[inline]
+            fn alarm_count(&self) -> u8 {
+                self.$with(|rtc| rtc.alarm_count())
+            }
+
+            #[inline]
+            fn alarm_capabilities(&self, id: AlarmId) -> AlarmCapabilities {
+                self.$with(|rtc| rtc.alarm_capabilities(id))
+            }
+
+            #[inline]
+            fn set_alarm(&mut self, id: AlarmId, config: &AlarmConfig) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_alarm(id, config))
+            }
+
+            #[inline]
+            fn disable_alarm(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.disable_alarm(id))
+            }
+
+            #[inline]
+            fn alarm_triggered(&mut self, id: AlarmId) -> Result<bool, Self::Error> {
+                self.$with(|rtc| rtc.alarm_triggered(id))
+            }
+
+            #[inline]
+            fn clear_alarm_flag(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.clear_alarm_flag(id))
+            }
+
+            #[inline]
+            fn enable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.enable_alarm_interrupt(id))
+            }
+
+            #[inline]
+            fn disable_alarm_interrupt(&mut self, id: AlarmId) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.disable_alarm_interrupt(id))
+            }
+
+            #[inline]
+            fn disable_all_alarms(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.disable_all_alarms())
+            }
+
+            #[inline]
+            fn set_alarm_in_seconds(
+                &mut self,
+                id: AlarmId,
+                seconds: u32,
+            ) -> Result<DateTime, Self::Error>
+            where
+                Self::Error: From<DateTimeError>,
+            {
+                self.$with(|rtc| rtc.set_alarm_in_seconds(id, seconds))
+            }
+        }
+
+        impl<T: SquareWaveDuty> SquareWaveDuty for $handle<'_, T> {
+            #[inline]
+            fn set_duty_cycle(&mut self, duty: DutyCycle) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.set_duty_cycle(duty))
+            }
+
+            #[inline]
+            fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error> {
+                self.$with(|rtc| rtc.duty_cycle())
+            }
+        }
+
+        impl<T: SquareWaveBatteryBacked> SquareWaveBatteryBacked for $handle<'_, T> {
+            #[inline]
+            fn enable_square_wave_on_battery(&mut self, enabled: bool) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.enable_square_wave_on_battery(enabled))
+            }
+
+            #[inline]
+            fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error> {
+                self.$with(|rtc| rtc.is_square_wave_on_battery_enabled())
+            }
+        }
+
+        impl<T: SquareWavePins> SquareWavePins for $handle<'_, T> {
+            #[inline]
+            fn output_pin_count(&self) -> u8 {
+                self.$with(|rtc| rtc.output_pin_count())
+            }
+
+            #[inline]
+            fn start_square_wave_on(
+                &mut self,
+                pin: OutputPin,
+                freq: SquareWaveFreq,
+            ) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.start_square_wave_on(pin, freq))
+            }
+
+            #[inline]
+            fn disable_square_wave_on(&mut self, pin: OutputPin) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.disable_square_wave_on(pin))
+            }
+        }
+
+        impl<T: Output32kHz> Output32kHz for $handle<'_, T> {
+            #[inline]
+            fn enable_32khz_output(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.enable_32khz_output())
+            }
+
+            #[inline]
+            fn disable_32khz_output(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.disable_32khz_output())
+            }
+
+            #[inline]
+            fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error> {
+                self.$with(|rtc| rtc.is_32khz_output_enabled())
+            }
+        }
+
+        impl<T: PeriodicInterrupt> PeriodicInterrupt for $handle<'_, T> {
+            #[inline]
+            fn enable_tick(&mut self, period: TickPeriod) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.enable_tick(period))
+            }
+
+            #[inline]
+            fn disable_tick(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.disable_tick())
+            }
+
+            #[inline]
+            fn tick_flag(&mut self) -> Result<bool, Self::Error> {
+                self.$with(|rtc| rtc.tick_flag())
+            }
+
+            #[inline]
+            fn clear_tick_flag(&mut self) -> Result<(), Self::Error> {
+                self.$with(|rtc| rtc.clear_tick_flag())
+            }
+        }
+    };
+}
+
+/// Handle to an RTC shared through a [`RefCell`]
+///
+/// # Panics
+///
+/// Every method panics if the RTC is already borrowed, which can only happen
+/// if a handle is used while another call on the same RTC is in progress
+/// (e.g. from inside a callback).
+pub struct RefCellRtc<'a, T> {
+    rtc: &'a RefCell<T>,
+}
+
+impl<'a, T> RefCellRtc<'a, T> {
+    /// Create a handle to the shared RTC
+    pub fn new(rtc: &'a RefCell<T>) -> Self {
+        Self { rtc }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.rtc.borrow_mut())
+    }
+}
+
+impl_shared_traits!(RefCellRtc, with);
+
+/// Handle to an RTC shared through a `critical_section::Mutex`
+///
+/// Every call runs inside a critical section, so interrupts are masked for
+/// the duration of the bus transaction. Keep this in mind for slow buses or
+/// large NVRAM transfers.
+///
+/// # Panics
+///
+/// Every method panics if the RTC is already borrowed, which can only happen
+/// on reentrant use of the same RTC within one critical section.
+#[cfg(feature = "critical-section")]
+pub struct CriticalSectionRtc<'a, T> {
+    rtc: &'a critical_section::Mutex<RefCell<T>>,
+}
+
+#[cfg(feature = "critical-section")]
+impl<'a, T> CriticalSectionRtc<'a, T> {
+    /// Create a handle to the shared RTC
+    pub fn new(rtc: &'a critical_section::Mutex<RefCell<T>>) -> Self {
+        Self { rtc }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.rtc.borrow_ref_mut(cs)))
+    }
+}
+
+#[cfg(feature = "critical-section")]
+impl_shared_traits!(CriticalSectionRtc, with);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    /// Advances one second per read and counts reads
+    struct MockRtc {
+        now: DateTime,
+        reads: usize,
+        running: bool,
+    }
+
+    impl MockRtc {
+        fn new() -> Self {
+            Self {
+                now: DateTime::new(2024, 8, 16, 12, 0, 0).unwrap(),
+                reads: 0,
+                running: false,
+            }
+        }
+    }
+
+    impl ErrorType for MockRtc {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for MockRtc {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            let now = self.now;
+            self.reads += 1;
+            self.now = self.now.checked_add_seconds(1)?;
+            Ok(now)
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.now = *datetime;
+            Ok(())
+        }
+    }
+
+    impl RtcPowerControl for MockRtc {
+        fn start_clock(&mut self) -> Result<(), Self::Error> {
+            self.running = true;
+            Ok(())
+        }
+
+        fn halt_clock(&mut self) -> Result<(), Self::Error> {
+            self.running = false;
+            Ok(())
+        }
+    }
+
+    fn seconds<R: Rtc>(rtc: &mut R) -> u8 {
+        rtc.get_datetime().unwrap().second()
+    }
+
+    #[test]
+    fn test_refcell_interleaved_owners() {
+        let rtc = RefCell::new(MockRtc::new());
+        let mut display = RefCellRtc::new(&rtc);
+        let mut logger = RefCellRtc::new(&rtc);
+
+        assert_eq!(seconds(&mut display), 0);
+        assert_eq!(seconds(&mut logger), 1);
+        assert_eq!(seconds(&mut display), 2);
+        assert_eq!(logger.get_time(), Ok(Time::new(12, 0, 3).unwrap()));
+        assert_eq!(rtc.borrow().reads, 4);
+
+        logger.start_clock().unwrap();
+        assert!(rtc.borrow().running);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_refcell_reentrant_use_panics() {
+        let rtc = RefCell::new(MockRtc::new());
+        let mut handle = RefCellRtc::new(&rtc);
+        let _guard = rtc.borrow_mut();
+        let _ = handle.get_datetime();
+    }
+
+    #[cfg(feature = "critical-section")]
+    #[test]
+    fn test_critical_section_interleaved_owners() {
+        let rtc = critical_section::Mutex::new(RefCell::new(MockRtc::new()));
+        let mut display = CriticalSectionRtc::new(&rtc);
+        let mut logger = CriticalSectionRtc::new(&rtc);
+
+        assert_eq!(seconds(&mut display), 0);
+        assert_eq!(seconds(&mut logger), 1);
+        logger.set_unix_time(0).unwrap();
+        assert_eq!(display.get_datetime(), Ok(DateTime::MIN));
+
+        display.halt_clock().unwrap();
+        critical_section::with(|cs| {
+            let rtc = rtc.borrow_ref(cs);
+            assert_eq!(rtc.reads, 3);
+            assert!(!rtc.running);
+        });
+    }
+}
@@ -1,9 +1,18 @@
 //! Traits for Square Wave control
 
+use crate::error::ErrorKind;
 use crate::rtc::Rtc;
 
 /// Square wave output frequencies
+///
+/// Frequencies are ordered by their exact value, so `Custom`, `MilliHz` and
+/// `PerMinute` values sort among the named variants, e.g.
+/// `PerMinute < MilliHz(17) < MilliHz(500) < Hz1 < Custom(512) < Hz1024`. As
+/// `Custom(1024)` and `Hz1024` are not equal, the named variant sorts first
+/// when both have the same frequency, then `Custom`, then `MilliHz`; use
+/// [`same_frequency`](SquareWaveFreq::same_frequency) to compare by value.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SquareWaveFreq {
     /// 1 Hz
     Hz1,
@@ -17,10 +26,30 @@ pub enum SquareWaveFreq {
     Hz32768,
     /// Custom frequency (if supported by device)
     Custom(u32),
+    /// Frequency in millihertz, for sub-hertz rates such as 0.5 Hz
+    MilliHz(u32),
+    /// One pulse per minute, exactly 1/60 Hz, e.g. on the RV-8803 or PCF85263
+    ///
+    /// 1/60 Hz is not a whole number of millihertz, so it has its own variant
+    /// rather than a `MilliHz` value.
+    PerMinute,
 }
 
 impl SquareWaveFreq {
+    /// The named frequencies, from lowest to highest
+    pub const STANDARD: [Self; 5] = [
+        Self::Hz1,
+        Self::Hz1024,
+        Self::Hz4096,
+        Self::Hz8192,
+        Self::Hz32768,
+    ];
+
     /// Get frequency value in Hz
+    ///
+    /// `MilliHz` values are rounded down, so sub-hertz rates, including
+    /// `PerMinute`, read as 0; use [`to_millihz`](Self::to_millihz) where they
+    /// matter.
     pub fn to_hz(&self) -> u32 {
         match self {
             Self::Hz1 => 1,
@@ -29,10 +58,59 @@ impl SquareWaveFreq {
             Self::Hz8192 => 8192,
             Self::Hz32768 => 32768,
             Self::Custom(freq) => *freq,
+            Self::MilliHz(millihz) => millihz / 1000,
+            Self::PerMinute => 0,
         }
     }
 
+    /// Get frequency value in millihertz
+    ///
+    /// Exact for every variant but `PerMinute`, which is rounded to 17 mHz.
+    pub fn to_millihz(&self) -> u64 {
+        match self {
+            Self::MilliHz(millihz) => (*millihz).into(),
+            Self::PerMinute => 17,
+            other => u64::from(other.to_hz()) * 1000,
+        }
+    }
+
+    /// Frequency in sixtieths of a millihertz, exact for every variant
+    fn to_sixtieth_millihz(self) -> u64 {
+        match self {
+            Self::PerMinute => 1000,
+            other => other.to_millihz() * 60,
+        }
+    }
+
+    /// Check whether both are the same frequency, e.g. `Custom(1024)` and
+    /// `Hz1024`, or `MilliHz(2000)` and `Custom(2)`
+    pub fn same_frequency(&self, other: &Self) -> bool {
+        self.to_sixtieth_millihz() == other.to_sixtieth_millihz()
+    }
+
+    /// Create from millihertz value
+    ///
+    /// Whole hertz go through [`from_hz`](Self::from_hz), so `1000` is `Hz1`
+    /// and only fractional frequencies become `MilliHz`.
+    pub fn from_millihz(millihz: u32) -> Self {
+        if millihz.is_multiple_of(1000) {
+            Self::from_hz(millihz / 1000)
+        } else {
+            Self::MilliHz(millihz)
+        }
+    }
+
+    /// Highest frequency accepted by [`try_from_hz`](Self::try_from_hz)
+    ///
+    /// Clock outputs are divided down from the 32.768 kHz oscillator, so no
+    /// RTC produces more.
+    pub const MAX_HZ: u32 = 32768;
+
     /// Create from Hz value
+    ///
+    /// Any value is accepted, including 0, which no chip can produce. Use
+    /// [`try_from_hz`](Self::try_from_hz) to validate frequencies from users
+    /// or configuration.
     pub fn from_hz(hz: u32) -> Self {
         match hz {
             1 => Self::Hz1,
@@ -43,10 +121,610 @@ impl SquareWaveFreq {
             other => Self::Custom(other),
         }
     }
+
+    /// Create from Hz value, rejecting frequencies no RTC can produce
+    ///
+    /// Named frequencies become their variant, like with
+    /// [`from_hz`](Self::from_hz). Drivers can call this before checking the
+    /// frequency against what the chip supports.
+    ///
+    /// # Errors
+    ///
+    /// - `SquareWaveError::ZeroFrequency` for 0 Hz
+    /// - `SquareWaveError::AboveMax` above [`MAX_HZ`](Self::MAX_HZ)
+    pub fn try_from_hz(hz: u32) -> Result<Self, SquareWaveError> {
+        match hz {
+            0 => Err(SquareWaveError::ZeroFrequency),
+            hz if hz > Self::MAX_HZ => Err(SquareWaveError::AboveMax),
+            hz => Ok(Self::from_hz(hz)),
+        }
+    }
+
+    /// Create from the 32.768 kHz oscillator divided by 2^`pow2`
+    ///
+    /// 0 is 32768 Hz and 15 is 1 Hz, which matches chips that specify CLKOUT
+    /// as a prescaler tap. Named frequencies become their variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SquareWaveError::DividerTooLarge` for an exponent above 15,
+    /// which would be below 1 Hz.
+    pub fn from_divider(pow2: u8) -> Result<Self, SquareWaveError> {
+        match Self::MAX_HZ.checked_shr(pow2.into()) {
+            Some(hz) if hz > 0 => Ok(Self::from_hz(hz)),
+            _ => Err(SquareWaveError::DividerTooLarge),
+        }
+    }
+
+    /// Get the exponent of the divider of the 32.768 kHz oscillator that
+    /// produces this frequency, the inverse of
+    /// [`from_divider`](Self::from_divider)
+    ///
+    /// Returns `None` for frequencies that are not 32768 Hz divided by a power
+    /// of two, e.g. `Custom(1000)`, `Custom(0)`, `MilliHz(500)` or `PerMinute`.
+    pub fn to_divider(&self) -> Option<u8> {
+        let millihz = self.to_millihz();
+        let hz = u32::try_from(millihz / 1000).ok()?;
+        if millihz.is_multiple_of(1000) && hz.is_power_of_two() && hz <= Self::MAX_HZ {
+            Some((Self::MAX_HZ.trailing_zeros() - hz.trailing_zeros()) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether this is one of the [`STANDARD`](Self::STANDARD)
+    /// frequencies, also when given as `Custom` or `MilliHz`
+    pub fn is_standard(&self) -> bool {
+        Self::STANDARD.iter().any(|freq| freq.same_frequency(self))
+    }
+
+    /// Pick the frequency in `supported` closest to `requested` Hz
+    ///
+    /// When a lower and a higher frequency are equally close, the lower one
+    /// is picked, so a tie never makes the output faster than requested. Of
+    /// several entries with the same frequency, the first is picked. Distances
+    /// are exact, so sub-hertz entries such as `PerMinute` and `MilliHz(17)`
+    /// are told apart.
+    ///
+    /// Returns `None` if `supported` is empty.
+    ///
+    /// ```
+    /// use rtc_hal::square_wave::SquareWaveFreq;
+    ///
+    /// let ds3231 = &SquareWaveFreq::STANDARD[..4];
+    /// assert_eq!(
+    ///     SquareWaveFreq::nearest_supported(2000, ds3231),
+    ///     Some(SquareWaveFreq::Hz1024)
+    /// );
+    /// ```
+    pub fn nearest_supported(requested: u32, supported: &[Self]) -> Option<Self> {
+        supported.iter().copied().min_by_key(|freq| {
+            let value = freq.to_sixtieth_millihz();
+            (value.abs_diff(u64::from(requested) * 60_000), value)
+        })
+    }
+}
+
+impl PartialOrd for SquareWaveFreq {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SquareWaveFreq {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let key = |freq: &Self| {
+            let rank = match freq {
+                Self::Custom(_) => 1,
+                Self::MilliHz(_) => 2,
+                _ => 0,
+            };
+            (freq.to_sixtieth_millihz(), rank)
+        };
+        key(self).cmp(&key(other))
+    }
+}
+
+/// Error returned by [`SquareWaveFreq::try_from_hz`]
+///
+/// Maps to [`ErrorKind::UnsupportedSqwFrequency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum SquareWaveError {
+    /// The frequency is 0 Hz
+    ZeroFrequency,
+    /// The frequency is above [`SquareWaveFreq::MAX_HZ`]
+    AboveMax,
+    /// The divider exponent is above 15, so the frequency is below 1 Hz
+    DividerTooLarge,
+}
+
+impl core::fmt::Display for SquareWaveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SquareWaveError::ZeroFrequency => write!(f, "square wave frequency is 0 Hz"),
+            SquareWaveError::AboveMax => write!(
+                f,
+                "square wave frequency above {} Hz",
+                SquareWaveFreq::MAX_HZ
+            ),
+            SquareWaveError::DividerTooLarge => {
+                write!(f, "square wave divider exponent above 15")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SquareWaveError {}
+
+impl SquareWaveError {
+    /// Every error, in declaration order
+    #[cfg(feature = "serde")]
+    const ALL: [Self; 3] = [Self::ZeroFrequency, Self::AboveMax, Self::DividerTooLarge];
+
+    /// Stable names used by the `serde` impls, the variant names in declaration order
+    #[cfg(feature = "serde")]
+    const NAMES: [&'static str; 3] = ["ZeroFrequency", "AboveMax", "DividerTooLarge"];
+}
+
+/// Serialized as the variant name, e.g. `"ZeroFrequency"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for SquareWaveError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Self::NAMES[*self as usize])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SquareWaveError {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index =
+            deserializer.deserialize_str(crate::serde_names::Name::Variant(&Self::NAMES))?;
+        Ok(Self::ALL[index])
+    }
+}
+
+/// Serialized as an externally tagged enum: `{"Hz": 1024}`,
+/// `{"MilliHz": 500}` or `"PerMinute"` in JSON
+///
+/// Named and `Custom` frequencies are written as `Hz` and read back through
+/// [`from_hz`](SquareWaveFreq::from_hz), so `Custom(1024)` comes back as
+/// `Hz1024`. `MilliHz` values are read back through
+/// [`from_millihz`](SquareWaveFreq::from_millihz).
+#[cfg(feature = "serde")]
+impl serde::Serialize for SquareWaveFreq {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "SquareWaveFreq";
+        match self {
+            Self::MilliHz(millihz) => {
+                serializer.serialize_newtype_variant(NAME, 1, "MilliHz", millihz)
+            }
+            Self::PerMinute => serializer.serialize_unit_variant(NAME, 2, "PerMinute"),
+            other => serializer.serialize_newtype_variant(NAME, 0, "Hz", &other.to_hz()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SquareWaveFreq {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{EnumAccess, VariantAccess};
+
+        const VARIANTS: &[&str] = &["Hz", "MilliHz", "PerMinute"];
+
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = SquareWaveFreq;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a square wave frequency")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<SquareWaveFreq, A::Error> {
+                let (index, variant) =
+                    data.variant_seed(crate::serde_names::Name::Variant(VARIANTS))?;
+                match index {
+                    0 => variant.newtype_variant().map(SquareWaveFreq::from_hz),
+                    1 => variant.newtype_variant().map(SquareWaveFreq::from_millihz),
+                    _ => variant.unit_variant().map(|()| SquareWaveFreq::PerMinute),
+                }
+            }
+        }
+
+        deserializer.deserialize_enum("SquareWaveFreq", VARIANTS, Visitor)
+    }
+}
+
+/// State of the output pin while the square wave is disabled
+///
+/// On the DS1307 the OUT bit selects low or high, which lets the pin double
+/// as a general purpose output. Open-drain pins such as the DS3231 INT/SQW
+/// can only pull low or float, and push-pull pins cannot float.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DisabledOutputLevel {
+    /// Driven or pulled low
+    Low,
+    /// Driven or pulled high
+    High,
+    /// Not driven, left to an external pull-up or pull-down
+    HighImpedance,
+}
+
+#[cfg(feature = "serde")]
+impl DisabledOutputLevel {
+    const ALL: [Self; 3] = [Self::Low, Self::High, Self::HighImpedance];
+    const NAMES: [&'static str; 3] = ["Low", "High", "HighImpedance"];
+}
+
+/// Serialized as `"Low"`, `"High"` or `"HighImpedance"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for DisabledOutputLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Self::NAMES[*self as usize])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DisabledOutputLevel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index =
+            deserializer.deserialize_str(crate::serde_names::Name::Variant(&Self::NAMES))?;
+        Ok(Self::ALL[index])
+    }
+}
+
+/// What the square-wave/interrupt output pin carries
+///
+/// On DS1307/DS3231-family parts one pin carries either the square wave or the
+/// alarm interrupt (INTCN on the DS3231), so the modes are mutually exclusive.
+/// The current mode is read back with [`SquareWave::current_pin_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OutputPinConfig {
+    /// Output a square wave at the given frequency
+    SquareWave(SquareWaveFreq),
+    /// Assert the pin when an alarm with its interrupt enabled fires
+    AlarmInterrupt,
+    /// Hold the pin at a fixed level, e.g. the DS1307 OUT bit, or let it float
+    Disabled {
+        /// State while disabled
+        level: DisabledOutputLevel,
+    },
+}
+
+/// Serialized as an externally tagged enum: `{"SquareWave": {"Hz": 1024}}`,
+/// `"AlarmInterrupt"` or `{"Disabled": {"level": "High"}}` in JSON
+#[cfg(feature = "serde")]
+impl serde::Serialize for OutputPinConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStructVariant;
+
+        const NAME: &str = "OutputPinConfig";
+        match self {
+            Self::SquareWave(freq) => {
+                serializer.serialize_newtype_variant(NAME, 0, "SquareWave", freq)
+            }
+            Self::AlarmInterrupt => serializer.serialize_unit_variant(NAME, 1, "AlarmInterrupt"),
+            Self::Disabled { level } => {
+                let mut variant = serializer.serialize_struct_variant(NAME, 2, "Disabled", 1)?;
+                variant.serialize_field("level", level)?;
+                variant.end()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OutputPinConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use crate::serde_names::Name;
+        use serde::de::{EnumAccess, MapAccess, VariantAccess};
+
+        const VARIANTS: &[&str] = &["SquareWave", "AlarmInterrupt", "Disabled"];
+        const FIELDS: &[&str] = &["level"];
+
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = OutputPinConfig;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "an output pin configuration")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<OutputPinConfig, A::Error> {
+                let (index, variant) = data.variant_seed(Name::Variant(VARIANTS))?;
+                match index {
+                    0 => variant.newtype_variant().map(OutputPinConfig::SquareWave),
+                    1 => variant
+                        .unit_variant()
+                        .map(|()| OutputPinConfig::AlarmInterrupt),
+                    _ => variant.struct_variant(FIELDS, DisabledVisitor),
+                }
+            }
+        }
+
+        struct DisabledVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for DisabledVisitor {
+            type Value = OutputPinConfig;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "the level of a disabled output pin")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<OutputPinConfig, A::Error> {
+                let mut level = None;
+                while map.next_key_seed(Name::Field(FIELDS))?.is_some() {
+                    level = Some(map.next_value()?);
+                }
+                let level = level.ok_or_else(|| serde::de::Error::missing_field("level"))?;
+                Ok(OutputPinConfig::Disabled { level })
+            }
+        }
+
+        deserializer.deserialize_enum("OutputPinConfig", VARIANTS, Visitor)
+    }
+}
+
+/// Share of each square wave period spent high, in percent
+///
+/// Always within 1..=99, so drivers only need to check whether the chip can
+/// produce the ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DutyCycle(u8);
+
+impl DutyCycle {
+    /// A symmetric square wave, high half of the time
+    pub const HALF: Self = Self(50);
+
+    /// Create a duty cycle of `percent`, or `None` outside 1..=99
+    pub const fn new(percent: u8) -> Option<Self> {
+        if matches!(percent, 1..=99) {
+            Some(Self(percent))
+        } else {
+            None
+        }
+    }
+
+    /// Get the duty cycle in percent
+    pub const fn percent(self) -> u8 {
+        self.0
+    }
+}
+
+impl Default for DutyCycle {
+    fn default() -> Self {
+        Self::HALF
+    }
+}
+
+impl core::fmt::Display for DutyCycle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+/// Serialized as the percentage, e.g. `25`
+#[cfg(feature = "serde")]
+impl serde::Serialize for DutyCycle {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+/// Percentages that [`DutyCycle::new`] would reject are rejected here too
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DutyCycle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = DutyCycle;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a duty cycle of 1 to 99 percent")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, percent: u64) -> Result<DutyCycle, E> {
+                u8::try_from(percent)
+                    .ok()
+                    .and_then(DutyCycle::new)
+                    .ok_or_else(|| {
+                        E::invalid_value(serde::de::Unexpected::Unsigned(percent), &self)
+                    })
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, percent: i64) -> Result<DutyCycle, E> {
+                u64::try_from(percent)
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(percent), &self))
+                    .and_then(|percent| self.visit_u64(percent))
+            }
+        }
+
+        deserializer.deserialize_u8(Visitor)
+    }
+}
+
+/// Identifies one of the clock output pins of a chip, see [`SquareWavePins`]
+///
+/// Pins are numbered from 0. [`OutputPin::PRIMARY`] is the pin the
+/// [`SquareWave`] methods drive, and the only one on single-output chips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutputPin(pub u8);
+
+impl OutputPin {
+    /// The pin driven by [`SquareWave`], e.g. CLKOUT
+    pub const PRIMARY: OutputPin = OutputPin(0);
+    /// The second clock output, on chips that have one
+    pub const SECONDARY: OutputPin = OutputPin(1);
+
+    /// Check that this pin exists on a chip with `pin_count` output pins.
+    ///
+    /// Lets drivers reject unknown pins with `?`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ErrorKind::InvalidAddress` if the pin is not below `pin_count`.
+    pub const fn check(self, pin_count: u8) -> Result<Self, ErrorKind> {
+        if self.0 < pin_count {
+            Ok(self)
+        } else {
+            Err(ErrorKind::InvalidAddress)
+        }
+    }
+}
+
+/// Serialized as the pin number from 0, e.g. `1` for [`OutputPin::SECONDARY`]
+#[cfg(feature = "serde")]
+impl serde::Serialize for OutputPin {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OutputPin {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u8 as serde::Deserialize>::deserialize(deserializer).map(OutputPin)
+    }
+}
+
+/// Square wave output as read back from the chip, see
+/// [`SquareWave::square_wave_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SquareWaveConfig {
+    /// Whether the output pin carries the square wave
+    pub enabled: bool,
+    /// Selected frequency, also while the output is disabled
+    pub freq: SquareWaveFreq,
+    /// State of the pin while the output is disabled, see
+    /// [`SquareWave::set_disabled_output_level`]
+    pub disabled_level: DisabledOutputLevel,
+}
+
+/// Serialized as a struct with fields `enabled`, `freq` and `disabled_level`,
+/// e.g. `{"enabled": true, "freq": {"Hz": 1024}, "disabled_level": "High"}` in JSON
+#[cfg(feature = "serde")]
+impl serde::Serialize for SquareWaveConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("SquareWaveConfig", 3)?;
+        state.serialize_field("enabled", &self.enabled)?;
+        state.serialize_field("freq", &self.freq)?;
+        state.serialize_field("disabled_level", &self.disabled_level)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SquareWaveConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use crate::serde_names::Name;
+        use serde::de::{Error, MapAccess};
+
+        const FIELDS: &[&str] = &["enabled", "freq", "disabled_level"];
+
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = SquareWaveConfig;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a square wave configuration")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<SquareWaveConfig, A::Error> {
+                let (mut enabled, mut freq, mut disabled_level) = (None, None, None);
+                while let Some(field) = map.next_key_seed(Name::Field(FIELDS))? {
+                    match field {
+                        0 => enabled = Some(map.next_value()?),
+                        1 => freq = Some(map.next_value()?),
+                        _ => disabled_level = Some(map.next_value()?),
+                    }
+                }
+                Ok(SquareWaveConfig {
+                    enabled: enabled.ok_or_else(|| A::Error::missing_field("enabled"))?,
+                    freq: freq.ok_or_else(|| A::Error::missing_field("freq"))?,
+                    disabled_level: disabled_level
+                        .ok_or_else(|| A::Error::missing_field("disabled_level"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("SquareWaveConfig", FIELDS, Visitor)
+    }
 }
 
 /// Square wave functionality trait
+///
+/// ## Output pin
+///
+/// [`configure_output_pin`](SquareWave::configure_output_pin) selects what the
+/// output pin carries. The other methods are shorthands for it:
+/// [`start_square_wave`](SquareWave::start_square_wave) and
+/// [`enable_square_wave`](SquareWave::enable_square_wave) switch the pin to
+/// [`OutputPinConfig::SquareWave`], taking it away from alarm interrupts, and
+/// [`disable_square_wave`](SquareWave::disable_square_wave) returns it to the
+/// chip's idle state. Conversely, on chips with a shared pin,
+/// [`Alarm::enable_alarm_interrupt`](crate::alarm::Alarm::enable_alarm_interrupt)
+/// switches it to [`OutputPinConfig::AlarmInterrupt`], which stops the square
+/// wave.
+///
+/// The last call wins, and the switch must be observable: after
+/// `start_square_wave` [`current_pin_mode`](SquareWave::current_pin_mode)
+/// reports `SquareWave` and the alarms no longer reach the pin, and after
+/// `enable_alarm_interrupt` it reports `AlarmInterrupt`. Drivers must not
+/// refuse either call because the other mode is active. Code sharing the pin
+/// with other parts of a program can check whether it was taken over:
+///
+/// ```
+/// use rtc_hal::square_wave::{OutputPinConfig, SquareWave, SquareWaveFreq};
+///
+/// /// Restart the 1 Hz tick if an alarm interrupt took the pin
+/// fn keep_ticking<R: SquareWave>(rtc: &mut R) -> Result<(), R::Error> {
+///     if !matches!(rtc.current_pin_mode()?, OutputPinConfig::SquareWave(_)) {
+///         rtc.start_square_wave(SquareWaveFreq::Hz1)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// On chips with separate clock and interrupt pins, such as the PCF8563 with
+/// CLKOUT and INT, the configuration applies to the clock pin:
+/// `AlarmInterrupt` turns the clock output off and leaves INT to the alarm
+/// interrupt enables.
+///
+/// While disabled the pin takes the level selected with
+/// [`set_disabled_output_level`](SquareWave::set_disabled_output_level).
+///
+/// A separate fixed 32.768 kHz pin, such as the DS3231 32kHz output, is not
+/// the square wave output even when it can be switched off; drivers expose it
+/// through [`Output32kHz`], and `SquareWave` never touches it.
+///
+/// On chips that can route the clock to more than one pin, these methods act
+/// on [`OutputPin::PRIMARY`]; the other pins are reached through
+/// [`SquareWavePins`].
 pub trait SquareWave: Rtc {
+    /// Select what the output pin carries, see [Output pin](SquareWave#output-pin).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnsupportedSqwFrequency`] for a
+    /// frequency the chip cannot output, [`ErrorKind::Unsupported`] for a mode
+    /// or level the pin cannot produce, or `Self::Error` if communication fails.
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error>;
+
     /// Configure Frequency and enable square wave
     fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error>;
 
@@ -58,6 +736,340 @@ pub trait SquareWave: Rtc {
 
     /// Set the frequency (without enabling/disabling)
     fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error>;
+
+    /// Select the state of the pin while the square wave is disabled.
+    ///
+    /// Does not enable or disable the output; if it is disabled the new level
+    /// applies at once, e.g. to use the DS1307 SQW/OUT pin as an output:
+    ///
+    /// ```
+    /// use rtc_hal::square_wave::{DisabledOutputLevel, SquareWave};
+    ///
+    /// fn set_led<R: SquareWave>(rtc: &mut R, on: bool) -> Result<(), R::Error> {
+    ///     rtc.disable_square_wave()?;
+    ///     rtc.set_disabled_output_level(if on {
+    ///         DisabledOutputLevel::Low
+    ///     } else {
+    ///         DisabledOutputLevel::High
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::Unsupported`] for a level the
+    /// pin cannot produce, e.g. `HighImpedance` on a push-pull output or
+    /// anything but `Low` or `HighImpedance` on an open-drain one, or
+    /// `Self::Error` if communication fails.
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error>;
+
+    /// Read back whether the square wave is output, at which frequency, and
+    /// the state of the pin while disabled.
+    ///
+    /// Lets start-up code leave the control register alone when it already
+    /// holds the wanted configuration:
+    ///
+    /// ```
+    /// use rtc_hal::square_wave::{SquareWave, SquareWaveFreq};
+    ///
+    /// fn ensure_1hz<R: SquareWave>(rtc: &mut R) -> Result<(), R::Error> {
+    ///     let config = rtc.square_wave_config()?;
+    ///     if !config.enabled || config.freq != SquareWaveFreq::Hz1 {
+    ///         rtc.start_square_wave(SquareWaveFreq::Hz1)?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::Unsupported`] if the configuration
+    /// cannot be read back, e.g. from write-only registers, or `Self::Error` if
+    /// communication fails.
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error>;
+
+    /// Frequencies the square wave output can produce, e.g. for a settings
+    /// menu.
+    ///
+    /// Setting any other frequency still fails with
+    /// [`ErrorKind::UnsupportedSqwFrequency`].
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq];
+
+    /// Check whether `freq` is one of the
+    /// [supported frequencies](SquareWave::supported_square_wave_frequencies).
+    ///
+    /// Frequencies are compared by value, so `Custom(1024)` is supported
+    /// wherever `Hz1024` is.
+    fn supports_frequency(&self, freq: SquareWaveFreq) -> bool {
+        self.supported_square_wave_frequencies()
+            .iter()
+            .any(|supported| supported.same_frequency(&freq))
+    }
+
+    /// Read back what the output pin carries, see
+    /// [Output pin](SquareWave#output-pin).
+    ///
+    /// The default derives the mode from
+    /// [`square_wave_config`](SquareWave::square_wave_config), so it never
+    /// reports [`OutputPinConfig::AlarmInterrupt`]. Drivers whose pin can
+    /// carry the alarm interrupt must override it.
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        let config = self.square_wave_config()?;
+        Ok(if config.enabled {
+            OutputPinConfig::SquareWave(config.freq)
+        } else {
+            OutputPinConfig::Disabled {
+                level: config.disabled_level,
+            }
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: SquareWave + ?Sized> SquareWave for alloc::boxed::Box<T> {
+    #[inline]
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        T::configure_output_pin(self, config)
+    }
+
+    #[inline]
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        T::start_square_wave(self, freq)
+    }
+
+    #[inline]
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        T::enable_square_wave(self)
+    }
+
+    #[inline]
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        T::disable_square_wave(self)
+    }
+
+    #[inline]
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        T::set_square_wave_frequency(self, freq)
+    }
+
+    #[inline]
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        T::set_disabled_output_level(self, level)
+    }
+
+    #[inline]
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        T::square_wave_config(self)
+    }
+
+    #[inline]
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        T::supported_square_wave_frequencies(self)
+    }
+
+    #[inline]
+    fn supports_frequency(&self, freq: SquareWaveFreq) -> bool {
+        T::supports_frequency(self, freq)
+    }
+
+    #[inline]
+    fn current_pin_mode(&mut self) -> Result<OutputPinConfig, Self::Error> {
+        T::current_pin_mode(self)
+    }
+}
+
+/// This trait extends [`SquareWave`] with a selectable duty cycle.
+///
+/// For chips such as the RV-3028 whose clock output can be asymmetric in some
+/// modes. The duty cycle is kept when the frequency changes, unless the chip
+/// cannot produce it at the new frequency.
+pub trait SquareWaveDuty: SquareWave {
+    /// Set the share of each period the output is high.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::Unsupported`] for a duty cycle the
+    /// chip cannot produce, or `Self::Error` if communication fails.
+    fn set_duty_cycle(&mut self, duty: DutyCycle) -> Result<(), Self::Error>;
+
+    /// Read back the current duty cycle.
+    fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: SquareWaveDuty + ?Sized> SquareWaveDuty for alloc::boxed::Box<T> {
+    #[inline]
+    fn set_duty_cycle(&mut self, duty: DutyCycle) -> Result<(), Self::Error> {
+        T::set_duty_cycle(self, duty)
+    }
+
+    #[inline]
+    fn duty_cycle(&mut self) -> Result<DutyCycle, Self::Error> {
+        T::duty_cycle(self)
+    }
+}
+
+/// This trait extends [`SquareWave`] with keeping the output running on the
+/// backup supply.
+///
+/// On the DS3231 the square wave stops when the chip switches to VBAT unless
+/// BBSQW is set, so a 1 Hz output used as an external tick disappears with
+/// main power. The setting only matters while on battery; enabling and
+/// disabling the output through [`SquareWave`] work as before, and a disabled
+/// output stays off on battery too.
+///
+/// ## Power consumption
+///
+/// Keeping the clock on battery typically takes a few µA. A running output
+/// costs far more: every low phase sinks current through the pull-up, e.g.
+/// 300 µA through 10 kΩ at 3 V, which at 1 Hz averages about 150 µA and
+/// drains a CR2032 in roughly two months instead of years. Only enable it when
+/// something consumes the output during power loss.
+pub trait SquareWaveBatteryBacked: SquareWave {
+    /// Keep the square wave running on the backup supply, or let it stop.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication fails.
+    fn enable_square_wave_on_battery(&mut self, enabled: bool) -> Result<(), Self::Error>;
+
+    /// Check whether the square wave keeps running on the backup supply.
+    fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: SquareWaveBatteryBacked + ?Sized> SquareWaveBatteryBacked for alloc::boxed::Box<T> {
+    #[inline]
+    fn enable_square_wave_on_battery(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        T::enable_square_wave_on_battery(self, enabled)
+    }
+
+    #[inline]
+    fn is_square_wave_on_battery_enabled(&mut self) -> Result<bool, Self::Error> {
+        T::is_square_wave_on_battery_enabled(self)
+    }
+}
+
+/// This trait extends [`SquareWave`] with clock output on more than one pin.
+///
+/// For chips such as the PCF2129 or RV-8803 with several clock outputs or
+/// per-pin enables. Each pin is configured independently, and the
+/// [`SquareWave`] methods keep acting on [`OutputPin::PRIMARY`], so
+/// `start_square_wave_on(OutputPin::PRIMARY, freq)` is the same as
+/// `start_square_wave(freq)`.
+///
+/// ```
+/// use rtc_hal::square_wave::{OutputPin, SquareWaveFreq, SquareWavePins};
+///
+/// /// Drive a 1 Hz tick on every clock output the chip has
+/// fn tick_everywhere<R: SquareWavePins>(rtc: &mut R) -> Result<(), R::Error> {
+///     for pin in (0..rtc.output_pin_count()).map(OutputPin) {
+///         rtc.start_square_wave_on(pin, SquareWaveFreq::Hz1)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub trait SquareWavePins: SquareWave {
+    /// Number of clock output pins, addressed as `OutputPin(0)` to
+    /// `OutputPin(output_pin_count() - 1)`.
+    fn output_pin_count(&self) -> u8;
+
+    /// Start the square wave at `freq` on `pin`, leaving the other pins alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind
+    /// [`ErrorKind::InvalidAddress`] for a pin the chip does not have (see
+    /// [`OutputPin::check`]), an error of kind
+    /// [`ErrorKind::UnsupportedSqwFrequency`] for a frequency that pin cannot
+    /// produce, or `Self::Error` if communication fails.
+    fn start_square_wave_on(
+        &mut self,
+        pin: OutputPin,
+        freq: SquareWaveFreq,
+    ) -> Result<(), Self::Error>;
+
+    /// Stop the square wave on `pin`, leaving the other pins alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidAddress`] for a pin the
+    /// chip does not have, or `Self::Error` if communication fails.
+    fn disable_square_wave_on(&mut self, pin: OutputPin) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: SquareWavePins + ?Sized> SquareWavePins for alloc::boxed::Box<T> {
+    #[inline]
+    fn output_pin_count(&self) -> u8 {
+        T::output_pin_count(self)
+    }
+
+    #[inline]
+    fn start_square_wave_on(
+        &mut self,
+        pin: OutputPin,
+        freq: SquareWaveFreq,
+    ) -> Result<(), Self::Error> {
+        T::start_square_wave_on(self, pin, freq)
+    }
+
+    #[inline]
+    fn disable_square_wave_on(&mut self, pin: OutputPin) -> Result<(), Self::Error> {
+        T::disable_square_wave_on(self, pin)
+    }
+}
+
+/// Dedicated 32.768 kHz output, independent of the [`SquareWave`] pin
+///
+/// The DS3231 32kHz pin (EN32kHz) and similar always-on clock outputs run
+/// from the oscillator regardless of what the square wave/interrupt pin
+/// carries, so they are controlled separately. A typical use is clocking an
+/// MCU's low-speed oscillator input in bypass mode:
+///
+/// ```
+/// use rtc_hal::square_wave::Output32kHz;
+///
+/// /// Switch the MCU's LSE to the RTC's 32 kHz output
+/// fn use_rtc_as_lse<R: Output32kHz>(
+///     rtc: &mut R,
+///     enable_lse_bypass: impl FnOnce(),
+/// ) -> Result<(), R::Error> {
+///     if !rtc.is_32khz_output_enabled()? {
+///         rtc.enable_32khz_output()?;
+///     }
+///     // Only bypass the LSE crystal driver once the clock is present
+///     enable_lse_bypass();
+///     Ok(())
+/// }
+/// ```
+pub trait Output32kHz: Rtc {
+    /// Start the 32.768 kHz output
+    fn enable_32khz_output(&mut self) -> Result<(), Self::Error>;
+
+    /// Stop the 32.768 kHz output
+    fn disable_32khz_output(&mut self) -> Result<(), Self::Error>;
+
+    /// Check whether the 32.768 kHz output is running
+    fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Output32kHz + ?Sized> Output32kHz for alloc::boxed::Box<T> {
+    #[inline]
+    fn enable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        T::enable_32khz_output(self)
+    }
+
+    #[inline]
+    fn disable_32khz_output(&mut self) -> Result<(), Self::Error> {
+        T::disable_32khz_output(self)
+    }
+
+    #[inline]
+    fn is_32khz_output_enabled(&mut self) -> Result<bool, Self::Error> {
+        T::is_32khz_output_enabled(self)
+    }
 }
 
 #[cfg(test)]
@@ -138,9 +1150,149 @@ mod tests {
 
         for i in 1..hz_values.len() {
             assert!(hz_values[i] > hz_values[i - 1]);
+            assert!(frequencies[i] > frequencies[i - 1]);
         }
     }
 
+    #[test]
+    fn test_custom_frequencies_sort_by_hz() {
+        let mut frequencies = vec![
+            SquareWaveFreq::Hz32768,
+            SquareWaveFreq::Custom(1024),
+            SquareWaveFreq::Custom(512),
+            SquareWaveFreq::Hz1,
+            SquareWaveFreq::Hz1024,
+            SquareWaveFreq::Custom(0),
+        ];
+        frequencies.sort();
+        assert_eq!(
+            frequencies,
+            [
+                SquareWaveFreq::Custom(0),
+                SquareWaveFreq::Hz1,
+                SquareWaveFreq::Custom(512),
+                SquareWaveFreq::Hz1024,
+                SquareWaveFreq::Custom(1024),
+                SquareWaveFreq::Hz32768,
+            ]
+        );
+        assert_eq!(
+            SquareWaveFreq::Custom(1024).cmp(&SquareWaveFreq::Hz1024),
+            core::cmp::Ordering::Greater
+        );
+        assert_eq!(frequencies.iter().max(), Some(&SquareWaveFreq::Hz32768));
+    }
+
+    #[test]
+    fn test_sub_hertz_frequencies() {
+        let per_minute = SquareWaveFreq::PerMinute;
+        assert_ne!(per_minute, SquareWaveFreq::MilliHz(17));
+        assert!(!per_minute.same_frequency(&SquareWaveFreq::MilliHz(17)));
+        assert!(per_minute.same_frequency(&per_minute));
+        assert_eq!(per_minute.to_millihz(), 17);
+        assert_eq!(per_minute.to_divider(), None);
+        assert_eq!(per_minute.to_hz(), 0);
+        assert!(!per_minute.is_standard());
+        assert_eq!(
+            SquareWaveFreq::from_millihz(17),
+            SquareWaveFreq::MilliHz(17)
+        );
+        assert!(SquareWaveFreq::Custom(2).same_frequency(&SquareWaveFreq::MilliHz(2000)));
+        assert_eq!(SquareWaveFreq::from_millihz(1000), SquareWaveFreq::Hz1);
+        assert_eq!(
+            SquareWaveFreq::from_millihz(2000),
+            SquareWaveFreq::Custom(2)
+        );
+        assert_eq!(
+            SquareWaveFreq::from_millihz(1500),
+            SquareWaveFreq::MilliHz(1500)
+        );
+        assert_eq!(SquareWaveFreq::MilliHz(1500).to_hz(), 1);
+        assert_eq!(SquareWaveFreq::Hz4096.to_millihz(), 4_096_000);
+        assert_eq!(
+            SquareWaveFreq::Custom(u32::MAX).to_millihz(),
+            u64::from(u32::MAX) * 1000
+        );
+
+        // Sub-hertz rates sort below Hz1, and by value among themselves
+        let mut frequencies = vec![
+            SquareWaveFreq::Hz1,
+            SquareWaveFreq::MilliHz(500),
+            SquareWaveFreq::MilliHz(1000),
+            SquareWaveFreq::MilliHz(17),
+            per_minute,
+            SquareWaveFreq::MilliHz(16),
+            SquareWaveFreq::Custom(0),
+        ];
+        frequencies.sort();
+        assert_eq!(
+            frequencies,
+            [
+                SquareWaveFreq::Custom(0),
+                SquareWaveFreq::MilliHz(16),
+                per_minute,
+                SquareWaveFreq::MilliHz(17),
+                SquareWaveFreq::MilliHz(500),
+                SquareWaveFreq::Hz1,
+                SquareWaveFreq::MilliHz(1000),
+            ]
+        );
+        assert!(per_minute < SquareWaveFreq::Hz1);
+        assert!(SquareWaveFreq::MilliHz(1500) < SquareWaveFreq::Custom(2));
+
+        // Values are compared exactly, not rounded to Hz
+        assert!(!SquareWaveFreq::MilliHz(1500).is_standard());
+        assert!(SquareWaveFreq::MilliHz(1000).is_standard());
+        assert_eq!(SquareWaveFreq::MilliHz(1500).to_divider(), None);
+        assert_eq!(SquareWaveFreq::MilliHz(500).to_divider(), None);
+        assert_eq!(SquareWaveFreq::MilliHz(2000).to_divider(), Some(14));
+
+        let rv8803 = [per_minute, SquareWaveFreq::Hz1, SquareWaveFreq::Hz1024];
+        assert_eq!(
+            SquareWaveFreq::nearest_supported(0, &rv8803),
+            Some(per_minute)
+        );
+        assert_eq!(
+            SquareWaveFreq::nearest_supported(1, &rv8803),
+            Some(SquareWaveFreq::Hz1)
+        );
+        // 1/60 Hz is closer to 0 Hz than 17 mHz is
+        assert_eq!(
+            SquareWaveFreq::nearest_supported(0, &[SquareWaveFreq::MilliHz(17), per_minute]),
+            Some(per_minute)
+        );
+    }
+
+    #[test]
+    fn test_nearest_supported() {
+        let supported = [
+            SquareWaveFreq::Hz1,
+            SquareWaveFreq::Hz1024,
+            SquareWaveFreq::Hz4096,
+        ];
+        let nearest = |hz| SquareWaveFreq::nearest_supported(hz, &supported);
+
+        // Exact match
+        assert_eq!(nearest(1024), Some(SquareWaveFreq::Hz1024));
+        // Nearest below, and above
+        assert_eq!(nearest(2000), Some(SquareWaveFreq::Hz1024));
+        assert_eq!(nearest(3000), Some(SquareWaveFreq::Hz4096));
+        assert_eq!(nearest(u32::MAX), Some(SquareWaveFreq::Hz4096));
+        assert_eq!(nearest(0), Some(SquareWaveFreq::Hz1));
+        // 2560 Hz is 1536 Hz from both, so the lower one wins
+        assert_eq!(nearest(2560), Some(SquareWaveFreq::Hz1024));
+        assert_eq!(nearest(2561), Some(SquareWaveFreq::Hz4096));
+        // Equal frequencies keep the slice order
+        assert_eq!(
+            SquareWaveFreq::nearest_supported(
+                1000,
+                &[SquareWaveFreq::Custom(1024), SquareWaveFreq::Hz1024]
+            ),
+            Some(SquareWaveFreq::Custom(1024))
+        );
+        assert_eq!(SquareWaveFreq::nearest_supported(1024, &[]), None);
+    }
+
     #[test]
     fn test_custom_frequency_edge_cases() {
         let edge_cases = vec![
@@ -179,6 +1331,99 @@ mod tests {
         assert_eq!(freq_32768, freq_8192 * 4);
     }
 
+    #[test]
+    fn test_try_from_hz() {
+        assert_eq!(
+            SquareWaveFreq::try_from_hz(0),
+            Err(SquareWaveError::ZeroFrequency)
+        );
+        assert_eq!(SquareWaveFreq::try_from_hz(1), Ok(SquareWaveFreq::Hz1));
+        assert_eq!(
+            SquareWaveFreq::try_from_hz(2),
+            Ok(SquareWaveFreq::Custom(2))
+        );
+        for named in SquareWaveFreq::STANDARD {
+            let hz = named.to_hz();
+            assert_eq!(SquareWaveFreq::try_from_hz(hz), Ok(named));
+            assert_eq!(
+                SquareWaveFreq::try_from_hz(hz - 1).map(|freq| freq.is_standard()),
+                if hz == 1 {
+                    Err(SquareWaveError::ZeroFrequency)
+                } else {
+                    Ok(false)
+                }
+            );
+        }
+        assert_eq!(
+            SquareWaveFreq::try_from_hz(1025),
+            Ok(SquareWaveFreq::Custom(1025))
+        );
+        assert_eq!(
+            SquareWaveFreq::try_from_hz(SquareWaveFreq::MAX_HZ),
+            Ok(SquareWaveFreq::Hz32768)
+        );
+        assert_eq!(
+            SquareWaveFreq::try_from_hz(32769),
+            Err(SquareWaveError::AboveMax)
+        );
+        assert_eq!(
+            SquareWaveFreq::try_from_hz(u32::MAX),
+            Err(SquareWaveError::AboveMax)
+        );
+        assert_eq!(
+            format!("{}", SquareWaveError::AboveMax),
+            "square wave frequency above 32768 Hz"
+        );
+    }
+
+    #[test]
+    fn test_divider_round_trip() {
+        for pow2 in 0..=15 {
+            let freq = SquareWaveFreq::from_divider(pow2).unwrap();
+            assert_eq!(freq.to_hz(), 32768 >> pow2);
+            assert_eq!(freq.to_divider(), Some(pow2));
+        }
+        assert_eq!(SquareWaveFreq::from_divider(0), Ok(SquareWaveFreq::Hz32768));
+        assert_eq!(SquareWaveFreq::from_divider(5), Ok(SquareWaveFreq::Hz1024));
+        assert_eq!(SquareWaveFreq::from_divider(15), Ok(SquareWaveFreq::Hz1));
+        assert_eq!(
+            SquareWaveFreq::from_divider(6),
+            Ok(SquareWaveFreq::Custom(512))
+        );
+        assert_eq!(
+            SquareWaveFreq::from_divider(16),
+            Err(SquareWaveError::DividerTooLarge)
+        );
+        assert_eq!(
+            SquareWaveFreq::from_divider(u8::MAX),
+            Err(SquareWaveError::DividerTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_to_divider_rejects_other_frequencies() {
+        assert_eq!(SquareWaveFreq::Hz8192.to_divider(), Some(2));
+        assert_eq!(SquareWaveFreq::Custom(64).to_divider(), Some(9));
+        assert_eq!(SquareWaveFreq::Custom(0).to_divider(), None);
+        assert_eq!(SquareWaveFreq::Custom(3).to_divider(), None);
+        assert_eq!(SquareWaveFreq::Custom(1000).to_divider(), None);
+        assert_eq!(SquareWaveFreq::Custom(65536).to_divider(), None);
+    }
+
+    #[test]
+    fn test_is_standard() {
+        assert!(
+            SquareWaveFreq::STANDARD
+                .iter()
+                .all(SquareWaveFreq::is_standard)
+        );
+        assert!(SquareWaveFreq::Custom(4096).is_standard());
+        assert!(!SquareWaveFreq::Custom(0).is_standard());
+        assert!(!SquareWaveFreq::Custom(2).is_standard());
+        assert!(!SquareWaveFreq::Custom(1023).is_standard());
+        assert!(!SquareWaveFreq::Custom(65536).is_standard());
+    }
+
     #[test]
     fn test_custom_with_standard_values() {
         let custom_1024 = SquareWaveFreq::Custom(1024);
@@ -190,4 +1435,361 @@ mod tests {
         assert_ne!(custom_1024, SquareWaveFreq::Hz1024);
         assert_ne!(custom_4096, SquareWaveFreq::Hz4096);
     }
+
+    #[test]
+    fn test_duty_cycle_range() {
+        assert_eq!(DutyCycle::new(0), None);
+        assert_eq!(DutyCycle::new(1).map(DutyCycle::percent), Some(1));
+        assert_eq!(DutyCycle::new(50), Some(DutyCycle::HALF));
+        assert_eq!(DutyCycle::new(99).map(DutyCycle::percent), Some(99));
+        assert_eq!(DutyCycle::new(100), None);
+        assert_eq!(DutyCycle::new(u8::MAX), None);
+        assert_eq!(DutyCycle::default(), DutyCycle::HALF);
+        assert_eq!(format!("{}", DutyCycle::new(25).unwrap()), "25%");
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_supported_frequencies_differ_per_chip() {
+        use crate::mock::{MockRtc, Transaction};
+
+        /// Start `wanted` if the chip has it, otherwise fall back to 1 Hz
+        fn start_or_1hz<R: SquareWave>(
+            rtc: &mut R,
+            wanted: SquareWaveFreq,
+        ) -> Result<(), R::Error> {
+            if rtc.supports_frequency(wanted) {
+                rtc.start_square_wave(wanted)
+            } else {
+                rtc.start_square_wave(SquareWaveFreq::Hz1)
+            }
+        }
+
+        let mut ds1307 = MockRtc::new(&[Transaction::start_square_wave(SquareWaveFreq::Hz1)]);
+        ds1307.set_square_wave_frequencies(&[
+            SquareWaveFreq::Hz1,
+            SquareWaveFreq::Hz4096,
+            SquareWaveFreq::Hz8192,
+            SquareWaveFreq::Hz32768,
+        ]);
+        let mut ds3231 =
+            MockRtc::new(&[Transaction::start_square_wave(SquareWaveFreq::Custom(1024))]);
+        ds3231.set_square_wave_frequencies(&SquareWaveFreq::STANDARD[..4]);
+
+        start_or_1hz(&mut ds1307, SquareWaveFreq::Custom(1024)).unwrap();
+        start_or_1hz(&mut ds3231, SquareWaveFreq::Custom(1024)).unwrap();
+        assert!(ds1307.supports_frequency(SquareWaveFreq::Hz32768));
+        assert!(!ds3231.supports_frequency(SquareWaveFreq::Hz32768));
+        ds1307.done();
+        ds3231.done();
+    }
+
+    /// Chip with two clock outputs, each with its own enable and frequency
+    #[derive(Default)]
+    struct TwoPins {
+        pins: [Option<SquareWaveFreq>; 2],
+    }
+
+    impl crate::error::ErrorType for TwoPins {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for TwoPins {
+        fn get_datetime(&mut self) -> Result<crate::datetime::DateTime, Self::Error> {
+            Ok(crate::datetime::DateTime::MIN)
+        }
+
+        fn set_datetime(&mut self, _: &crate::datetime::DateTime) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl SquareWave for TwoPins {
+        fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+            match config {
+                OutputPinConfig::SquareWave(freq) => self.start_square_wave(freq),
+                _ => self.disable_square_wave(),
+            }
+        }
+
+        fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+            self.start_square_wave_on(OutputPin::PRIMARY, freq)
+        }
+
+        fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+            self.start_square_wave(SquareWaveFreq::Hz1)
+        }
+
+        fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+            self.disable_square_wave_on(OutputPin::PRIMARY)
+        }
+
+        fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+            self.start_square_wave(freq)
+        }
+
+        fn set_disabled_output_level(&mut self, _: DisabledOutputLevel) -> Result<(), Self::Error> {
+            Err(ErrorKind::Unsupported)
+        }
+
+        fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+            Ok(SquareWaveConfig {
+                enabled: self.pins[0].is_some(),
+                freq: self.pins[0].unwrap_or(SquareWaveFreq::Hz1),
+                disabled_level: DisabledOutputLevel::Low,
+            })
+        }
+
+        fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+            &SquareWaveFreq::STANDARD
+        }
+    }
+
+    impl SquareWavePins for TwoPins {
+        fn output_pin_count(&self) -> u8 {
+            2
+        }
+
+        fn start_square_wave_on(
+            &mut self,
+            pin: OutputPin,
+            freq: SquareWaveFreq,
+        ) -> Result<(), Self::Error> {
+            let pin = pin.check(self.output_pin_count())?;
+            if !self.supports_frequency(freq) {
+                return Err(ErrorKind::UnsupportedSqwFrequency);
+            }
+            self.pins[usize::from(pin.0)] = Some(freq);
+            Ok(())
+        }
+
+        fn disable_square_wave_on(&mut self, pin: OutputPin) -> Result<(), Self::Error> {
+            let pin = pin.check(self.output_pin_count())?;
+            self.pins[usize::from(pin.0)] = None;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_default_pin_mode_follows_config() {
+        let mut rtc = TwoPins::default();
+        assert_eq!(
+            rtc.current_pin_mode(),
+            Ok(OutputPinConfig::Disabled {
+                level: DisabledOutputLevel::Low
+            })
+        );
+        rtc.start_square_wave(SquareWaveFreq::Hz4096).unwrap();
+        assert_eq!(
+            rtc.current_pin_mode(),
+            Ok(OutputPinConfig::SquareWave(SquareWaveFreq::Hz4096))
+        );
+    }
+
+    #[test]
+    fn test_output_pins_are_independent() {
+        let mut rtc = TwoPins::default();
+        rtc.start_square_wave_on(OutputPin::SECONDARY, SquareWaveFreq::Hz32768)
+            .unwrap();
+        rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+        assert_eq!(
+            rtc.pins,
+            [Some(SquareWaveFreq::Hz1), Some(SquareWaveFreq::Hz32768)]
+        );
+
+        // The primary pin is the one `SquareWave` drives
+        rtc.disable_square_wave().unwrap();
+        assert_eq!(rtc.pins, [None, Some(SquareWaveFreq::Hz32768)]);
+        assert!(!rtc.square_wave_config().unwrap().enabled);
+        rtc.disable_square_wave_on(OutputPin::SECONDARY).unwrap();
+        rtc.start_square_wave_on(OutputPin::PRIMARY, SquareWaveFreq::Hz4096)
+            .unwrap();
+        assert_eq!(rtc.pins, [Some(SquareWaveFreq::Hz4096), None]);
+
+        // Unknown pins are rejected without touching the others
+        assert_eq!(
+            rtc.start_square_wave_on(OutputPin(2), SquareWaveFreq::Hz1),
+            Err(ErrorKind::InvalidAddress)
+        );
+        assert_eq!(
+            rtc.disable_square_wave_on(OutputPin(u8::MAX)),
+            Err(ErrorKind::InvalidAddress)
+        );
+        assert_eq!(rtc.pins, [Some(SquareWaveFreq::Hz4096), None]);
+        assert_eq!(
+            OutputPin::SECONDARY.check(1),
+            Err(ErrorKind::InvalidAddress)
+        );
+        assert_eq!(OutputPin::PRIMARY.check(1), Ok(OutputPin::PRIMARY));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use serde_test::{Token, assert_de_tokens, assert_de_tokens_error, assert_tokens};
+
+        let hz = Token::NewtypeVariant {
+            name: "SquareWaveFreq",
+            variant: "Hz",
+        };
+        let millihz = Token::NewtypeVariant {
+            name: "SquareWaveFreq",
+            variant: "MilliHz",
+        };
+        assert_tokens(&SquareWaveFreq::Hz1, &[hz, Token::U32(1)]);
+        assert_tokens(&SquareWaveFreq::Hz32768, &[hz, Token::U32(32768)]);
+        assert_tokens(&SquareWaveFreq::Custom(2048), &[hz, Token::U32(2048)]);
+        assert_tokens(&SquareWaveFreq::MilliHz(1500), &[millihz, Token::U32(1500)]);
+        assert_tokens(&SquareWaveFreq::MilliHz(17), &[millihz, Token::U32(17)]);
+        // Exactly 1/60 Hz, not the nearest millihertz
+        assert_tokens(
+            &SquareWaveFreq::PerMinute,
+            &[Token::UnitVariant {
+                name: "SquareWaveFreq",
+                variant: "PerMinute",
+            }],
+        );
+        assert_de_tokens(&SquareWaveFreq::Hz1024, &[hz, Token::U64(1024)]);
+        assert_de_tokens(&SquareWaveFreq::Hz1, &[millihz, Token::U32(1000)]);
+        assert_de_tokens(&SquareWaveFreq::Hz4096, &[hz, Token::I64(4096)]);
+        for value in [-1, 1 << 32] {
+            assert_de_tokens_error::<SquareWaveFreq>(
+                &[hz, Token::I64(value)],
+                &format!("invalid value: integer `{value}`, expected u32"),
+            );
+        }
+        assert_de_tokens_error::<SquareWaveFreq>(
+            &[Token::UnitVariant {
+                name: "SquareWaveFreq",
+                variant: "KiloHz",
+            }],
+            "unknown variant `KiloHz`, expected one of `Hz`, `MilliHz`, `PerMinute`",
+        );
+        assert_tokens(&OutputPin::SECONDARY, &[Token::U8(1)]);
+        assert_tokens(&SquareWaveError::AboveMax, &[Token::Str("AboveMax")]);
+
+        assert_tokens(
+            &OutputPinConfig::SquareWave(SquareWaveFreq::Hz4096),
+            &[
+                Token::NewtypeVariant {
+                    name: "OutputPinConfig",
+                    variant: "SquareWave",
+                },
+                hz,
+                Token::U32(4096),
+            ],
+        );
+        assert_tokens(
+            &OutputPinConfig::AlarmInterrupt,
+            &[Token::UnitVariant {
+                name: "OutputPinConfig",
+                variant: "AlarmInterrupt",
+            }],
+        );
+        for (level, name) in [
+            (DisabledOutputLevel::Low, "Low"),
+            (DisabledOutputLevel::High, "High"),
+            (DisabledOutputLevel::HighImpedance, "HighImpedance"),
+        ] {
+            assert_tokens(&level, &[Token::Str(name)]);
+            assert_tokens(
+                &OutputPinConfig::Disabled { level },
+                &[
+                    Token::StructVariant {
+                        name: "OutputPinConfig",
+                        variant: "Disabled",
+                        len: 1,
+                    },
+                    Token::Str("level"),
+                    Token::Str(name),
+                    Token::StructVariantEnd,
+                ],
+            );
+        }
+        assert_de_tokens_error::<OutputPinConfig>(
+            &[
+                Token::StructVariant {
+                    name: "OutputPinConfig",
+                    variant: "Disabled",
+                    len: 1,
+                },
+                Token::StructVariantEnd,
+            ],
+            "missing field `level`",
+        );
+        assert_de_tokens_error::<OutputPinConfig>(
+            &[Token::UnitVariant {
+                name: "OutputPinConfig",
+                variant: "Off",
+            }],
+            "unknown variant `Off`, expected one of `SquareWave`, `AlarmInterrupt`, `Disabled`",
+        );
+
+        assert_tokens(&DutyCycle::new(25).unwrap(), &[Token::U8(25)]);
+        for percent in [0, 100, 300] {
+            assert_de_tokens_error::<DutyCycle>(
+                &[Token::U64(percent)],
+                &format!(
+                    "invalid value: integer `{percent}`, expected a duty cycle of 1 to 99 percent"
+                ),
+            );
+        }
+        assert_de_tokens(&DutyCycle::new(50).unwrap(), &[Token::I64(50)]);
+        for percent in [-1, 0, 100] {
+            assert_de_tokens_error::<DutyCycle>(
+                &[Token::I64(percent)],
+                &format!(
+                    "invalid value: integer `{percent}`, expected a duty cycle of 1 to 99 percent"
+                ),
+            );
+        }
+
+        assert_tokens(
+            &SquareWaveConfig {
+                enabled: true,
+                freq: SquareWaveFreq::Hz1024,
+                disabled_level: DisabledOutputLevel::HighImpedance,
+            },
+            &[
+                Token::Struct {
+                    name: "SquareWaveConfig",
+                    len: 3,
+                },
+                Token::Str("enabled"),
+                Token::Bool(true),
+                Token::Str("freq"),
+                hz,
+                Token::U32(1024),
+                Token::Str("disabled_level"),
+                Token::Str("HighImpedance"),
+                Token::StructEnd,
+            ],
+        );
+        assert_de_tokens_error::<SquareWaveConfig>(
+            &[
+                Token::Struct {
+                    name: "SquareWaveConfig",
+                    len: 1,
+                },
+                Token::Str("enabled"),
+                Token::Bool(false),
+                Token::StructEnd,
+            ],
+            "missing field `freq`",
+        );
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<SquareWaveFreq>();
+        assert_format::<DisabledOutputLevel>();
+        assert_format::<OutputPinConfig>();
+        assert_format::<SquareWaveConfig>();
+        assert_format::<DutyCycle>();
+        assert_format::<OutputPin>();
+        assert_format::<SquareWaveError>();
+    }
 }
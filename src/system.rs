@@ -0,0 +1,127 @@
+//! # Host system clock
+//!
+//! [`SystemRtc`] implements [`Rtc`] on top of [`std::time::SystemTime`], so
+//! application code written against the RTC traits also runs on a desktop,
+//! in simulators or in HIL orchestration.
+//!
+//! Enabled with the `std` feature.
+//!
+//! ```
+//! use rtc_hal::datetime::DateTime;
+//! use rtc_hal::rtc::Rtc;
+//! use rtc_hal::system::SystemRtc;
+//!
+//! let mut rtc = SystemRtc::new();
+//! let now = rtc.get_datetime()?;
+//! assert!(now.year() >= 2024);
+//!
+//! // Only this instance is affected; the OS clock is left alone
+//! rtc.set_datetime(&DateTime::new(2000, 1, 1, 0, 0, 0)?)?;
+//! assert_eq!(rtc.get_datetime()?.year(), 2000);
+//! # Ok::<(), rtc_hal::error::ErrorKind>(())
+//! ```
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::datetime::DateTime;
+use crate::error::{ErrorKind, ErrorType};
+use crate::rtc::Rtc;
+
+/// RTC reading the operating system clock
+///
+/// [`set_datetime`](Rtc::set_datetime) does not change the system clock.
+/// Instead it stores the difference to it, which is added to every later
+/// read. Each `SystemRtc` has its own offset.
+///
+/// Time follows the system clock, so it jumps if the system clock is
+/// adjusted (e.g. by NTP).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemRtc {
+    offset: i64,
+}
+
+impl SystemRtc {
+    /// Create an RTC reading the system time unchanged
+    pub fn new() -> Self {
+        Self { offset: 0 }
+    }
+
+    /// Seconds added to the system time on every read
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Current system time in whole seconds since the Unix epoch
+    fn system_secs() -> Result<i64, ErrorKind> {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| ErrorKind::InvalidDateTime)?;
+        i64::try_from(since_epoch.as_secs()).map_err(|_| ErrorKind::InvalidDateTime)
+    }
+}
+
+impl ErrorType for SystemRtc {
+    type Error = ErrorKind;
+}
+
+impl Rtc for SystemRtc {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let secs = Self::system_secs()?
+            .checked_add(self.offset)
+            .and_then(|secs| u64::try_from(secs).ok())
+            .ok_or(ErrorKind::InvalidDateTime)?;
+        Ok(DateTime::from_unix_timestamp(secs)?)
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        let target =
+            i64::try_from(datetime.to_unix_timestamp()).map_err(|_| ErrorKind::InvalidDateTime)?;
+        self.offset = target - Self::system_secs()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follows_system_clock() {
+        let mut rtc = SystemRtc::new();
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let read = rtc.get_unix_time().unwrap();
+        let after = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        assert!((before..=after).contains(&read));
+    }
+
+    #[test]
+    fn test_monotonic_progression() {
+        let mut rtc = SystemRtc::new();
+        let mut last = rtc.get_datetime().unwrap();
+        for _ in 0..100 {
+            let now = rtc.get_datetime().unwrap();
+            assert!(now >= last);
+            last = now;
+        }
+    }
+
+    #[test]
+    fn test_offset_from_set_datetime() {
+        let mut rtc = SystemRtc::new();
+        let target = DateTime::new(2000, 2, 29, 12, 0, 0).unwrap();
+        rtc.set_datetime(&target).unwrap();
+        assert!(rtc.offset() < 0);
+
+        let read = rtc.get_datetime().unwrap();
+        let drift = read.to_unix_timestamp() - target.to_unix_timestamp();
+        assert!(drift <= 1, "read {read:?} after setting {target:?}");
+
+        // Other instances are unaffected
+        assert!(SystemRtc::new().get_datetime().unwrap() > read);
+    }
+}
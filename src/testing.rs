@@ -0,0 +1,237 @@
+//! # Shared test data
+//!
+//! Fixtures that driver crates otherwise copy between their test suites:
+//! tricky dates, known-answer BCD register images, and a seeded generator of
+//! valid `DateTime`s for property-style tests. The generator is deterministic
+//! and needs no RNG crate, so a failing seed can be replayed.
+//!
+//! Enabled with the `mock` feature.
+//!
+//! ```
+//! use rtc_hal::bcd::{self, RegisterLayout};
+//! use rtc_hal::testing::{DS_STYLE_REGISTER_VECTORS, RandomDateTimes};
+//!
+//! for (registers, datetime) in DS_STYLE_REGISTER_VECTORS {
+//!     assert_eq!(bcd::decode_datetime(registers, RegisterLayout::Ds1307, 2000), Ok(*datetime));
+//! }
+//!
+//! for datetime in RandomDateTimes::new(42).with_years(2000, 2099).take(1000) {
+//!     let mut registers = [0; 7];
+//!     bcd::encode_datetime(&datetime, &mut registers, RegisterLayout::Ds1307);
+//!     assert_eq!(bcd::decode_datetime(&registers, RegisterLayout::Ds1307, 2000), Ok(datetime));
+//! }
+//! ```
+use crate::datetime::{DateTime, days_in_month};
+
+const fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+    DateTime::new_unchecked(year, month, day, hour, minute, second)
+}
+
+/// Dates and times that commonly break conversions, in ascending order
+///
+/// Covers the representable range ends, leap days including the century
+/// rules (2000 is a leap year, 2100 is not, 2400 is), month and year ends,
+/// and the rollover points of 32-bit signed and unsigned Unix timestamps.
+pub const EDGE_CASE_DATETIMES: &[DateTime] = &[
+    DateTime::MIN,
+    dt(1970, 1, 1, 0, 0, 1),
+    dt(1999, 12, 31, 23, 59, 59),
+    dt(2000, 1, 1, 0, 0, 0),
+    dt(2000, 2, 29, 12, 0, 0),
+    dt(2000, 3, 1, 0, 0, 0),
+    dt(2001, 2, 28, 23, 59, 59),
+    dt(2023, 2, 28, 23, 59, 59),
+    dt(2024, 2, 29, 0, 0, 0),
+    dt(2024, 4, 30, 23, 59, 59),
+    dt(2024, 12, 31, 23, 59, 59),
+    dt(2038, 1, 19, 3, 14, 7),
+    dt(2038, 1, 19, 3, 14, 8),
+    dt(2099, 12, 31, 23, 59, 59),
+    dt(2100, 2, 28, 23, 59, 59),
+    dt(2100, 3, 1, 0, 0, 0),
+    dt(2106, 2, 7, 6, 28, 15),
+    dt(2106, 2, 7, 6, 28, 16),
+    dt(2400, 2, 29, 12, 0, 0),
+    dt(9999, 12, 31, 23, 59, 59),
+    DateTime::MAX,
+];
+
+/// Register images in [`RegisterLayout::Ds1307`](crate::bcd::RegisterLayout::Ds1307)
+/// order with the `DateTime` they hold
+///
+/// Registers are seconds, minutes, hours (24-hour mode), weekday (1 = Sunday),
+/// day of month, month and two-digit year, with no control bits set. Years are
+/// 2000-2099, i.e. a century base of 2000.
+pub const DS_STYLE_REGISTER_VECTORS: &[([u8; 7], DateTime)] = &[
+    (
+        [0x00, 0x00, 0x00, 0x07, 0x01, 0x01, 0x00],
+        dt(2000, 1, 1, 0, 0, 0),
+    ),
+    (
+        [0x56, 0x34, 0x12, 0x03, 0x29, 0x02, 0x00],
+        dt(2000, 2, 29, 12, 34, 56),
+    ),
+    (
+        [0x59, 0x59, 0x23, 0x04, 0x28, 0x02, 0x01],
+        dt(2001, 2, 28, 23, 59, 59),
+    ),
+    (
+        [0x59, 0x59, 0x23, 0x03, 0x31, 0x12, 0x19],
+        dt(2019, 12, 31, 23, 59, 59),
+    ),
+    (
+        [0x59, 0x59, 0x23, 0x03, 0x28, 0x02, 0x23],
+        dt(2023, 2, 28, 23, 59, 59),
+    ),
+    (
+        [0x59, 0x59, 0x23, 0x05, 0x29, 0x02, 0x24],
+        dt(2024, 2, 29, 23, 59, 59),
+    ),
+    (
+        [0x00, 0x00, 0x00, 0x06, 0x01, 0x03, 0x24],
+        dt(2024, 3, 1, 0, 0, 0),
+    ),
+    (
+        [0x07, 0x05, 0x09, 0x03, 0x30, 0x04, 0x24],
+        dt(2024, 4, 30, 9, 5, 7),
+    ),
+    (
+        [0x07, 0x14, 0x03, 0x03, 0x19, 0x01, 0x38],
+        dt(2038, 1, 19, 3, 14, 7),
+    ),
+    (
+        [0x59, 0x59, 0x23, 0x05, 0x31, 0x12, 0x99],
+        dt(2099, 12, 31, 23, 59, 59),
+    ),
+];
+
+/// Endless, seeded sequence of valid pseudo-random `DateTime`s
+///
+/// The same seed always yields the same sequence, on every platform. Years
+/// cover the whole representable range unless narrowed with
+/// [`with_years`](Self::with_years); all other fields are uniform over their
+/// valid values.
+#[derive(Debug, Clone)]
+pub struct RandomDateTimes {
+    state: u64,
+    min_year: u16,
+    max_year: u16,
+}
+
+impl RandomDateTimes {
+    /// Create a generator from `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed,
+            min_year: DateTime::MIN.year(),
+            max_year: DateTime::MAX.year(),
+        }
+    }
+
+    /// Only generate years from `min` to `max`, inclusive
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max` or `min` is before 1970.
+    pub fn with_years(mut self, min: u16, max: u16) -> Self {
+        assert!(min <= max, "min year must not exceed max year");
+        assert!(
+            min >= DateTime::MIN.year(),
+            "years before 1970 are not representable"
+        );
+        self.min_year = min;
+        self.max_year = max;
+        self
+    }
+
+    /// SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `min..=max`
+    fn next_in(&mut self, min: u16, max: u16) -> u16 {
+        let span = u64::from(max - min) + 1;
+        min + (self.next_u64() % span) as u16
+    }
+}
+
+impl Iterator for RandomDateTimes {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<DateTime> {
+        let year = self.next_in(self.min_year, self.max_year);
+        let month = self.next_in(1, 12) as u8;
+        let day = self.next_in(1, days_in_month(year, month).into()) as u8;
+        let hour = self.next_in(0, 23) as u8;
+        let minute = self.next_in(0, 59) as u8;
+        let second = self.next_in(0, 59) as u8;
+        Some(dt(year, month, day, hour, minute, second))
+    }
+}
+
+/// `count` pseudo-random valid `DateTime`s from `seed`, see [`RandomDateTimes`]
+pub fn random_datetimes(seed: u64, count: usize) -> impl Iterator<Item = DateTime> {
+    RandomDateTimes::new(seed).take(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_cases_valid_and_sorted() {
+        for datetime in EDGE_CASE_DATETIMES {
+            datetime.validate().unwrap();
+        }
+        assert!(EDGE_CASE_DATETIMES.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            EDGE_CASE_DATETIMES
+                .iter()
+                .find(|dt| dt.year() == 2038 && dt.second() == 8)
+                .unwrap()
+                .to_unix_timestamp(),
+            1 << 31
+        );
+    }
+
+    #[test]
+    fn test_register_vectors_valid_with_weekday() {
+        for (registers, datetime) in DS_STYLE_REGISTER_VECTORS {
+            datetime.validate().unwrap();
+            assert_eq!(registers[3], datetime.weekday().to_number());
+        }
+    }
+
+    #[test]
+    fn test_generator_valid_and_deterministic() {
+        let first: std::vec::Vec<_> = random_datetimes(7, 500).collect();
+        for datetime in &first {
+            datetime.validate().unwrap();
+        }
+        assert!(random_datetimes(7, 500).eq(first.iter().copied()));
+        assert!(!random_datetimes(8, 500).eq(first.iter().copied()));
+    }
+
+    #[test]
+    fn test_generator_year_range() {
+        let mut seen_min = false;
+        let mut seen_max = false;
+        for datetime in RandomDateTimes::new(1).with_years(2023, 2024).take(1000) {
+            assert!((2023..=2024).contains(&datetime.year()));
+            seen_min |= datetime.year() == 2023;
+            seen_max |= datetime.year() == 2024;
+        }
+        assert!(seen_min && seen_max);
+        assert!(
+            RandomDateTimes::new(3)
+                .with_years(2000, 2000)
+                .take(2000)
+                .any(|dt| dt.month() == 2 && dt.day_of_month() == 29)
+        );
+    }
+}
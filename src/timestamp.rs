@@ -0,0 +1,202 @@
+//! # Seconds-counter RTC interface
+//!
+//! Some chips (e.g. the RV-8803 in binary mode, or RTCs built into PMICs)
+//! keep a plain seconds counter instead of calendar registers. The
+//! [`RtcTimestamp`] trait exposes that counter as seconds since the Unix epoch
+//! without any calendar decoding.
+//!
+//! Calendar RTCs can be used through the same interface by wrapping them in
+//! [`TimestampAdapter`], which converts through [`DateTime`](crate::datetime::DateTime).
+use crate::{datetime::DateTimeError, error::ErrorType, rtc::Rtc};
+
+/// RTC that keeps time as seconds since the Unix epoch (1970-01-01 00:00:00).
+pub trait RtcTimestamp: ErrorType {
+    /// Get the current time as seconds since the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails.
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error>;
+
+    /// Set the current time as seconds since the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if communication with the RTC fails or
+    /// if `secs` is out of range for this device.
+    fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error>;
+}
+
+/// blanket impl for all `&mut T`
+impl<T: RtcTimestamp + ?Sized> RtcTimestamp for &mut T {
+    #[inline]
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        T::get_timestamp(self)
+    }
+
+    #[inline]
+    fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+        T::set_timestamp(self, secs)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: RtcTimestamp + ?Sized> RtcTimestamp for alloc::boxed::Box<T> {
+    #[inline]
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        T::get_timestamp(self)
+    }
+
+    #[inline]
+    fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+        T::set_timestamp(self, secs)
+    }
+}
+
+/// Implements [`RtcTimestamp`] for a calendar [`Rtc`] by converting through
+/// [`DateTime`](crate::datetime::DateTime).
+///
+/// This is a wrapper rather than a blanket impl so that a chip can implement
+/// both `Rtc` and a native `RtcTimestamp`.
+///
+/// The driver's error type must implement `From<DateTimeError>`, which
+/// reports a timestamp past [`DateTime::MAX`](crate::datetime::DateTime::MAX)
+/// (see [`Rtc::set_unix_time`]).
+///
+/// ```
+/// use rtc_hal::timestamp::{RtcTimestamp, TimestampAdapter};
+/// # use rtc_hal::{datetime::DateTime, error::{ErrorKind, ErrorType}, rtc::Rtc};
+/// # struct Ds1307(DateTime);
+/// # impl ErrorType for Ds1307 { type Error = ErrorKind; }
+/// # impl Rtc for Ds1307 {
+/// #     fn get_datetime(&mut self) -> Result<DateTime, ErrorKind> { Ok(self.0) }
+/// #     fn set_datetime(&mut self, dt: &DateTime) -> Result<(), ErrorKind> { self.0 = *dt; Ok(()) }
+/// # }
+///
+/// let mut rtc = TimestampAdapter::new(Ds1307(DateTime::MIN));
+/// rtc.set_timestamp(1_723_809_600)?;
+/// assert_eq!(rtc.inner_mut().get_datetime()?, DateTime::new(2024, 8, 16, 12, 0, 0)?);
+/// # Ok::<(), ErrorKind>(())
+/// ```
+#[derive(Debug)]
+pub struct TimestampAdapter<R> {
+    rtc: R,
+}
+
+impl<R> TimestampAdapter<R> {
+    /// Wrap a calendar RTC
+    pub fn new(rtc: R) -> Self {
+        Self { rtc }
+    }
+
+    /// Get a mutable reference to the wrapped RTC
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.rtc
+    }
+
+    /// Release the wrapped RTC
+    pub fn into_inner(self) -> R {
+        self.rtc
+    }
+}
+
+impl<R: ErrorType> ErrorType for TimestampAdapter<R> {
+    type Error = R::Error;
+}
+
+impl<R: Rtc> RtcTimestamp for TimestampAdapter<R>
+where
+    R::Error: From<DateTimeError>,
+{
+    fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+        self.rtc.get_unix_time()
+    }
+
+    fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+        self.rtc.set_unix_time(secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::DateTime;
+    use crate::error::ErrorKind;
+
+    /// 32-bit seconds counter
+    struct Counter {
+        seconds: u32,
+    }
+
+    impl ErrorType for Counter {
+        type Error = ErrorKind;
+    }
+
+    impl RtcTimestamp for Counter {
+        fn get_timestamp(&mut self) -> Result<u64, Self::Error> {
+            Ok(self.seconds as u64)
+        }
+
+        fn set_timestamp(&mut self, secs: u64) -> Result<(), Self::Error> {
+            self.seconds = u32::try_from(secs).map_err(|_| ErrorKind::InvalidDateTime)?;
+            Ok(())
+        }
+    }
+
+    struct Calendar {
+        now: DateTime,
+    }
+
+    impl ErrorType for Calendar {
+        type Error = ErrorKind;
+    }
+
+    impl Rtc for Calendar {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(self.now)
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.now = *datetime;
+            Ok(())
+        }
+    }
+
+    fn advance<T: RtcTimestamp>(mut rtc: T, secs: u64) -> Result<u64, T::Error> {
+        let now = rtc.get_timestamp()?;
+        rtc.set_timestamp(now + secs)?;
+        rtc.get_timestamp()
+    }
+
+    #[test]
+    fn test_native_counter() {
+        let mut rtc = Counter { seconds: 100 };
+        assert_eq!(advance(&mut rtc, 20), Ok(120));
+        assert_eq!(rtc.seconds, 120);
+        assert_eq!(
+            rtc.set_timestamp(u32::MAX as u64 + 1),
+            Err(ErrorKind::InvalidDateTime)
+        );
+    }
+
+    #[test]
+    fn test_bridged_calendar() {
+        let mut rtc = TimestampAdapter::new(Calendar {
+            now: DateTime::new(2024, 8, 16, 23, 59, 50).unwrap(),
+        });
+        assert_eq!(rtc.get_timestamp(), Ok(1_723_852_790));
+        assert_eq!(advance(&mut rtc, 20), Ok(1_723_852_810));
+        assert_eq!(
+            rtc.into_inner().now,
+            DateTime::new(2024, 8, 17, 0, 0, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bridged_out_of_range() {
+        let mut rtc = TimestampAdapter::new(Calendar { now: DateTime::MIN });
+        let past_max = DateTime::MAX.to_unix_timestamp() + 1;
+        assert_eq!(rtc.set_timestamp(past_max), Err(ErrorKind::InvalidDateTime));
+        assert_eq!(rtc.inner_mut().now, DateTime::MIN);
+    }
+}
@@ -0,0 +1,25 @@
+//! Reads the kernel RTC, when the machine has one we may open
+
+#![cfg(target_os = "linux")]
+
+use rtc_hal::linux::{DEFAULT_PATH, LinuxRtc};
+use rtc_hal::rtc::Rtc;
+
+#[test]
+fn test_read_dev_rtc0() {
+    if !std::path::Path::new(DEFAULT_PATH).exists() {
+        eprintln!("skipping: {DEFAULT_PATH} does not exist");
+        return;
+    }
+    let mut rtc = match LinuxRtc::open() {
+        Ok(rtc) => rtc,
+        Err(e) => {
+            eprintln!("skipping: cannot open {DEFAULT_PATH}: {e}");
+            return;
+        }
+    };
+    // Read only: setting the time would change the host's hardware clock
+    let first = rtc.get_datetime().unwrap();
+    let second = rtc.get_datetime().unwrap();
+    assert!(second >= first);
+}
@@ -0,0 +1,204 @@
+//! Implements every trait on one mock, using only the prelude
+
+use rtc_hal::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MockError {
+    Bus,
+    OutOfBounds,
+}
+
+impl Error for MockError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            MockError::Bus => ErrorKind::Bus,
+            MockError::OutOfBounds => ErrorKind::NvramOutOfBounds,
+        }
+    }
+}
+
+struct MockRtc {
+    now: DateTime,
+    running: bool,
+    square_wave: Option<SquareWaveFreq>,
+    square_wave_enabled: bool,
+    disabled_level: DisabledOutputLevel,
+    nvram: [u8; 8],
+}
+
+impl MockRtc {
+    fn new() -> Self {
+        Self {
+            now: DateTime::new(2024, 8, 16, 12, 0, 0).unwrap(),
+            running: false,
+            square_wave: None,
+            square_wave_enabled: false,
+            disabled_level: DisabledOutputLevel::Low,
+            nvram: [0; 8],
+        }
+    }
+
+    fn nvram_range(&self, offset: u32, len: usize) -> Result<core::ops::Range<usize>, MockError> {
+        let start = offset as usize;
+        let end = start + len;
+        if end > self.nvram.len() {
+            return Err(MockError::OutOfBounds);
+        }
+        Ok(start..end)
+    }
+}
+
+impl ErrorType for MockRtc {
+    type Error = MockError;
+}
+
+impl Rtc for MockRtc {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        Ok(self.now)
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.now = *datetime;
+        Ok(())
+    }
+}
+
+impl RtcPowerControl for MockRtc {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        self.running = true;
+        Ok(())
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        self.running = false;
+        Ok(())
+    }
+}
+
+impl SquareWave for MockRtc {
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        match config {
+            OutputPinConfig::SquareWave(freq) => self.start_square_wave(freq),
+            _ => self.disable_square_wave(),
+        }
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.square_wave = Some(freq);
+        self.square_wave_enabled = true;
+        Ok(())
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.square_wave_enabled = true;
+        Ok(())
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.square_wave_enabled = false;
+        Ok(())
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.square_wave = Some(freq);
+        Ok(())
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        self.disabled_level = level;
+        Ok(())
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        Ok(SquareWaveConfig {
+            enabled: self.square_wave_enabled,
+            freq: self.square_wave.unwrap_or(SquareWaveFreq::Hz1),
+            disabled_level: self.disabled_level,
+        })
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        &SquareWaveFreq::STANDARD
+    }
+}
+
+impl RtcNvram for MockRtc {
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        let range = self.nvram_range(offset, buffer.len())?;
+        buffer.copy_from_slice(&self.nvram[range]);
+        Ok(())
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        let range = self.nvram_range(offset, data.len())?;
+        self.nvram[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.nvram.len() as u32
+    }
+}
+
+// Generic code written against the prelude traits only
+fn configure<R>(rtc: &mut R) -> Result<(), R::Error>
+where
+    R: RtcPowerControl + SquareWave + RtcNvram,
+{
+    rtc.start_clock()?;
+    rtc.start_square_wave(SquareWaveFreq::Hz1)?;
+    rtc.write_nvram(0, &[0xC0, 0xFF, 0xEE])?;
+    Ok(())
+}
+
+#[test]
+fn prelude_traits_on_one_mock() {
+    let mut rtc = MockRtc::new();
+    configure(&mut rtc).unwrap();
+    assert!(rtc.running);
+    assert!(rtc.square_wave_enabled);
+    assert_eq!(rtc.square_wave, Some(SquareWaveFreq::Hz1));
+
+    let mut buffer = [0; 3];
+    rtc.read_nvram(0, &mut buffer).unwrap();
+    assert_eq!(buffer, [0xC0, 0xFF, 0xEE]);
+    assert_eq!(rtc.nvram_size(), 8);
+
+    rtc.set_square_wave_frequency(SquareWaveFreq::Hz4096)
+        .unwrap();
+    rtc.disable_square_wave().unwrap();
+    rtc.set_disabled_output_level(DisabledOutputLevel::High)
+        .unwrap();
+    rtc.enable_square_wave().unwrap();
+    assert_eq!(rtc.square_wave, Some(SquareWaveFreq::Hz4096));
+    assert_eq!(
+        rtc.square_wave_config(),
+        Ok(SquareWaveConfig {
+            enabled: true,
+            freq: SquareWaveFreq::Hz4096,
+            disabled_level: DisabledOutputLevel::High
+        })
+    );
+    rtc.halt_clock().unwrap();
+    assert!(!rtc.running);
+}
+
+#[test]
+fn prelude_types() {
+    let mut rtc = MockRtc::new();
+    let datetime = DateTime::new(2024, 12, 25, 8, 30, 0).unwrap();
+    rtc.set_datetime(&datetime).unwrap();
+
+    assert_eq!(rtc.get_datetime().unwrap().weekday(), Weekday::Wednesday);
+    assert_eq!(rtc.get_date().unwrap(), Date::new(2024, 12, 25).unwrap());
+    assert_eq!(rtc.get_time().unwrap(), Time::new(8, 30, 0).unwrap());
+}
+
+#[test]
+fn prelude_errors() {
+    let mut rtc = MockRtc::new();
+    let err = rtc.write_nvram(6, &[0; 4]).unwrap_err();
+    assert_eq!(err, MockError::OutOfBounds);
+    assert_eq!(err.kind(), ErrorKind::NvramOutOfBounds);
+    assert_eq!(MockError::Bus.kind(), ErrorKind::Bus);
+}
@@ -0,0 +1,130 @@
+//! Runs the conformance suite against a simulated driver built on `FakeRtc`
+
+use rtc_hal::mock::FakeRtc;
+use rtc_hal::nvram;
+use rtc_hal::prelude::*;
+
+/// DS1307-like driver: years 2000-2099, 56 bytes of NVRAM, four SQW rates
+struct SimulatedDs1307 {
+    clock: FakeRtc,
+    nvram: [u8; 56],
+}
+
+impl SimulatedDs1307 {
+    fn new() -> Self {
+        Self {
+            clock: FakeRtc::new(DateTime::new(2000, 1, 1, 0, 0, 0).unwrap()),
+            nvram: [0; 56],
+        }
+    }
+
+    fn nvram_range(&self, offset: u32, len: usize) -> Result<core::ops::Range<usize>, ErrorKind> {
+        nvram::check_bounds(offset, len, self.nvram_size())?;
+        let start = offset as usize;
+        Ok(start..start + len)
+    }
+}
+
+impl ErrorType for SimulatedDs1307 {
+    type Error = ErrorKind;
+}
+
+impl Rtc for SimulatedDs1307 {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        self.clock.get_datetime()
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        if !(2000..=2099).contains(&datetime.year()) {
+            return Err(ErrorKind::InvalidDateTime);
+        }
+        self.clock.set_datetime(datetime)
+    }
+}
+
+impl RtcNvram for SimulatedDs1307 {
+    fn read_nvram(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        let range = self.nvram_range(offset, buffer.len())?;
+        buffer.copy_from_slice(&self.nvram[range]);
+        Ok(())
+    }
+
+    fn write_nvram(&mut self, offset: u32, data: &[u8]) -> Result<(), Self::Error> {
+        let range = self.nvram_range(offset, data.len())?;
+        self.nvram[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn nvram_size(&self) -> u32 {
+        self.nvram.len() as u32
+    }
+}
+
+impl SquareWave for SimulatedDs1307 {
+    fn configure_output_pin(&mut self, config: OutputPinConfig) -> Result<(), Self::Error> {
+        match config {
+            OutputPinConfig::SquareWave(freq) => self.start_square_wave(freq),
+            // SQW/OUT has no alarm to signal
+            OutputPinConfig::AlarmInterrupt => Err(ErrorKind::Unsupported),
+            OutputPinConfig::Disabled { .. } => self.disable_square_wave(),
+        }
+    }
+
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.set_square_wave_frequency(freq)?;
+        self.clock.enable_square_wave()
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.clock.enable_square_wave()
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.clock.disable_square_wave()
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        if !self.supports_frequency(freq) {
+            return Err(ErrorKind::UnsupportedSqwFrequency);
+        }
+        self.clock.set_square_wave_frequency(freq)
+    }
+
+    fn set_disabled_output_level(&mut self, level: DisabledOutputLevel) -> Result<(), Self::Error> {
+        // The OUT bit drives SQW/OUT low or high
+        match level {
+            DisabledOutputLevel::HighImpedance => Err(ErrorKind::Unsupported),
+            _ => self.clock.set_disabled_output_level(level),
+        }
+    }
+
+    fn square_wave_config(&mut self) -> Result<SquareWaveConfig, Self::Error> {
+        self.clock.square_wave_config()
+    }
+
+    fn supported_square_wave_frequencies(&self) -> &'static [SquareWaveFreq] {
+        &[
+            SquareWaveFreq::Hz1,
+            SquareWaveFreq::Hz4096,
+            SquareWaveFreq::Hz8192,
+            SquareWaveFreq::Hz32768,
+        ]
+    }
+}
+
+rtc_hal::test_suite! {
+    mod ds1307;
+    new: SimulatedDs1307::new;
+    years: 2000..=2099;
+    nvram;
+    square_wave: supported [SquareWaveFreq::Hz1, SquareWaveFreq::Hz32768],
+        unsupported [SquareWaveFreq::Hz1024, SquareWaveFreq::Custom(2)];
+}
+
+// `FakeRtc` accepts every representable year
+rtc_hal::test_suite! {
+    mod fake;
+    new: || FakeRtc::new(DateTime::MIN).with_nvram(56);
+    years: 1970..=65535;
+    nvram;
+}